@@ -0,0 +1,94 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use kingslayer::Cli;
+
+const ROOMS: usize = 10_000;
+
+// a long corridor of rooms, each holding a few items to pick up
+fn generate_world() -> String {
+    let mut rooms = String::new();
+    for i in 0..ROOMS {
+        let mut paths = Vec::new();
+        if i + 1 < ROOMS {
+            paths.push(format!(
+                "(directions:[\"north\"],target:\"Room {}\",desc:\"The hall continues north.\",inspect:\"It is dark.\")",
+                i + 1
+            ));
+        }
+        if i > 0 {
+            paths.push(format!(
+                "(directions:[\"south\"],target:\"Room {}\",desc:\"The hall continues south.\",inspect:\"It is dark.\")",
+                i - 1
+            ));
+        }
+        let items = (0..5)
+            .map(|n| {
+                format!(
+                    "Thing((name:\"pebble {}\",desc:\"There is a pebble here.\",inspect:\"It is smooth.\"))",
+                    n
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        rooms.push_str(&format!(
+            "\"Room {i}\":(name:\"Room {i}\",desc:\"You are in room {i}.\",paths:[{}],items:[{}]),",
+            paths.join(","),
+            items,
+            i = i
+        ));
+    }
+    format!("(world:(curr_room:\"Room 0\",rooms:{{{}}}))", rooms)
+}
+
+fn loading(c: &mut Criterion) {
+    let ron = generate_world();
+    let mut group = c.benchmark_group("loading");
+    // each of these takes long enough that a handful of samples is plenty
+    group.sample_size(10);
+
+    group.bench_function("load", |b| b.iter(|| Cli::from_ron_str(&ron).unwrap()));
+    // every room is read once to check it, then only the rooms entered are kept read
+    group.bench_function("lazy load", |b| {
+        b.iter(|| Cli::from_ron_str_lazy(&ron, 64).unwrap())
+    });
+    group.bench_function("generate", |b| b.iter(|| Cli::generate(ROOMS, 0)));
+
+    let cli = Cli::from_ron_str(&ron).unwrap();
+    group.bench_function("validate", |b| b.iter(|| cli.validate()));
+    group.bench_function("save", |b| b.iter(|| cli.to_ron()));
+    group.finish();
+}
+
+fn playing(c: &mut Criterion) {
+    let ron = generate_world();
+
+    // walking a hundred rooms each way keeps putting rooms back into text and reading them again
+    let lazy = Cli::from_ron_str_lazy(&ron, 64).unwrap();
+    let mut step = 0;
+    c.bench_function("lazy move", |b| {
+        b.iter(|| {
+            step += 1;
+            lazy.ask(if step / 100 % 2 == 0 { "n" } else { "s" })
+        })
+    });
+
+    let cli = Cli::from_ron_str(&ron).unwrap();
+    c.bench_function("move", |b| {
+        b.iter(|| {
+            cli.ask("n");
+            cli.ask("s")
+        })
+    });
+    c.bench_function("look", |b| b.iter(|| cli.ask("l")));
+    // undo would copy the world every turn, which is not what is being measured
+    cli.set_undo(false);
+    c.bench_function("take all", |b| {
+        b.iter(|| {
+            cli.ask("take all");
+            cli.ask("drop all")
+        })
+    });
+}
+
+criterion_group!(benches, loading, playing);
+criterion_main!(benches);
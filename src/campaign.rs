@@ -0,0 +1,317 @@
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "serde")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Cli;
+#[cfg(feature = "serde")]
+use crate::{FileStore, SaveStore};
+
+type NewGame = Box<dyn Fn() -> Cli + Send>;
+
+// a room that sends the character on to another chapter when they walk into it
+struct Exit {
+    room: String,
+    to: String,
+    // leaving this way marks the chapter it leads out of complete
+    completes: bool,
+}
+
+/// One world of a campaign and the ways out of it into the others; the world is built the
+/// first time the character arrives and kept as they left it after that
+pub struct Chapter {
+    new_game: NewGame,
+    exits: Vec<Exit>,
+}
+
+impl Chapter {
+    pub fn new<F>(new_game: F) -> Self
+    where
+        F: Fn() -> Cli + Send + 'static,
+    {
+        Self {
+            new_game: Box::new(new_game),
+            exits: Vec::new(),
+        }
+    }
+
+    /// Send the character on to another chapter when they walk into the room
+    pub fn with_exit(mut self, room: &str, to: &str) -> Self {
+        self.exits.push(Exit {
+            room: room.to_owned(),
+            to: to.to_owned(),
+            completes: false,
+        });
+        self
+    }
+
+    /// Send the character on to another chapter when they walk into the room, marking this
+    /// one complete
+    pub fn with_finish(mut self, room: &str, to: &str) -> Self {
+        self.exits.push(Exit {
+            room: room.to_owned(),
+            to: to.to_owned(),
+            completes: true,
+        });
+        self
+    }
+
+    fn exit(&self, room: &str) -> Option<&Exit> {
+        self.exits.iter().find(|exit| exit.room == room)
+    }
+}
+
+/// How a chapter of a campaign was first completed
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Record {
+    chapter: String,
+    moves: u32,
+    score: u32,
+}
+
+impl Record {
+    pub fn chapter(&self) -> &str {
+        &self.chapter
+    }
+
+    /// The moves made in the chapter's world, counting any earlier visits
+    pub const fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    /// The character's score on finishing it
+    pub const fn score(&self) -> u32 {
+        self.score
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+struct CampaignSave {
+    chapter: String,
+    games: BTreeMap<String, Cli>,
+    records: Vec<Record>,
+}
+
+/// Separately written worlds played one after another by the same character, like the chapters
+/// of a story or the levels beyond a hub; inventory, stats, and everything else about the
+/// character go along through each exit, and each world stays as the character left it
+pub struct Campaign {
+    chapters: Vec<(String, Chapter)>,
+    games: BTreeMap<String, Cli>,
+    current: String,
+    records: Vec<Record>,
+    #[cfg(feature = "serde")]
+    save_store: Arc<Mutex<dyn SaveStore>>,
+}
+
+impl Campaign {
+    /// Start a campaign in its first chapter, often a hub whose exits lead to the rest
+    pub fn new(name: &str, chapter: Chapter) -> Self {
+        let mut games = BTreeMap::new();
+        games.insert(name.to_owned(), (chapter.new_game)());
+        Self {
+            chapters: vec![(name.to_owned(), chapter)],
+            games,
+            current: name.to_owned(),
+            records: Vec::new(),
+            #[cfg(feature = "serde")]
+            save_store: Arc::new(Mutex::new(FileStore::default())),
+        }
+    }
+
+    pub fn with_chapter(mut self, name: &str, chapter: Chapter) -> Self {
+        self.chapters.push((name.to_owned(), chapter));
+        self
+    }
+
+    #[cfg(feature = "serde")]
+    /// Keep campaign saves in the given store instead of files in the working directory
+    pub fn with_save_store(mut self, store: Arc<Mutex<dyn SaveStore>>) -> Self {
+        self.save_store = store;
+        self
+    }
+
+    /// Handle user input in the current chapter, moving the character on to the next one when
+    /// they walk into an exit
+    ///
+    /// "save" saves the whole campaign rather than the one world
+    pub fn ask(&mut self, input: &str) -> String {
+        #[cfg(feature = "serde")]
+        {
+            let mut words = input.split_whitespace();
+            if words.next().map(str::to_lowercase).as_deref() == Some("save") {
+                return self.save_command(words.next().unwrap_or("campaign"));
+            }
+        }
+
+        let cli = self.cli();
+        let left = cli.status().room().to_owned();
+        let output = cli.ask(input);
+        let status = cli.status();
+        if status.room() == left || cli.is_over() {
+            return output;
+        }
+        let (to, completes) = match self
+            .chapter_named(&self.current)
+            .and_then(|chapter| chapter.exit(status.room()))
+        {
+            Some(exit) => (exit.to.clone(), exit.completes),
+            None => return output,
+        };
+
+        if completes && !self.is_complete(&self.current) {
+            debug!("completed chapter {}", self.current);
+            self.records.push(Record {
+                chapter: self.current.clone(),
+                moves: status.turns(),
+                score: status.score(),
+            });
+        }
+        match self.travel(&to) {
+            Ok(arrival) => format!("{}\n\n{}", output, arrival),
+            Err(err) => format!("{}\n{}", output, err),
+        }
+    }
+
+    /// Move the character to the named chapter, building its world if they have not been
+    /// there before, and describe where they arrive
+    pub fn travel(&mut self, chapter: &str) -> Result<String, String> {
+        let new_game = &self
+            .chapter_named(chapter)
+            .ok_or_else(|| format!("There is no chapter called {}.", chapter))?
+            .new_game;
+        let first_visit = !self.games.contains_key(chapter);
+        if first_visit {
+            self.games.insert(chapter.to_owned(), new_game());
+        }
+        debug!("travelling from chapter {} to {}", self.current, chapter);
+
+        let arrival = self.games[chapter].carry_over(&self.games[&self.current]);
+        let intro = self.games[chapter].intro();
+        self.current = chapter.to_owned();
+        Ok(if first_visit && !intro.is_empty() {
+            format!("{}\n{}", intro, arrival)
+        } else {
+            arrival
+        })
+    }
+
+    fn chapter_named(&self, name: &str) -> Option<&Chapter> {
+        self.chapters
+            .iter()
+            .find(|(chapter, _)| chapter == name)
+            .map(|(_, chapter)| chapter)
+    }
+
+    /// The chapter the character is in
+    pub fn chapter(&self) -> &str {
+        &self.current
+    }
+
+    /// The game of the current chapter
+    pub fn cli(&self) -> &Cli {
+        &self.games[&self.current]
+    }
+
+    /// How each completed chapter was first completed, in the order they were
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    pub fn is_complete(&self, chapter: &str) -> bool {
+        self.records.iter().any(|record| record.chapter == chapter)
+    }
+
+    /// Whether the character has quit or died
+    pub fn is_over(&self) -> bool {
+        self.cli().is_over()
+    }
+
+    #[cfg(feature = "serde")]
+    /// The whole campaign as RON: every world visited, where the character is, and the records
+    pub fn to_ron(&self) -> String {
+        let save = CampaignSave {
+            chapter: self.current.clone(),
+            games: self.games.clone(),
+            records: self.records.clone(),
+        };
+        ron::ser::to_string(&save).expect("Error serializing campaign save file.")
+    }
+
+    #[cfg(feature = "serde")]
+    /// Pick up a campaign saved with `to_ron`, refusing one with chapters this one lacks
+    pub fn load_ron(&mut self, ron: &str) -> Result<(), String> {
+        let save: CampaignSave = ron::de::from_str(ron).map_err(|err| {
+            debug!("could not read campaign: {}", err);
+            err.to_string()
+        })?;
+        if let Some(missing) = save
+            .games
+            .keys()
+            .find(|chapter| self.chapter_named(chapter).is_none())
+        {
+            return Err(format!("This campaign has no chapter called {}.", missing));
+        }
+        if !save.games.contains_key(&save.chapter) {
+            return Err(String::from("The save is not in any of its chapters."));
+        }
+        self.current = save.chapter;
+        self.games = save.games;
+        self.records = save.records;
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Keep the whole campaign in the save store under the name, returning where it went
+    pub fn save(&self, name: &str) -> Result<String, String> {
+        self.save_store
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .write(name, &self.to_ron())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Pick up the campaign kept in the save store under the name
+    pub fn load_saved(&mut self, name: &str) -> Result<(), String> {
+        let ron = self
+            .save_store
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .read(name)?;
+        self.load_ron(&ron)
+    }
+
+    // "save" typed in play, refused where the chapter would refuse it
+    #[cfg(feature = "serde")]
+    fn save_command(&self, name: &str) -> String {
+        if let Err(refusal) = self.cli().can_save() {
+            return refusal;
+        }
+        match self.save(name) {
+            Ok(location) => format!("Saved to '{}'.", location),
+            Err(_) => String::from("Error saving campaign."),
+        }
+    }
+}
+
+impl fmt::Debug for Campaign {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Campaign")
+            .field(
+                "chapters",
+                &self
+                    .chapters
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("current", &self.current)
+            .field("records", &self.records)
+            .finish()
+    }
+}
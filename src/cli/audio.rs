@@ -0,0 +1,60 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use crate::types::{Cue, Rumble};
+
+/// Receives the sound effect and music cues that each command triggers, in order,
+/// so a front-end can play them with its own audio backend
+pub trait Audio: Send {
+    fn cue(&mut self, cue: &Cue);
+}
+
+/// Receives rumble hints for big hits, deaths, and explosions, for controller-based front-ends
+pub trait Haptics: Send {
+    fn rumble(&mut self, rumble: Rumble);
+}
+
+// sound and music go to the audio backend and rumbles to the controller
+#[derive(Clone, Default)]
+pub(crate) struct AudioHook {
+    audio: Option<Arc<Mutex<dyn Audio>>>,
+    haptics: Option<Arc<Mutex<dyn Haptics>>>,
+}
+
+impl AudioHook {
+    pub(crate) fn set_audio(&mut self, audio: Arc<Mutex<dyn Audio>>) {
+        self.audio = Some(audio);
+    }
+
+    pub(crate) fn set_haptics(&mut self, haptics: Arc<Mutex<dyn Haptics>>) {
+        self.haptics = Some(haptics);
+    }
+
+    pub(crate) fn play(&self, cues: &[Cue]) {
+        for cue in cues {
+            match (cue, &self.audio, &self.haptics) {
+                (Cue::Rumble(rumble), _, Some(haptics)) => haptics
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .rumble(*rumble),
+                (Cue::Sound(_), Some(audio), _)
+                | (Cue::Music(_), Some(audio), _)
+                | (Cue::Region(_), Some(audio), _) => {
+                    audio.lock().unwrap_or_else(|err| err.into_inner()).cue(cue)
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl fmt::Debug for AudioHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AudioHook")
+            .field("audio", &self.audio.is_some())
+            .field("haptics", &self.haptics.is_some())
+            .finish()
+    }
+}
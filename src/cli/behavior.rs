@@ -0,0 +1,42 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Runs every turn the player carries a particular item, for daemons a world file cannot
+/// describe; returns what the item does that turn, if anything
+pub trait Behavior: Send {
+    fn turn(&mut self, item: &str, room: &str) -> Option<String>;
+}
+
+// the host's behaviors with the names of the items they belong to
+#[derive(Clone, Default)]
+pub(crate) struct BehaviorHook(Vec<(String, Arc<Mutex<dyn Behavior>>)>);
+
+impl BehaviorHook {
+    pub(crate) fn add(&mut self, item: &str, behavior: Arc<Mutex<dyn Behavior>>) {
+        self.0.push((item.to_owned(), behavior));
+    }
+
+    // what the behaviors of carried items do this turn
+    pub(crate) fn run(&self, carried: impl Fn(&str) -> bool, room: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(item, _)| carried(item))
+            .filter_map(|(item, behavior)| {
+                behavior
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .turn(item, room)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for BehaviorHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(item, _)| item))
+            .finish()
+    }
+}
@@ -0,0 +1,305 @@
+// Small complete worlds built with the same methods a host uses to build a world in code, for
+// new users to play right away and for tests that want more than a room or two
+
+use super::Cli;
+use crate::{
+    entity::{
+        item::{Container, Thing},
+        Element, Item, Opening,
+    },
+    types::Metadata,
+};
+
+#[cfg(feature = "example-house")]
+use crate::{
+    entity::Pet,
+    types::{Ability, Recipe},
+};
+#[cfg(feature = "example-dungeon")]
+use crate::{
+    entity::{
+        item::{Armor, Gold, Light, Revival, Weapon},
+        Enemy,
+    },
+    types::EnemyStatus,
+};
+
+// the Rooms of an example after its first, each with its description
+fn add_rooms(cli: &Cli, rooms: &[(&str, &str)]) {
+    for (name, desc) in rooms {
+        cli.add_room(name, desc);
+    }
+}
+
+// the ways between the Rooms of an example, each as (room, directions, target, description)
+fn add_paths(cli: &Cli, paths: &[(&str, &[&str], &str, &str)]) {
+    for (room, directions, target, desc) in paths {
+        cli.add_path(
+            room,
+            directions,
+            target,
+            desc,
+            "Nothing about the way stands out.",
+        );
+    }
+}
+
+impl Cli {
+    #[cfg(feature = "example-dungeon")]
+    /// Construct a tiny example dungeon to fight through: four rooms, a goblin, a skeleton
+    /// king, and gear to find on the way
+    pub fn tiny_dungeon() -> Self {
+        let cli = Self::new(
+            "Entrance",
+            "You stand at the mouth of a dungeon. Cold air drifts up from below.",
+        );
+        add_rooms(
+            &cli,
+            &[
+                (
+                    "Guard Room",
+                    "A cramped guard room, with a broken table in the corner.",
+                ),
+                ("Armory", "Empty racks line the walls of the old armory."),
+                ("Crypt", "Stone coffins lie open around a cracked throne."),
+            ],
+        );
+        add_paths(
+            &cli,
+            &[
+                (
+                    "Entrance",
+                    &["north", "stairs"],
+                    "Guard Room",
+                    "Worn stairs lead north and down.",
+                ),
+                (
+                    "Guard Room",
+                    &["south", "stairs"],
+                    "Entrance",
+                    "The stairs lead back up to the south.",
+                ),
+                (
+                    "Guard Room",
+                    &["east"],
+                    "Armory",
+                    "A low doorway opens to the east.",
+                ),
+                (
+                    "Guard Room",
+                    &["north"],
+                    "Crypt",
+                    "A dark passage to the north smells of dust.",
+                ),
+                (
+                    "Armory",
+                    &["west"],
+                    "Guard Room",
+                    "The guard room is back to the west.",
+                ),
+                (
+                    "Crypt",
+                    &["south"],
+                    "Guard Room",
+                    "The passage leads south to the guard room.",
+                ),
+            ],
+        );
+
+        cli.add_item(
+            "Entrance",
+            Item::Light(Light::new("torch", "The torch burns steadily.", 1)),
+        );
+        cli.add_element(
+            "Entrance",
+            Element::new(
+                "rusted gate",
+                "A rusted gate hangs open beside the stairs.",
+                "Whoever kept it shut left long ago.",
+            ),
+        );
+        cli.add_item(
+            "Guard Room",
+            Item::Weapon(Weapon::new("short sword", "It is chipped but sharp.", 6)),
+        );
+        cli.spawn_enemy(
+            "Guard Room",
+            Enemy::new("goblin", "It clutches a rusty knife.", EnemyStatus::Asleep)
+                .with_desc("A goblin snores against the wall.")
+                .with_hp(7)
+                .with_ac(10)
+                .with_xp(50)
+                .with_damage(3)
+                .with_item(Item::Gold(Gold::new(5))),
+        );
+        cli.add_item(
+            "Armory",
+            Item::Armor(Armor::new("leather jerkin", "It is stiff with age.", 11)),
+        );
+        cli.add_item(
+            "Armory",
+            Item::Container(
+                Container::new("chest", "The chest is banded with iron.", Opening::Closed)
+                    .with_item(Item::Gold(Gold::new(20)))
+                    .with_item(Item::Revival(Revival::new(
+                        "healing potion",
+                        "It glows a faint red.",
+                        50,
+                    ))),
+            ),
+        );
+        cli.spawn_enemy(
+            "Crypt",
+            Enemy::new(
+                "skeleton king",
+                "Its crown sits crooked on a bare skull.",
+                EnemyStatus::Angry,
+            )
+            .with_desc("A skeleton king rises from the throne.")
+            .with_hp(15)
+            .with_ac(12)
+            .with_xp(200)
+            .with_damage(5)
+            .with_item(Item::Thing(Thing::new(
+                "crown",
+                "It is heavy, tarnished gold.",
+            ))),
+        );
+        cli.add_shrine("Entrance");
+        cli.set_metadata(
+            Metadata::new("The Tiny Dungeon")
+                .with_blurb("Clear a small dungeon of its last king.")
+                .with_intro("Somewhere below, a skeleton king still guards its crown."),
+        );
+        cli
+    }
+
+    #[cfg(feature = "example-house")]
+    /// Construct an example house with nothing to fight, whose descriptions teach the commands
+    pub fn tutorial_house() -> Self {
+        let cli = Self::new(
+            "Bedroom",
+            "You wake up in your bedroom. Type \"look\" or \"l\" to look around again, \
+             and \"take note\" to pick up the note.",
+        );
+        add_rooms(
+            &cli,
+            &[
+                (
+                    "Hallway",
+                    "A narrow hallway. Type \"i\" to see what you carry.",
+                ),
+                (
+                    "Kitchen",
+                    "A warm kitchen. Type \"recipes\" to see what you can make here.",
+                ),
+                (
+                    "Garden",
+                    "A small garden. Type \"examine cat\" to look closer at something.",
+                ),
+            ],
+        );
+        add_paths(
+            &cli,
+            &[
+                (
+                    "Bedroom",
+                    &["door", "south"],
+                    "Hallway",
+                    "A door leads south. Type \"s\" to go there.",
+                ),
+                (
+                    "Hallway",
+                    &["door", "north"],
+                    "Bedroom",
+                    "Your bedroom is to the north.",
+                ),
+                (
+                    "Hallway",
+                    &["east"],
+                    "Kitchen",
+                    "The kitchen is to the east.",
+                ),
+                (
+                    "Hallway",
+                    &["west", "back door"],
+                    "Garden",
+                    "The back door opens to the west.",
+                ),
+                (
+                    "Kitchen",
+                    &["west"],
+                    "Hallway",
+                    "The hallway is back to the west.",
+                ),
+                (
+                    "Garden",
+                    &["east", "back door"],
+                    "Hallway",
+                    "The back door leads east.",
+                ),
+            ],
+        );
+
+        cli.add_item(
+            "Bedroom",
+            Item::Thing(
+                Thing::new(
+                    "note",
+                    "It reads: Make some toast, then type \"feed cat\" in the garden.",
+                )
+                .with_desc("A note lies on your pillow."),
+            ),
+        );
+        cli.add_item(
+            "Kitchen",
+            Item::Container(
+                Container::new("cupboard", "It is painted blue.", Opening::Closed)
+                    .with_item(Item::Thing(Thing::new("bread", "A slice of bread."))),
+            ),
+        );
+        cli.add_element(
+            "Kitchen",
+            Element::new(
+                "stove",
+                "A stove sits against the wall. Type \"open cupboard\" to look inside the cupboard.",
+                "It is warm to the touch.",
+            )
+            .with_station("stove"),
+        );
+        cli.add_recipe(
+            "toast",
+            Recipe::new(
+                "stove",
+                Item::Thing(Thing::new("toast", "It is golden brown.")),
+                Ability::Wisdom,
+                0,
+            )
+            .with_ingredient("bread"),
+        );
+        cli.add_pet(
+            "Garden",
+            Pet::new("cat", "The cat watches you hopefully.", "toast")
+                .with_desc("A cat naps in the sun."),
+        );
+        cli.set_metadata(
+            Metadata::new("The Tutorial House")
+                .with_blurb("Learn to play by making breakfast.")
+                .with_intro("Morning light falls across your bed."),
+        );
+        cli
+    }
+
+    #[cfg(feature = "example-dungeon")]
+    /// Construct the example game built in, the tiny dungeon
+    pub fn example() -> Self {
+        Self::tiny_dungeon()
+    }
+
+    #[cfg(not(feature = "example-dungeon"))]
+    /// Construct the example game built in, the tutorial house in builds without the tiny
+    /// dungeon
+    pub fn example() -> Self {
+        Self::tutorial_house()
+    }
+}
@@ -0,0 +1,44 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Receives anonymous gameplay events so hosts can find where players get stuck;
+/// nothing leaves the process unless the host sends it somewhere
+pub trait Metrics: Send {
+    // only the verb is reported, never the rest of what the player typed
+    fn command_parsed(&mut self, _verb: &str) {}
+
+    fn parse_failed(&mut self) {}
+
+    fn player_died(&mut self, _room: &str) {}
+
+    // called once when the player quits or dies
+    fn game_over(&mut self, _turns: u32) {}
+}
+
+// a shared handle so one collector can gather events from many games
+#[derive(Clone, Default)]
+pub(crate) struct MetricsHook(Option<Arc<Mutex<dyn Metrics>>>);
+
+impl MetricsHook {
+    pub(crate) fn new(metrics: Arc<Mutex<dyn Metrics>>) -> Self {
+        Self(Some(metrics))
+    }
+
+    pub(crate) fn record(&self, event: impl FnOnce(&mut dyn Metrics)) {
+        if let Some(metrics) = &self.0 {
+            event(&mut *metrics.lock().unwrap_or_else(|err| err.into_inner()));
+        }
+    }
+}
+
+impl fmt::Debug for MetricsHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "MetricsHook(Some(..))"
+        } else {
+            "MetricsHook(None)"
+        })
+    }
+}
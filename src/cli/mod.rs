@@ -1,55 +1,358 @@
+#[cfg(feature = "serde")]
+use std::path::Path;
 use std::{
     cell::{Cell, RefCell},
-    convert::TryInto,
-    fs::{self, File},
-    io::{BufReader, Read, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    entity::{Element, Enemy, Entity, Item},
-    input::{read_line, CmdTokens, Lexer, Parser},
+    entity::{Ally, Element, Enemy, Entity, Item, Pathway, Pet},
+    input::{normalize, same_name, CmdTokens, Lexer, Parser},
     player::Player,
-    types::{Action, Class, CmdResult, Race},
+    prose,
+    types::{
+        Action, Answer, Calendar, Check, Class, CmdResult, Consequence, Cue, Deck, Direction,
+        Director, Finding, Hazard, HpDisplay, InputContext, InventoryFormat, Listener, LoadError,
+        LootTable, Metadata, Pause, Pronouns, Quest, QuestGraph, Race, Recipe, Region, Reply, Role,
+        Rumble, Sequence, StatusLine, Stir, Variations, WorldStats,
+    },
     world::World,
+    Dice, RandomSource,
 };
 
+#[cfg(feature = "terminal")]
+use crate::input::read_line;
+#[cfg(feature = "pack")]
+use crate::Archive;
+#[cfg(feature = "serde")]
+use crate::{
+    types::{read_text, WorldFormat},
+    world::{
+        include::{self, ReadInclude},
+        lazy,
+    },
+};
+
+mod audio;
+mod behavior;
+#[cfg(any(feature = "example-dungeon", feature = "example-house"))]
+mod examples;
+mod metrics;
+mod profile;
+#[cfg(feature = "serde")]
+mod save_info;
+mod save_store;
+mod settings;
+mod speech;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod timing;
+mod transcript;
+
+use audio::AudioHook;
+pub use audio::{Audio, Haptics};
+pub use behavior::Behavior;
+use behavior::BehaviorHook;
+pub use metrics::Metrics;
+use metrics::MetricsHook;
+use profile::{Phase, Profile, Stopwatch, PROFILE_TURNS};
+#[cfg(feature = "serde")]
+pub use save_info::SaveInfo;
+use save_store::SaveStoreHook;
+pub use save_store::{FileStore, SaveStore};
+use settings::{on_off, parse_on_off, Settings};
+pub use speech::Speech;
+use speech::SpeechHook;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteGame;
+pub use timing::{NoDelay, RealTime, Timing};
+pub use transcript::Transcript;
+
+const STATUS_WIDTH: usize = 80;
+const UNDO_LIMIT: usize = 100;
+
 /// The Cli type provides a simple way to interface into the mechanics of Kingslayer with custom worlds
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+///
+/// A Cli is `Send`, so a session can be moved to another thread or held across an `.await`.
+/// It is not `Sync`; share one between threads behind a `Mutex`.
+/// Each game rolls its own dice, so a seeded game replays the same on any thread.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Cli {
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     running: Cell<bool>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     num_moves: Cell<u32>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     last_cmd_res: RefCell<CmdResult>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     last_successful_cmd: RefCell<CmdTokens>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    metadata: RefCell<Metadata>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    require_acknowledgement: Cell<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    acknowledged: Cell<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    settings: RefCell<Settings>,
+    #[cfg_attr(feature = "serde", serde(default))]
     player: RefCell<Box<Player>>,
     world: RefCell<Box<World>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pristine: RefCell<Option<Arc<Snapshot>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    undo: RefCell<Vec<Snapshot>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    quit: Cell<bool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    role: Cell<Role>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    contexts: RefCell<Vec<InputContext>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    metrics: RefCell<MetricsHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    transcript: RefCell<Option<Transcript>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    speech: RefCell<SpeechHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    audio: RefCell<AudioHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    behaviors: RefCell<BehaviorHook>,
+    // debug cheats that have been turned on, kept in saves so a loaded game is still marked;
+    // giveall stays for the rest of the game
+    #[cfg_attr(feature = "serde", serde(default))]
+    cheats: RefCell<Vec<String>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    variations: RefCell<Variations>,
+    // set from outside, as by a Ctrl-C handler, to stop a running fight after the current round
+    #[cfg_attr(feature = "serde", serde(skip))]
+    interrupt: Arc<AtomicBool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    save_store: RefCell<SaveStoreHook>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    profile: RefCell<Profile>,
+    // when the last command came in, and how much play has gone by since the last autosave
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_command: Cell<Option<Instant>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    unsaved_play: Cell<Duration>,
+    // how much real time makes a turn for hosts that tick, and what is left over toward the next
+    #[cfg_attr(feature = "serde", serde(skip))]
+    turn_length: Cell<Duration>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    unticked: Cell<Duration>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dice: Dice,
+}
+
+// a Cli must stay Send for async hosts, so fail to compile if a field ever breaks that
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<Cli>();
+};
+
+// a longer gap between commands is time away from the game, which autosaves do not count
+const IDLE_GAP: Duration = Duration::from_secs(2 * 60);
+
+// the save that the command loop writes on its own
+const AUTOSAVE: &str = "autosave";
+
+// the most rounds one "fight" command plays out, so a fight that cannot be won still ends
+const FIGHT_ROUNDS: usize = 20;
+
+// the most turns one "wait" command lets pass
+const WAIT_TURNS: u32 = 20;
+// the most turns "wait until" fast-forwards before giving up
+const WAIT_UNTIL_TURNS: u32 = 100;
+
+// What a wait stops for
+#[derive(Clone, Debug, PartialEq)]
+enum Until {
+    // anything at all happening
+    Anything,
+    // an Enemy turning up in the Room
+    Enemy,
+    // the move counter reaching a number
+    Turn(u32),
+    // something happening that mentions these words, like "wolf"
+    Words(String),
+}
+
+impl Until {
+    fn parse(event: &str) -> Self {
+        if let Some(turn) = event
+            .strip_prefix("turn ")
+            .and_then(|turn| turn.parse().ok())
+        {
+            return Self::Turn(turn);
+        }
+        match event {
+            "" | "something" | "anything" | "something happens" => Self::Anything,
+            "enemy" | "enemies" | "company" => Self::Enemy,
+            _ => Self::Words(event.to_lowercase()),
+        }
+    }
+}
+
+// The state of a game at one moment; cheap to take because Rooms are shared copy-on-write
+#[derive(Clone, Debug)]
+struct Snapshot {
+    world: Box<World>,
+    player: Box<Player>,
+    num_moves: u32,
 }
 
 impl Cli {
-    /// Construct from a RON file
-    pub fn from_file(path: &str) -> Self {
-        let metadata = fs::metadata(path).expect("Error getting metadata from file");
-        let world_file = File::open(path).expect("Unable to open world file");
-        let mut world_file_reader = BufReader::new(world_file);
-        let mut data = String::with_capacity(metadata.len().try_into().unwrap());
-        world_file_reader
-            .read_to_string(&mut data)
-            .expect("Unable to read string from world file");
+    #[cfg(feature = "serde")]
+    /// Construct from a RON file, or say why it could not be loaded
+    pub fn from_file(path: &str) -> Result<Self, LoadError> {
+        let data = read_text(path)?;
+        debug!("loading world from {} ({} bytes)", path, data.len());
+        Self::read(&data, Path::new(path), &include::read_file)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Construct from a string containing RON, or say why it is not a valid world
+    pub fn from_ron_str(ron: &str) -> Result<Self, LoadError> {
+        ron::de::from_str(ron).map_err(LoadError::World)
+    }
+
+    // construct from the text of a world file read from `main`, reading the files it includes
+    // with `read`; the World is read on its own so it knows where they are
+    #[cfg(feature = "serde")]
+    fn read(ron: &str, main: &Path, read: &ReadInclude) -> Result<Self, LoadError> {
+        let start = match lazy::world_start(ron) {
+            Some(start) => start,
+            // a file laid out in a way the scan does not follow includes relative to the
+            // working directory
+            None => return Self::from_ron_str(ron),
+        };
+        let (world, end) = World::read_at(ron, start, main, read).map_err(LoadError::World)?;
+        let cli = Self::from_ron_str(&lazy::stub_world(ron, start, end))?;
+        cli.world.replace(Box::new(world));
+        Ok(cli)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Construct from a RON file, keeping each Room as text until it is first needed so huge
+    /// worlds take little memory; at most `hydrated` entered Rooms are kept read at once, the least
+    /// recently entered going back to text, or every Room stays read once needed if it is 0
+    ///
+    /// Loading only checks that the file is laid out as RON and reads the Room the player starts
+    /// in, so another Room that is broken is reported when it is first read: a command that would
+    /// enter it gives the error instead and leaves the game as it was, and `lint` and `validate`
+    /// list every such Room. Only entering a Room counts toward the limit: a Room read for
+    /// anything else, like looking through a doorway or a whole-world check such as `validate`,
+    /// stays read until it is entered and later left behind, so those checks read the whole
+    /// world into memory
+    pub fn from_file_lazy(path: &str, hydrated: usize) -> Result<Self, LoadError> {
+        let data = read_text(path)?;
+        debug!("loading world lazily from {} ({} bytes)", path, data.len());
+        Self::read_lazily(&data, hydrated, Path::new(path), &include::read_file)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Construct from a string containing RON, reading each Room only when it is first needed;
+    /// see `from_file_lazy`
+    pub fn from_ron_str_lazy(ron: &str, hydrated: usize) -> Result<Self, LoadError> {
+        Self::read_lazily(ron, hydrated, Path::new(""), &include::read_file)
+    }
 
-        ron::de::from_str(&data).expect("Error creating world from RON file.")
+    #[cfg(feature = "serde")]
+    fn read_lazily(
+        ron: &str,
+        hydrated: usize,
+        main: &Path,
+        read: &ReadInclude,
+    ) -> Result<Self, LoadError> {
+        match lazy::split_rooms(ron) {
+            Some((stubbed, rooms)) => {
+                let cli = Self::read(&stubbed, main, read)?;
+                cli.world.borrow_mut().read_lazily(rooms, hydrated)?;
+                Ok(cli)
+            }
+            // a file laid out in a way the scan does not follow is read whole
+            None => Self::read(ron, main, read),
+        }
+    }
+
+    #[cfg(feature = "pack")]
+    /// Construct from the first world file in an archive made by `Archive::pack` with the
+    /// same key, such as one embedded in the game with `include_bytes!`
+    pub fn from_packed(packed: &[u8], key: &[u8]) -> Result<Self, LoadError> {
+        let archive = Archive::unpack(packed, key).map_err(LoadError::Archive)?;
+        let (name, world) = archive
+            .files()
+            .next()
+            .map(|(name, world)| (name.to_owned(), world.to_owned()))
+            .ok_or_else(|| LoadError::Archive(String::from("The archive holds no world.")))?;
+        debug!("loading world {} from a packed archive", name);
+        // the files the world includes come from the archive too
+        let read = move |path: &Path| {
+            archive
+                .get(&path.to_string_lossy())
+                .map(str::to_owned)
+                .ok_or_else(|| String::from("It is not in the archive."))
+        };
+        Self::read(&world, Path::new(&name), &read)
+    }
+
+    /// Construct a procedurally generated dungeon; the same seed always builds the same dungeon
+    pub fn generate(num_rooms: usize, seed: u64) -> Self {
+        Self {
+            world: RefCell::new(Box::new(World::generate(num_rooms, seed))),
+            ..Self::default()
+        }
+    }
+
+    /// Construct a world of one Room for the player to start in, to build on with `add_room`,
+    /// `add_path`, and the other methods that add to the world
+    pub fn new(start: &str, desc: &str) -> Self {
+        Self {
+            world: RefCell::new(Box::new(World::new(start, desc))),
+            ..Self::default()
+        }
+    }
+
+    /// Construct a skeleton world from a Trizbort XML map, with rooms and connections
+    /// but TODO placeholders for every description
+    pub fn from_trizbort(xml: &str) -> Result<Self, LoadError> {
+        Ok(Self {
+            world: RefCell::new(Box::new(World::from_trizbort(xml).map_err(LoadError::Map)?)),
+            ..Self::default()
+        })
+    }
+
+    /// Check the world for broken references, unreachable rooms, and unused content,
+    /// for authors to run in CI
+    pub fn lint(&self) -> Vec<Finding> {
+        self.world.borrow().lint()
+    }
+
+    /// Which quests unlock which, where they are completed, and who holds the items they need,
+    /// for authors to draw with `to_dot` or `to_json`
+    pub fn quest_graph(&self) -> QuestGraph {
+        self.world.borrow().quest_graph()
+    }
+
+    /// Count the world's rooms, items, and enemies, with an estimate of how long it takes to play
+    pub fn world_stats(&self) -> WorldStats {
+        self.world.borrow().stats()
     }
 
-    /// Construct from a string containing RON
-    pub fn from_ron_str(ron: &str) -> Self {
-        ron::de::from_str(ron).expect("Error creating world from string.")
+    /// List any problems with the world, such as paths that lead to missing rooms
+    pub fn validate(&self) -> Vec<String> {
+        self.world.borrow().validate()
     }
 
+    #[cfg(feature = "terminal")]
     /// Prompts the user for input from stdin
     pub fn prompt(&self, prompt: &str) -> String {
         loop {
@@ -81,6 +384,12 @@ Some available commands:
     Game commands
         quit            quit the game
         save            save the game state to [object].save.ron or world.save.ron
+        undo            take back the last turn
+        restart         start the game over from the beginning
+        about           show the title, author, and release of the world
+        settings        list the game settings
+        set             change a setting, e.g. set undo off
+            set autosave 5 also saves every 5 minutes of play
 
     Explore around the world
         go, enter       move in a direction or through a listed entrance
@@ -92,177 +401,1800 @@ Some available commands:
                up, down, (any other listed entrance)
         
         r, again        repeat last command
+        z, wait         let a turn pass; wait 5 lets up to 5 pass, stopping if anything happens
+            wait until enemy, wait until turn 40, or wait until wolf fast-forwards to that event
         l, look         look around the room
+            look north peeks through an exit by the light of a lit lamp
+            look through a window or keyhole to see into another room
+        knock           knock on a door, e.g. knock on door
+        listen          listen at a door, or look under door to check the gap beneath it
         open | close    open/close an item or pathway
+        sit, get on     sit on or climb into furniture such as a chair or cage
+        stand, get off  get off or out of furniture
 
     Manipulate items found in the world
         take            put an item from the room into your inventory
         drop            drop an item from your inventory into the room
+        give            hand an item to someone here, who will remember it, e.g. give apple to squire
         i, inventory    print the contents of your inventory
+        assign          put an item in a numbered quick slot, e.g. assign potion to 1, then use 1;
+                        unassign 1 empties the slot
+        bestiary        list the enemies you have met
+        codex           list the items you have come across
         x, examine      show additional information about an item
+        compare         compare two items, e.g. compare sword with axe
         draw, equip     use an item from your inventory as your default weapon
-        don, put on     don a set of armor to increase your armor class
+            equip best picks the hardest-hitting weapon you carry
+        don, put on     don a set of armor to increase your armor class, or a disguise
+                        to slip past the guards of a faction
+        doff            take off a disguise
         kill            attack an enemy with your main hand or a chosen weapon
+            kill alone goes after the enemy you last attacked, or the only one here
+        fight           attack an enemy every turn until it falls or your HP runs low,
+                        e.g. fight goblin; set fight threshold 40 changes when you break off
+        grapple         wrestle an enemy into your grip
+        push, shove     push an enemy through an exit, e.g. push ogre off bridge
+        pull, drag      move a heavy object through an exit and follow it, e.g. pull crate south
+        smash, break    break through a weak floor or ceiling, e.g. smash floorboards with hammer
+        continue        press on with a long journey after fighting off whatever waylaid you
+        camp            make camp out in the wilds with a tent or firewood
+        cook            cook raw food over a campfire, e.g. cook fish
+        brew, make      make a recipe at a cauldron or campfire, e.g. brew healing potion
+        recipes         list what can be made at the stations here
+        gather, forage  gather from a bush, fishing spot, or vein, e.g. fish pond
+        feed            feed a treat to an animal to tame it, e.g. feed dog
+        name            name a tame animal, e.g. name dog rex
+        command         command a tame animal to search, carry an item, or drop what it carries,
+                        e.g. command dog to search
+        tell            tell a companion to wait, follow, or do what they can, which they do on
+                        their turn, e.g. tell thief to pick lock on gate
+        tie, untie      tie a rope to something sturdy to climb by, e.g. tie rope to post
+        lower           lower an item on a rope, e.g. lower lantern down; haul rope brings it back
+        use, cast       use an item such as a summoning charm
+        turn, set       turn a combination dial one number at a time or all at once,
+                        e.g. turn dial to 3, set dial to 7-2-9; reset dial starts over
+        play            play an instrument, or a learned song on one, e.g. play song of opening on lute
+        learn           learn songs from a teacher or a scroll, e.g. learn song of opening from bard;
+                        songs lists the songs you know
+        answer          hear a riddle out and answer it, e.g. answer sphinx
+        shout           shout a word for those in nearby rooms to hear, e.g. shout sesame
+        light | douse   light or put out a lamp or torch
 
     Manage your character
         heal            replenish some HP
+        pray            pray at a shrine to be returned there on death
         increase        increase a chosen ability score by 1 if stat points are available
-        c | stats          display information on the state of your character",
+        c | stats          display information on the state of your character
+        karma           show how good or wicked your deeds have been, in worlds that track it
+        date            show what day it is, in worlds that keep a calendar",
         )
     }
 
+    #[cfg(feature = "terminal")]
     /// Start a typical game for the command line
     pub fn start(&self) {
+        self.start_with_timing(&RealTime);
+    }
+
+    #[cfg(feature = "terminal")]
+    /// Start a game for the command line, carrying out pauses with the given Timing
+    pub fn start_with_timing(&self, timing: &dyn Timing) {
         if !self.running.get() {
             self.create_character();
         }
 
+        println!("{}", self.intro());
         println!("Type \"help\" if you are unfamiliar with text-based games.\n");
-        println!("{}", self.ask("l"));
+        self.print_paged(&self.ask("l"));
 
         self.running.set(true);
         while self.running.get() && self.player.borrow().is_alive() {
-            println!("{}", self.ask(&self.prompt("")));
+            let output = self.ask(&self.prompt(""));
+            if let Some(pause) = self.pause() {
+                timing.pause(pause);
+            }
+            self.print_paged(&output);
+            if let Some(saved) = self.autosave_if_due(Instant::now()) {
+                println!("\n{}", saved);
+            }
+        }
+    }
+
+    /// The title and starting message of the world, for the start of a new game
+    pub fn intro(&self) -> String {
+        let metadata = self.metadata.borrow();
+        let parts: Vec<String> = vec![
+            Some(metadata.title().to_owned()),
+            metadata.content_notice(),
+            Some(metadata.intro().to_owned()),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect();
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", parts.join("\n\n"))
+        }
+    }
+
+    /// Hold back the first turn until the player accepts the world's content warnings
+    pub fn require_acknowledgement(&self, required: bool) {
+        self.require_acknowledgement.set(required);
+    }
+
+    /// Accept the content warnings on the player's behalf, for hosts that ask in their own UI
+    pub fn acknowledge(&self) {
+        self.acknowledged.set(true);
+    }
+
+    /// Whether input is being held back until the content warnings are accepted
+    pub fn needs_acknowledgement(&self) -> bool {
+        self.require_acknowledgement.get()
+            && !self.acknowledged.get()
+            && self.metadata.borrow().content_notice().is_some()
+    }
+
+    /// Set the title, author, and other information about the world
+    pub fn set_metadata(&self, metadata: Metadata) {
+        self.metadata.replace(metadata);
+    }
+
+    pub fn metadata(&self) -> Metadata {
+        self.metadata.borrow().clone()
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load the save kept under the name in the save store, as "world" for a plain "save"
+    pub fn load_saved(&self, name: &str) -> Result<Self, LoadError> {
+        let ron = self
+            .save_store
+            .borrow()
+            .read(name)
+            .map_err(LoadError::Store)?;
+        let save = self.load_save(&ron)?;
+        save.save_store.replace(self.save_store.borrow().clone());
+        Ok(save)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Look inside a save from the save store without loading it, as for a save slot
+    pub fn inspect_saved(&self, name: &str) -> Result<SaveInfo, LoadError> {
+        let ron = self
+            .save_store
+            .borrow()
+            .read(name)
+            .map_err(LoadError::Store)?;
+        SaveInfo::from_ron_str(&ron)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load a save, refusing one made from a different world or release
+    pub fn load_save(&self, ron: &str) -> Result<Self, LoadError> {
+        let mut save: Self = ron::de::from_str(ron).map_err(|err| {
+            debug!("could not read save: {}", err);
+            LoadError::Save(err)
+        })?;
+        // a loaded game keeps rolling the dice the host gave this one
+        save.dice = self.dice.clone();
+        debug!("loaded save ({} bytes)", ron.len());
+        if self
+            .metadata
+            .borrow()
+            .is_compatible(&save.metadata.borrow())
+        {
+            Ok(save)
+        } else {
+            Err(LoadError::Incompatible {
+                save: Box::new(save.metadata()),
+                game: Box::new(self.metadata()),
+            })
         }
     }
 
+    #[cfg(feature = "terminal")]
     pub fn create_character(&self) {
-        self.player
-            .borrow_mut()
-            .set_race(Race::select_race(&self.prompt(&Race::race_prompt())));
+        self.set_race(Race::select_race(&self.prompt(&Race::race_prompt())));
+        self.set_class(Class::select_class(&self.prompt(&Class::class_prompt())));
+        self.set_pronouns(Pronouns::select_pronouns(
+            &self.prompt(&Pronouns::pronouns_prompt()),
+        ));
+    }
 
-        self.player
-            .borrow_mut()
-            .set_class(Class::select_class(&self.prompt(&Class::class_prompt())));
+    /// Choose the player's race without prompting, for hosts that have no terminal
+    pub fn set_race(&self, race: Race) {
+        self.player.borrow_mut().set_race(race);
+    }
+
+    /// Choose the player's pronouns without prompting, for hosts that have no terminal
+    pub fn set_pronouns(&self, pronouns: Pronouns) {
+        self.player.borrow_mut().set_pronouns(pronouns);
+    }
+
+    /// The pronouns other players see used for this player
+    pub fn pronouns(&self) -> Pronouns {
+        self.player.borrow().pronouns().clone()
+    }
+
+    /// Choose the player's class without prompting, for hosts that have no terminal
+    pub fn set_class(&self, class: Class) {
+        self.player.borrow_mut().set_class(class);
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            world: self.world.borrow().clone(),
+            player: self.player.borrow().clone(),
+            num_moves: self.num_moves.get(),
+        }
+    }
+
+    fn restore(&self, snapshot: Snapshot) {
+        self.world.replace(snapshot.world);
+        self.player.replace(snapshot.player);
+        self.num_moves.set(snapshot.num_moves);
+        self.last_cmd_res.replace(CmdResult::default());
+    }
+
+    /// Restore the game to the moment before the last turn was taken, repeatable up to 100 turns back
+    pub fn undo(&self) -> String {
+        let snapshot = self.undo.borrow_mut().pop();
+        if let Some(snapshot) = snapshot {
+            self.restore(snapshot);
+            format!("Undone.\n\n{}", self.world.borrow().look().output())
+        } else {
+            String::from("There is nothing to undo.")
+        }
+    }
+
+    /// Turn undo history on or off; sessions that never undo can skip the cost of keeping it
+    pub fn set_undo(&self, enabled: bool) {
+        self.settings.borrow_mut().undo = enabled;
+        if !enabled {
+            self.undo.borrow_mut().clear();
+        }
+    }
+
+    /// Start over from the world as it was when play began
+    pub fn restart(&self) -> String {
+        let pristine = self.pristine.borrow().clone();
+        if let Some(pristine) = pristine {
+            self.restore((*pristine).clone());
+            self.undo.borrow_mut().clear();
+            self.contexts.borrow_mut().clear();
+        }
+        format!("Restarted.\n\n{}", self.world.borrow().look().output())
+    }
+
+    /// Start over from the world as it was when play began, keeping the current character
+    pub fn new_game_plus(&self) -> String {
+        let player = self.player.borrow().clone();
+        self.restart();
+        self.player.replace(player);
+        self.player.borrow_mut().restore();
+        format!(
+            "A new journey begins.\n\n{}",
+            self.world.borrow().look().output()
+        )
+    }
+
+    /// Bring the character over from another game, as between the chapters of a campaign,
+    /// and describe where they arrive
+    pub fn carry_over(&self, from: &Self) -> String {
+        let player = from.player.borrow().clone();
+        self.player.replace(player);
+        self.world.borrow().look().output().to_owned()
     }
 
     /// Handle user input and return the results of commands and events
     pub fn ask(&self, input: &str) -> String {
-        let command = Lexer::lex(input);
+        let recording = if self.transcript.borrow().is_some() {
+            let world = self.world.borrow();
+            let room = world.get_curr_room();
+            Some((room.name().to_owned(), room.exits()))
+        } else {
+            None
+        };
 
-        let res = if let Some(last_cmd) = self.last_cmd_res.borrow().request_input() {
-            let last_cmd = if let Some(verb) = command.verb() {
-                if last_cmd.obj().is_some() {
-                    last_cmd.with_obj_prep(verb)
-                } else {
-                    last_cmd.with_obj(verb)
-                }
+        // undo and reload swap out the Player and World, so cheats are reapplied every turn
+        let god = self.is_cheating("god");
+        self.player.borrow_mut().set_god(god);
+        self.world
+            .borrow_mut()
+            .set_noclip(self.is_cheating("noclip"));
+
+        let room = self.world.borrow().get_curr_room().name().to_owned();
+        let hp = self.player.borrow().hp();
+        let output = self.dice.rolling(|| {
+            let typed = normalize(input);
+            let output = if let Some(enemy) = self.fight_target(&typed) {
+                self.fight(&enemy)
+            } else if let Some((turns, until)) = self.wait_turns(&typed) {
+                self.wait(turns, &until)
             } else {
-                last_cmd
+                self.answer(&typed)
             };
-            Parser::parse(
-                &last_cmd,
-                &mut self.world.borrow_mut(),
-                &mut self.player.borrow_mut(),
-            )
-        } else {
-            match command.verb() {
-                Some("quit") => self.quit(),
-                Some("save") => self.save(command.obj()),
-                Some("again") => Parser::parse(
-                    &self.last_successful_cmd.borrow(),
-                    &mut self.world.borrow_mut(),
-                    &mut self.player.borrow_mut(),
-                ),
-                _ => Parser::parse(
-                    &command,
-                    &mut self.world.borrow_mut(),
-                    &mut self.player.borrow_mut(),
-                ),
+            self.timed(Phase::Render, || {
+                let output = if self.settings.borrow().variety {
+                    self.variations.borrow_mut().vary(&output)
+                } else {
+                    output
+                };
+                self.speech.borrow().speak(&output);
+                self.play_cues(&room, hp);
+                if self.settings.borrow().status_line && !self.needs_acknowledgement() {
+                    format!("{}\n\n{}", self.status().render(STATUS_WIDTH), output)
+                } else {
+                    output
+                }
+            })
+        });
+        self.profile.borrow_mut().finish(self.num_moves.get());
+
+        if let Some((room, exits)) = recording {
+            if let Some(transcript) = self.transcript.borrow_mut().as_mut() {
+                transcript.record(&room, exits, input, &output, self.cheats.borrow().clone());
             }
-        };
+        }
+        output
+    }
 
-        if res.succeeded()
-            && !self.last_cmd_res.borrow().has_request()
-            && command.verb() != Some("again")
-        {
-            self.last_successful_cmd.replace(command);
+    // a Room's theme starts when the player arrives from a Room with a different name,
+    // and the controller rumbles when the player dies or loses a lot of health at once
+    fn play_cues(&self, left: &str, hp: i32) {
+        let mut world = self.world.borrow_mut();
+        let mut cues = world.take_cues();
+        let player = self.player.borrow();
+        let rumble = if hp > 0 && !player.is_alive() {
+            Some(Rumble::Heavy)
+        } else {
+            Rumble::for_damage(hp - player.hp(), player.hp_cap())
+        };
+        if let Some(rumble) = rumble {
+            cues.push(Cue::Rumble(rumble));
+        }
+        let room = world.get_curr_room();
+        if room.name() != left {
+            if let Some(music) = room.music() {
+                cues.push(Cue::Music(music.to_owned()));
+            }
         }
+        self.audio.borrow().play(&cues);
+    }
 
-        self.last_cmd_res.replace(res.clone());
+    /// Vary the wording of often repeated responses like "Taken." using the seeded RNG;
+    /// off by default so that output stays exact
+    pub fn set_variety(&self, variety: bool) {
+        self.settings.borrow_mut().variety = variety;
+    }
 
-        if res.is_active() {
-            self.num_moves.set(self.num_moves.get() + 1);
+    /// Break off a fight once HP falls to this percent of the maximum; 0 fights to the end
+    pub fn set_fight_threshold(&self, percent: u32) {
+        self.settings.borrow_mut().fight_threshold = percent;
+    }
 
-            format!("{}{}", res.output(), self.combat())
-        } else {
-            res.output().to_owned()
+    /// Save on its own after this many minutes of play, as well as whenever the player saves;
+    /// 0 turns it off
+    pub fn set_autosave(&self, minutes: u32) {
+        self.settings.borrow_mut().autosave = minutes;
+    }
+
+    /// Count the play since the last command toward the next autosave, and save once there
+    /// has been enough and the game may be saved, saying where; the command loop calls this
+    /// after every command, and hosts with their own loop can do the same
+    pub fn autosave_if_due(&self, now: Instant) -> Option<String> {
+        let minutes = self.settings.borrow().autosave;
+        let last = self.last_command.replace(Some(now));
+        if minutes == 0 {
+            return None;
+        }
+
+        let played = last.map_or(Duration::ZERO, |last| {
+            now.saturating_duration_since(last).min(IDLE_GAP)
+        });
+        let unsaved = self.unsaved_play.get() + played;
+        if unsaved < Duration::from_secs(u64::from(minutes) * 60) || self.can_save().is_err() {
+            self.unsaved_play.set(unsaved);
+            return None;
         }
+
+        self.unsaved_play.set(Duration::ZERO);
+        debug!("autosaving after {:?} of play", unsaved);
+        Some(match self.write_save(AUTOSAVE) {
+            Ok(location) => format!("(Autosaved to '{}'.)", location),
+            Err(err) => format!("(Could not autosave: {})", err),
+        })
     }
 
-    // manages actions taken by Enemies in the current room
-    fn combat(&self) -> String {
-        let mut events_str =
-            String::with_capacity(50 * self.world.borrow().get_curr_room().enemies().len());
+    /// Let this much real time make a turn for `tick`; zero, the default, keeps the world
+    /// still between commands
+    pub fn set_turn_length(&self, turn_length: Duration) {
+        self.turn_length.set(turn_length);
+        self.unticked.set(Duration::ZERO);
+    }
 
-        for enemy in self.world.borrow_mut().get_curr_room_mut().enemies_mut() {
-            if enemy.is_angry() && enemy.is_alive() {
-                let enemy_damage = enemy.damage();
+    /// Let real time go by between commands for hosts that run in real time, like a MUD server,
+    /// advancing the clock, calendar, regrowth, happenings, wandering enemies, and the time of
+    /// summoned allies by a turn for each turn length that has passed, without a command,
+    /// combat, or counting a move; returns what happens, which is empty when nothing does
+    pub fn tick(&self, elapsed: Duration) -> String {
+        let turn_length = self.turn_length.get();
+        if turn_length.is_zero() || self.is_over() || self.needs_acknowledgement() {
+            return String::new();
+        }
 
-                events_str.push_str(
-                    &self
-                        .player
-                        .borrow_mut()
-                        .take_damage(enemy.name(), enemy_damage),
-                );
+        let unticked = self.unticked.get() + elapsed;
+        let turns = unticked.as_nanos() / turn_length.as_nanos();
+        let left = unticked.as_nanos() % turn_length.as_nanos();
+        self.unticked.set(Duration::from_nanos(left as u64));
+        // a host that stalls does not fast-forward the world by hours
+        let turns = turns.min(u128::from(WAIT_TURNS)) as u32;
+        if turns > 0 {
+            debug!("ticking {} turns", turns);
+        }
 
-                self.player.borrow_mut().engage_combat();
+        let mut events = String::new();
+        self.dice.rolling(|| {
+            for _ in 0..turns {
+                events.push_str(&self.world.borrow_mut().pass_turns(1));
+                events.push_str(&self.happen());
+                events.push_str(&self.stir(1, false));
+                if let Some(res) = self.world.borrow_mut().wander() {
+                    events.push('\n');
+                    events.push_str(res.output());
+                }
+                events.push_str(&self.world.borrow_mut().fade_allies());
             }
-            if !enemy.is_alive() {
-                events_str.push_str(&format!("\nYou gained {} XP.", enemy.xp()));
-                self.player.borrow_mut().disengage_combat();
-                self.player.borrow_mut().gain_xp(enemy.xp());
+        });
+        events.trim_start_matches('\n').to_owned()
+    }
+
+    /// A flag that stops a running fight after the current round once set, as from a Ctrl-C
+    /// handler; it is cleared when the fight stops for it
+    pub fn interrupt_handle(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.interrupt)
+    }
+
+    // the enemy named by "fight goblin", when the input is not an answer to something else
+    fn fight_target(&self, input: &str) -> Option<String> {
+        if self.needs_acknowledgement()
+            || !self.contexts.borrow().is_empty()
+            || self.last_cmd_res.borrow().has_request()
+        {
+            return None;
+        }
+        let command = Lexer::lex(input);
+        match (command.verb(), command.obj()) {
+            (Some("fight"), Some(enemy)) => Some(enemy.to_owned()),
+            _ => None,
+        }
+    }
+
+    // the most turns to let pass for "wait 5" or "wait until wolf", and what to stop for, when
+    // the input is not an answer to something else
+    fn wait_turns(&self, input: &str) -> Option<(u32, Until)> {
+        if self.needs_acknowledgement()
+            || !self.contexts.borrow().is_empty()
+            || self.last_cmd_res.borrow().has_request()
+        {
+            return None;
+        }
+        let command = Lexer::lex(input);
+        let obj = match command.verb() {
+            Some("wait") | Some("z") => command.obj()?,
+            _ => return None,
+        };
+        match (obj.parse::<u32>(), obj.split_once(' ')) {
+            (Ok(turns), _) if turns > 0 => Some((turns.min(WAIT_TURNS), Until::Anything)),
+            (_, Some(("until", event))) => Some((WAIT_UNTIL_TURNS, Until::parse(event))),
+            _ if obj == "until" => Some((WAIT_UNTIL_TURNS, Until::Anything)),
+            _ => None,
+        }
+    }
+
+    // let turns pass one at a time until the awaited event, reporting everything that happened
+    // along the way in order; being hurt always cuts a wait short
+    fn wait(&self, turns: u32, until: &Until) -> String {
+        let passed = Player::wait().output().to_owned();
+        let mut events = Vec::new();
+        let mut waited = 0;
+        let mut done = false;
+        while waited < turns && !done {
+            waited += 1;
+            let hp = self.player.borrow().hp();
+            let output = self.answer("wait");
+            let event = match output.strip_prefix(&passed) {
+                Some(event) => event.trim_start_matches('\n').to_owned(),
+                None => output,
+            };
+            done = match until {
+                Until::Anything => !event.is_empty(),
+                Until::Enemy => !self.world.borrow().get_curr_room().enemies().is_empty(),
+                Until::Turn(turn) => self.num_moves.get() >= *turn,
+                Until::Words(words) => event.to_lowercase().contains(words.as_str()),
+            } || self.player.borrow().hp() < hp;
+            if !event.is_empty() {
+                events.push(event);
             }
         }
-        self.world.borrow_mut().clear_dead_enemies();
+        let mut report = format!("{} ({})", passed, prose::count(waited as usize, "turn"));
+        for event in events {
+            report.push_str(&format!("\n{}", event));
+        }
+        if !done && *until != Until::Anything {
+            report.push_str("\nYou give up waiting.");
+        }
+        report
+    }
 
-        if !self.player.borrow().is_alive() {
-            events_str.push_str("\n\nYou died. Farewell.");
-        } else {
-            events_str.push_str(&self.player.borrow_mut().level_up());
+    // attack every turn until the enemy falls, HP runs low, or the player interrupts
+    fn fight(&self, enemy: &str) -> String {
+        let attack = format!("attack {}", enemy);
+        let mut rounds = Vec::new();
+        for _ in 0..FIGHT_ROUNDS {
+            rounds.push(self.answer(&attack));
+            if !self.last_cmd_res.borrow().is_active() || !self.player.borrow().is_alive() {
+                return rounds.join("\n");
+            }
+            let standing = self
+                .world
+                .borrow()
+                .get_curr_room()
+                .find_enemy(enemy)
+                .is_some_and(|enemy| enemy.is_alive());
+            if !standing {
+                return rounds.join("\n");
+            }
+            let player = self.player.borrow();
+            let threshold = self.settings.borrow().fight_threshold;
+            if player.hp() * 100 <= (player.hp_cap() * threshold) as i32 {
+                rounds.push(format!(
+                    "You break off the fight with {} HP left.",
+                    player.hp()
+                ));
+                return rounds.join("\n");
+            }
+            if self.interrupt.swap(false, Ordering::SeqCst) {
+                rounds.push(String::from("You hold off and take stock."));
+                return rounds.join("\n");
+            }
         }
-        events_str.shrink_to_fit();
-        events_str
+        rounds.push(String::from(
+            "The fight drags on, and you step back to take stock.",
+        ));
+        rounds.join("\n")
     }
 
-    pub fn add_element(&self, room: &str, el: Element) {
-        self.world.borrow_mut().add_element(room, el)
+    /// Replace the weighted wordings used for a response when variety is on,
+    /// or stop varying it when given none
+    pub fn set_variations(&self, message: &str, pool: &[(u32, &str)]) {
+        self.variations.borrow_mut().set_pool(message, pool);
     }
 
-    pub fn add_item(&self, room: &str, item: Item) {
-        self.world.borrow_mut().add_item(room, item)
+    /// How many of the most recent wordings of a response are held back before repeating
+    pub fn set_variation_window(&self, window: usize) {
+        self.variations.borrow_mut().set_window(window);
     }
 
-    pub fn spawn_enemy(&self, room: &str, enemy: Enemy) {
-        self.world.borrow_mut().spawn_enemy(room, enemy)
+    /// Whether a debug cheat (god, noclip, or giveall) is in effect
+    pub fn is_cheating(&self, cheat: &str) -> bool {
+        self.cheats.borrow().iter().any(|c| c == cheat)
     }
 
-    fn quit(&self) -> CmdResult {
-        self.running.set(false);
-        CmdResult::new(Action::Passive, String::from("\nFarewell.\n"))
+    /// Send sound effect and music cues to a front-end's audio backend as commands trigger them
+    pub fn set_audio(&self, audio: Arc<Mutex<dyn Audio>>) {
+        self.audio.borrow_mut().set_audio(audio);
     }
 
-    fn save(&self, name: Option<&str>) -> CmdResult {
-        let saved = ron::ser::to_string(&self).expect("Error serializing world save file.");
+    /// Send rumble hints for big hits, deaths, and explosions to a controller
+    pub fn set_haptics(&self, haptics: Arc<Mutex<dyn Haptics>>) {
+        self.audio.borrow_mut().set_haptics(haptics);
+    }
+
+    /// Play the given music cue whenever the player enters the room
+    pub fn set_music(&self, room: &str, cue: &str) {
+        self.world.borrow_mut().set_music(room, cue);
+    }
+
+    /// Send every response to a text-to-speech engine as plain sentences, without the status
+    /// line, bars, or abbreviations, whatever the visual output settings are
+    pub fn set_speech(&self, speech: Arc<Mutex<dyn Speech>>) {
+        self.speech.replace(SpeechHook::new(speech));
+    }
+
+    /// Run a behavior every turn the player carries the named item, alongside any daemon the
+    /// world gives it
+    pub fn add_behavior(&self, item: &str, behavior: Arc<Mutex<dyn Behavior>>) {
+        self.behaviors.borrow_mut().add(item, behavior);
+    }
+
+    /// Keep saves, including autosaves, in the given store instead of files in the working
+    /// directory
+    pub fn set_save_store(&self, store: Arc<Mutex<dyn SaveStore>>) {
+        self.save_store.replace(SaveStoreHook::new(store));
+    }
+
+    /// Start recording every command and its output, discarding any earlier recording
+    pub fn record_transcript(&self) {
+        self.transcript.replace(Some(Transcript::default()));
+    }
 
-        let filename = if let Some(name) = name {
-            format!("{}.save.ron", name)
+    /// The recording so far, or None if recording was never started
+    pub fn transcript(&self) -> Option<Transcript> {
+        self.transcript.borrow().clone()
+    }
+
+    fn answer(&self, input: &str) -> String {
+        if self.needs_acknowledgement() {
+            return self.content_gate(input);
+        }
+        let command = self.timed(Phase::Parse, || Lexer::lex(input));
+
+        if self.pristine.borrow().is_none() {
+            self.pristine.replace(Some(Arc::new(self.snapshot())));
+        }
+        let left = self.world.borrow().get_curr_room().name().to_owned();
+        let context = self.contexts.borrow_mut().pop();
+        // input that does not answer a pending question sets it aside and is handled like any
+        // other command, unless the parser cannot make sense of it either
+        let mut set_aside = None;
+        let context = match context {
+            Some(InputContext::Question(prompt)) if prompt.answer(input).is_none() => {
+                set_aside = Some(prompt);
+                None
+            }
+            context => context,
+        };
+        if context.is_none() {
+            match command.verb() {
+                Some("undo") => return self.undo(),
+                Some("restart") => return self.restart(),
+                _ => (),
+            }
+        }
+        let was_over = self.is_over();
+        let undo = self.settings.borrow().undo;
+        // a Room of a lazily loaded world may turn out to be broken, and is then not entered
+        #[cfg(feature = "serde")]
+        let lazy = self.world.borrow().reads_lazily();
+        #[cfg(not(feature = "serde"))]
+        let lazy = false;
+        let before = if undo || lazy {
+            Some(self.snapshot())
         } else {
-            String::from("world.save.ron")
+            None
         };
 
-        if let Ok(mut file) = File::create(&filename) {
-            if let Ok(()) = file.write_all(saved.as_bytes()) {
-                CmdResult::new(
-                    Action::Passive,
-                    format!("Moves: {}\nSaved to '{}'.", self.num_moves.get(), filename),
+        let answering = context.is_some();
+        let mut res = self.timed(Phase::Resolve, || {
+            if let Some(context) = context {
+                self.respond(context, input)
+            } else if let Some(last_cmd) = self.last_cmd_res.borrow().request_input() {
+                let last_cmd = if let Some(verb) = command.verb() {
+                    if last_cmd.obj().is_some() {
+                        last_cmd.with_obj_prep(verb)
+                    } else {
+                        last_cmd.with_obj(verb)
+                    }
+                } else {
+                    last_cmd
+                };
+                Parser::parse(
+                    &last_cmd,
+                    &mut self.world.borrow_mut(),
+                    &mut self.player.borrow_mut(),
                 )
             } else {
-                CmdResult::new(Action::Failed, String::from("Error saving world."))
+                match command.verb() {
+                    Some("quit") => self.quit(),
+                    Some("save") => self.save(command.obj()),
+                    Some("about") | Some("version") => {
+                        CmdResult::new(Action::Passive, self.metadata.borrow().about())
+                    }
+                    Some("settings") | Some("options") => CmdResult::new(
+                        Action::Passive,
+                        self.settings.borrow().list(
+                            self.player.borrow().hp_display(),
+                            self.player.borrow().inventory_format(),
+                            self.player.borrow().auto_equip(),
+                        ),
+                    ),
+                    Some("set") if !Parser::turns_dial(&command, &self.world.borrow()) => {
+                        self.change_setting(&command)
+                    }
+                    Some("tp") | Some("spawn") | Some("setflag") | Some("reload")
+                    | Some("worldstats") | Some("god") | Some("noclip") | Some("giveall")
+                    | Some("profile")
+                        if self.role.get().can_debug() =>
+                    {
+                        self.debug(&command)
+                    }
+                    Some("again") => Parser::parse(
+                        &self.last_successful_cmd.borrow(),
+                        &mut self.world.borrow_mut(),
+                        &mut self.player.borrow_mut(),
+                    ),
+                    _ => Parser::parse(
+                        &command,
+                        &mut self.world.borrow_mut(),
+                        &mut self.player.borrow_mut(),
+                    ),
+                }
             }
-        } else {
-            CmdResult::new(Action::Failed, String::from("Error saving world."))
+        });
+
+        #[cfg(feature = "serde")]
+        if let Some(before) = before.as_ref().filter(|_| lazy) {
+            let broken = self.world.borrow().broken_room();
+            if let Some(err) = broken {
+                self.restore(before.clone());
+                return err.to_string();
+            }
+        }
+
+        if let Some(prompt) = set_aside {
+            if res.is_misunderstood() {
+                res = CmdResult::new(Action::Failed, prompt.question().to_owned())
+                    .with_prompt(prompt);
+            }
+        }
+
+        debug!("{:?} parsed as {:?}: {:?}", input, command, res.action());
+        if let Some(context) = res.take_context() {
+            debug!("waiting on {:?}", context);
+            self.contexts.borrow_mut().push(context);
         }
+
+        if !answering {
+            if res.is_misunderstood() {
+                self.metrics.borrow().record(|m| m.parse_failed());
+            } else if let Some(verb) = command.verb() {
+                self.metrics.borrow().record(|m| m.command_parsed(verb));
+            }
+        }
+
+        if res.succeeded()
+            && !answering
+            && !self.last_cmd_res.borrow().has_request()
+            && command.verb() != Some("again")
+        {
+            self.last_successful_cmd.replace(command);
+        }
+
+        self.last_cmd_res.replace(res.clone());
+
+        let output = if res.is_active() {
+            if let Some(before) = before.filter(|_| undo) {
+                let mut undo = self.undo.borrow_mut();
+                if undo.len() == UNDO_LIMIT {
+                    undo.remove(0);
+                }
+                undo.push(before);
+            }
+            let elapsed = self.world.borrow_mut().take_elapsed();
+            self.num_moves.set(self.num_moves.get() + 1 + elapsed);
+            let events = self.timed(Phase::Events, || {
+                let events = self.world.borrow_mut().pass_turns(1 + elapsed);
+                let moved = self.world.borrow().get_curr_room().name() != left;
+                events + &self.happen() + &self.stir(1 + elapsed, moved)
+            });
+
+            let output = format!(
+                "{}{}{}",
+                res.output(),
+                events,
+                self.timed(Phase::Combat, || self.combat())
+            );
+            let (hp, hp_cap) = {
+                let player = self.player.borrow();
+                (player.hp(), player.hp_cap())
+            };
+            self.world.borrow_mut().pace(hp, hp_cap);
+            if !self.player.borrow().is_alive() {
+                self.last_cmd_res.replace(res.with_pause(Pause::Dramatic));
+                if !was_over {
+                    let room = self.world.borrow().get_curr_room().name().to_owned();
+                    debug!("player died in {}", room);
+                    self.metrics.borrow().record(|m| m.player_died(&room));
+                }
+            }
+            output
+        } else {
+            res.output().to_owned()
+        };
+        self.player
+            .borrow_mut()
+            .discover(self.world.borrow().get_curr_room());
+        let room = self.world.borrow().get_curr_room().name().to_owned();
+        if room != left {
+            debug!("moved from {} to {}", left, room);
+        }
+
+        if !was_over && self.is_over() {
+            let turns = self.num_moves.get();
+            self.metrics.borrow().record(|m| m.game_over(turns));
+        }
+
+        output
+    }
+
+    // time one part of the turn for the "profile" command
+    fn timed<T>(&self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let stopwatch = Stopwatch::start();
+        let res = f();
+        self.profile.borrow_mut().add(phase, stopwatch.elapsed());
+        res
+    }
+
+    /// Paginate output longer than the given number of lines behind a [MORE] prompt; 0 disables paging
+    pub fn set_page_height(&self, height: usize) {
+        self.settings.borrow_mut().page_height = height;
+    }
+
+    /// Split output into pages of the configured height
+    pub fn pages(&self, output: &str) -> Vec<String> {
+        let height = self.settings.borrow().page_height;
+        if height == 0 {
+            return vec![output.to_owned()];
+        }
+        output
+            .lines()
+            .collect::<Vec<&str>>()
+            .chunks(height)
+            .map(|page| page.join("\n"))
+            .collect()
+    }
+
+    #[cfg(feature = "terminal")]
+    // print each page, waiting for a keypress between them
+    fn print_paged(&self, output: &str) {
+        let pages = self.pages(output);
+        for (i, page) in pages.iter().enumerate() {
+            println!("{}", page);
+            if i + 1 < pages.len() {
+                read_line("[MORE]");
+            }
+        }
+    }
+
+    /// Report anonymous gameplay events to a collector that may be shared between games
+    pub fn set_metrics(&self, metrics: Arc<Mutex<dyn Metrics>>) {
+        self.metrics.replace(MetricsHook::new(metrics));
+    }
+
+    /// Whether the player has quit or died
+    pub fn is_over(&self) -> bool {
+        self.quit.get() || !self.player.borrow().is_alive()
+    }
+
+    /// How long a front-end should wait before showing the output of the last command
+    pub fn pause(&self) -> Option<Pause> {
+        self.last_cmd_res.borrow().pause()
+    }
+
+    /// How many enemies with this name the player has killed
+    pub fn kills(&self, enemy_name: &str) -> u32 {
+        self.player.borrow().bestiary().kills(enemy_name)
+    }
+
+    /// Current room, score, and turn count for front-ends that draw their own header
+    pub fn status(&self) -> StatusLine {
+        StatusLine::new(
+            self.world.borrow().get_curr_room().name(),
+            self.player.borrow().score(),
+            self.num_moves.get(),
+        )
+    }
+
+    /// The compass points, up and down, and in and out that lead somewhere from the player's
+    /// room, with where each goes, for hosts that draw a compass rose
+    pub fn compass(&self) -> Vec<(Direction, String)> {
+        self.world
+            .borrow()
+            .get_curr_room()
+            .compass()
+            .into_iter()
+            .map(|(direction, room)| (direction, room.to_owned()))
+            .collect()
+    }
+
+    /// Names of the rooms one exit away from the player
+    pub fn neighbors(&self) -> Vec<String> {
+        self.world
+            .borrow()
+            .get_curr_room()
+            .neighbors()
+            .iter()
+            .map(|room| (*room).to_owned())
+            .collect()
+    }
+
+    /// Print a status line above the output of every command
+    pub fn show_status_line(&self, show: bool) {
+        self.settings.borrow_mut().status_line = show;
+    }
+
+    // give a dead player one last chance through a revival item or a bound shrine
+    fn intercept_death(&self) -> String {
+        if let Some(res) = self.player.borrow_mut().revive_with_item() {
+            return res;
+        }
+        let shrine = self.world.borrow_mut().return_to_shrine();
+        if let Some(res) = shrine {
+            let mut player = self.player.borrow_mut();
+            player.disengage_combat();
+            format!("{}{}", res, player.revive(25))
+        } else {
+            String::new()
+        }
+    }
+
+    // how the player died, if they did, and their last chance, ending the game if it fails
+    fn mourn(&self) -> String {
+        let mut events = String::new();
+        if !self.player.borrow().is_alive() {
+            if let Some(death) = self.player.borrow().death() {
+                events.push_str(&format!("\n{}", death));
+            }
+            events.push_str(&self.intercept_death());
+        }
+        if !self.player.borrow().is_alive() {
+            events.push_str("\n\nYou died. Farewell.");
+        }
+        events
+    }
+
+    // manages actions taken by Enemies in the current room
+    fn combat(&self) -> String {
+        let mut events_str =
+            String::with_capacity(50 * self.world.borrow().get_curr_room().enemies().len());
+
+        events_str.push_str(&self.world.borrow_mut().ally_turn());
+        events_str.push_str(&self.world.borrow_mut().check_morale());
+
+        let mut knocked_back = false;
+        let mut cues = Vec::new();
+        for enemy in self.world.borrow_mut().get_curr_room_mut().enemies_mut() {
+            if enemy.is_angry() && enemy.is_alive() && enemy.is_grappled() {
+                let strength_mod = self.player.borrow().strength_mod();
+                events_str.push_str(&enemy.break_free(strength_mod));
+                self.player.borrow_mut().engage_combat();
+            } else if enemy.is_angry() && enemy.is_alive() && enemy.recover() {
+                events_str.push_str(&format!(
+                    "\n{} reels, recovering {} senses.",
+                    prose::capitalize(&enemy.the()),
+                    enemy.possessive()
+                ));
+            } else if !self.player.borrow().is_alive() {
+                continue;
+            } else if let (true, true, Some(save)) =
+                (enemy.is_angry(), enemy.is_alive(), enemy.deadly_attack())
+            {
+                events_str.push_str(&self.player.borrow_mut().save_against(save));
+                self.player.borrow_mut().engage_combat();
+            } else if enemy.is_angry() && enemy.is_alive() {
+                let enemy_damage = enemy.damage();
+
+                events_str.push_str(
+                    &self
+                        .player
+                        .borrow_mut()
+                        .take_damage(&enemy.the(), enemy_damage),
+                );
+                knocked_back |= enemy.knocks_back();
+                if let Some(sound) = enemy.sound() {
+                    cues.push(Cue::Sound(sound.to_owned()));
+                }
+
+                self.player.borrow_mut().engage_combat();
+            }
+            if !enemy.is_alive() {
+                cues.push(Cue::Rumble(Rumble::Light));
+                events_str.push_str(&format!("\nYou gained {} XP.", enemy.xp()));
+                self.player.borrow_mut().disengage_combat();
+                self.player.borrow_mut().gain_xp(enemy.xp());
+                self.player.borrow_mut().record_kill(enemy.name());
+                self.player.borrow_mut().adjust_karma(enemy.karma());
+            }
+        }
+        for cue in cues {
+            self.world.borrow_mut().cue(cue);
+        }
+        self.world.borrow_mut().clear_dead_enemies();
+
+        if knocked_back && self.player.borrow().is_alive() {
+            let forced = self.world.borrow_mut().force_player();
+            if let Some((res, hazard)) = forced {
+                events_str.push_str(&res);
+                if let Some(hazard) = hazard {
+                    events_str.push_str(&self.player.borrow_mut().suffer_hazard(&hazard));
+                }
+            }
+        }
+        if !self.world.borrow().any_angry_enemies() {
+            self.player.borrow_mut().disengage_combat();
+        }
+
+        events_str.push_str(&self.mourn());
+        if self.player.borrow().is_alive() {
+            events_str.push_str(&self.player.borrow_mut().level_up());
+        }
+        events_str.shrink_to_fit();
+        events_str
+    }
+
+    pub fn add_ally(&self, room: &str, ally: Ally) {
+        self.world.borrow_mut().add_ally(room, ally)
+    }
+
+    /// Make the player a true member of a faction, whose guards let them pass without a disguise
+    pub fn join_faction(&self, faction: &str) {
+        self.player.borrow_mut().join_faction(faction)
+    }
+
+    /// Place an animal that can be tamed with its treat to follow the player
+    pub fn add_pet(&self, room: &str, pet: Pet) {
+        self.world.borrow_mut().add_pet(room, pet)
+    }
+
+    /// Add an empty Room kept under its name, unless the world already has a Room by that name
+    pub fn add_room(&self, name: &str, desc: &str) {
+        self.world.borrow_mut().add_room(name, desc)
+    }
+
+    /// Add a way out of a Room to another, taken by any of its directions
+    pub fn add_path(
+        &self,
+        room: &str,
+        directions: &[&str],
+        target: &str,
+        desc: &str,
+        inspect: &str,
+    ) {
+        self.world
+            .borrow_mut()
+            .add_path(room, Pathway::new(directions, target, desc, inspect))
+    }
+
+    /// Add a way out of a Room built up with a door, lock, guards, or the like
+    pub fn add_pathway(&self, room: &str, path: Pathway) {
+        self.world.borrow_mut().add_path(room, path)
+    }
+
+    pub fn add_element(&self, room: &str, el: Element) {
+        self.world.borrow_mut().add_element(room, el)
+    }
+
+    /// Take an item out of the player's inventory, unless they cannot give it up
+    pub fn release_item(&self, name: &str) -> Result<Item, String> {
+        self.player
+            .borrow_mut()
+            .release(name)
+            .map(|item| *item)
+            .map_err(|res| res.output().to_owned())
+    }
+
+    /// Put an item into the player's inventory, such as one released from another game; none of
+    /// the world it came from comes with it
+    pub fn receive_item(&self, item: Item) {
+        self.player.borrow_mut().take_back(Box::new(item))
+    }
+
+    pub fn add_item(&self, room: &str, item: Item) {
+        self.world.borrow_mut().add_item(room, item)
+    }
+
+    pub fn spawn_enemy(&self, room: &str, enemy: Enemy) {
+        self.world.borrow_mut().spawn_enemy(room, enemy)
+    }
+
+    /// Have something in a room answer, and perhaps open a way, when a word is shouted nearby
+    pub fn add_listener(&self, room: &str, listener: Listener) {
+        self.world.borrow_mut().add_listener(room, listener)
+    }
+
+    /// Have steps in a room that must be done in order, starting over when one is done out of turn
+    pub fn add_sequence(&self, room: &str, sequence: Sequence) {
+        self.world.borrow_mut().add_sequence(room, sequence)
+    }
+
+    /// Let Listeners near the player hear a shout made outside of the parser, as between
+    /// hosted players, returning their answers
+    pub fn hear(&self, words: &str) -> Vec<String> {
+        self.world.borrow_mut().hear(words)
+    }
+
+    pub fn add_hazard(&self, room: &str, hazard: Hazard) {
+        self.world.borrow_mut().add_hazard(room, hazard)
+    }
+
+    /// List the inventory in one list or under categories, in pickup or alphabetical order
+    pub fn set_inventory_format(&self, format: InventoryFormat) {
+        self.player.borrow_mut().set_inventory_format(format)
+    }
+
+    /// Equip a better weapon as soon as it is picked up
+    pub fn set_auto_equip(&self, auto_equip: bool) {
+        self.player.borrow_mut().set_auto_equip(auto_equip)
+    }
+
+    /// Choose how the player's HP is shown: exact numbers, words, or a bar
+    pub fn set_hp_display(&self, hp_display: HpDisplay) {
+        self.player.borrow_mut().set_hp_display(hp_display)
+    }
+
+    /// Put an item or element out of reach unless the player is on or in the named furniture
+    pub fn set_reach(&self, room: &str, name: &str, furniture: &str) {
+        self.world.borrow_mut().set_reach(room, name, furniture)
+    }
+
+    /// Let a hosted player use debug commands: authors get tp, spawn, and setflag,
+    /// and admins can also reload the world
+    pub fn set_role(&self, role: Role) {
+        self.role.set(role)
+    }
+
+    pub fn role(&self) -> Role {
+        self.role.get()
+    }
+
+    /// How good or wicked the player's deeds have been so far
+    pub fn karma(&self) -> i32 {
+        self.player.borrow().karma()
+    }
+
+    /// Add to or take from the player's karma, as for a choice made in a quest
+    pub fn adjust_karma(&self, change: i32) {
+        self.player.borrow_mut().adjust_karma(change)
+    }
+
+    /// Say something else for a verb that does nothing, such as "jump" or "xyzzy", in the named
+    /// room, or everywhere no room says otherwise
+    pub fn set_response(&self, room: Option<&str>, verb: &str, response: &str) {
+        self.world.borrow_mut().set_response(room, verb, response)
+    }
+
+    /// Say something else when a verb is done to one thing, like taking a sword set in stone,
+    /// in the named room, or everywhere no room says otherwise
+    pub fn add_reply(&self, room: Option<&str>, reply: Reply) {
+        self.world.borrow_mut().add_reply(room, reply)
+    }
+
+    /// Count days off the clock, with festivals and story events that happen on set days
+    pub fn set_calendar(&self, calendar: Calendar) {
+        self.world.borrow_mut().set_calendar(calendar)
+    }
+
+    /// Draw now and then from a deck of happenings while the player is out in a region
+    pub fn set_deck(&self, deck: Deck) {
+        self.world.borrow_mut().set_deck(deck)
+    }
+
+    /// Let the player see their karma with the karma command
+    pub fn set_shows_karma(&self, shows_karma: bool) {
+        self.world.borrow_mut().set_shows_karma(shows_karma)
+    }
+
+    /// Refuse saves while enemies are upon the player and in rooms marked dangerous, for
+    /// tension; autosaves wait for the next safe moment
+    pub fn restrict_saves(&self, restrict: bool) {
+        self.world.borrow_mut().set_restrict_saves(restrict)
+    }
+
+    /// Mark a room too perilous to save in when saves are restricted
+    pub fn set_dangerous(&self, room: &str, dangerous: bool) {
+        self.world.borrow_mut().set_dangerous(room, dangerous)
+    }
+
+    /// Whether the game may be saved now, or why not; hosts that save on their own should
+    /// ask first
+    pub fn can_save(&self) -> Result<(), String> {
+        match self.world.borrow().save_refusal() {
+            Some(refusal) => Err(refusal.to_owned()),
+            None => Ok(()),
+        }
+    }
+
+    /// Name a quest of the world and the quests it needs done first
+    pub fn add_quest(&self, name: &str, quest: Quest) {
+        self.world.borrow_mut().add_quest(name, quest)
+    }
+
+    /// Mark a quest as complete, freeing its quest items to be dropped
+    pub fn complete_quest(&self, quest: &str) {
+        self.player.borrow_mut().complete_quest(quest)
+    }
+
+    /// Limit how many items can lie on the floor of a room
+    pub fn set_floor_capacity(&self, room: &str, capacity: usize) {
+        self.world.borrow_mut().set_floor_capacity(room, capacity)
+    }
+
+    /// Let players hosted together by a SessionManager fight each other in a room
+    pub fn allow_pvp(&self, room: &str) {
+        self.world.borrow_mut().allow_pvp(room)
+    }
+
+    // whether the player stands in a room where players may fight each other
+    pub(crate) fn in_pvp_room(&self) -> bool {
+        self.world.borrow().get_curr_room().is_pvp()
+    }
+
+    // roll the player's damage against another player with the main hand or a named weapon,
+    // which can be a quick slot, as when attacking an enemy
+    pub(crate) fn attack_player(&self, weapon: Option<&str>) -> Result<u32, String> {
+        let mut player = self.player.borrow_mut();
+        let weapon = match weapon {
+            Some(weapon) => match player.slotted(weapon) {
+                Ok(weapon) => Some(weapon),
+                Err(res) => return Err(res.output().to_owned()),
+            },
+            None => None,
+        };
+        let attack = self.dice.rolling(|| match &weapon {
+            Some(weapon) => player.attack_with(weapon),
+            None => player.attack_main(),
+        });
+        match (attack.damage(), weapon) {
+            (Some(damage), _) => {
+                player.engage_combat();
+                Ok(damage)
+            }
+            (None, Some(weapon)) => Err(CmdResult::dont_have(&weapon).output().to_owned()),
+            (None, None) => Err(String::from("You have no weapon in hand.")),
+        }
+    }
+
+    // take a blow from another player just as a blow from an enemy is taken, returning whether
+    // it landed and what the player saw; a slain player can be saved by a revival item or shrine
+    // like any other death
+    pub(crate) fn defend(&self, attacker: &str, damage: u32) -> (bool, String) {
+        let (landed, mut report) = self
+            .dice
+            .rolling(|| self.player.borrow_mut().take_blow(attacker, damage));
+        self.player.borrow_mut().engage_combat();
+        if !self.player.borrow().is_alive() {
+            report.push_str(&self.intercept_death());
+        }
+        (landed, report.trim_start().to_owned())
+    }
+
+    // whether an enemy in the current room answers to the name, which an attack reaches before
+    // any player of that name
+    pub(crate) fn has_enemy_here(&self, name: &str) -> bool {
+        self.world
+            .borrow()
+            .get_curr_room()
+            .find_enemy(name)
+            .is_some()
+    }
+
+    // the name the current room is kept under, which is the same in every game of one world
+    pub(crate) fn room_key(&self) -> String {
+        self.world.borrow().curr_room_key().to_owned()
+    }
+
+    // how many items lying in a room have exactly this name
+    pub(crate) fn count_items(&self, room: &str, name: &str) -> usize {
+        self.world.borrow().count_items(room, name)
+    }
+
+    // take an item with exactly this name out of a room
+    pub(crate) fn remove_item(&self, room: &str, name: &str) -> Option<Item> {
+        self.world
+            .borrow_mut()
+            .remove_item(room, name)
+            .map(|item| *item)
+    }
+
+    // give up a percent of the inventory, as when slain by another player
+    pub(crate) fn forfeit_items(&self, percent: u32) -> Vec<Item> {
+        self.player
+            .borrow_mut()
+            .forfeit(percent)
+            .into_iter()
+            .map(|item| *item)
+            .collect()
+    }
+
+    pub fn add_shrine(&self, room: &str) {
+        self.world.borrow_mut().add_shrine(room)
+    }
+
+    pub fn add_loot_table(&self, name: &str, table: LootTable) {
+        self.world.borrow_mut().add_loot_table(name, table)
+    }
+
+    /// Define something that can be brewed or cooked at a station such as a cauldron or campfire
+    pub fn add_recipe(&self, name: &str, recipe: Recipe) {
+        self.world.borrow_mut().add_recipe(name, recipe)
+    }
+
+    /// Define an overland region whose wandering enemies roam every room placed in it
+    pub fn add_region(&self, name: &str, region: Region) {
+        self.world.borrow_mut().add_region(name, region)
+    }
+
+    /// Mark what lies in a room as belonging to someone, so that anyone there who sees the
+    /// player take it remembers the theft
+    pub fn set_owned(&self, room: &str, owned: bool) {
+        self.world.borrow_mut().set_owned(room, owned)
+    }
+
+    /// Join two rooms both ways, the way back leading in the opposite direction, like "south"
+    /// for "north"; directions without an opposite need a path each way instead
+    pub fn connect(&self, from: &str, direction: &str, to: &str) -> Result<(), String> {
+        self.world.borrow_mut().connect(from, direction, to)
+    }
+
+    /// Say this when the player tries a direction from the Room that leads nowhere, instead
+    /// of "You cannot go that way."
+    pub fn add_dead_end(&self, room: &str, direction: &str, message: &str) {
+        self.world
+            .borrow_mut()
+            .add_dead_end(room, direction, message);
+    }
+
+    /// Place a room in a region so its wandering enemies can find the player there
+    pub fn set_region(&self, room: &str, region: &str) {
+        self.world.borrow_mut().set_region(room, region)
+    }
+
+    /// Raise the chance of wandering and waylaying enemies the longer the player goes without
+    /// a fight, and lower it while their HP falls, within the director's bounds
+    pub fn set_director(&self, director: Director) {
+        self.world.borrow_mut().set_director(director)
+    }
+
+    /// Replace the random number generator used for every roll in this game, and in its clones
+    pub fn set_random_source<R: RandomSource + 'static>(&self, source: R) {
+        self.dice.set_source(source);
+    }
+
+    /// Seed this game's random number generator so that it can be replayed exactly, whatever
+    /// other games share the thread
+    pub fn seed(&self, seed: u64) {
+        self.dice.seed(seed);
+    }
+
+    // nothing but accepting the content warnings gets past them
+    fn content_gate(&self, input: &str) -> String {
+        if matches!(input.trim().to_lowercase().as_str(), "accept" | "yes" | "y") {
+            self.acknowledge();
+            self.world.borrow().look().output().to_owned()
+        } else {
+            format!(
+                "{}\nType \"accept\" to continue.",
+                self.metadata.borrow().content_notice().unwrap_or_default()
+            )
+        }
+    }
+
+    // route input to the innermost context; a context that still wants input comes back on the result
+    fn respond(&self, context: InputContext, input: &str) -> CmdResult {
+        match context {
+            InputContext::Question(prompt) => match prompt.answer(input) {
+                Some(Answer::Reply(reply)) => CmdResult::new(Action::Passive, reply.to_owned()),
+                Some(Answer::Command(cmd)) => Parser::parse(
+                    cmd,
+                    &mut self.world.borrow_mut(),
+                    &mut self.player.borrow_mut(),
+                ),
+                None => {
+                    CmdResult::new(Action::Failed, prompt.question().to_owned()).with_prompt(prompt)
+                }
+            },
+            InputContext::Riddle(name) => match input.trim().to_lowercase().as_str() {
+                "leave" | "bye" | "nevermind" => {
+                    CmdResult::new(Action::Passive, format!("You step away from the {}.", name))
+                }
+                _ => {
+                    let (res, solved, penalty) =
+                        self.world.borrow_mut().answer_riddle(&name, input);
+                    if solved {
+                        return res;
+                    }
+                    let hurt = if penalty > 0 {
+                        self.player.borrow_mut().suffer(penalty)
+                    } else {
+                        String::new()
+                    };
+                    // a penalty can kill as surely as a blow in a fight
+                    let res = CmdResult::new(
+                        Action::Failed,
+                        format!("{}{}{}", res.output(), hurt, self.mourn()),
+                    );
+                    if self.player.borrow().is_alive() {
+                        res.with_riddle(&name)
+                    } else {
+                        res.with_pause(Pause::Dramatic)
+                    }
+                }
+            },
+            InputContext::Conversation(mut conversation) => {
+                if let Some(check) = conversation.take_check(input) {
+                    let (res, provoked) = self.attempt(conversation.speaker(), &check);
+                    return if provoked {
+                        res
+                    } else {
+                        res.with_conversation(conversation)
+                    };
+                }
+                let (res, talking) = conversation.respond(input);
+                if talking {
+                    res.with_conversation(conversation)
+                } else {
+                    res
+                }
+            }
+        }
+    }
+
+    // roll a dialogue check and carry out what follows, returning the result and whether a
+    // fight broke out, which ends the conversation
+    fn attempt(&self, speaker: &str, check: &Check) -> (CmdResult, bool) {
+        let passed = self.player.borrow().passes(check);
+        let (reply, consequences) = check.outcome(passed);
+        let res = format!("{} {}", if passed { "Success!" } else { "Failure." }, reply);
+        self.world.borrow_mut().spend_check(speaker, check.topic());
+        let (received, provoked) = self.carry_out(consequences);
+        (
+            CmdResult::new(Action::Active, format!("{}{}", res, received)),
+            provoked,
+        )
+    }
+
+    // carry out what follows from a check or a happening, returning what the player receives
+    // and whether a fight broke out
+    fn carry_out(&self, consequences: &[Consequence]) -> (String, bool) {
+        let mut res = String::new();
+        let mut provoked = false;
+        let mut world = self.world.borrow_mut();
+        for consequence in consequences {
+            match consequence {
+                Consequence::Gift(item) => {
+                    res.push_str(&format!("\nYou receive {}.", prose::a(item.name())));
+                    self.player
+                        .borrow_mut()
+                        .take(item.name(), Some(item.clone()));
+                }
+                Consequence::Calm => world.calm_enemies(),
+                Consequence::Provoke => {
+                    world.provoke_enemies();
+                    provoked = true;
+                }
+                Consequence::Karma(change) => self.player.borrow_mut().adjust_karma(*change),
+                Consequence::Arrive(ally) => world.arrive(*ally.clone()),
+                Consequence::Open(direction) => world.swing_open(direction),
+            }
+        }
+        (res, provoked)
+    }
+
+    // draw from the World's deck of happenings, telling of whatever happens
+    fn happen(&self) -> String {
+        let happening = self.world.borrow_mut().happening();
+        match happening {
+            Some(happening) => {
+                debug!("happening: {}", happening.text());
+                let (received, _) = self.carry_out(happening.effects());
+                format!("\n{}{}", happening.text(), received)
+            }
+            None => String::new(),
+        }
+    }
+
+    // tell of what carried Things do on their own as turns pass; a compass only points after a
+    // move
+    fn stir(&self, turns: u32, moved: bool) -> String {
+        let stirs = self.player.borrow_mut().stir_daemons(turns);
+        let mut events = String::new();
+        for (item, stir) in stirs {
+            let the = prose::capitalize(&prose::the(&item, false));
+            let event = match stir {
+                Stir::Say(line) => line,
+                Stir::Count(left) => format!(
+                    "{} ticks. {} left.",
+                    the,
+                    prose::count(left as usize, "turn")
+                ),
+                Stir::Point(_) if !moved => continue,
+                Stir::Point(room) => {
+                    let world = self.world.borrow();
+                    match world.way_toward(&room) {
+                        Some(direction) if direction.is_custom() => {
+                            format!("{} points toward the {}.", the, direction)
+                        }
+                        Some(direction) => format!("{} points {}.", the, direction),
+                        None if same_name(world.get_curr_room().name(), &room) => {
+                            format!("{} spins in place.", the)
+                        }
+                        None => format!("{} wavers, pointing nowhere.", the),
+                    }
+                }
+                Stir::GoOff(text, damage) => {
+                    debug!("{} went off", item);
+                    if damage > 0 {
+                        text + &self.player.borrow_mut().suffer(damage)
+                    } else {
+                        text
+                    }
+                }
+            };
+            events.push('\n');
+            events.push_str(&event);
+        }
+
+        let player = self.player.borrow();
+        let room = self.world.borrow().get_curr_room().name().to_owned();
+        for event in self.behaviors.borrow().run(|item| player.has(item), &room) {
+            events.push('\n');
+            events.push_str(&event);
+        }
+        events
+    }
+
+    // "set undo off" and the like; the value is the last word and the setting is the rest
+    fn change_setting(&self, command: &CmdTokens) -> CmdResult {
+        // "on" is lexed as a preposition, so put the words back together
+        let words = [command.obj(), command.prep(), command.obj_prep()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let (setting, value) = match words.rsplit_once(' ') {
+            Some(parts) => parts,
+            None => return CmdResult::do_what("set? (settings to list them)"),
+        };
+        let invalid = || {
+            CmdResult::new(
+                Action::Failed,
+                format!("\"{}\" is not a value for {}.", value, setting),
+            )
+        };
+
+        let changed = match setting {
+            "status" | "status line" => match parse_on_off(value) {
+                Some(on) => {
+                    self.show_status_line(on);
+                    on_off(on).to_owned()
+                }
+                None => return invalid(),
+            },
+            "paging" | "page height" => match (parse_on_off(value), value.parse::<usize>()) {
+                (Some(false), _) => {
+                    self.set_page_height(0);
+                    String::from("off")
+                }
+                (_, Ok(height)) => {
+                    self.set_page_height(height);
+                    format!("{} lines", height)
+                }
+                _ => return invalid(),
+            },
+            "variety" | "variation" => match parse_on_off(value) {
+                Some(on) => {
+                    self.set_variety(on);
+                    on_off(on).to_owned()
+                }
+                None => return invalid(),
+            },
+            "undo" => match parse_on_off(value) {
+                Some(on) => {
+                    self.set_undo(on);
+                    on_off(on).to_owned()
+                }
+                None => return invalid(),
+            },
+            "hp" | "hp display" => match HpDisplay::from_name(value) {
+                Some(hp_display) => {
+                    self.set_hp_display(hp_display);
+                    hp_display.name().to_owned()
+                }
+                None => return invalid(),
+            },
+            "inventory" => {
+                let format = self.player.borrow().inventory_format();
+                let categorized = match value {
+                    "categorized" | "categories" | "grouped" => true,
+                    "plain" | "list" => false,
+                    _ => return invalid(),
+                };
+                self.set_inventory_format(format.with_categories(categorized));
+                value.to_owned()
+            }
+            "sort" => {
+                let format = self.player.borrow().inventory_format();
+                let alphabetical = match value {
+                    "alphabetical" | "name" => true,
+                    "acquired" | "none" => false,
+                    _ => return invalid(),
+                };
+                self.set_inventory_format(format.with_alphabetical(alphabetical));
+                value.to_owned()
+            }
+            "fight" | "fight threshold" => match (
+                parse_on_off(value),
+                value.trim_end_matches('%').parse::<u32>(),
+            ) {
+                (Some(false), _) => {
+                    self.set_fight_threshold(0);
+                    String::from("off")
+                }
+                (_, Ok(percent)) if percent <= 100 => {
+                    self.set_fight_threshold(percent);
+                    format!("{}% HP", percent)
+                }
+                _ => return invalid(),
+            },
+            "autosave" => match (parse_on_off(value), value.parse::<u32>()) {
+                (Some(false), _) | (_, Ok(0)) => {
+                    self.set_autosave(0);
+                    String::from("off")
+                }
+                (_, Ok(minutes)) => {
+                    self.set_autosave(minutes);
+                    format!("every {}", prose::count(minutes as usize, "minute"))
+                }
+                _ => return invalid(),
+            },
+            "auto equip" | "autoequip" => match parse_on_off(value) {
+                Some(on) => {
+                    self.set_auto_equip(on);
+                    on_off(on).to_owned()
+                }
+                None => return invalid(),
+            },
+            _ => {
+                return CmdResult::new(
+                    Action::Failed,
+                    format!("There is no setting called \"{}\".", setting),
+                )
+            }
+        };
+        CmdResult::new(Action::Passive, format!("{} set to {}.", setting, changed))
+    }
+
+    // commands for testing a world, only understood by authors and admins
+    fn debug(&self, command: &CmdTokens) -> CmdResult {
+        let obj = command.obj();
+        match (command.verb(), obj) {
+            (Some("reload"), _) if !self.role.get().is_admin() => {
+                CmdResult::new(Action::Failed, "Only an admin can reload the world.")
+            }
+            (Some("reload"), _) => {
+                let pristine = self.pristine.borrow().clone();
+                if let Some(pristine) = pristine {
+                    self.world.replace(pristine.world.clone());
+                    self.contexts.borrow_mut().clear();
+                }
+                CmdResult::new(
+                    Action::Passive,
+                    format!("World reloaded.\n\n{}", self.world.borrow().look().output()),
+                )
+            }
+            (Some("god"), _) | (Some("noclip"), _) => {
+                let (cheat, name) = if command.verb() == Some("god") {
+                    ("god", "God mode")
+                } else {
+                    ("noclip", "Noclip")
+                };
+                let on = !self.is_cheating(cheat);
+                if on {
+                    self.cheats.borrow_mut().push(cheat.to_owned());
+                } else {
+                    self.cheats.borrow_mut().retain(|c| c != cheat);
+                }
+                self.player.borrow_mut().set_god(self.is_cheating("god"));
+                self.world
+                    .borrow_mut()
+                    .set_noclip(self.is_cheating("noclip"));
+                CmdResult::new(
+                    Action::Passive,
+                    format!(
+                        "{} {}. Transcripts mark turns played with cheats.",
+                        name,
+                        on_off(on)
+                    ),
+                )
+            }
+            (Some("giveall"), _) => {
+                let items = self.world.borrow().all_items();
+                let count = items.len();
+                for item in items {
+                    self.player.borrow_mut().take_back(item);
+                }
+                if !self.is_cheating("giveall") {
+                    self.cheats.borrow_mut().push(String::from("giveall"));
+                }
+                CmdResult::new(
+                    Action::Passive,
+                    format!(
+                        "You now carry a copy of every item in the world ({}). Transcripts mark \
+                         every turn from here on.",
+                        count
+                    ),
+                )
+            }
+            (Some("worldstats"), _) => {
+                CmdResult::new(Action::Passive, self.world.borrow().stats().to_string())
+            }
+            (Some("profile"), last) => {
+                let last = last
+                    .and_then(|last| last.parse().ok())
+                    .unwrap_or(PROFILE_TURNS);
+                CmdResult::new(Action::Passive, self.profile.borrow().report(last))
+            }
+            (Some("tp"), Some(room)) => self.world.borrow_mut().teleport(room),
+            (Some("spawn"), Some(enemy)) => self.world.borrow_mut().spawn_copy(enemy),
+            (Some("setflag"), Some(quest)) => {
+                self.complete_quest(quest);
+                CmdResult::new(Action::Passive, format!("Quest \"{}\" complete.", quest))
+            }
+            (Some(verb), None) => CmdResult::do_what(verb),
+            _ => CmdResult::no_comprendo(),
+        }
+    }
+
+    fn quit(&self) -> CmdResult {
+        self.running.set(false);
+        self.quit.set(true);
+        CmdResult::new(Action::Passive, String::from("\nFarewell.\n"))
+    }
+
+    #[cfg(feature = "serde")]
+    /// Serialize the whole game, including the player, into a RON string
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string(&self).expect("Error serializing world save file.")
+    }
+
+    #[cfg(feature = "serde")]
+    /// Write only the world, as it stands now, to a world file that `from_file` can load,
+    /// so generated or imported worlds can be edited by hand
+    pub fn export_world(&self, path: &str, format: WorldFormat) -> Result<(), String> {
+        self.world.borrow().to_file(path, format)
+    }
+
+    #[cfg(feature = "serde")]
+    fn save(&self, name: Option<&str>) -> CmdResult {
+        if let Err(refusal) = self.can_save() {
+            return CmdResult::new(Action::Failed, refusal);
+        }
+        match self.write_save(name.unwrap_or("world")) {
+            Ok(location) => CmdResult::new(
+                Action::Passive,
+                format!("Moves: {}\nSaved to '{}'.", self.num_moves.get(), location),
+            ),
+            Err(_) => CmdResult::new(Action::Failed, String::from("Error saving world.")),
+        }
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn save(&self, _name: Option<&str>) -> CmdResult {
+        CmdResult::new(Action::Failed, "Saving is not available in this build.")
+    }
+
+    // put the whole game in the save store under the name, returning where it went
+    #[cfg(feature = "serde")]
+    fn write_save(&self, name: &str) -> Result<String, String> {
+        let saved = ron::ser::to_string(&self).map_err(|err| err.to_string())?;
+        let location = self
+            .save_store
+            .borrow()
+            .write(name, &saved)
+            .map_err(|err| {
+                debug!("could not save {}: {}", name, err);
+                err
+            })?;
+        debug!("saved {} bytes to {}", saved.len(), location);
+        Ok(location)
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn write_save(&self, _name: &str) -> Result<String, String> {
+        Err(String::from("Saving is not available in this build."))
     }
 
     pub fn last_output(&self) -> String {
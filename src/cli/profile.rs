@@ -0,0 +1,134 @@
+use std::{collections::VecDeque, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use crate::prose;
+
+// the most turns the "profile" command can look back over
+pub(crate) const PROFILE_TURNS: usize = 50;
+
+// the parts of a turn that are timed separately, in the order they are reported
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Phase {
+    Parse,
+    Resolve,
+    Events,
+    Combat,
+    Render,
+}
+
+const PHASES: [&str; 5] = ["parse", "resolve", "events", "combat", "render"];
+
+// how long each phase of one turn took
+#[derive(Clone, Copy, Debug, Default)]
+struct TurnTimes {
+    turn: u32,
+    phases: [Duration; 5],
+}
+
+impl TurnTimes {
+    fn total(&self) -> Duration {
+        self.phases.iter().sum()
+    }
+}
+
+// timings of the last turns played, kept in memory for authors of large worlds to see what
+// is slow
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Profile {
+    turns: VecDeque<TurnTimes>,
+    current: TurnTimes,
+}
+
+impl Profile {
+    pub(crate) fn add(&mut self, phase: Phase, time: Duration) {
+        self.current.phases[phase as usize] += time;
+    }
+
+    // close off the turn that was being timed
+    pub(crate) fn finish(&mut self, turn: u32) {
+        let mut times = std::mem::take(&mut self.current);
+        times.turn = turn;
+        if self.turns.len() == PROFILE_TURNS {
+            self.turns.pop_front();
+        }
+        self.turns.push_back(times);
+    }
+
+    // the average and slowest time of each phase over the last turns
+    pub(crate) fn report(&self, last: usize) -> String {
+        let turns: Vec<&TurnTimes> = self.turns.iter().rev().take(last).collect();
+        if turns.is_empty() {
+            return String::from("No turns have been timed yet.");
+        }
+
+        let count = turns.len() as u32;
+        let mut lines = vec![format!("Last {}:", prose::count(turns.len(), "turn"))];
+        for (pos, name) in PHASES.iter().enumerate() {
+            let times = turns.iter().map(|turn| turn.phases[pos]);
+            lines.push(row(
+                name,
+                times.clone().sum::<Duration>() / count,
+                times.max(),
+            ));
+        }
+        let totals = turns.iter().map(|turn| turn.total());
+        lines.push(row(
+            "total",
+            totals.clone().sum::<Duration>() / count,
+            totals.max(),
+        ));
+        if let Some(slowest) = turns.iter().max_by_key(|turn| turn.total()) {
+            lines.push(format!(
+                "Slowest: turn {} ({})",
+                slowest.turn,
+                millis(slowest.total())
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+fn row(name: &str, average: Duration, max: Option<Duration>) -> String {
+    format!(
+        "  {:<8}{} average, {} at most",
+        name,
+        millis(average),
+        millis(max.unwrap_or_default())
+    )
+}
+
+fn millis(time: Duration) -> String {
+    format!("{:.3} ms", time.as_secs_f64() * 1000.0)
+}
+
+// a running clock for one phase
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct Stopwatch(Instant);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self(Instant::now())
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+// wall-clock time is not available on the web, where every phase reads as taking no time
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct Stopwatch;
+
+#[cfg(target_arch = "wasm32")]
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
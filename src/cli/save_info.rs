@@ -0,0 +1,168 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::Deserialize;
+
+use crate::types::{read_text, Class, LoadError, Metadata, Race};
+
+// the parts of a saved Player worth showing, skipping the rest of it
+#[derive(Deserialize)]
+#[serde(rename = "Player")]
+struct PlayerPart {
+    lvl: u32,
+    race: Race,
+    class: Class,
+    hp: (i32, u32),
+    xp: (u32, u32),
+    #[serde(default)]
+    quests_done: Vec<String>,
+    #[serde(default)]
+    score: u32,
+    #[serde(default)]
+    karma: i32,
+}
+
+// the name a saved Room is shown by, skipping the rest of it
+#[derive(Deserialize)]
+#[serde(rename = "Room")]
+struct RoomPart {
+    name: String,
+}
+
+// the parts of a saved World worth showing, skipping all but the names of its Rooms
+#[derive(Deserialize)]
+#[serde(rename = "World")]
+struct WorldPart {
+    curr_room: String,
+    #[serde(default)]
+    rooms: HashMap<String, RoomPart>,
+    #[serde(default)]
+    clock: u32,
+}
+
+#[derive(Deserialize)]
+struct SaveFile {
+    #[serde(default)]
+    num_moves: u32,
+    #[serde(default)]
+    metadata: Metadata,
+    player: PlayerPart,
+    world: WorldPart,
+}
+
+/// A look inside a save without loading the game, for launchers showing save slots and for
+/// support tools reading saves players send in
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaveInfo {
+    metadata: Metadata,
+    moves: u32,
+    clock: u32,
+    room: String,
+    level: u32,
+    race: Race,
+    class: Class,
+    hp: (i32, u32),
+    xp: (u32, u32),
+    score: u32,
+    karma: i32,
+    flags: Vec<String>,
+}
+
+impl SaveInfo {
+    /// Read a save from RON, as written by `Cli::to_ron` or a save store
+    pub fn from_ron_str(ron: &str) -> Result<Self, LoadError> {
+        let save: SaveFile = ron::de::from_str(ron).map_err(|err| {
+            debug!("could not inspect save: {}", err);
+            LoadError::Save(err)
+        })?;
+        // the Room is shown by its name rather than the key it is saved under, like "Hold 1"
+        let mut world = save.world;
+        let room = match world.rooms.remove(&world.curr_room) {
+            Some(room) => room.name,
+            None => world.curr_room,
+        };
+        Ok(Self {
+            metadata: save.metadata,
+            moves: save.num_moves,
+            clock: world.clock,
+            room,
+            level: save.player.lvl,
+            race: save.player.race,
+            class: save.player.class,
+            hp: save.player.hp,
+            xp: save.player.xp,
+            score: save.player.score,
+            karma: save.player.karma,
+            flags: save.player.quests_done,
+        })
+    }
+
+    /// Read a save file, like "world.save.ron"
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        Self::from_ron_str(&read_text(path)?)
+    }
+
+    /// The title, release, and the rest of what the world said about itself
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// The moves the player had made
+    pub const fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    /// The turns the World's clock had counted, travel and waiting included
+    pub const fn clock(&self) -> u32 {
+        self.clock
+    }
+
+    /// The name of the Room the player was in
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    pub const fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub const fn race(&self) -> &Race {
+        &self.race
+    }
+
+    pub const fn class(&self) -> &Class {
+        &self.class
+    }
+
+    pub const fn hp(&self) -> i32 {
+        self.hp.0
+    }
+
+    pub const fn hp_cap(&self) -> u32 {
+        self.hp.1
+    }
+
+    pub const fn xp(&self) -> u32 {
+        self.xp.0
+    }
+
+    pub const fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub const fn karma(&self) -> i32 {
+        self.karma
+    }
+
+    /// The quests the player had completed, which "setflag" also marks
+    pub fn flags(&self) -> &[String] {
+        &self.flags
+    }
+
+    /// A line for a save slot, like "Central Room - level 2 Elf Ranger, 14 moves"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} - level {} {} {}, {} moves",
+            self.room, self.level, self.race, self.class, self.moves
+        )
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    fmt, fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Where saved games are kept, so hosts can keep them in a database, object storage, or
+/// browser storage instead of files; each save is a RON string under a name like "world"
+pub trait SaveStore: Send {
+    /// Keep the save under the name, returning where it went for the player to see
+    fn write(&mut self, name: &str, save: &str) -> Result<String, String>;
+
+    /// The save kept under the name
+    fn read(&mut self, name: &str) -> Result<String, String>;
+}
+
+/// Keeps each save in a file named after it, like "world.save.ron", in one directory;
+/// the default is the working directory
+///
+/// A name that could reach outside the directory, holding a path separator, a drive colon, or
+/// nothing but dots, is refused
+#[derive(Clone, Debug, Default)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    // the file for a name, which must stay in the directory; players can type the name after
+    // "save", so it is checked as untrusted
+    fn path(&self, name: &str) -> Result<PathBuf, String> {
+        let unsafe_char = |c: char| matches!(c, '/' | '\\' | ':' | '\0');
+        if name.is_empty() || name.contains(unsafe_char) || name.chars().all(|c| c == '.') {
+            return Err(format!("\"{}\" cannot be used as a save name.", name));
+        }
+        Ok(self.dir.join(format!("{}.save.ron", name)))
+    }
+}
+
+impl SaveStore for FileStore {
+    fn write(&mut self, name: &str, save: &str) -> Result<String, String> {
+        let path = self.path(name)?;
+        fs::write(&path, save).map_err(|err| err.to_string())?;
+        Ok(path.display().to_string())
+    }
+
+    fn read(&mut self, name: &str) -> Result<String, String> {
+        fs::read_to_string(self.path(name)?).map_err(|err| err.to_string())
+    }
+}
+
+// a shared handle, so the host can reach its backend from outside the game; files are used
+// until a store is set
+#[derive(Clone, Default)]
+pub(crate) struct SaveStoreHook(Option<Arc<Mutex<dyn SaveStore>>>);
+
+impl SaveStoreHook {
+    pub(crate) fn new(store: Arc<Mutex<dyn SaveStore>>) -> Self {
+        Self(Some(store))
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn write(&self, name: &str, save: &str) -> Result<String, String> {
+        match &self.0 {
+            Some(store) => store
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .write(name, save),
+            None => FileStore::default().write(name, save),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn read(&self, name: &str) -> Result<String, String> {
+        match &self.0 {
+            Some(store) => store
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .read(name),
+            None => FileStore::default().read(name),
+        }
+    }
+}
+
+impl fmt::Debug for SaveStoreHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "SaveStoreHook(Some(..))"
+        } else {
+            "SaveStoreHook(None)"
+        })
+    }
+}
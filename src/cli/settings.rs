@@ -0,0 +1,106 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prose,
+    types::{HpDisplay, InventoryFormat},
+};
+
+// Options the player can change during play with "set"; saved along with the game
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub(crate) struct Settings {
+    pub status_line: bool,
+    pub page_height: usize,
+    pub undo: bool,
+    // vary the wording of repeated responses; off by default so output matches exactly
+    pub variety: bool,
+    // "fight" breaks off once HP falls to this percent of the maximum; 0 fights to the end
+    pub fight_threshold: u32,
+    // minutes of play between saves made by the command loop; 0 leaves saving to the player
+    pub autosave: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            status_line: false,
+            page_height: 0,
+            undo: true,
+            variety: false,
+            fight_threshold: 25,
+            autosave: 0,
+        }
+    }
+}
+
+impl Settings {
+    pub fn list(
+        &self,
+        hp_display: HpDisplay,
+        inventory: InventoryFormat,
+        auto_equip: bool,
+    ) -> String {
+        format!(
+            "Settings:\
+            \n  status line: {}\
+            \n  paging: {}\
+            \n  undo: {}\
+            \n  hp display: {}\
+            \n  inventory: {}\
+            \n  sort: {}\
+            \n  auto equip: {}\
+            \n  variety: {}\
+            \n  fight threshold: {}\
+            \n  autosave: {}\
+            \nChange one with \"set <setting> <value>\", such as \"set undo off\".",
+            on_off(self.status_line),
+            if self.page_height == 0 {
+                String::from("off")
+            } else {
+                format!("{} lines", self.page_height)
+            },
+            on_off(self.undo),
+            hp_display.name(),
+            if inventory.is_categorized() {
+                "categorized"
+            } else {
+                "plain"
+            },
+            if inventory.is_alphabetical() {
+                "alphabetical"
+            } else {
+                "acquired"
+            },
+            on_off(auto_equip),
+            on_off(self.variety),
+            if self.fight_threshold == 0 {
+                String::from("off")
+            } else {
+                format!("{}% HP", self.fight_threshold)
+            },
+            if self.autosave == 0 {
+                String::from("off")
+            } else {
+                format!("every {}", prose::count(self.autosave as usize, "minute"))
+            },
+        )
+    }
+}
+
+pub(crate) const fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+pub(crate) fn parse_on_off(value: &str) -> Option<bool> {
+    match value {
+        "on" | "yes" | "true" => Some(true),
+        "off" | "no" | "false" => Some(false),
+        _ => None,
+    }
+}
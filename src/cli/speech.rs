@@ -0,0 +1,108 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Receives every response as plain sentences for a text-to-speech engine, whatever the
+/// visual output looks like
+pub trait Speech: Send {
+    fn speak(&mut self, sentences: &[String]);
+}
+
+// a shared handle, so the host can keep driving its engine from outside the game
+#[derive(Clone, Default)]
+pub(crate) struct SpeechHook(Option<Arc<Mutex<dyn Speech>>>);
+
+impl SpeechHook {
+    pub(crate) fn new(speech: Arc<Mutex<dyn Speech>>) -> Self {
+        Self(Some(speech))
+    }
+
+    pub(crate) fn speak(&self, output: &str) {
+        if let Some(speech) = &self.0 {
+            let sentences = speakable(output);
+            if !sentences.is_empty() {
+                speech
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .speak(&sentences);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SpeechHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "SpeechHook(Some(..))"
+        } else {
+            "SpeechHook(None)"
+        })
+    }
+}
+
+// read the way a narrator would say them
+fn expand(word: &str) -> String {
+    let (word, punctuation) = word.split_at(word.trim_end_matches(['.', ',', ':', ';']).len());
+    let expanded = match word {
+        "HP" => String::from("hit points"),
+        "AC" => String::from("armor class"),
+        "XP" => String::from("experience"),
+        "lvl" | "Lvl" => String::from("level"),
+        _ => match word.split_once('d') {
+            Some((rolls, sides))
+                if !sides.is_empty()
+                    && rolls.chars().all(|c| c.is_ascii_digit())
+                    && sides.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                format!("{} d {}", if rolls.is_empty() { "1" } else { rolls }, sides)
+            }
+            _ => word.to_owned(),
+        },
+    };
+    format!("{}{}", expanded, punctuation)
+}
+
+// split output into sentences, dropping bars and other drawings, expanding abbreviations,
+// and making sure every sentence ends in punctuation
+pub(crate) fn speakable(output: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    for line in output.lines() {
+        let line = line
+            .replace("...", ".")
+            .replace('…', ".")
+            .replace(" / ", " of ")
+            .replace('&', "and");
+        let words: Vec<String> = line
+            .split_whitespace()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .map(|word| {
+                let word: String = word
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || ".,!?'-:;".contains(*c))
+                    .collect();
+                expand(word.trim_start_matches(['-', ':', ';']))
+            })
+            .collect();
+        if words.is_empty() {
+            continue;
+        }
+
+        let mut line = words.join(" ");
+        if line.ends_with([':', ';', ',']) {
+            line.pop();
+        }
+        if !line.ends_with(['.', '!', '?']) {
+            line.push('.');
+        }
+        let mut start = 0;
+        for (i, c) in line.char_indices() {
+            if matches!(c, '.' | '!' | '?') && line[i + 1..].starts_with(' ') {
+                sentences.push(line[start..=i].trim().to_owned());
+                start = i + 1;
+            }
+        }
+        sentences.push(line[start..].trim().to_owned());
+    }
+    sentences
+}
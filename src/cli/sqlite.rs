@@ -0,0 +1,134 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::Cli;
+use crate::{
+    entity::Room,
+    world::{World, WorldHeader},
+};
+
+/// Keeps one game in a SQLite database for long-running servers, writing only the Rooms that
+/// changed since the last write, so a turn in a huge world never stalls on saving all of it;
+/// call `persist` after every turn, as from `SessionManager::with_autosave`
+pub struct SqliteGame {
+    conn: Connection,
+    game: String,
+    // the World as it was last written, sharing every Room that has not changed since
+    written: Option<World>,
+}
+
+impl SqliteGame {
+    /// Open or create the database at the path, keeping this game under its own key so many
+    /// games can share one database
+    pub fn open(path: &str, game: &str) -> Result<Self, String> {
+        let conn = Connection::open(path).map_err(|err| err.to_string())?;
+        Self::with_connection(conn, game)
+    }
+
+    /// A database that lasts only as long as this value, for tests
+    pub fn in_memory(game: &str) -> Result<Self, String> {
+        let conn = Connection::open_in_memory().map_err(|err| err.to_string())?;
+        Self::with_connection(conn, game)
+    }
+
+    fn with_connection(conn: Connection, game: &str) -> Result<Self, String> {
+        // the write-ahead log keeps readers going while a turn is written and survives a crash
+        // part way through one
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(|err| err.to_string())?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS games (
+                game TEXT PRIMARY KEY,
+                cli TEXT NOT NULL,
+                world TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS rooms (
+                game TEXT NOT NULL,
+                name TEXT NOT NULL,
+                room TEXT NOT NULL,
+                PRIMARY KEY (game, name)
+            );",
+        )
+        .map_err(|err| err.to_string())?;
+        Ok(Self {
+            conn,
+            game: game.to_owned(),
+            written: None,
+        })
+    }
+
+    /// Write the game as it stands in one transaction, returning how many Rooms were written
+    pub fn persist(&mut self, cli: &Cli) -> Result<usize, String> {
+        let world = cli.world.borrow();
+        let header = ron::ser::to_string(&world.header()).map_err(|err| err.to_string())?;
+        let rooms = world
+            .rooms_changed_since(self.written.as_ref())
+            .into_iter()
+            .map(|(name, room)| Ok((name.to_owned(), ron::ser::to_string(room)?)))
+            .collect::<Result<Vec<(String, String)>, ron::Error>>()
+            .map_err(|err| err.to_string())?;
+
+        // the rest of the game is small, so it is written whole with a stand-in World
+        let rest = cli.clone();
+        rest.world.replace(Box::default());
+        let rest = ron::ser::to_string(&rest).map_err(|err| err.to_string())?;
+
+        let tx = self.conn.transaction().map_err(|err| err.to_string())?;
+        tx.execute(
+            "INSERT OR REPLACE INTO games (game, cli, world) VALUES (?1, ?2, ?3)",
+            params![self.game, rest, header],
+        )
+        .map_err(|err| err.to_string())?;
+        for (name, room) in &rooms {
+            tx.execute(
+                "INSERT OR REPLACE INTO rooms (game, name, room) VALUES (?1, ?2, ?3)",
+                params![self.game, name, room],
+            )
+            .map_err(|err| err.to_string())?;
+        }
+        tx.commit().map_err(|err| err.to_string())?;
+
+        debug!("persisted {} rooms of {}", rooms.len(), self.game);
+        self.written = Some((**world).clone());
+        Ok(rooms.len())
+    }
+
+    /// The game last persisted under this key, or None if there is none yet
+    pub fn restore(&mut self) -> Result<Option<Cli>, String> {
+        let row: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT cli, world FROM games WHERE game = ?1",
+                params![self.game],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|err| err.to_string())?;
+        let (rest, header) = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, room FROM rooms WHERE game = ?1 ORDER BY name")
+            .map_err(|err| err.to_string())?;
+        let rooms = stmt
+            .query_map(params![self.game], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|err| err.to_string())?
+            .map(|row| {
+                let (name, room) = row.map_err(|err| err.to_string())?;
+                let room: Room = ron::de::from_str(&room).map_err(|err| err.to_string())?;
+                Ok((name, room))
+            })
+            .collect::<Result<Vec<(String, Room)>, String>>()?;
+
+        let header: WorldHeader = ron::de::from_str(&header).map_err(|err| err.to_string())?;
+        let world = World::from_parts(header, rooms)?;
+        let cli: Cli = ron::de::from_str(&rest).map_err(|err| err.to_string())?;
+        self.written = Some(world.clone());
+        cli.world.replace(Box::new(world));
+        Ok(Some(cli))
+    }
+}
@@ -0,0 +1,26 @@
+use std::thread;
+
+use crate::types::Pause;
+
+/// Decides how a Pause is carried out, letting tests skip it and web hosts animate it
+pub trait Timing {
+    fn pause(&self, pause: Pause);
+}
+
+/// Sleeps for the full length of every Pause
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RealTime;
+
+impl Timing for RealTime {
+    fn pause(&self, pause: Pause) {
+        thread::sleep(pause.duration());
+    }
+}
+
+/// Ignores every Pause
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoDelay;
+
+impl Timing for NoDelay {
+    fn pause(&self, _pause: Pause) {}
+}
@@ -0,0 +1,118 @@
+// One command as it was played: where it was typed, the way out of there, and what came back
+#[derive(Clone, Debug, PartialEq)]
+struct Turn {
+    room: String,
+    exits: Vec<String>,
+    input: String,
+    output: String,
+    // debug cheats in effect, so testers can tell impossible states from real bugs
+    cheats: Vec<String>,
+}
+
+/// A recording of a playthrough that can be shared as Markdown or HTML
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transcript {
+    turns: Vec<Turn>,
+}
+
+impl Transcript {
+    pub(crate) fn record(
+        &mut self,
+        room: &str,
+        exits: Vec<String>,
+        input: &str,
+        output: &str,
+        cheats: Vec<String>,
+    ) {
+        self.turns.push(Turn {
+            room: room.to_owned(),
+            exits,
+            input: input.to_owned(),
+            output: output.to_owned(),
+            cheats,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.turns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+
+    // the turns that begin in a different Room than the turn before them
+    fn entered(&self, i: usize) -> bool {
+        i == 0 || self.turns[i - 1].room != self.turns[i].room
+    }
+
+    /// Format with a header and a snapshot of the exits for each room, each command in bold,
+    /// and the game's replies in plain text blocks
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        for (i, turn) in self.turns.iter().enumerate() {
+            if self.entered(i) {
+                markdown.push_str(&format!("## {}\n\n", turn.room));
+                if !turn.exits.is_empty() {
+                    markdown.push_str(&format!("*Exits: {}*\n\n", turn.exits.join(", ")));
+                }
+            }
+            if turn.cheats.is_empty() {
+                markdown.push_str(&format!("> **{}**\n\n", turn.input));
+            } else {
+                markdown.push_str(&format!(
+                    "> **{}** *[cheats: {}]*\n\n",
+                    turn.input,
+                    turn.cheats.join(", ")
+                ));
+            }
+            if !turn.output.is_empty() {
+                markdown.push_str(&format!("```text\n{}\n```\n\n", turn.output));
+            }
+        }
+        markdown
+    }
+
+    /// Format as an HTML fragment with classes on each part for styling
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<article class=\"transcript\">\n");
+        for (i, turn) in self.turns.iter().enumerate() {
+            if self.entered(i) {
+                html.push_str(&format!("<h2 class=\"room\">{}</h2>\n", escape(&turn.room)));
+                if !turn.exits.is_empty() {
+                    html.push_str(&format!(
+                        "<p class=\"exits\">Exits: {}</p>\n",
+                        escape(&turn.exits.join(", "))
+                    ));
+                }
+            }
+            if turn.cheats.is_empty() {
+                html.push_str(&format!(
+                    "<p class=\"command\">&gt; {}</p>\n",
+                    escape(&turn.input)
+                ));
+            } else {
+                html.push_str(&format!(
+                    "<p class=\"command cheat\">&gt; {} <span class=\"cheats\">[cheats: {}]</span></p>\n",
+                    escape(&turn.input),
+                    turn.cheats.join(", ")
+                ));
+            }
+            if !turn.output.is_empty() {
+                html.push_str(&format!(
+                    "<pre class=\"output\">{}</pre>\n",
+                    escape(&turn.output)
+                ));
+            }
+        }
+        html.push_str("</article>\n");
+        html
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,16 +1,259 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use super::Entity;
+use super::{Entity, Item};
+use crate::{
+    dice_roll, prose,
+    types::{Check, Deed, Items, Memory, Order, Pronouns},
+};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ally {
     name: String,
     desc: String,
     inspect: String,
     hp: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    damage: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    turns: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    topics: Vec<(String, String)>,
+    // dialogue options won or lost on a roll, each tried only once
+    #[cfg_attr(feature = "serde", serde(default))]
+    checks: Vec<Check>,
+    // songs the Ally can teach the player
+    #[cfg_attr(feature = "serde", serde(default))]
+    songs: Vec<String>,
+    // a named character like "Gandalf" rather than "the squire"
+    #[cfg_attr(feature = "serde", serde(default))]
+    proper: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    possessive: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pronouns: Pronouns,
+    // what it can be told to do besides waiting and following, like "attack", "pick", or "carry"
+    #[cfg_attr(feature = "serde", serde(default))]
+    orders: Vec<String>,
+    // said when it is told to do something it will not
+    #[cfg_attr(feature = "serde", serde(default))]
+    refusal: Option<String>,
+    // what it was last told to do, carried out on its turn
+    #[cfg_attr(feature = "serde", serde(default))]
+    order: Option<Order>,
+    // whether it was told to come along or to wait; otherwise only a summoned Ally follows
+    #[cfg_attr(feature = "serde", serde(default))]
+    follows: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    carrying: Items,
+    // what it remembers of the player
+    #[cfg_attr(feature = "serde", serde(default))]
+    memory: Memory,
 }
 
-impl Ally {}
+impl Ally {
+    pub fn new(name: &str, inspect: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+            hp: 1,
+            damage: 0,
+            turns: None,
+            topics: Vec::new(),
+            checks: Vec::new(),
+            songs: Vec::new(),
+            proper: false,
+            possessive: None,
+            pronouns: Pronouns::it(),
+            orders: Vec::new(),
+            refusal: None,
+            order: None,
+            follows: None,
+            carrying: Items::new(),
+            memory: Memory::default(),
+        }
+    }
+
+    pub fn with_desc(mut self, desc: &str) -> Self {
+        self.desc = String::from(desc);
+        self
+    }
+    pub fn with_proper(mut self) -> Self {
+        if self.desc == format!("There is {} here.", prose::a(&self.name)) {
+            self.desc = format!("{} is here.", self.name);
+        }
+        self.proper = true;
+        self
+    }
+    pub fn with_pronouns(mut self, pronouns: Pronouns) -> Self {
+        self.pronouns = pronouns;
+        self
+    }
+    // the word used for what it owns when it is not the pronouns' own, like "Gandalf's"
+    pub fn with_possessive(mut self, possessive: &str) -> Self {
+        self.possessive = Some(String::from(possessive));
+        self
+    }
+    pub fn with_hp(mut self, hp: i32) -> Self {
+        self.hp = hp;
+        self
+    }
+    pub fn with_damage(mut self, damage: u32) -> Self {
+        self.damage = damage;
+        self
+    }
+    // an Ally with a set number of turns disappears once they run out
+    pub fn with_turns(mut self, turns: u32) -> Self {
+        self.turns = Some(turns);
+        self
+    }
+    // something the Ally can be asked about in conversation
+    pub fn with_topic(mut self, topic: &str, reply: &str) -> Self {
+        self.topics.push((topic.to_owned(), reply.to_owned()));
+        self
+    }
+
+    pub fn with_check(mut self, check: Check) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn with_song(mut self, song: &str) -> Self {
+        self.songs.push(song.to_owned());
+        self
+    }
+
+    // something it can be told to do, by the name of the Order
+    pub fn with_order(mut self, order: &str) -> Self {
+        self.orders.push(order.to_owned());
+        self
+    }
+
+    pub fn with_refusal(mut self, refusal: &str) -> Self {
+        self.refusal = Some(refusal.to_owned());
+        self
+    }
+
+    // everything it can be told to do, by name
+    pub fn orders(&self) -> Vec<&str> {
+        let mut orders = vec!["wait", "follow"];
+        orders.extend(self.orders.iter().map(String::as_str));
+        orders
+    }
+
+    // take an order, waiting or following at once and doing anything else on its turn
+    pub fn obey(&mut self, order: Order) -> Result<String, String> {
+        if !self.orders().contains(&order.name()) {
+            return Err(self.refusal.clone().unwrap_or_else(|| {
+                format!("{} will not do that.", prose::capitalize(&self.the()))
+            }));
+        }
+        let the = prose::capitalize(&self.the());
+        Ok(match order {
+            Order::Wait => {
+                self.follows = Some(false);
+                self.order = None;
+                format!("{} will wait here.", the)
+            }
+            Order::Follow => {
+                self.follows = Some(true);
+                format!("{} falls in beside you.", the)
+            }
+            order => {
+                self.order = Some(order);
+                format!("{} nods.", the)
+            }
+        })
+    }
+
+    pub const fn order(&self) -> Option<&Order> {
+        self.order.as_ref()
+    }
+
+    pub fn take_order(&mut self) -> Option<Order> {
+        self.order.take()
+    }
+
+    pub fn follows_player(&self) -> bool {
+        self.follows.unwrap_or(self.is_summoned())
+    }
+
+    pub fn carry(&mut self, item: Box<Item>) {
+        self.carrying.push(item);
+    }
+
+    pub fn unload(&mut self) -> Items {
+        self.carrying.take()
+    }
+
+    pub fn carrying(&self) -> &Items {
+        &self.carrying
+    }
+
+    pub fn with_memory(mut self, memory: Memory) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    pub const fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    pub fn remember(&mut self, deed: Deed) {
+        self.memory.remember(deed);
+    }
+
+    // something the player hands over, carried along with the rest
+    pub fn receive(&mut self, item: Box<Item>) {
+        self.carry(item);
+        self.remember(Deed::Gift);
+    }
+
+    pub fn topics(&self) -> &[(String, String)] {
+        &self.topics
+    }
+
+    pub fn checks(&self) -> &[Check] {
+        &self.checks
+    }
+
+    pub fn songs(&self) -> &[String] {
+        &self.songs
+    }
+
+    pub fn spend_check(&mut self, topic: &str) {
+        self.checks.retain(|check| check.topic() != topic);
+    }
+
+    pub const fn is_fighter(&self) -> bool {
+        self.damage > 0
+    }
+
+    pub fn damage(&self) -> u32 {
+        dice_roll(1, self.damage)
+    }
+
+    pub const fn is_summoned(&self) -> bool {
+        self.turns.is_some()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.turns == Some(0)
+    }
+
+    // count down a summoned Ally's remaining turns, returning whether it is still here
+    pub fn tick(&mut self) -> bool {
+        if let Some(turns) = self.turns {
+            let turns = turns.saturating_sub(1);
+            self.turns = Some(turns);
+            turns > 0
+        } else {
+            true
+        }
+    }
+}
 
 impl Entity for Ally {
     fn name(&self) -> &str {
@@ -24,4 +267,20 @@ impl Entity for Ally {
     fn inspect(&self) -> &str {
         &self.inspect
     }
+
+    fn is_proper(&self) -> bool {
+        self.proper
+    }
+
+    fn pronouns(&self) -> Pronouns {
+        self.pronouns.clone()
+    }
+
+    fn possessive(&self) -> String {
+        match &self.possessive {
+            Some(possessive) => possessive.to_owned(),
+            None if self.proper && self.pronouns == Pronouns::it() => prose::possessive(&self.name),
+            None => self.pronouns.possessive().to_owned(),
+        }
+    }
 }
@@ -1,17 +1,31 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-impl Default for Durability {
-    fn default() -> Self {
-        Durability::Unbreakable
-    }
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Durability {
     Durable(u32),
+    #[default]
     Unbreakable,
 }
 
-impl Durability {}
+impl Durability {
+    // broken once it has taken this much damage
+    pub const fn new(hits: u32) -> Self {
+        Self::Durable(hits)
+    }
+
+    // wear it down by the given damage, returning whether it broke
+    pub fn wear(&mut self, damage: u32) -> bool {
+        match self {
+            Self::Durable(hp) => {
+                *hp = hp.saturating_sub(damage);
+                *hp == 0
+            }
+            Self::Unbreakable => false,
+        }
+    }
+}
 
+#[allow(dead_code)]
 pub trait Breakable {}
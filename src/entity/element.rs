@@ -1,13 +1,52 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::entity::Entity;
+use crate::{
+    entity::Entity,
+    types::{Breach, Dial, Furniture, Harvest, Plate, Riddle, ToolUse, Tune},
+};
 
 // An object to be interacted with by the user
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Element {
     name: String,
     desc: String,
     inspect: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    furniture: Option<Furniture>,
+    // the Room that can be seen through a window or keyhole
+    #[cfg_attr(feature = "serde", serde(default))]
+    view: Option<String>,
+    // too heavy to carry, but it can be pushed or pulled from room to room
+    #[cfg_attr(feature = "serde", serde(default))]
+    heavy: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    plate: Option<Plate>,
+    // something sturdy enough to tie a rope to
+    #[cfg_attr(feature = "serde", serde(default))]
+    anchor: bool,
+    // a floor or ceiling that can be broken through
+    #[cfg_attr(feature = "serde", serde(default))]
+    breach: Option<Breach>,
+    // a bush, fishing spot, or vein that materials can be gathered from
+    #[cfg_attr(feature = "serde", serde(default))]
+    harvest: Option<Harvest>,
+    // a cauldron, forge, or the like that Recipes can be made at
+    #[cfg_attr(feature = "serde", serde(default))]
+    station: Option<String>,
+    // a question that must be answered, as posed by a sphinx or a talking door
+    #[cfg_attr(feature = "serde", serde(default))]
+    riddle: Option<Riddle>,
+    // a combination lock turned to a sequence of numbers
+    #[cfg_attr(feature = "serde", serde(default))]
+    dial: Option<Dial>,
+    // a stone, harp, or door that stirs when a particular song is played nearby
+    #[cfg_attr(feature = "serde", serde(default))]
+    tune: Option<Tune>,
+    // what can be done to it with the right tool, such as cutting or digging
+    #[cfg_attr(feature = "serde", serde(default))]
+    uses: Vec<ToolUse>,
 }
 
 impl Element {
@@ -16,6 +55,18 @@ impl Element {
             name: name.to_owned(),
             desc: desc.to_owned(),
             inspect: inspect.to_owned(),
+            furniture: None,
+            view: None,
+            heavy: false,
+            plate: None,
+            anchor: false,
+            breach: None,
+            harvest: None,
+            station: None,
+            riddle: None,
+            dial: None,
+            tune: None,
+            uses: Vec::new(),
         }
     }
 
@@ -23,6 +74,162 @@ impl Element {
         self.desc = String::from(desc);
         self
     }
+
+    // furniture can be sat on or climbed into
+    pub fn with_furniture(mut self, furniture: Furniture) -> Self {
+        self.furniture = Some(furniture);
+        self
+    }
+
+    pub fn with_view(mut self, room: &str) -> Self {
+        self.view = Some(String::from(room));
+        self
+    }
+
+    pub fn view(&self) -> Option<&str> {
+        self.view.as_deref()
+    }
+
+    // rewrite the name of the Room this looks or opens into
+    #[cfg(feature = "serde")]
+    pub fn rename_rooms(&mut self, rename: &impl Fn(&mut String)) {
+        if let Some(view) = &mut self.view {
+            rename(view);
+        }
+        if let Some(breach) = &mut self.breach {
+            breach.rename_target(rename);
+        }
+    }
+
+    pub fn with_heavy(mut self) -> Self {
+        self.heavy = true;
+        self
+    }
+
+    pub const fn is_heavy(&self) -> bool {
+        self.heavy
+    }
+
+    pub fn with_plate(mut self, plate: Plate) -> Self {
+        self.plate = Some(plate);
+        self
+    }
+
+    pub fn plate_mut(&mut self) -> Option<&mut Plate> {
+        self.plate.as_mut()
+    }
+
+    pub fn with_anchor(mut self) -> Self {
+        self.anchor = true;
+        self
+    }
+
+    pub const fn is_anchor(&self) -> bool {
+        self.anchor
+    }
+
+    pub fn with_breach(mut self, breach: Breach) -> Self {
+        self.breach = Some(breach);
+        self
+    }
+
+    pub fn is_breakable(&self) -> bool {
+        self.breach.is_some()
+    }
+
+    pub fn breach_mut(&mut self) -> Option<&mut Breach> {
+        self.breach.as_mut()
+    }
+
+    pub fn into_breach(self) -> Option<Breach> {
+        self.breach
+    }
+
+    pub fn breach_target(&self) -> Option<&str> {
+        self.breach.as_ref().map(Breach::target)
+    }
+
+    pub fn with_harvest(mut self, harvest: Harvest) -> Self {
+        self.harvest = Some(harvest);
+        self
+    }
+
+    pub fn harvest(&self) -> Option<&Harvest> {
+        self.harvest.as_ref()
+    }
+
+    pub fn harvest_mut(&mut self) -> Option<&mut Harvest> {
+        self.harvest.as_mut()
+    }
+
+    pub fn with_station(mut self, station: &str) -> Self {
+        self.station = Some(station.to_owned());
+        self
+    }
+
+    pub fn station(&self) -> Option<&str> {
+        self.station.as_deref()
+    }
+
+    pub fn with_riddle(mut self, riddle: Riddle) -> Self {
+        self.riddle = Some(riddle);
+        self
+    }
+
+    pub fn riddle(&self) -> Option<&Riddle> {
+        self.riddle.as_ref()
+    }
+
+    pub fn riddle_mut(&mut self) -> Option<&mut Riddle> {
+        self.riddle.as_mut()
+    }
+
+    pub fn with_dial(mut self, dial: Dial) -> Self {
+        self.dial = Some(dial);
+        self
+    }
+
+    pub fn dial(&self) -> Option<&Dial> {
+        self.dial.as_ref()
+    }
+
+    pub fn dial_mut(&mut self) -> Option<&mut Dial> {
+        self.dial.as_mut()
+    }
+
+    pub fn with_tune(mut self, tune: Tune) -> Self {
+        self.tune = Some(tune);
+        self
+    }
+
+    pub fn tune(&self) -> Option<&Tune> {
+        self.tune.as_ref()
+    }
+
+    pub fn tune_mut(&mut self) -> Option<&mut Tune> {
+        self.tune.as_mut()
+    }
+
+    pub fn with_use(mut self, tool_use: ToolUse) -> Self {
+        self.uses.push(tool_use);
+        self
+    }
+
+    pub fn tool_use(&self, verb: &str) -> Option<&ToolUse> {
+        self.uses.iter().find(|tool_use| tool_use.is_for(verb))
+    }
+
+    pub fn take_tool_use(&mut self, verb: &str) -> Option<ToolUse> {
+        let pos = self
+            .uses
+            .iter()
+            .position(|tool_use| tool_use.is_for(verb))?;
+        Some(self.uses.remove(pos))
+    }
+
+    pub const fn furniture(&self) -> Option<Furniture> {
+        self.furniture
+    }
 }
 
 impl Entity for Element {
@@ -1,37 +1,117 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use super::{Entity, Item};
 use crate::{
     dice_roll,
-    types::{Action, CmdResult, EnemyStatus, Items},
+    input::same_name,
+    opposed_roll, prose,
+    types::{
+        Action, BodyPart, Check, CmdResult, DamageType, Deed, EnemyStatus, Hazard, Items,
+        LootTables, Memory, PartEffect, Pronouns, SavingThrow,
+    },
 };
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+fn default_strength() -> i32 {
+    10
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Enemy {
     name: String,
     desc: String,
     inspect: String,
     hp: i32,
+    // left out of a world file, it is taken from its HP when it is first hurt
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_hp: i32,
     ac: i32,
     xp: u32,
     damage: u32,
     status: EnemyStatus,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     loot: Items,
+    #[cfg_attr(feature = "serde", serde(default))]
+    loot_table: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    plural: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    morale: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    resistances: Vec<DamageType>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    vulnerabilities: Vec<DamageType>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    immunities: Vec<DamageType>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    body_parts: Vec<BodyPart>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    stunned: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    slowed: bool,
+    #[cfg_attr(feature = "serde", serde(default = "default_strength"))]
+    strength: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    grappled: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    knockback: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    deadly: Option<SavingThrow>,
+    // an audio cue for when it attacks
+    #[cfg_attr(feature = "serde", serde(default))]
+    sound: Option<String>,
+    // a named character like "Gandalf" rather than "the goblin"
+    #[cfg_attr(feature = "serde", serde(default))]
+    proper: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    possessive: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pronouns: Pronouns,
+    // what can be said to talk it out of a fight, or into one
+    #[cfg_attr(feature = "serde", serde(default))]
+    checks: Vec<Check>,
+    // added to the player's karma when it is killed, taken off for a civilian
+    #[cfg_attr(feature = "serde", serde(default))]
+    karma: i32,
+    // what it remembers of the player
+    #[cfg_attr(feature = "serde", serde(default))]
+    memory: Memory,
 }
 
 impl Enemy {
     pub fn new(name: &str, inspect: &str, status: EnemyStatus) -> Self {
         Self {
             name: name.to_owned(),
-            desc: format!("There is a {} here.", name),
+            desc: format!("There is {} here.", prose::a(name)),
             inspect: inspect.to_owned(),
             hp: 1,
+            max_hp: 1,
             ac: 0,
             xp: 0,
             damage: 1,
             status,
             loot: Items::new(),
+            loot_table: None,
+            plural: None,
+            morale: None,
+            resistances: Vec::new(),
+            vulnerabilities: Vec::new(),
+            immunities: Vec::new(),
+            body_parts: Vec::new(),
+            stunned: 0,
+            slowed: false,
+            strength: default_strength(),
+            grappled: false,
+            knockback: 0,
+            deadly: None,
+            sound: None,
+            proper: false,
+            possessive: None,
+            pronouns: Pronouns::it(),
+            checks: Vec::new(),
+            karma: 0,
+            memory: Memory::default(),
         }
     }
     pub fn new_rats(status: EnemyStatus) -> Self {
@@ -40,25 +120,68 @@ impl Enemy {
             desc: String::from("A swarm of rats raves along the floor."),
             inspect: String::from("The creatures chatter and scrape viciously."),
             hp: 24,
+            max_hp: 24,
             ac: 0,
             xp: 25,
             damage: 2,
             status,
             loot: Items::new(),
+            loot_table: None,
+            plural: None,
+            morale: None,
+            resistances: Vec::new(),
+            vulnerabilities: Vec::new(),
+            immunities: Vec::new(),
+            body_parts: Vec::new(),
+            stunned: 0,
+            slowed: false,
+            strength: default_strength(),
+            grappled: false,
+            knockback: 0,
+            deadly: None,
+            sound: None,
+            proper: false,
+            possessive: None,
+            pronouns: Pronouns::it(),
+            checks: Vec::new(),
+            karma: 0,
+            memory: Memory::default(),
         }
     }
 
     pub fn new_pirate(status: EnemyStatus) -> Self {
+        let hp = dice_roll(2, 7) as i32 + 2;
         Self {
             name: String::from("pirate"),
             desc: String::from("There is a pirate here."),
             inspect: String::from("The pirate is armed and smells vile."),
-            hp: dice_roll(2, 7) as i32 + 2,
+            hp,
+            max_hp: hp,
             ac: 10,
             xp: 50,
             damage: 6,
             status,
             loot: Items::new(),
+            loot_table: None,
+            plural: None,
+            morale: None,
+            resistances: Vec::new(),
+            vulnerabilities: Vec::new(),
+            immunities: Vec::new(),
+            body_parts: Vec::new(),
+            stunned: 0,
+            slowed: false,
+            strength: default_strength(),
+            grappled: false,
+            knockback: 0,
+            deadly: None,
+            sound: None,
+            proper: false,
+            possessive: None,
+            pronouns: Pronouns::it(),
+            checks: Vec::new(),
+            karma: 0,
+            memory: Memory::default(),
         }
     }
 
@@ -72,6 +195,7 @@ impl Enemy {
     }
     pub fn with_hp(mut self, hp: i32) -> Self {
         self.hp = hp;
+        self.max_hp = hp;
         self
     }
     pub fn with_ac(mut self, ac: i32) -> Self {
@@ -82,6 +206,13 @@ impl Enemy {
         self.xp = xp;
         self
     }
+    pub fn with_sound(mut self, cue: &str) -> Self {
+        self.sound = Some(cue.to_owned());
+        self
+    }
+    pub fn sound(&self) -> Option<&str> {
+        self.sound.as_deref()
+    }
     pub fn with_damage(mut self, damage: u32) -> Self {
         self.damage = damage;
         self
@@ -90,14 +221,184 @@ impl Enemy {
         self.loot.push(Box::new(item));
         self
     }
+    // the higher the morale (out of 20), the less likely the Enemy is to flee or surrender
+    pub fn with_morale(mut self, morale: u32) -> Self {
+        self.morale = Some(morale);
+        self
+    }
+    pub fn with_resistance(mut self, damage_type: DamageType) -> Self {
+        self.resistances.push(damage_type);
+        self
+    }
+    pub fn with_vulnerability(mut self, damage_type: DamageType) -> Self {
+        self.vulnerabilities.push(damage_type);
+        self
+    }
+    pub fn with_immunity(mut self, damage_type: DamageType) -> Self {
+        self.immunities.push(damage_type);
+        self
+    }
+    pub fn with_strength(mut self, strength: i32) -> Self {
+        self.strength = strength;
+        self
+    }
+    // percent chance each turn to hurl the player through an exit
+    pub fn with_knockback(mut self, knockback: u32) -> Self {
+        self.knockback = knockback;
+        self
+    }
+    // an attack like a petrifying gaze that the player must save against or die
+    pub fn with_deadly(mut self, save: SavingThrow) -> Self {
+        self.deadly = Some(save);
+        self
+    }
+    pub fn with_body_part(mut self, part: BodyPart) -> Self {
+        self.body_parts.push(part);
+        self
+    }
+    pub fn with_proper(mut self) -> Self {
+        if self.desc == format!("There is {} here.", prose::a(&self.name)) {
+            self.desc = format!("{} is here.", self.name);
+        }
+        self.proper = true;
+        self
+    }
+    pub fn with_pronouns(mut self, pronouns: Pronouns) -> Self {
+        self.pronouns = pronouns;
+        self
+    }
+    // the word used for what it owns when it is not the pronouns' own, like "Gandalf's"
+    pub fn with_possessive(mut self, possessive: &str) -> Self {
+        self.possessive = Some(String::from(possessive));
+        self
+    }
+    pub fn with_plural(mut self, plural: &str) -> Self {
+        self.plural = Some(String::from(plural));
+        self
+    }
+    pub fn with_check(mut self, check: Check) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn with_karma(mut self, karma: i32) -> Self {
+        self.karma = karma;
+        self
+    }
+    pub const fn karma(&self) -> i32 {
+        self.karma
+    }
+
+    pub fn with_memory(mut self, memory: Memory) -> Self {
+        self.memory = memory;
+        self
+    }
+    pub const fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    // a deed it holds a grudge over turns it on the player
+    pub fn remember(&mut self, deed: Deed) {
+        self.memory.remember(deed);
+        if self.memory.bears_grudge() {
+            self.make_angry();
+        }
+    }
+
+    // something the player hands over, dropped with the rest of its loot
+    pub fn receive(&mut self, item: Box<Item>) {
+        self.loot.push(item);
+        self.remember(Deed::Gift);
+    }
+
+    pub fn checks(&self) -> &[Check] {
+        &self.checks
+    }
+
+    pub fn spend_check(&mut self, topic: &str) {
+        self.checks.retain(|check| check.topic() != topic);
+    }
+
+    // talked down from a fight, unless it holds a grudge
+    pub fn calm(&mut self) {
+        if self.status == EnemyStatus::Angry && !self.memory.bears_grudge() {
+            self.status = EnemyStatus::Distracted;
+        }
+    }
+
+    pub fn loot_table(&self) -> Option<&str> {
+        self.loot_table.as_deref()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rename_loot_table(&mut self, rename: &impl Fn(&mut String)) {
+        if let Some(table) = &mut self.loot_table {
+            rename(table);
+        }
+    }
+
+    // rewrite the names of the Items it carries, and what they refer to
+    #[cfg(feature = "serde")]
+    pub fn rename_loot(
+        &mut self,
+        item: &impl Fn(&mut String),
+        code: &impl Fn(&mut String),
+        quest: &impl Fn(&mut String),
+    ) {
+        for carried in self.loot.iter_mut() {
+            carried.rename_references(item, code, quest);
+        }
+    }
+
+    pub fn with_loot_table(mut self, table: &str) -> Self {
+        self.loot_table = Some(String::from(table));
+        self
+    }
 
     pub fn long_desc(&self) -> String {
         match self.status {
-            EnemyStatus::Asleep => format!("{} It is asleep.", self.desc),
+            EnemyStatus::Asleep => {
+                format!("{} {} asleep.", self.desc, self.pronouns.act("is", "are"))
+            }
+            EnemyStatus::Surrendered => format!(
+                "{} {} surrendered.",
+                self.desc,
+                self.pronouns.act("has", "have")
+            ),
             _ => self.desc.to_owned(),
         }
     }
 
+    // "The goblin" or "Gandalf" at the start of a sentence
+    fn the_cap(&self) -> String {
+        prose::capitalize(&self.the())
+    }
+
+    pub fn plural(&self) -> String {
+        if let Some(plural) = &self.plural {
+            plural.to_owned()
+        } else {
+            prose::plural(&self.name)
+        }
+    }
+
+    // describes several identical Enemies at once
+    pub fn group_desc(&self, count: usize) -> String {
+        match self.status {
+            EnemyStatus::Asleep => format!(
+                "There are {} {} here. They are asleep.",
+                count,
+                self.plural()
+            ),
+            EnemyStatus::Surrendered => format!(
+                "There are {} {} here. They have surrendered.",
+                count,
+                self.plural()
+            ),
+            _ => format!("There are {} {} here.", count, self.plural()),
+        }
+    }
+
     pub const fn xp(&self) -> u32 {
         self.xp
     }
@@ -122,31 +423,292 @@ impl Enemy {
         &self.loot
     }
 
-    pub fn take_damage(&mut self, damage: u32) -> Option<CmdResult> {
+    // adjust incoming damage by the Enemy's resistances, describing any reaction
+    pub fn resist(
+        &self,
+        damage: u32,
+        damage_type: Option<DamageType>,
+        weapon_name: &str,
+    ) -> (u32, Option<String>) {
+        if let Some(damage_type) = damage_type {
+            if self.immunities.contains(&damage_type) {
+                (
+                    0,
+                    Some(format!(
+                        "{} shrugs off the {}.",
+                        self.the_cap(),
+                        weapon_name
+                    )),
+                )
+            } else if self.resistances.contains(&damage_type) {
+                (
+                    damage / 2,
+                    Some(format!(
+                        "{} resists the {} damage.",
+                        self.the_cap(),
+                        damage_type
+                    )),
+                )
+            } else if self.vulnerabilities.contains(&damage_type) {
+                (
+                    damage * 2,
+                    Some(format!("The {} tears into {}!", weapon_name, self.the())),
+                )
+            } else {
+                (damage, None)
+            }
+        } else {
+            (damage, None)
+        }
+    }
+
+    // the most HP it can have; one read without a maximum has its HP until it is first hurt
+    fn max_hp(&self) -> i32 {
+        self.max_hp.max(self.hp)
+    }
+
+    // every blow goes through here, so the HP it had before the first is kept as its maximum
+    fn lose_hp(&mut self, damage: u32) {
+        self.max_hp = self.max_hp();
+        self.hp -= damage as i32;
+    }
+
+    // roll against the Enemy's morale when it is badly hurt or its allies have fallen
+    pub fn morale_breaks(&self, allies_fell: bool) -> bool {
+        if let Some(morale) = self.morale {
+            (allies_fell || self.hp * 2 <= self.max_hp()) && dice_roll(1, 20) > morale
+        } else {
+            false
+        }
+    }
+
+    pub fn surrender(&mut self) -> String {
+        self.status = EnemyStatus::Surrendered;
+        format!(
+            "\n{} throws down {} arms and surrenders.",
+            self.the_cap(),
+            self.possessive()
+        )
+    }
+
+    // take a blow from someone other than the player
+    pub fn struck_by(&mut self, attacker: &str, damage: u32) -> String {
         self.make_angry();
 
         if dice_roll(1, 20) as i32 >= self.ac {
-            self.hp -= damage as i32;
+            self.lose_hp(damage);
+            if self.is_alive() {
+                format!(
+                    "\n{} hits {} for {} damage.",
+                    prose::capitalize(attacker),
+                    self.the(),
+                    damage
+                )
+            } else {
+                format!(
+                    "\n{} hits {} for {} damage. {} dead.",
+                    prose::capitalize(attacker),
+                    self.the(),
+                    damage,
+                    self.pronouns.act("is", "are")
+                )
+            }
+        } else {
+            format!("\n{} misses {}.", prose::capitalize(attacker), self.the())
+        }
+    }
+
+    pub fn body_part(&self, name: &str) -> Option<&BodyPart> {
+        self.body_parts
+            .iter()
+            .find(|part| same_name(part.name(), name))
+    }
+
+    // apply the effect of a hit to a body part, describing what happened
+    pub fn wound(&mut self, part: &BodyPart) -> String {
+        match part.effect() {
+            Some(PartEffect::Stun) if dice_roll(1, 2) == 1 => {
+                self.stunned = 1;
+                format!("\n{} is stunned!", self.the_cap())
+            }
+            Some(PartEffect::Slow) => {
+                self.slowed = true;
+                format!(
+                    "\n{} staggers on {} wounded {}.",
+                    self.the_cap(),
+                    self.possessive(),
+                    part.name()
+                )
+            }
+            _ => String::new(),
+        }
+    }
+
+    // a slowed or grappled Enemy cannot run away
+    pub const fn can_flee(&self) -> bool {
+        !self.slowed && !self.grappled
+    }
+
+    pub fn strength_mod(&self) -> i32 {
+        (f64::from(self.strength - 10) / 2.0).floor() as i32
+    }
+
+    pub const fn is_grappled(&self) -> bool {
+        self.grappled
+    }
+
+    pub fn grapple(&mut self, strength_mod: i32) -> CmdResult {
+        self.make_angry();
+
+        if self.grappled {
+            CmdResult::new(
+                Action::Passive,
+                format!("You already have hold of {}.", self.the()),
+            )
+        } else if opposed_roll(strength_mod, self.strength_mod()) {
+            self.grappled = true;
+            CmdResult::new(
+                Action::Active,
+                format!("You wrestle {} into your grip.", self.the()),
+            )
+        } else {
+            CmdResult::new(
+                Action::Active,
+                format!("{} twists out of your grasp.", self.the_cap()),
+            )
+        }
+    }
+
+    // the deadly attack is used on roughly one turn in four
+    pub fn deadly_attack(&self) -> Option<&SavingThrow> {
+        if self.deadly.is_some() && dice_roll(1, 4) == 1 {
+            self.deadly.as_ref()
+        } else {
+            None
+        }
+    }
+
+    // the inspect text plus whatever the player has learned from killing its kind
+    pub fn intel(&self, kills: u32) -> String {
+        let mut intel = self.inspect.clone();
+        if kills >= 1 {
+            let max_hp = self.max_hp();
+            intel.push_str(&format!(
+                "\nHP: {}-{}",
+                (max_hp * 3 / 4).max(1),
+                max_hp * 5 / 4 + 1
+            ));
+        }
+        if kills >= 2 {
+            let list = |types: &[DamageType]| {
+                types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            };
+            let mut defenses = Vec::new();
+            if !self.resistances.is_empty() {
+                defenses.push(format!("resists {}", list(&self.resistances)));
+            }
+            if !self.vulnerabilities.is_empty() {
+                defenses.push(format!("vulnerable to {}", list(&self.vulnerabilities)));
+            }
+            if !self.immunities.is_empty() {
+                defenses.push(format!("immune to {}", list(&self.immunities)));
+            }
+            if defenses.is_empty() {
+                intel.push_str("\nDefenses: none");
+            } else {
+                intel.push_str(&format!("\nDefenses: {}", defenses.join("; ")));
+            }
+        }
+        if kills >= 3 {
+            let mut attacks = vec![format!("1d{} damage", self.damage)];
+            if let Some(save) = &self.deadly {
+                attacks.push(format!("{} ({} save)", save.desc(), save.ability()));
+            }
+            if self.knockback > 0 {
+                attacks.push(String::from("knocks foes back"));
+            }
+            intel.push_str(&format!("\nAttacks: {}", attacks.join(", ")));
+        }
+        intel
+    }
+
+    pub fn knocks_back(&self) -> bool {
+        self.knockback > 0 && dice_roll(1, 100) <= self.knockback
+    }
+
+    pub fn suffer_hazard(&mut self, hazard: &Hazard) -> String {
+        self.lose_hp(hazard.damage());
+        if self.is_alive() {
+            format!("\n{} is caught in {}.", self.the_cap(), hazard.desc())
+        } else {
+            format!(
+                "\n{} is caught in {}. {} dead.",
+                self.the_cap(),
+                hazard.desc(),
+                self.pronouns.act("is", "are")
+            )
+        }
+    }
+
+    pub fn release(&mut self) {
+        self.grappled = false;
+    }
+
+    // a grappled Enemy spends its turn trying to escape
+    pub fn break_free(&mut self, strength_mod: i32) -> String {
+        if opposed_roll(self.strength_mod(), strength_mod) {
+            self.grappled = false;
+            format!("\n{} breaks free of your grip.", self.the_cap())
+        } else {
+            format!("\n{} struggles in your grip.", self.the_cap())
+        }
+    }
+
+    // a stunned Enemy loses its turn while it recovers
+    pub fn recover(&mut self) -> bool {
+        if self.stunned > 0 {
+            self.stunned -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn take_damage(&mut self, damage: u32) -> Option<CmdResult> {
+        self.take_damage_at(damage, 0)
+    }
+
+    pub fn take_damage_at(&mut self, damage: u32, hit_modifier: i32) -> Option<CmdResult> {
+        self.make_angry();
+
+        if dice_roll(1, 20) as i32 + hit_modifier >= self.ac {
+            self.lose_hp(damage);
             None
         } else {
             match dice_roll(1, 3) {
                 0 => Some(CmdResult::new(
                     Action::Active,
                     format!(
-                        "\nYou swung at the {}, but it dodged out of the way.",
-                        self.name
+                        "\nYou swung at {}, but {} dodged out of the way.",
+                        self.the(),
+                        self.pronouns.subject()
                     ),
                 )),
                 1 => Some(CmdResult::new(
                     Action::Active,
                     format!(
-                        "\nYou hit the {}, but its armor absorbed the blow.",
-                        self.name
+                        "\nYou hit {}, but {} armor absorbed the blow.",
+                        self.the(),
+                        self.possessive()
                     ),
                 )),
                 _ => Some(CmdResult::new(
                     Action::Active,
-                    format!("\nThe {} deftly blocked your attack.", self.name),
+                    format!("\n{} deftly blocked your attack.", self.the_cap()),
                 )),
             }
         }
@@ -156,8 +718,17 @@ impl Enemy {
         self.hp > 0
     }
 
+    // add the drops rolled from the Enemy's loot table to its fixed loot
+    pub fn roll_loot(&mut self, tables: &LootTables) {
+        if let Some(table) = self.loot_table.take() {
+            if let Some(table) = tables.get(&table) {
+                self.loot.extend(table.roll());
+            }
+        }
+    }
+
     pub fn drop_loot(&mut self) -> Items {
-        self.loot.drain(0..).collect()
+        self.loot.take()
     }
 }
 
@@ -173,4 +744,20 @@ impl Entity for Enemy {
     fn inspect(&self) -> &str {
         &self.inspect
     }
+
+    fn is_proper(&self) -> bool {
+        self.proper
+    }
+
+    fn pronouns(&self) -> Pronouns {
+        self.pronouns.clone()
+    }
+
+    fn possessive(&self) -> String {
+        match &self.possessive {
+            Some(possessive) => possessive.to_owned(),
+            None if self.proper && self.pronouns == Pronouns::it() => prose::possessive(&self.name),
+            None => self.pronouns.possessive().to_owned(),
+        }
+    }
 }
@@ -1,25 +1,39 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::entity::Entity;
+use crate::{entity::Entity, prose, types::Requirements, world::namespace};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Armor {
-    name: String,
+    pub(super) name: String,
     desc: String,
     inspect: String,
     ac: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    requirements: Requirements,
 }
 
 impl Armor {
     pub fn new(name: &str, inspect: &str, ac: u32) -> Self {
         Self {
             name: name.to_owned(),
-            desc: format!("There is a {} here.", name),
+            desc: format!("There is {} here.", prose::a(name)),
             inspect: inspect.to_owned(),
             ac,
+            requirements: Requirements::default(),
         }
     }
 
+    pub fn with_requirements(mut self, requirements: Requirements) -> Self {
+        self.requirements = requirements;
+        self
+    }
+
+    pub const fn requirements(&self) -> &Requirements {
+        &self.requirements
+    }
+
     pub const fn ac(&self) -> u32 {
         self.ac
     }
@@ -27,6 +41,10 @@ impl Armor {
 
 impl Entity for Armor {
     fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
         &self.name
     }
 
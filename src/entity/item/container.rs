@@ -1,23 +1,54 @@
-use rayon::prelude::*;
-
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use super::Item;
 use crate::{
     entity::{Closeable, Entity, Opening},
-    types::{Action, CmdResult, Items},
+    input::named,
+    prose,
+    types::{Action, CmdResult, Items, SavingThrow},
+    world::namespace,
 };
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Container {
-    name: String,
+    pub(super) name: String,
     desc: String,
     inspect: String,
     opening: Opening,
     contents: Items,
+    #[cfg_attr(feature = "serde", serde(default))]
+    trap: Option<SavingThrow>,
 }
 
 impl Container {
+    pub fn new(name: &str, inspect: &str, opening: Opening) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+            opening,
+            contents: Items::new(),
+            trap: None,
+        }
+    }
+
+    pub fn with_item(mut self, item: Item) -> Self {
+        self.contents.push(Box::new(item));
+        self
+    }
+
+    // a trapped Container springs its trap the first time it is opened
+    pub fn with_trap(mut self, trap: SavingThrow) -> Self {
+        self.trap = Some(trap);
+        self
+    }
+
+    pub fn take_trap(&mut self) -> Option<SavingThrow> {
+        self.trap.take()
+    }
+
     pub fn long_name(&self) -> String {
         if !self.contents.is_empty() && self.opening.is_open() {
             self.contents
@@ -42,31 +73,20 @@ impl Container {
     }
 
     fn item_pos(&self, item_name: &str) -> Option<usize> {
-        if cfg!(target_arch = "wasm32") {
-            self.contents
-                .iter()
-                .map(|item| item.name().split_whitespace().collect())
-                .position(|direction: Vec<&str>| {
-                    item_name
-                        .split_whitespace()
-                        .all(|ref word| direction.contains(word))
-                })
-        } else {
-            self.contents
-                .par_iter()
-                .map(|item| item.name().par_split_whitespace().collect())
-                .position_any(|direction: Vec<&str>| {
-                    item_name
-                        .par_split_whitespace()
-                        .all(|ref word| direction.contains(word))
-                })
-        }
+        self.contents
+            .iter()
+            .position(|item| named(item.name(), item_name))
+    }
+
+    pub const fn contents(&self) -> &Items {
+        &self.contents
     }
 
     pub fn push_item(&mut self, item: Box<Item>) {
         self.contents.push(item);
     }
 
+    #[allow(clippy::result_large_err)]
     pub fn give_item(&mut self, item_name: &str) -> Result<Box<Item>, CmdResult> {
         if self.is_closed() {
             Err(CmdResult::new(
@@ -82,10 +102,19 @@ impl Container {
             ))
         }
     }
+
+    #[cfg(feature = "serde")]
+    pub fn contents_mut(&mut self) -> &mut Items {
+        &mut self.contents
+    }
 }
 
 impl Entity for Container {
     fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
         &self.name
     }
 
@@ -1,28 +1,60 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::entity::Entity;
+use crate::{entity::Entity, prose, world::namespace};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Key {
-    name: String,
+    pub(super) name: String,
     desc: String,
     inspect: String,
     code: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quest: Option<String>,
 }
 
 impl Key {
     pub fn new(name: &str, inspect: &str, code: &str) -> Self {
         Self {
             name: name.to_owned(),
-            desc: format!("There is a {} here.", name),
+            desc: format!("There is {} here.", prose::a(name)),
             inspect: inspect.to_owned(),
             code: code.to_owned(),
+            quest: None,
+        }
+    }
+
+    // a quest item cannot be given up until its quest is complete
+    pub fn with_quest(mut self, quest: &str) -> Self {
+        self.quest = Some(quest.to_owned());
+        self
+    }
+
+    pub fn quest(&self) -> Option<&str> {
+        self.quest.as_deref()
+    }
+
+    // rewrite the code of the locks it opens
+    #[cfg(feature = "serde")]
+    pub fn rename_code(&mut self, rename: &impl Fn(&mut String)) {
+        rename(&mut self.code);
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rename_quest(&mut self, rename: &impl Fn(&mut String)) {
+        if let Some(quest) = &mut self.quest {
+            rename(quest);
         }
     }
 }
 
 impl Entity for Key {
     fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
         &self.name
     }
 
@@ -0,0 +1,94 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::Entity,
+    prose,
+    types::{Action, CmdResult},
+    world::namespace,
+};
+
+// A lamp or torch that, once lit, lets the player see into the rooms along an exit
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Light {
+    pub(super) name: String,
+    desc: String,
+    inspect: String,
+    // how many rooms away the light carries
+    radius: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    lit: bool,
+}
+
+impl Light {
+    pub fn new(name: &str, inspect: &str, radius: u32) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+            radius,
+            lit: false,
+        }
+    }
+
+    pub fn with_desc(mut self, desc: &str) -> Self {
+        self.desc = String::from(desc);
+        self
+    }
+
+    pub const fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    pub const fn is_lit(&self) -> bool {
+        self.lit
+    }
+
+    pub fn light(&mut self) -> CmdResult {
+        if self.lit {
+            CmdResult::new(
+                Action::Passive,
+                format!("The {} is already lit.", self.name),
+            )
+        } else {
+            self.lit = true;
+            CmdResult::new(Action::Active, format!("You light the {}.", self.name))
+        }
+    }
+
+    pub fn douse(&mut self) -> CmdResult {
+        if self.lit {
+            self.lit = false;
+            CmdResult::new(Action::Active, format!("You put out the {}.", self.name))
+        } else {
+            CmdResult::new(Action::Passive, format!("The {} is not lit.", self.name))
+        }
+    }
+
+    pub fn long_desc(&self) -> String {
+        if self.lit {
+            format!("{} It is lit.", self.desc)
+        } else {
+            self.desc.to_owned()
+        }
+    }
+}
+
+impl Entity for Light {
+    fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
+        &self.name
+    }
+
+    fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    fn inspect(&self) -> &str {
+        &self.inspect
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{Enemy, Entity},
+    prose,
+    world::namespace,
+};
+
+// An Enemy disguised as an Item until the player tries to take or open it
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mimic {
+    pub(super) name: String,
+    desc: String,
+    inspect: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    reveal: String,
+    enemy: Box<Enemy>,
+}
+
+impl Mimic {
+    pub fn new(name: &str, inspect: &str, enemy: Enemy) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+            reveal: format!(
+                "The {} springs to life! It was {} all along!",
+                name,
+                prose::a(enemy.name())
+            ),
+            enemy: Box::new(enemy),
+        }
+    }
+
+    pub fn with_desc(mut self, desc: &str) -> Self {
+        self.desc = String::from(desc);
+        self
+    }
+    pub fn with_reveal(mut self, reveal: &str) -> Self {
+        self.reveal = String::from(reveal);
+        self
+    }
+
+    pub fn reveal(&self) -> &str {
+        if self.reveal.is_empty() {
+            "It springs to life!"
+        } else {
+            &self.reveal
+        }
+    }
+
+    // drop the disguise and become a hostile Enemy
+    pub fn into_enemy(self) -> Enemy {
+        let mut enemy = *self.enemy;
+        enemy.make_angry();
+        enemy
+    }
+}
+
+impl Entity for Mimic {
+    fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
+        &self.name
+    }
+
+    fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    fn inspect(&self) -> &str {
+        &self.inspect
+    }
+}
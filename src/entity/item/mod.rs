@@ -1,11 +1,20 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::entity::Entity;
+use crate::{
+    entity::Entity,
+    types::{Category, Daemon, Gear, Requirements},
+};
 
 mod armor;
 mod container;
 mod gold;
 mod key;
+mod light;
+mod mimic;
+mod revival;
+mod rope;
+mod summon;
 mod thing;
 mod weapon;
 
@@ -13,37 +22,311 @@ pub use armor::Armor;
 pub use container::Container;
 pub use gold::Gold;
 pub use key::Key;
+pub use light::Light;
+pub use mimic::Mimic;
+pub use revival::Revival;
+pub use rope::Rope;
+pub use summon::Summon;
 pub use thing::Thing;
 pub use weapon::Weapon;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Item {
     Armor(Armor),
     Container(Container),
     Gold(Gold),
     Key(Key),
+    Light(Light),
+    Mimic(Mimic),
+    Revival(Revival),
+    Rope(Rope),
+    Summon(Summon),
     Thing(Thing),
     Weapon(Weapon),
 }
 
 impl Item {
+    pub fn category(&self) -> Category {
+        if self.quest().is_some() {
+            return Category::Quest;
+        }
+        match self {
+            Self::Weapon(_) => Category::Weapons,
+            Self::Armor(_) => Category::Armor,
+            Self::Revival(_) | Self::Summon(_) => Category::Consumables,
+            Self::Key(_) => Category::Quest,
+            Self::Container(_)
+            | Self::Gold(_)
+            | Self::Light(_)
+            | Self::Mimic(_)
+            | Self::Rope(_)
+            | Self::Thing(_) => Category::Misc,
+        }
+    }
+
+    // a side-by-side table of the stats two Items have
+    pub fn compare(&self, other: &Item) -> String {
+        let (left, right) = (self.stats(), other.stats());
+        let labels = ["Category", "Damage", "Armor class", "Healing", "Special"];
+        let rows: Vec<(&str, String, String)> = labels
+            .iter()
+            .filter_map(|label| {
+                let find = |stats: &[(&str, String)]| {
+                    stats
+                        .iter()
+                        .find(|(l, _)| l == label)
+                        .map(|(_, value)| value.clone())
+                };
+                match (find(&left), find(&right)) {
+                    (None, None) => None,
+                    (l, r) => Some((
+                        *label,
+                        l.unwrap_or_else(|| String::from("-")),
+                        r.unwrap_or_else(|| String::from("-")),
+                    )),
+                }
+            })
+            .collect();
+
+        let label_width = rows.iter().map(|(l, _, _)| l.len()).max().unwrap_or(0) + 2;
+        let left_width = rows
+            .iter()
+            .map(|(_, l, _)| l.len())
+            .chain(std::iter::once(self.name().len()))
+            .max()
+            .unwrap_or(0)
+            + 2;
+
+        let mut table = format!(
+            "{:label_width$}{:left_width$}{}",
+            "",
+            self.name(),
+            other.name(),
+            label_width = label_width,
+            left_width = left_width
+        );
+        for (label, l, r) in rows {
+            table.push_str(&format!(
+                "\n{:label_width$}{:left_width$}{}",
+                label,
+                l,
+                r,
+                label_width = label_width,
+                left_width = left_width
+            ));
+        }
+        table
+    }
+
+    fn stats(&self) -> Vec<(&'static str, String)> {
+        let mut stats = vec![("Category", self.category().name().to_lowercase())];
+        let mut special = Vec::new();
+        match self {
+            Self::Weapon(weapon) => {
+                stats.push(("Damage", format!("1d{}", weapon.max_damage())));
+                if let Some(damage_type) = weapon.damage_type() {
+                    special.push(damage_type.to_string());
+                }
+                if weapon.is_area() {
+                    special.push(String::from("area"));
+                }
+            }
+            Self::Armor(armor) => stats.push(("Armor class", armor.ac().to_string())),
+            Self::Revival(revival) => stats.push(("Healing", format!("{}%", revival.hp_percent()))),
+            Self::Summon(_) => special.push(String::from("summons an ally")),
+            Self::Light(light) => special.push(format!("light radius {}", light.radius())),
+            Self::Rope(_) => special.push(String::from("can be tied")),
+            _ => (),
+        }
+        if !special.is_empty() {
+            stats.push(("Special", special.join(", ")));
+        }
+        stats
+    }
+
     pub fn long_name(&self) -> String {
         match self {
             Self::Armor(armor) => armor.name().to_owned(),
             Self::Container(container) => container.long_name(),
             Self::Gold(gold) => gold.name().to_owned(),
             Self::Key(key) => key.name().to_owned(),
+            Self::Light(light) => light.name().to_owned(),
+            Self::Mimic(mimic) => mimic.name().to_owned(),
+            Self::Revival(revival) => revival.name().to_owned(),
+            Self::Rope(rope) => rope.name().to_owned(),
+            Self::Summon(summon) => summon.name().to_owned(),
             Self::Thing(thing) => thing.name().to_owned(),
             Self::Weapon(weapon) => weapon.name().to_owned(),
         }
     }
 
+    // what it takes to wield or wear this Item
+    pub fn requirements(&self) -> Option<&Requirements> {
+        match self {
+            Self::Armor(armor) => Some(armor.requirements()),
+            Self::Weapon(weapon) => Some(weapon.requirements()),
+            _ => None,
+        }
+    }
+
+    // the inspect text followed by any requirements
+    pub fn details(&self) -> String {
+        match self.requirements() {
+            Some(requirements) if !requirements.is_empty() => {
+                format!("{}\n{}", self.inspect(), requirements.describe())
+            }
+            _ => self.inspect().to_owned(),
+        }
+    }
+
+    // the quest this Item is needed for
+    pub fn quest(&self) -> Option<&str> {
+        match self {
+            Self::Key(key) => key.quest(),
+            Self::Thing(thing) => thing.quest(),
+            _ => None,
+        }
+    }
+
+    // the quests this Item and everything inside it are needed for
+    pub fn quests(&self) -> Vec<&str> {
+        let mut quests: Vec<&str> = self.quest().into_iter().collect();
+        if let Self::Container(container) = self {
+            for item in container.contents() {
+                quests.extend(item.quests());
+            }
+        }
+        quests
+    }
+
+    pub fn gear(&self) -> Option<Gear> {
+        if let Self::Thing(thing) = self {
+            thing.gear()
+        } else {
+            None
+        }
+    }
+
+    // what raw food becomes once cooked
+    pub fn cooked(&self) -> Option<&Item> {
+        if let Self::Thing(thing) = self {
+            thing.cooked()
+        } else {
+            None
+        }
+    }
+
+    // the faction a disguise passes the wearer off as
+    pub fn disguise(&self) -> Option<&str> {
+        if let Self::Thing(thing) = self {
+            thing.disguise()
+        } else {
+            None
+        }
+    }
+
+    pub fn is_instrument(&self) -> bool {
+        matches!(self, Self::Thing(thing) if thing.is_instrument())
+    }
+
+    // the song a scroll or sheet of music teaches
+    pub fn song(&self) -> Option<&str> {
+        if let Self::Thing(thing) = self {
+            thing.song()
+        } else {
+            None
+        }
+    }
+
+    // what a carried Thing does on its own each turn
+    pub fn daemon(&self) -> Option<&Daemon> {
+        if let Self::Thing(thing) = self {
+            thing.daemon()
+        } else {
+            None
+        }
+    }
+
+    pub fn daemon_mut(&mut self) -> Option<&mut Daemon> {
+        if let Self::Thing(thing) = self {
+            thing.daemon_mut()
+        } else {
+            None
+        }
+    }
+
+    // why a fixed piece of scenery cannot be taken
+    pub fn scenery(&self) -> Option<&str> {
+        if let Self::Thing(thing) = self {
+            thing.scenery()
+        } else {
+            None
+        }
+    }
+
+    // rewrite the name this Item is defined under, the code of the locks it opens, and the quest
+    // it is needed for, and those of everything inside it; gold is the same coin everywhere and
+    // keeps its name
+    #[cfg(feature = "serde")]
+    pub fn rename_references(
+        &mut self,
+        item: &impl Fn(&mut String),
+        code: &impl Fn(&mut String),
+        quest: &impl Fn(&mut String),
+    ) {
+        if let Some(id) = self.id_mut() {
+            item(id);
+        }
+        match self {
+            Self::Container(container) => {
+                for inner in container.contents_mut().iter_mut() {
+                    inner.rename_references(item, code, quest);
+                }
+            }
+            Self::Key(key) => {
+                key.rename_code(code);
+                key.rename_quest(quest);
+            }
+            Self::Thing(thing) => {
+                thing.rename_quest(quest);
+                if let Some(cooked) = thing.cooked_mut() {
+                    cooked.rename_references(item, code, quest);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // the name this Item is defined under, which gold does not have
+    #[cfg(feature = "serde")]
+    fn id_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Self::Armor(armor) => Some(&mut armor.name),
+            Self::Container(container) => Some(&mut container.name),
+            Self::Gold(_) => None,
+            Self::Key(key) => Some(&mut key.name),
+            Self::Light(light) => Some(&mut light.name),
+            Self::Mimic(mimic) => Some(&mut mimic.name),
+            Self::Revival(revival) => Some(&mut revival.name),
+            Self::Rope(rope) => Some(&mut rope.name),
+            Self::Summon(summon) => Some(&mut summon.name),
+            Self::Thing(thing) => Some(&mut thing.name),
+            Self::Weapon(weapon) => Some(&mut weapon.name),
+        }
+    }
+
     pub fn long_desc(&self) -> String {
         match self {
             Self::Armor(armor) => armor.desc().to_owned(),
             Self::Container(container) => container.long_desc(),
             Self::Gold(gold) => gold.desc().to_owned(),
             Self::Key(key) => key.desc().to_owned(),
+            Self::Light(light) => light.long_desc(),
+            Self::Mimic(mimic) => mimic.desc().to_owned(),
+            Self::Revival(revival) => revival.desc().to_owned(),
+            Self::Rope(rope) => rope.desc().to_owned(),
+            Self::Summon(summon) => summon.desc().to_owned(),
             Self::Thing(thing) => thing.desc().to_owned(),
             Self::Weapon(weapon) => weapon.desc().to_owned(),
         }
@@ -57,17 +340,43 @@ impl Entity for Item {
             Self::Container(container) => container.name(),
             Self::Gold(gold) => gold.name(),
             Self::Key(key) => key.name(),
+            Self::Light(light) => light.name(),
+            Self::Mimic(mimic) => mimic.name(),
+            Self::Revival(revival) => revival.name(),
+            Self::Rope(rope) => rope.name(),
+            Self::Summon(summon) => summon.name(),
             Self::Thing(thing) => thing.name(),
             Self::Weapon(weapon) => weapon.name(),
         }
     }
 
+    fn id(&self) -> &str {
+        match self {
+            Self::Armor(armor) => armor.id(),
+            Self::Container(container) => container.id(),
+            Self::Gold(gold) => gold.id(),
+            Self::Key(key) => key.id(),
+            Self::Light(light) => light.id(),
+            Self::Mimic(mimic) => mimic.id(),
+            Self::Revival(revival) => revival.id(),
+            Self::Rope(rope) => rope.id(),
+            Self::Summon(summon) => summon.id(),
+            Self::Thing(thing) => thing.id(),
+            Self::Weapon(weapon) => weapon.id(),
+        }
+    }
+
     fn desc(&self) -> &str {
         match self {
             Self::Armor(armor) => armor.desc(),
             Self::Container(container) => container.desc(),
             Self::Gold(gold) => gold.desc(),
             Self::Key(key) => key.desc(),
+            Self::Light(light) => light.desc(),
+            Self::Mimic(mimic) => mimic.desc(),
+            Self::Revival(revival) => revival.desc(),
+            Self::Rope(rope) => rope.desc(),
+            Self::Summon(summon) => summon.desc(),
             Self::Thing(thing) => thing.desc(),
             Self::Weapon(weapon) => weapon.desc(),
         }
@@ -79,6 +388,11 @@ impl Entity for Item {
             Self::Container(container) => container.inspect(),
             Self::Gold(gold) => gold.inspect(),
             Self::Key(key) => key.inspect(),
+            Self::Light(light) => light.inspect(),
+            Self::Mimic(mimic) => mimic.inspect(),
+            Self::Revival(revival) => revival.inspect(),
+            Self::Rope(rope) => rope.inspect(),
+            Self::Summon(summon) => summon.inspect(),
             Self::Thing(thing) => thing.inspect(),
             Self::Weapon(weapon) => weapon.inspect(),
         }
@@ -0,0 +1,52 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{entity::Entity, prose, world::namespace};
+
+// A rare Item that is consumed to bring the player back from death
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Revival {
+    pub(super) name: String,
+    desc: String,
+    inspect: String,
+    hp_percent: u32,
+}
+
+impl Revival {
+    pub fn new(name: &str, inspect: &str, hp_percent: u32) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+            hp_percent,
+        }
+    }
+
+    pub fn with_desc(mut self, desc: &str) -> Self {
+        self.desc = String::from(desc);
+        self
+    }
+
+    pub const fn hp_percent(&self) -> u32 {
+        self.hp_percent
+    }
+}
+
+impl Entity for Revival {
+    fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
+        &self.name
+    }
+
+    fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    fn inspect(&self) -> &str {
+        &self.inspect
+    }
+}
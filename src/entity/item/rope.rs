@@ -0,0 +1,46 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{entity::Entity, prose, world::namespace};
+
+// A rope or chain that can be tied to an anchor to climb by, or to an Item to lower it down
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rope {
+    pub(super) name: String,
+    desc: String,
+    inspect: String,
+}
+
+impl Rope {
+    pub fn new(name: &str, inspect: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+        }
+    }
+
+    pub fn with_desc(mut self, desc: &str) -> Self {
+        self.desc = String::from(desc);
+        self
+    }
+}
+
+impl Entity for Rope {
+    fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
+        &self.name
+    }
+
+    fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    fn inspect(&self) -> &str {
+        &self.inspect
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{Ally, Entity},
+    prose,
+    world::namespace,
+};
+
+// An Item that is used up to call an Ally to fight alongside the player for a while
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Summon {
+    pub(super) name: String,
+    desc: String,
+    inspect: String,
+    // kept boxed, as an Ally is much larger than any other Item
+    ally: Box<Ally>,
+    turns: u32,
+}
+
+impl Summon {
+    pub fn new(name: &str, inspect: &str, ally: Ally, turns: u32) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+            ally: Box::new(ally),
+            turns,
+        }
+    }
+
+    pub fn with_desc(mut self, desc: &str) -> Self {
+        self.desc = String::from(desc);
+        self
+    }
+
+    pub fn into_ally(self) -> Ally {
+        self.ally.with_turns(self.turns)
+    }
+}
+
+impl Entity for Summon {
+    fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
+        &self.name
+    }
+
+    fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    fn inspect(&self) -> &str {
+        &self.inspect
+    }
+}
@@ -1,20 +1,56 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::entity::Entity;
+use crate::{
+    entity::{Entity, Item},
+    prose,
+    types::{Daemon, Gear},
+    world::namespace,
+};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Thing {
-    name: String,
+    pub(super) name: String,
     desc: String,
     inspect: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    scenery: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quest: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    gear: Option<Gear>,
+    // raw food becomes this once cooked over a campfire
+    #[cfg_attr(feature = "serde", serde(default))]
+    cooked: Option<Box<Item>>,
+    // the faction whose members it lets the wearer pass for
+    #[cfg_attr(feature = "serde", serde(default))]
+    disguise: Option<String>,
+    // a flute, lute, or the like that songs can be played on
+    #[cfg_attr(feature = "serde", serde(default))]
+    instrument: bool,
+    // the song written on a scroll or sheet of music, learned by reading it
+    #[cfg_attr(feature = "serde", serde(default))]
+    song: Option<String>,
+    // what it does on its own each turn it is carried
+    #[cfg_attr(feature = "serde", serde(default))]
+    daemon: Option<Daemon>,
 }
 
 impl Thing {
     pub fn new(name: &str, inspect: &str) -> Self {
         Self {
             name: name.to_owned(),
-            desc: format!("There is a {} here.", name),
+            desc: format!("There is {} here.", prose::a(name)),
             inspect: inspect.to_owned(),
+            scenery: None,
+            quest: None,
+            gear: None,
+            cooked: None,
+            disguise: None,
+            instrument: false,
+            song: None,
+            daemon: None,
         }
     }
 
@@ -22,10 +58,104 @@ impl Thing {
         self.desc = String::from(desc);
         self
     }
+
+    // scenery can be inspected but never taken; the message says why
+    pub fn with_scenery(mut self, refusal: &str) -> Self {
+        self.scenery = Some(refusal.to_owned());
+        self
+    }
+
+    pub fn scenery(&self) -> Option<&str> {
+        self.scenery.as_deref()
+    }
+
+    // a quest item cannot be given up until its quest is complete
+    pub fn with_quest(mut self, quest: &str) -> Self {
+        self.quest = Some(quest.to_owned());
+        self
+    }
+
+    pub fn quest(&self) -> Option<&str> {
+        self.quest.as_deref()
+    }
+
+    pub fn with_gear(mut self, gear: Gear) -> Self {
+        self.gear = Some(gear);
+        self
+    }
+
+    pub const fn gear(&self) -> Option<Gear> {
+        self.gear
+    }
+
+    pub fn with_cooked(mut self, cooked: Item) -> Self {
+        self.cooked = Some(Box::new(cooked));
+        self
+    }
+
+    pub fn cooked(&self) -> Option<&Item> {
+        self.cooked.as_deref()
+    }
+
+    pub fn with_disguise(mut self, faction: &str) -> Self {
+        self.disguise = Some(faction.to_owned());
+        self
+    }
+
+    pub fn disguise(&self) -> Option<&str> {
+        self.disguise.as_deref()
+    }
+
+    pub fn with_instrument(mut self) -> Self {
+        self.instrument = true;
+        self
+    }
+
+    pub const fn is_instrument(&self) -> bool {
+        self.instrument
+    }
+
+    pub fn with_song(mut self, song: &str) -> Self {
+        self.song = Some(song.to_owned());
+        self
+    }
+
+    pub fn song(&self) -> Option<&str> {
+        self.song.as_deref()
+    }
+
+    pub fn with_daemon(mut self, daemon: Daemon) -> Self {
+        self.daemon = Some(daemon);
+        self
+    }
+
+    pub const fn daemon(&self) -> Option<&Daemon> {
+        self.daemon.as_ref()
+    }
+
+    pub fn daemon_mut(&mut self) -> Option<&mut Daemon> {
+        self.daemon.as_mut()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rename_quest(&mut self, rename: &impl Fn(&mut String)) {
+        if let Some(quest) = &mut self.quest {
+            rename(quest);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn cooked_mut(&mut self) -> Option<&mut Item> {
+        self.cooked.as_deref_mut()
+    }
 }
 
 impl Entity for Thing {
     fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
         &self.name
     }
 
@@ -1,32 +1,98 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{dice_roll, entity::Entity};
+use crate::{
+    dice_roll,
+    entity::Entity,
+    prose,
+    types::{DamageType, Requirements},
+    world::namespace,
+};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Weapon {
-    name: String,
+    pub(super) name: String,
     desc: String,
     inspect: String,
     damage: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    area: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    damage_type: Option<DamageType>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    requirements: Requirements,
+    // an audio cue for when it hits
+    #[cfg_attr(feature = "serde", serde(default))]
+    sound: Option<String>,
 }
 
 impl Weapon {
     pub fn new(name: &str, inspect: &str, damage: u32) -> Self {
         Self {
             name: name.to_owned(),
-            desc: format!("There is a {} here.", name),
+            desc: format!("There is {} here.", prose::a(name)),
             inspect: inspect.to_owned(),
             damage,
+            area: false,
+            damage_type: None,
+            requirements: Requirements::default(),
+            sound: None,
         }
     }
 
+    pub fn with_area(mut self) -> Self {
+        self.area = true;
+        self
+    }
+
+    pub const fn is_area(&self) -> bool {
+        self.area
+    }
+
+    pub fn with_damage_type(mut self, damage_type: DamageType) -> Self {
+        self.damage_type = Some(damage_type);
+        self
+    }
+
+    pub const fn damage_type(&self) -> Option<DamageType> {
+        self.damage_type
+    }
+
+    pub fn with_requirements(mut self, requirements: Requirements) -> Self {
+        self.requirements = requirements;
+        self
+    }
+
+    pub const fn requirements(&self) -> &Requirements {
+        &self.requirements
+    }
+
+    pub fn with_sound(mut self, cue: &str) -> Self {
+        self.sound = Some(cue.to_owned());
+        self
+    }
+
+    pub fn sound(&self) -> Option<&str> {
+        self.sound.as_deref()
+    }
+
     pub fn damage(&self) -> u32 {
         dice_roll(1, self.damage)
     }
+
+    // the sides of the damage die
+    pub const fn max_damage(&self) -> u32 {
+        self.damage
+    }
 }
 
 impl Entity for Weapon {
     fn name(&self) -> &str {
+        namespace::local(&self.name)
+    }
+
+    fn id(&self) -> &str {
         &self.name
     }
 
@@ -1,14 +1,21 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::types::CmdResult;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DoorLock {
     Locked(String),
     Unlocked,
 }
 
 impl DoorLock {
+    // a lock opened by the Keys and codes of this name
+    pub fn new(code: &str) -> Self {
+        Self::Locked(code.to_owned())
+    }
+
     pub fn is_locked(&self) -> bool {
         match self {
             DoorLock::Locked(_) => true,
@@ -20,6 +27,7 @@ impl DoorLock {
 pub trait Lockable {
     fn unlock(&mut self) -> CmdResult;
 
+    #[allow(dead_code)]
     fn lock(&mut self) -> CmdResult;
 
     fn is_locked(&self) -> bool;
@@ -6,22 +6,56 @@ mod enemy;
 pub mod item;
 mod lockable;
 mod pathway;
+mod pet;
 mod room;
 
 pub use ally::Ally;
-pub use breakable::{Breakable, Durability};
+pub use breakable::Durability;
 pub use closeable::{Closeable, Opening};
 pub use element::Element;
 pub use enemy::Enemy;
 pub use item::Item;
 pub use lockable::{DoorLock, Lockable};
 pub use pathway::Pathway;
+pub use pet::Pet;
 pub use room::Room;
 
+use crate::{prose, types::Pronouns};
+
 pub trait Entity {
     fn name(&self) -> &str;
 
+    // the name it was defined under, which for what a content pack defines starts with the
+    // pack's name, as in "dragons:key"
+    fn id(&self) -> &str {
+        self.name()
+    }
+
     fn desc(&self) -> &str;
 
     fn inspect(&self) -> &str;
+
+    // a proper noun such as "Gandalf" is written without an article
+    fn is_proper(&self) -> bool {
+        false
+    }
+
+    // how messages refer to it: "the goblin" or "Gandalf"
+    fn the(&self) -> String {
+        prose::the(self.name(), self.is_proper())
+    }
+
+    fn pronouns(&self) -> Pronouns {
+        Pronouns::it()
+    }
+
+    // what it owns once it has been named in a sentence: "its", "her", or "Gandalf's"
+    fn possessive(&self) -> String {
+        let pronouns = self.pronouns();
+        if self.is_proper() && pronouns == Pronouns::it() {
+            prose::possessive(self.name())
+        } else {
+            pronouns.possessive().to_owned()
+        }
+    }
 }
@@ -1,28 +1,234 @@
-use rayon::prelude::*;
-
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use super::{Closeable, DoorLock, Durability, Entity, Lockable, Opening};
 use crate::{
     dice_roll,
-    types::{Action, CmdResult},
+    input::named,
+    types::{Action, CmdResult, Condition, Direction, Encounter, Guard, Karma},
 };
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Pathway {
-    directions: Vec<String>,
+    directions: Vec<Direction>,
     target: String,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     desc: String,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     inspect: String,
     opening: Option<Opening>,
     lock: Option<DoorLock>,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
     durability: Durability,
+    // an audio cue for when it opens or closes
+    #[cfg_attr(feature = "serde", serde(default))]
+    sound: Option<String>,
+    // what happens when a door is knocked on, listened at, or looked under
+    #[cfg_attr(feature = "serde", serde(default))]
+    knock: Option<String>,
+    // someone on the other side opens the door after a knock
+    #[cfg_attr(feature = "serde", serde(default))]
+    answered: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    listen: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    under: Option<String>,
+    // a sheer drop or cliff that can only be climbed with a rope tied at one end
+    #[cfg_attr(feature = "serde", serde(default))]
+    climb: bool,
+    // shown as the player goes through, like "You slide down the chute!"
+    #[cfg_attr(feature = "serde", serde(default))]
+    message: Option<String>,
+    // the path stays hidden until this holds in its Room
+    #[cfg_attr(feature = "serde", serde(default))]
+    condition: Option<Condition>,
+    // rooms a teleporter or unstable portal may lead to instead, each only while its
+    // condition holds
+    #[cfg_attr(feature = "serde", serde(default))]
+    destinations: Vec<(String, Option<Condition>)>,
+    // how many turns the way takes, like a desert crossing
+    #[cfg_attr(feature = "serde", serde(default))]
+    turns: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    encounter: Option<Encounter>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    guard: Option<Guard>,
+    // only a player whose karma falls within this can bring themselves to go this way
+    #[cfg_attr(feature = "serde", serde(default))]
+    karma: Option<Karma>,
 }
 
 impl Pathway {
+    pub fn new(directions: &[&str], target: &str, desc: &str, inspect: &str) -> Self {
+        Self {
+            directions: directions.iter().map(|dir| Direction::parse(dir)).collect(),
+            target: target.to_owned(),
+            desc: desc.to_owned(),
+            inspect: inspect.to_owned(),
+            opening: None,
+            lock: None,
+            durability: Durability::default(),
+            sound: None,
+            knock: None,
+            answered: false,
+            listen: None,
+            under: None,
+            climb: false,
+            message: None,
+            condition: None,
+            destinations: Vec::new(),
+            turns: 0,
+            encounter: None,
+            guard: None,
+            karma: None,
+        }
+    }
+
+    pub fn with_opening(mut self, opening: Opening) -> Self {
+        self.opening = Some(opening);
+        self
+    }
+
+    pub fn with_lock(mut self, lock: DoorLock) -> Self {
+        self.lock = Some(lock);
+        self
+    }
+
+    pub const fn with_durability(mut self, durability: Durability) -> Self {
+        self.durability = durability;
+        self
+    }
+
+    // how many turns the way takes, each a chance for its Encounter
+    pub const fn with_turns(mut self, turns: u32) -> Self {
+        self.turns = turns;
+        self
+    }
+
+    pub fn with_encounter(mut self, encounter: Encounter) -> Self {
+        self.encounter = Some(encounter);
+        self
+    }
+
+    pub fn with_guard(mut self, guard: Guard) -> Self {
+        self.guard = Some(guard);
+        self
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub const fn condition(&self) -> Option<&Condition> {
+        self.condition.as_ref()
+    }
+
+    pub fn destinations(&self) -> &[(String, Option<Condition>)] {
+        &self.destinations
+    }
+
+    // rewrite the name of every Room this Pathway may lead to
+    #[cfg(feature = "serde")]
+    pub fn rename_targets(&mut self, rename: &impl Fn(&mut String)) {
+        rename(&mut self.target);
+        for (target, _) in self.destinations.iter_mut() {
+            rename(target);
+        }
+    }
+
+    // rewrite the code of its lock and the Items its conditions ask for
+    #[cfg(feature = "serde")]
+    pub fn rename_keys(&mut self, code: &impl Fn(&mut String), item: &impl Fn(&mut String)) {
+        if let Some(DoorLock::Locked(lock)) = &mut self.lock {
+            code(lock);
+        }
+        let destinations = self.destinations.iter_mut().map(|(_, condition)| condition);
+        for condition in std::iter::once(&mut self.condition)
+            .chain(destinations)
+            .flatten()
+        {
+            condition.rename_item(item);
+        }
+    }
+
+    pub const fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    pub const fn encounter(&self) -> Option<&Encounter> {
+        self.encounter.as_ref()
+    }
+
+    pub const fn guard(&self) -> Option<&Guard> {
+        self.guard.as_ref()
+    }
+
+    pub const fn karma(&self) -> Option<&Karma> {
+        self.karma.as_ref()
+    }
+
+    pub const fn needs_rope(&self) -> bool {
+        self.climb
+    }
+
+    pub const fn is_door(&self) -> bool {
+        self.opening.is_some()
+    }
+
+    pub fn knock(&mut self, name: &str) -> CmdResult {
+        let reply = self
+            .knock
+            .clone()
+            .unwrap_or_else(|| format!("You knock on the {}. Nobody answers.", name));
+        if self.answered && self.is_closed() {
+            self.swing_open();
+        }
+        CmdResult::new(Action::Active, reply)
+    }
+
+    pub fn listen(&self, name: &str) -> CmdResult {
+        CmdResult::new(
+            Action::Passive,
+            self.listen
+                .clone()
+                .unwrap_or_else(|| format!("You press an ear to the {}, but hear nothing.", name)),
+        )
+    }
+
+    pub fn look_under(&self, name: &str) -> CmdResult {
+        CmdResult::new(
+            Action::Passive,
+            self.under
+                .clone()
+                .unwrap_or_else(|| format!("You peer under the {}, but see only shadow.", name)),
+        )
+    }
+
+    // unlock and open the way at once, as a gate worked from the other side
+    pub fn swing_open(&mut self) {
+        if self.lock.is_some() {
+            self.lock = Some(DoorLock::Unlocked);
+        }
+        if self.opening.is_some() {
+            self.opening = Some(Opening::Open);
+        }
+    }
+
+    pub fn sound(&self) -> Option<&str> {
+        self.sound.as_deref()
+    }
+
+    // the last direction is the plainest way to name the path, like "north" after "door"
+    pub fn direction(&self) -> &str {
+        self.directions.last().map_or("", Direction::name)
+    }
+
+    pub fn directions(&self) -> &[Direction] {
+        &self.directions
+    }
+
     pub fn long_desc(&self) -> String {
         if let Some(opening) = self.opening {
             match opening {
@@ -35,25 +241,9 @@ impl Pathway {
     }
 
     pub fn any_direction(&self, dir_name: &str) -> bool {
-        if cfg!(target_arch = "wasm32") {
-            self.directions
-                .iter()
-                .map(|direction| direction.split_whitespace().collect())
-                .any(|direction: Vec<&str>| {
-                    dir_name
-                        .par_split_whitespace()
-                        .all(|ref word| direction.contains(word))
-                })
-        } else {
-            self.directions
-                .par_iter()
-                .map(|direction| direction.par_split_whitespace().collect())
-                .any(|direction: Vec<&str>| {
-                    dir_name
-                        .par_split_whitespace()
-                        .all(|ref word| direction.contains(word))
-                })
-        }
+        self.directions
+            .iter()
+            .any(|direction| named(direction.name(), dir_name))
     }
 }
 
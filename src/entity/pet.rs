@@ -0,0 +1,147 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Entity, Item};
+use crate::{input::same_name, prose, types::Items};
+
+fn two() -> usize {
+    2
+}
+
+// An animal that can be tamed with treats to follow the player, carry a small load, and sniff
+// out what is hidden; unlike an Ally it never fights
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pet {
+    name: String,
+    desc: String,
+    inspect: String,
+    // the food it can be won over with
+    treat: String,
+    // what the player has called it, like "Rex"
+    #[cfg_attr(feature = "serde", serde(default))]
+    given_name: Option<String>,
+    // grows with every treat; it follows the player once above zero
+    #[cfg_attr(feature = "serde", serde(default))]
+    loyalty: u32,
+    #[cfg_attr(feature = "serde", serde(default = "two"))]
+    capacity: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pack: Items,
+}
+
+impl Pet {
+    pub const MAX_LOYALTY: u32 = 5;
+    // how loyal it must be before it will search on command
+    pub const OBEDIENT: u32 = 2;
+
+    pub fn new(name: &str, inspect: &str, treat: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: format!("There is {} here.", prose::a(name)),
+            inspect: inspect.to_owned(),
+            treat: treat.to_owned(),
+            given_name: None,
+            loyalty: 0,
+            capacity: two(),
+            pack: Items::new(),
+        }
+    }
+
+    pub fn with_desc(mut self, desc: &str) -> Self {
+        self.desc = String::from(desc);
+        self
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn treat(&self) -> &str {
+        &self.treat
+    }
+
+    pub const fn loyalty(&self) -> u32 {
+        self.loyalty
+    }
+
+    pub const fn is_tame(&self) -> bool {
+        self.loyalty > 0
+    }
+
+    // whether it answers to the given name, by kind or by what it has been called
+    pub fn answers_to(&self, name: &str) -> bool {
+        same_name(&self.name, name)
+            || self
+                .given_name
+                .as_ref()
+                .is_some_and(|given| same_name(given, name))
+    }
+
+    // how it is shown in a Room: "Rex the dog is here." once named
+    pub fn long_desc(&self) -> String {
+        match &self.given_name {
+            Some(given) => format!("{} the {} is here.", given, self.name),
+            None if self.is_tame() => format!("Your {} is here.", self.name),
+            None => self.desc.to_owned(),
+        }
+    }
+
+    pub fn details(&self) -> String {
+        let mood = match self.loyalty {
+            0 => "It eyes you warily.",
+            1 => "It seems to trust you.",
+            l if l < Pet::MAX_LOYALTY => "It is fond of you.",
+            _ => "It is utterly devoted to you.",
+        };
+        let mut details = format!("{} {}", self.inspect, mood);
+        if !self.pack.is_empty() {
+            let names: Vec<String> = self.pack.iter().map(|item| prose::a(item.name())).collect();
+            details.push_str(&format!(" It is carrying {}.", prose::list(&names)));
+        }
+        details
+    }
+
+    pub fn feed(&mut self) {
+        self.loyalty = (self.loyalty + 1).min(Pet::MAX_LOYALTY);
+    }
+
+    pub fn set_given_name(&mut self, given_name: &str) {
+        self.given_name = Some(prose::capitalize(given_name));
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.pack.len() >= self.capacity
+    }
+
+    pub fn carry(&mut self, item: Box<Item>) {
+        self.pack.push(item);
+    }
+
+    pub fn unload(&mut self) -> Items {
+        std::mem::take(&mut self.pack)
+    }
+}
+
+impl Entity for Pet {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    fn inspect(&self) -> &str {
+        &self.inspect
+    }
+
+    // a named Pet is spoken of by name
+    fn the(&self) -> String {
+        match &self.given_name {
+            Some(given) => given.to_owned(),
+            None => prose::the(&self.name, false),
+        }
+    }
+}
@@ -1,78 +1,379 @@
-use rayon::prelude::*;
-
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Closeable, Element, Enemy, Entity,
-    Item::{self, Container},
-    Lockable, Pathway,
+    Ally, Closeable, Element, Enemy, Entity,
+    Item::{self, Container, Mimic},
+    Lockable, Pathway, Pet,
+};
+use crate::{
+    dice_roll,
+    input::{fold, named, same_name, CmdTokens},
+    prose,
+    types::{
+        Action, Allies, AllyId, Answer, Attack, Bestiary, Breach, Check, CmdResult, Condition,
+        Conversation, Deed, Dial, Direction, Elements, Enemies, EnemyId, Furniture, Hazard, ItemId,
+        Items, Listener, LootTables, Memory, Order, Paths, PetId, Pets, Prompt, Pronouns, Reply,
+        Responses, Riddle, SavingThrow, Sequence, Tie, ToolUse,
+    },
 };
-use crate::types::{Action, Allies, Attack, CmdResult, Elements, Enemies, Items, Paths};
 
 // A section of the world connected by paths
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Room {
     name: String,
     desc: String,
     paths: Paths,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     enemies: Enemies,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     allies: Allies,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pets: Pets,
+    #[cfg_attr(feature = "serde", serde(default))]
     elements: Elements,
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     items: Items,
+    // Items hidden here that only a Pet's keen nose can find
+    #[cfg_attr(feature = "serde", serde(default))]
+    buried: Items,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hazard: Option<Hazard>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    shrine: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    floor_capacity: Option<usize>,
+    // players hosted together may fight each other here
+    #[cfg_attr(feature = "serde", serde(default))]
+    pvp: bool,
+    // an audio cue for the theme that plays while the player is here
+    #[cfg_attr(feature = "serde", serde(default))]
+    music: Option<String>,
+    // the overland area whose wandering Enemies roam here
+    #[cfg_attr(feature = "serde", serde(default))]
+    region: Option<String>,
+    // a camp the player made, safe from wanderers and packed up once they leave
+    #[cfg_attr(feature = "serde", serde(default))]
+    camp: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fire: bool,
+    // the furniture the player is on or in, and what can only be reached from furniture
+    #[cfg_attr(feature = "serde", serde(default))]
+    position: Option<(String, Furniture)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    reach: Vec<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    listeners: Vec<Listener>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    ties: Vec<Tie>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    sequences: Vec<Sequence>,
+    // what is said here for verbs that do nothing, in place of the World's
+    #[cfg_attr(feature = "serde", serde(default))]
+    responses: Responses,
+    // what is said here for verbs done to particular things, in place of the World's
+    #[cfg_attr(feature = "serde", serde(default))]
+    replies: Vec<Reply>,
+    // what lies here belongs to someone, so taking it in front of anyone is theft
+    #[cfg_attr(feature = "serde", serde(default))]
+    owned: bool,
+    // too perilous to save in, where a world restricts saves
+    #[cfg_attr(feature = "serde", serde(default))]
+    dangerous: bool,
+    // directions that lead nowhere, with what is said when the player tries them, like
+    // ("east", "The cliff drops away to the sea far below."); a path the same way goes first
+    #[cfg_attr(feature = "serde", serde(default))]
+    dead_ends: Vec<(String, String)>,
 }
 
 impl Room {
+    pub fn new(name: &str, desc: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            desc: desc.to_owned(),
+            ..Self::default()
+        }
+    }
+
+    // a camp made beside the given Room, with a way back out to it
+    pub fn camp(origin: &str, tent: bool, fire: bool) -> Self {
+        let desc = match (tent, fire) {
+            (true, true) => "Your tent is pitched beside a crackling campfire.",
+            (true, false) => "Your tent is pitched here, out of the wind.",
+            _ => "A campfire crackles in a ring of stones.",
+        };
+        let mut camp = Self::new("Camp", desc);
+        camp.camp = true;
+        camp.fire = fire;
+        camp.add_path(Pathway::new(
+            &["out", "leave", "break camp"],
+            origin,
+            "",
+            "",
+        ));
+        camp
+    }
+
+    // strike a camp, leaving nothing behind
+    pub fn pack_up(&mut self) {
+        *self = Self {
+            name: self.name.to_owned(),
+            camp: true,
+            ..Self::default()
+        };
+    }
+
+    pub const fn is_camp(&self) -> bool {
+        self.camp
+    }
+
+    pub const fn has_fire(&self) -> bool {
+        self.fire
+    }
+
+    // whether Recipes for the given station can be made here, a fire counting as a campfire
+    pub fn has_station(&self, station: &str) -> bool {
+        (self.fire && station == "campfire")
+            || self.elements.iter().any(|el| el.station() == Some(station))
+    }
+
+    pub fn add_path(&mut self, path: Pathway) {
+        self.paths.push(Box::new(path));
+    }
+
+    pub fn paths(&self) -> &Paths {
+        &self.paths
+    }
+
+    // problems with this Room that would break play, such as exits leading nowhere
+    pub fn validate(
+        &self,
+        is_room: impl Fn(&str) -> bool,
+        is_loot_table: impl Fn(&str) -> bool,
+    ) -> Vec<String> {
+        let mut problems = Vec::new();
+        for path in self.paths.iter() {
+            if !is_room(path.name()) {
+                problems.push(format!(
+                    "{}: a path leads to {}, which is not a room",
+                    self.name,
+                    path.name()
+                ));
+            }
+        }
+        for enemy in self.enemies.iter() {
+            if let Some(table) = enemy.loot_table() {
+                if !is_loot_table(table) {
+                    problems.push(format!(
+                        "{}: the {} uses the loot table {}, which does not exist",
+                        self.name,
+                        enemy.name(),
+                        table
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    // rewrite every name this Room uses to refer to other Rooms, loot tables, regions, Items, the
+    // codes of locks, and quests
+    #[cfg(feature = "serde")]
+    pub fn rename_references(
+        &mut self,
+        room: impl Fn(&mut String),
+        loot_table: impl Fn(&mut String),
+        region: impl Fn(&mut String),
+        item: impl Fn(&mut String),
+        code: impl Fn(&mut String),
+        quest: impl Fn(&mut String),
+    ) {
+        for path in self.paths.iter_mut() {
+            path.rename_targets(&room);
+            path.rename_keys(&code, &item);
+        }
+        for el in self.elements.iter_mut() {
+            el.rename_rooms(&room);
+        }
+        for enemy in self.enemies.iter_mut() {
+            enemy.rename_loot_table(&loot_table);
+        }
+        for sequence in self.sequences.iter_mut() {
+            sequence.rename_quest(&quest);
+        }
+        if let Some(name) = &mut self.region {
+            region(name);
+        }
+    }
+
+    // rewrite the names of the Items found here, lying about, buried, or carried by Enemies,
+    // and what those Items refer to
+    #[cfg(feature = "serde")]
+    pub fn rename_items(
+        &mut self,
+        item: &impl Fn(&mut String),
+        code: &impl Fn(&mut String),
+        quest: &impl Fn(&mut String),
+    ) {
+        for found in self.items.iter_mut().chain(self.buried.iter_mut()) {
+            found.rename_references(item, code, quest);
+        }
+        for enemy in self.enemies.iter_mut() {
+            enemy.rename_loot(item, code, quest);
+        }
+    }
+
     // collects all descriptions of entities in the Room for printing
     pub fn long_desc(&self) -> String {
-        format!(
-            "{}\n{}{}{}{}{}{}",
-            self.name,
-            self.desc,
-            self.elements.iter().fold(String::new(), |desc, el| {
-                format!("{}\n{}", desc, el.desc())
-            }),
-            self.paths.iter().fold(String::new(), |desc, path| {
-                format!("{}\n{}", desc, path.long_desc())
-            }),
-            self.items.iter().fold(String::new(), |desc, item| {
-                format!("{}\n{}", desc, item.long_desc())
-            }),
-            self.allies.iter().fold(String::new(), |desc, ally| {
-                format!("{}\n{}", desc, ally.desc())
-            }),
-            self.enemies.iter().fold(String::new(), |desc, enemy| {
-                format!("{}\n{}", desc, enemy.long_desc())
-            }),
-        )
+        let mut desc = format!("{}\n{}", self.name, self.desc);
+        for el in self.elements.iter() {
+            desc.push('\n');
+            desc.push_str(el.desc());
+        }
+        for tie in self.ties.iter() {
+            desc.push('\n');
+            desc.push_str(&tie.desc());
+        }
+        for path in self.paths.iter().filter(|path| self.is_shown(path)) {
+            desc.push('\n');
+            desc.push_str(&path.long_desc());
+        }
+        self.push_items_desc(&mut desc);
+        for ally in self.allies.iter() {
+            desc.push('\n');
+            desc.push_str(ally.desc());
+        }
+        for pet in self.pets.iter() {
+            desc.push('\n');
+            desc.push_str(&pet.long_desc());
+        }
+        self.push_enemies_desc(&mut desc);
+        if let Some((name, furniture)) = &self.position {
+            desc.push_str(&format!("\nYou are {} the {}.", furniture.prep(), name));
+        }
+        desc
+    }
+
+    // what can be seen from another Room: the short description and any Enemies
+    pub fn glimpse(&self) -> String {
+        let mut desc = self.desc.to_owned();
+        self.push_enemies_desc(&mut desc);
+        desc
+    }
+
+    // lists plainly described Items in one sentence, counting duplicates,
+    // while Items with their own descriptions keep their own lines
+    fn push_items_desc(&self, desc: &mut String) {
+        let mut groups: Vec<(&str, usize)> = Vec::new();
+        for item in self.items.iter() {
+            let item_desc = item.long_desc();
+            if item_desc == format!("There is {} here.", prose::a(item.name())) {
+                if let Some(group) = groups.iter_mut().find(|(name, _)| *name == item.name()) {
+                    group.1 += 1;
+                } else {
+                    groups.push((item.name(), 1));
+                }
+            } else {
+                desc.push('\n');
+                desc.push_str(&item_desc);
+            }
+        }
+
+        if let Some((_, first_count)) = groups.first() {
+            let verb = if *first_count > 1 { "are" } else { "is" };
+            let names: Vec<String> = groups
+                .iter()
+                .map(|(name, count)| {
+                    if *count > 1 {
+                        prose::count(*count, name)
+                    } else {
+                        prose::a(name)
+                    }
+                })
+                .collect();
+            desc.push_str(&format!("\nThere {} {} here.", verb, prose::list(&names)));
+        }
+    }
+
+    // groups identical Enemies so that a crowd is described in one line
+    fn push_enemies_desc(&self, desc: &mut String) {
+        let mut groups: Vec<(String, usize, &Enemy)> = Vec::new();
+        for enemy in self.enemies.iter() {
+            let enemy_desc = enemy.long_desc();
+            if let Some(group) = groups.iter_mut().find(|(d, _, _)| *d == enemy_desc) {
+                group.1 += 1;
+            } else {
+                groups.push((enemy_desc, 1, enemy));
+            }
+        }
+
+        for (enemy_desc, count, enemy) in groups {
+            desc.push('\n');
+            if count > 1 {
+                desc.push_str(&enemy.group_desc(count));
+            } else {
+                desc.push_str(&enemy_desc);
+            }
+        }
     }
 
     #[allow(clippy::borrowed_box)]
     fn find_element(&self, name: &str) -> Option<&Box<Element>> {
-        if cfg!(target_arch = "wasm32") {
-            self.elements.iter().find(|el| {
-                el.name().split_whitespace().any(|el_word| {
-                    name.split_whitespace()
-                        .any(|name_word| name_word == el_word)
-                })
+        let name = fold(name);
+        self.elements.iter().find(|el| {
+            fold(el.name()).split_whitespace().any(|el_word| {
+                name.split_whitespace()
+                    .any(|name_word| name_word == el_word)
             })
-        } else {
-            self.elements.par_iter().find_any(|el| {
-                el.name().par_split_whitespace().any(|el_word| {
-                    name.par_split_whitespace()
-                        .any(|name_word| name_word == el_word)
-                })
-            })
-        }
+        })
     }
 
     fn path_pos(&self, dir_name: &str) -> Option<usize> {
         self.paths
-            .par_iter()
-            .position_any(|pathway| pathway.any_direction(dir_name))
+            .iter()
+            .position(|pathway| pathway.any_direction(dir_name) && self.is_shown(pathway))
+    }
+
+    // a conditional path only shows once its condition holds here
+    fn is_shown(&self, path: &Pathway) -> bool {
+        path.condition()
+            .iter()
+            .all(|condition| self.holds(condition))
+    }
+
+    pub fn holds(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Cleared => self.enemies.is_empty(),
+            Condition::Item(name) => self.items.key_of(name).is_some(),
+            Condition::Open(direction) => self
+                .paths
+                .iter()
+                .any(|other| other.any_direction(direction) && !other.is_closed()),
+            Condition::Remembers(name, deed) => {
+                self.find_ally(name)
+                    .is_some_and(|ally| ally.memory().recalls(*deed))
+                    || self
+                        .find_enemy(name)
+                        .is_some_and(|enemy| enemy.memory().recalls(*deed))
+            }
+        }
+    }
+
+    // where a path leads this time: a random pick of its destinations whose conditions
+    // hold here, or its own target when there are none
+    pub fn destination<'a>(&self, path: &'a Pathway) -> &'a str {
+        let open: Vec<&str> = path
+            .destinations()
+            .iter()
+            .filter(|(_, condition)| condition.iter().all(|condition| self.holds(condition)))
+            .map(|(room, _)| room.as_str())
+            .collect();
+        if open.is_empty() {
+            path.name()
+        } else {
+            open[dice_roll(1, open.len() as u32) as usize - 1]
+        }
     }
     #[allow(clippy::borrowed_box)]
     pub fn find_path(&self, direction: &str) -> Option<&Box<Pathway>> {
@@ -91,11 +392,125 @@ impl Room {
         }
     }
 
+    // turn a disguised Enemy into a hostile one if the named Item is a Mimic
+    pub fn reveal(&mut self, name: &str) -> Option<CmdResult> {
+        let key = self.item_key(name)?;
+        if let Some(Mimic(_)) = self.items.get_key(key) {
+            if let Some(Mimic(mimic)) = self.items.remove_key(key).map(|item| *item) {
+                let res = CmdResult::new(Action::Active, mimic.reveal());
+                self.enemies.push(Box::new(mimic.into_enemy()));
+                return Some(res);
+            }
+        }
+        None
+    }
+
+    // reveal every Mimic in the Room at once
+    pub fn reveal_all(&mut self) -> Option<CmdResult> {
+        let mimics = self.items.extract(|item| matches!(item, Mimic(_)));
+
+        if mimics.is_empty() {
+            None
+        } else {
+            let mut reveals = Vec::with_capacity(mimics.len());
+            for mimic in mimics {
+                if let Mimic(mimic) = *mimic {
+                    reveals.push(mimic.reveal().to_owned());
+                    self.enemies.push(Box::new(mimic.into_enemy()));
+                }
+            }
+            Some(CmdResult::new(Action::Active, reveals.join("\n")))
+        }
+    }
+
+    // targets of every path an Enemy could flee through
+    pub fn escape_routes(&self) -> Vec<String> {
+        self.paths
+            .iter()
+            .filter(|path| self.is_shown(path) && !path.is_closed() && !path.is_locked())
+            .map(|path| path.name().to_owned())
+            .collect()
+    }
+
+    // an Enemy that surrenders leaves its loot for the taking
+    pub fn surrender(&mut self, enemy: EnemyId) -> String {
+        if let Some(enemy) = self.enemies.get_key_mut(enemy) {
+            let res = enemy.surrender();
+            self.items.extend(enemy.drop_loot());
+            res
+        } else {
+            String::new()
+        }
+    }
+
+    pub fn set_hazard(&mut self, hazard: Hazard) {
+        self.hazard = Some(hazard);
+    }
+
+    pub const fn hazard(&self) -> Option<&Hazard> {
+        self.hazard.as_ref()
+    }
+
+    pub fn set_shrine(&mut self) {
+        self.shrine = true;
+    }
+
+    pub const fn has_shrine(&self) -> bool {
+        self.shrine
+    }
+
+    pub const fn responses(&self) -> &Responses {
+        &self.responses
+    }
+
+    pub fn set_response(&mut self, verb: &str, response: &str) {
+        self.responses.set(verb, response);
+    }
+
+    pub fn replies(&self) -> &[Reply] {
+        &self.replies
+    }
+
+    pub fn add_reply(&mut self, reply: Reply) {
+        self.replies.push(reply);
+    }
+
+    // move an Enemy in from elsewhere, exposing it to any hazard here
+    pub fn receive_enemy(&mut self, mut enemy: Box<Enemy>) -> String {
+        if let Some(hazard) = &self.hazard {
+            let res = enemy.suffer_hazard(hazard);
+            if enemy.is_alive() {
+                self.enemies.push(enemy);
+            } else {
+                self.items.extend(enemy.drop_loot());
+            }
+            res
+        } else {
+            self.enemies.push(enemy);
+            String::new()
+        }
+    }
+
+    // every Item but the scenery and whatever is out of reach
     pub fn drain_all(&mut self) -> Items {
-        self.items.drain(0..).collect()
+        let mut reachable = self
+            .items
+            .iter()
+            .map(|item| item.scenery().is_none() && self.out_of_reach(item.name()).is_none())
+            .collect::<Vec<bool>>()
+            .into_iter();
+        self.items.extract(|_| reachable.next().unwrap_or(false))
+    }
+
+    // refuse to let a piece of scenery be taken
+    pub fn scenery(&self, name: &str) -> Option<CmdResult> {
+        self.find_item(name)
+            .and_then(|item| item.scenery())
+            .map(|refusal| CmdResult::new(Action::Failed, refusal))
     }
 
     // take an Item from a container Item in the current Room
+    #[allow(clippy::result_large_err)]
     pub fn give_from(
         &mut self,
         item_name: &str,
@@ -177,6 +592,16 @@ impl Room {
         }
     }
 
+    // disarm a trapped Container, handing its trap to whoever opened it
+    pub fn take_trap(&mut self, name: &str) -> Option<SavingThrow> {
+        if let Some(item) = self.find_item_mut(name) {
+            if let Container(ref mut container) = **item {
+                return container.take_trap();
+            }
+        }
+        None
+    }
+
     pub fn close(&mut self, name: &str) -> CmdResult {
         if let Some(path) = self.find_path_mut(name) {
             if path.is_closed() {
@@ -196,101 +621,956 @@ impl Room {
         }
     }
 
+    pub fn summon(&mut self, ally: Ally) {
+        self.allies.push(Box::new(ally));
+    }
+
+    // remove summoned Allies and those told to follow so that they can follow the player
+    pub fn take_followers(&mut self) -> Allies {
+        self.allies.extract(Ally::follows_player)
+    }
+
+    pub fn find_ally(&self, name: &str) -> Option<&Ally> {
+        self.allies
+            .iter()
+            .find(|ally| same_name(ally.name(), name))
+            .map(Box::as_ref)
+    }
+
+    // tell an Ally here to do something, which it does on its turn if it is willing
+    pub fn command_ally(&mut self, name: &str, order: Order) -> CmdResult {
+        match self
+            .allies
+            .iter_mut()
+            .find(|ally| same_name(ally.name(), name))
+        {
+            Some(ally) => match ally.obey(order) {
+                Ok(res) => CmdResult::new(Action::Active, res),
+                Err(refusal) => CmdResult::new(Action::Passive, refusal),
+            },
+            None => CmdResult::no_item_here(name),
+        }
+    }
+
+    // Allies pick locks, pick things up, and put them down as they were told
+    pub fn ally_orders(&mut self) -> String {
+        let mut events = String::new();
+        // an Ally is followed by its Key, since what it carries or puts down moves others
+        let keys: Vec<AllyId> = self.allies.keys().collect();
+        for key in keys {
+            let (the, order) = match self.allies.get_key(key) {
+                Some(ally) => (prose::capitalize(&ally.the()), ally.order().cloned()),
+                None => continue,
+            };
+            match order {
+                Some(Order::Pick(name)) => {
+                    self.take_order(key);
+                    let path = match name {
+                        Some(name) => self.find_path_mut(&name),
+                        None => self.paths.iter_mut().find(|path| path.is_locked()),
+                    };
+                    events.push_str(&match path {
+                        Some(path) if path.is_locked() => {
+                            path.unlock();
+                            if path.is_locked() {
+                                format!(
+                                    "\n{} fails to pick the lock on the {}.",
+                                    the,
+                                    path.direction()
+                                )
+                            } else {
+                                format!("\n{} picks the lock on the {}.", the, path.direction())
+                            }
+                        }
+                        _ => format!("\n{} finds no lock to pick.", the),
+                    });
+                }
+                Some(Order::Carry(name)) => {
+                    self.take_order(key);
+                    events.push_str(&match self.remove_item(&name) {
+                        Some(item) => {
+                            let res = format!("\n{} picks up the {}.", the, item.name());
+                            if let Some(ally) = self.allies.get_key_mut(key) {
+                                ally.carry(item);
+                            }
+                            res
+                        }
+                        None => format!("\n{} finds no {} to carry.", the, name),
+                    });
+                }
+                Some(Order::Drop) => {
+                    self.take_order(key);
+                    let items = self
+                        .allies
+                        .get_key_mut(key)
+                        .map_or_else(Items::new, Ally::unload);
+                    if items.is_empty() {
+                        events.push_str(&format!("\n{} has nothing to put down.", the));
+                    } else {
+                        let names: Vec<String> = items
+                            .iter()
+                            .map(|item| prose::the(item.name(), false))
+                            .collect();
+                        events.push_str(&format!("\n{} puts down {}.", the, prose::list(&names)));
+                        self.items.extend(items);
+                    }
+                }
+                _ => (),
+            }
+        }
+        events
+    }
+
+    fn take_order(&mut self, key: AllyId) {
+        if let Some(ally) = self.allies.get_key_mut(key) {
+            ally.take_order();
+        }
+    }
+
+    pub fn add_pet(&mut self, pet: Pet) {
+        self.pets.push(Box::new(pet));
+    }
+
+    // the first Pet here that answers to the name
+    fn pet_key(&self, name: &str) -> Option<PetId> {
+        let pos = self.pets.iter().position(|pet| pet.answers_to(name))?;
+        self.pets.key(pos)
+    }
+
+    pub fn find_pet(&self, name: &str) -> Option<&Pet> {
+        self.pets.get_key(self.pet_key(name)?)
+    }
+
+    pub fn find_pet_mut(&mut self, name: &str) -> Option<&mut Pet> {
+        let key = self.pet_key(name)?;
+        self.pets.get_key_mut(key)
+    }
+
+    // remove tame Pets so that they can follow the player
+    pub fn take_tame(&mut self) -> Pets {
+        self.pets.extract(Pet::is_tame)
+    }
+
+    // bring anything hidden here out into the open
+    pub fn unearth(&mut self) -> Items {
+        let found = std::mem::take(&mut self.buried);
+        self.items.extend(found.iter().cloned());
+        found
+    }
+
+    // fighting Allies each strike the first hostile Enemy
+    pub fn ally_attacks(&mut self, loot_tables: &LootTables) -> String {
+        let mut events = String::new();
+        for ally in self.allies.iter_mut().filter(|ally| ally.is_fighter()) {
+            // an Ally told to attack goes after that Enemy until it falls
+            let target = match ally.order() {
+                Some(Order::Attack(target)) => self
+                    .enemies
+                    .iter()
+                    .position(|enemy| enemy.is_alive() && named(enemy.name(), target)),
+                _ => None,
+            };
+            if target.is_none() && matches!(ally.order(), Some(Order::Attack(_))) {
+                ally.take_order();
+            }
+            let enemy = match target {
+                Some(pos) => self.enemies.get_mut(pos),
+                None => self
+                    .enemies
+                    .iter_mut()
+                    .find(|enemy| enemy.is_alive() && enemy.is_angry()),
+            };
+            if let Some(enemy) = enemy {
+                events.push_str(&enemy.struck_by(&ally.the(), ally.damage()));
+                if !enemy.is_alive() {
+                    enemy.roll_loot(loot_tables);
+                    self.items.extend(enemy.drop_loot());
+                }
+            }
+        }
+        events
+    }
+
+    // summoned Allies fade away once their time is up
+    pub fn tick_allies(&mut self) -> String {
+        let mut events = String::new();
+        for ally in self.allies.iter_mut() {
+            if !ally.tick() {
+                events.push_str(&format!("\n{} fades away.", prose::capitalize(&ally.the())));
+            }
+        }
+        self.allies.retain(|ally| !ally.is_expired());
+        events
+    }
+
     // interact with an Ally
-    pub fn hail(&self, _ally_name: &str) -> CmdResult {
-        CmdResult::new(Action::Passive, "Hail, friend.")
+    pub fn hail(&self, ally_name: &str) -> CmdResult {
+        if let Some(ally) = self
+            .allies
+            .iter()
+            .find(|ally| same_name(ally.name(), ally_name))
+        {
+            CmdResult::new(
+                Action::Passive,
+                format!(
+                    "Hail, friend. Shall {} stay by your side? (yes/no)",
+                    ally.the()
+                ),
+            )
+            .with_prompt(Prompt::yes_no(
+                &format!("Shall {} stay by your side? (yes/no)", ally.the()),
+                Answer::Reply(if ally.pronouns() == Pronouns::it() {
+                    format!("{} nods.", prose::capitalize(&ally.the()))
+                } else {
+                    format!("{}.", ally.pronouns().act("nods", "nod"))
+                }),
+                Answer::Command(CmdTokens::new("dismiss").with_obj(ally_name)),
+            ))
+        } else {
+            CmdResult::new(Action::Passive, "Hail, friend.")
+        }
+    }
+
+    // talk with someone here, offered only the checks open to a player with this karma and
+    // to what the speaker remembers of them, which it may bring up first
+    pub fn talk(&self, ally_name: &str, karma: i32) -> CmdResult {
+        let offered = |checks: &[Check], memory: &Memory| -> Vec<Check> {
+            checks
+                .iter()
+                .filter(|check| check.is_offered(karma, memory))
+                .cloned()
+                .collect()
+        };
+        let remark = |res: CmdResult, memory: &Memory| match memory.remark() {
+            Some(remark) => res.with_preface(remark),
+            None => res,
+        };
+        match self
+            .allies
+            .iter()
+            .find(|ally| same_name(ally.name(), ally_name))
+        {
+            Some(ally) if ally.memory().bears_grudge() => remark(
+                CmdResult::new(
+                    Action::Passive,
+                    format!("{} will not speak to you.", prose::capitalize(&ally.the())),
+                ),
+                ally.memory(),
+            ),
+            Some(ally)
+                if !ally.topics().is_empty()
+                    || !offered(ally.checks(), ally.memory()).is_empty() =>
+            {
+                let conversation = Conversation::new(&ally.the(), ally.topics())
+                    .with_checks(ally.name(), &offered(ally.checks(), ally.memory()));
+                remark(
+                    CmdResult::new(Action::Passive, conversation.topics())
+                        .with_conversation(conversation),
+                    ally.memory(),
+                )
+            }
+            Some(ally) => CmdResult::new(
+                Action::Passive,
+                format!("{} has nothing to say.", prose::capitalize(&ally.the())),
+            ),
+            None => match self.find_enemy(ally_name) {
+                Some(enemy) if !offered(enemy.checks(), enemy.memory()).is_empty() => {
+                    let conversation = Conversation::new(&enemy.the(), &[])
+                        .with_checks(enemy.name(), &offered(enemy.checks(), enemy.memory()));
+                    remark(
+                        CmdResult::new(Action::Passive, conversation.topics())
+                            .with_conversation(conversation),
+                        enemy.memory(),
+                    )
+                }
+                Some(enemy) => CmdResult::new(
+                    Action::Passive,
+                    format!("{} will not talk.", prose::capitalize(&enemy.the())),
+                ),
+                None => self
+                    .pose_riddle(ally_name)
+                    .unwrap_or_else(|| CmdResult::no_item_here(ally_name)),
+            },
+        }
+    }
+
+    // the name of a combination lock here
+    pub fn find_dial(&self, name: &str) -> Option<&str> {
+        self.elements
+            .iter()
+            .find(|el| el.dial().is_some() && named(el.name(), name))
+            .map(|el| el.name())
+    }
+
+    // turn a combination lock here, opening any way it guards once the combination is right
+    pub fn turn_dial(&mut self, name: &str, numbers: &[u32]) -> Option<CmdResult> {
+        let el = self
+            .elements
+            .iter_mut()
+            .find(|el| el.dial().is_some() && named(el.name(), name))?;
+        let el_name = el.name().to_owned();
+        let dial = el.dial_mut()?;
+        if dial.is_open() {
+            return Some(CmdResult::new(
+                Action::Passive,
+                format!("The {} is already open.", el_name),
+            ));
+        }
+        match dial.turn(numbers) {
+            Some(true) => {
+                let reply = dial.reply().to_owned();
+                if let Some(direction) = dial.opens().map(str::to_owned) {
+                    if let Some(path) = self.find_path_mut(&direction) {
+                        path.swing_open();
+                    }
+                }
+                Some(CmdResult::new(Action::Active, reply))
+            }
+            Some(false) => Some(CmdResult::new(
+                Action::Active,
+                format!(
+                    "Nothing gives, and the {} spins back to the start.",
+                    el_name
+                ),
+            )),
+            None => Some(CmdResult::new(
+                Action::Active,
+                format!("The dial clicks to {}.", dial.reading()),
+            )),
+        }
+    }
+
+    pub fn reset_dial(&mut self, name: &str) -> Option<CmdResult> {
+        let el = self
+            .elements
+            .iter_mut()
+            .find(|el| el.dial().is_some() && named(el.name(), name))?;
+        let res = format!("You spin the {} back to the start.", el.name());
+        el.dial_mut()?.reset();
+        Some(CmdResult::new(Action::Active, res))
+    }
+
+    // the first Element here stirred by a song, opening any way it guards
+    pub fn play_song(&mut self, song: &str) -> Option<CmdResult> {
+        let (reply, opens) = self.elements.iter_mut().find_map(|el| {
+            let tune = el.tune_mut()?;
+            let opens = tune.opens().map(str::to_owned);
+            tune.hear(song).map(|reply| (reply.to_owned(), opens))
+        })?;
+        if let Some(direction) = opens {
+            if let Some(path) = self.find_path_mut(&direction) {
+                path.swing_open();
+            }
+        }
+        Some(CmdResult::new(Action::Active, reply))
+    }
+
+    // who an Ally here is called and the songs they can teach
+    pub fn teacher(&self, ally_name: &str) -> Option<(String, &[String])> {
+        self.allies
+            .iter()
+            .find(|ally| same_name(ally.name(), ally_name))
+            .map(|ally| (ally.the(), ally.songs()))
+    }
+
+    // the riddles posed here, each with the name of what poses it
+    pub fn riddles(&self) -> impl Iterator<Item = (&str, &Riddle)> {
+        self.elements
+            .iter()
+            .filter_map(|el| Some((el.name(), el.riddle()?)))
+    }
+
+    // the riddle of an Element here, after which the next line of input is taken as the answer
+    pub fn pose_riddle(&self, name: &str) -> Option<CmdResult> {
+        let el = self
+            .elements
+            .iter()
+            .find(|el| el.riddle().is_some() && named(el.name(), name))?;
+        let riddle = el.riddle()?;
+        Some(if riddle.is_solved() {
+            CmdResult::new(
+                Action::Passive,
+                format!("The {} has nothing more to ask.", el.name()),
+            )
+        } else {
+            CmdResult::new(
+                Action::Passive,
+                format!("{}\n(leave to step away)", riddle.question()),
+            )
+            .with_riddle(el.name())
+        })
+    }
+
+    // the reply to a guess at a riddle here, whether it was right, and the damage for a wrong one;
+    // a right answer opens any way the riddle guards
+    pub fn answer_riddle(&mut self, name: &str, guess: &str) -> Option<(String, bool, u32)> {
+        let riddle = self
+            .elements
+            .iter_mut()
+            .find(|el| same_name(el.name(), name))?
+            .riddle_mut()?;
+        let (reply, solved) = riddle.guess(guess);
+        let penalty = if solved { 0 } else { riddle.penalty() };
+        if let Some(direction) = riddle.opens().filter(|_| solved).map(str::to_owned) {
+            if let Some(path) = self.find_path_mut(&direction) {
+                path.swing_open();
+            }
+        }
+        Some((reply, solved, penalty))
+    }
+
+    // a check tried in conversation cannot be tried again
+    pub fn spend_check(&mut self, speaker: &str, topic: &str) {
+        if let Some(ally) = self.allies.iter_mut().find(|ally| ally.name() == speaker) {
+            ally.spend_check(topic);
+        } else if let Some(enemy) = self
+            .enemies
+            .iter_mut()
+            .find(|enemy| enemy.name() == speaker)
+        {
+            enemy.spend_check(topic);
+        }
     }
 
-    fn harm(&mut self, enemy: usize, enemy_name: &str, attack: Attack) -> CmdResult {
-        if let Some(enemy) = self.enemies.get_mut(enemy) {
+    pub fn calm_enemies(&mut self) {
+        for enemy in self.enemies.iter_mut() {
+            enemy.calm();
+        }
+    }
+
+    pub fn provoke_enemies(&mut self) {
+        for enemy in self.enemies.iter_mut() {
+            enemy.make_angry();
+        }
+    }
+
+    pub fn dismiss(&mut self, ally_name: &str) -> CmdResult {
+        if let Some(pos) = self
+            .allies
+            .iter()
+            .position(|ally| same_name(ally.name(), ally_name))
+        {
+            let ally = self.allies.remove(pos);
+            CmdResult::new(
+                Action::Active,
+                format!("{} departs.", prose::capitalize(&ally.the())),
+            )
+        } else {
+            CmdResult::no_item_here(ally_name)
+        }
+    }
+
+    fn harm(
+        &mut self,
+        enemy: EnemyId,
+        enemy_name: &str,
+        attack: Attack,
+        loot_tables: &LootTables,
+    ) -> CmdResult {
+        if let Some(enemy) = self.enemies.get_key_mut(enemy) {
+            let part = if let Some(part_name) = attack.target_part() {
+                if let Some(part) = enemy.body_part(part_name) {
+                    Some(part.clone())
+                } else {
+                    return CmdResult::new(
+                        Action::Failed,
+                        format!("{} has no {}.", prose::capitalize(&enemy.the()), part_name),
+                    );
+                }
+            } else {
+                None
+            };
+            let target = if let Some(part) = &part {
+                format!("{} in the {}", enemy.the(), part.name())
+            } else {
+                enemy.the()
+            };
+            let hit_modifier = part.as_ref().map_or(0, |part| part.hit_modifier());
+
             if let Some(damage) = attack.damage() {
-                if let Some(res) = enemy.take_damage(damage) {
+                enemy.remember(Deed::Attacked);
+                let (damage, reaction) =
+                    enemy.resist(damage, attack.damage_type(), attack.weapon_name());
+                let res = if damage == 0 && reaction.is_some() {
+                    enemy.make_angry();
+                    CmdResult::new(Action::Active, "")
+                } else if let Some(res) = enemy.take_damage_at(damage, hit_modifier) {
                     res
                 } else if enemy.is_alive() {
                     CmdResult::new(
                         Action::Active,
                         format!(
-                            "You hit the {} with your {} for {} damage.",
-                            enemy_name,
+                            "You hit {} with your {} for {} damage.{}",
+                            target,
                             attack.weapon_name(),
                             damage,
+                            if let Some(part) = &part {
+                                enemy.wound(part)
+                            } else {
+                                String::new()
+                            }
                         ),
                     )
                 } else {
+                    enemy.roll_loot(loot_tables);
                     CmdResult::new(
                         Action::Active,
                         format!(
-                            "You hit the {} with your {} for {} damage. It is dead.\n{}",
-                            enemy_name,
+                            "You hit {} with your {} for {} damage. {} dead.\n{}",
+                            target,
                             attack.weapon_name(),
                             damage,
+                            enemy.pronouns().act("is", "are"),
                             if !enemy.loot().is_empty() {
-                                let res = enemy
-                                    .loot()
-                                    .iter()
-                                    .fold(String::from("It dropped:\n"), |drops, loot| {
-                                        format!("{} {},", drops, loot.long_name())
-                                    });
-                                if cfg!(target_arch = "wasm32") {
-                                    self.items.extend(enemy.drop_loot());
-                                } else {
-                                    self.items.par_extend(enemy.drop_loot());
-                                }
+                                let res = enemy.loot().iter().fold(
+                                    format!("{}:\n", enemy.pronouns().act("dropped", "dropped")),
+                                    |drops, loot| format!("{} {},", drops, loot.long_name()),
+                                );
+                                self.items.extend(enemy.drop_loot());
                                 res
                             } else {
                                 String::new()
                             }
                         ),
                     )
+                };
+
+                if let Some(reaction) = reaction {
+                    CmdResult::new(
+                        Action::Active,
+                        format!("{} {}", reaction, res.output().trim_start())
+                            .trim_end()
+                            .to_owned(),
+                    )
+                } else {
+                    res
                 }
             } else {
-                CmdResult::dont_have(&attack.weapon_name())
+                CmdResult::dont_have(attack.weapon_name())
             }
         } else {
             CmdResult::no_item_here(enemy_name)
         }
     }
 
-    pub fn harm_enemy(&mut self, enemy_name: &str, attack: Attack) -> CmdResult {
-        if let Some(enemy) = self.enemy_pos(enemy_name) {
-            self.harm(enemy, enemy_name, attack)
+    pub fn harm_enemy(
+        &mut self,
+        enemy_name: &str,
+        attack: Attack,
+        loot_tables: &LootTables,
+    ) -> CmdResult {
+        if attack.is_area() && attack.damage().is_some() {
+            let targets = self.enemy_keys(enemy_name);
+            if targets.is_empty() {
+                CmdResult::no_item_here(enemy_name)
+            } else {
+                let outputs: Vec<String> = targets
+                    .iter()
+                    .map(|enemy| {
+                        self.harm(*enemy, enemy_name, attack.clone(), loot_tables)
+                            .output()
+                            .trim()
+                            .to_owned()
+                    })
+                    .collect();
+                CmdResult::new(Action::Active, outputs.join("\n"))
+            }
+        } else if let Some(enemy) = self.enemy_key(enemy_name) {
+            let enemy_name = enemy_name
+                .trim_end_matches(|c: char| c.is_ascii_digit())
+                .trim_end();
+            self.harm(enemy, enemy_name, attack, loot_tables)
         } else {
             CmdResult::no_item_here(enemy_name)
         }
     }
 
-    pub fn inspect(&self, name: &str) -> Option<CmdResult> {
+    pub fn inspect(&self, name: &str, bestiary: &Bestiary) -> Option<CmdResult> {
         if let Some(item) = self.find_item(name) {
-            Some(CmdResult::new(Action::Active, item.inspect()))
+            Some(CmdResult::new(Action::Active, item.details()))
         } else if let Some(item) = self.find_element(name) {
-            Some(CmdResult::new(Action::Active, item.inspect()))
+            match item.dial().map(Dial::reading) {
+                Some(reading) if !reading.is_empty() => Some(CmdResult::new(
+                    Action::Active,
+                    format!("{} The dial reads {}.", item.inspect(), reading),
+                )),
+                _ => Some(CmdResult::new(Action::Active, item.inspect())),
+            }
         } else if let Some(pathway) = self.find_path(name) {
             Some(CmdResult::new(Action::Active, pathway.inspect()))
         } else if let Some(enemy) = self.find_enemy(name) {
-            Some(CmdResult::new(Action::Active, enemy.inspect()))
+            Some(CmdResult::new(
+                Action::Active,
+                enemy.intel(bestiary.kills(enemy.name())),
+            ))
+        } else if let Some(pet) = self.find_pet(name) {
+            Some(CmdResult::new(Action::Active, pet.details()))
         } else {
             self.allies
                 .iter()
-                .find(|x| x.name() == name)
+                .find(|x| same_name(x.name(), name))
                 .map(|ally| CmdResult::new(Action::Active, ally.inspect()))
         }
     }
 
-    pub fn take_item(&mut self, name: &str, item: Option<Box<Item>>) -> CmdResult {
+    // a Room with a floor capacity hands back an Item that does not fit
+    pub fn take_item(
+        &mut self,
+        name: &str,
+        item: Option<Box<Item>>,
+    ) -> (CmdResult, Option<Box<Item>>) {
         if let Some(item) = item {
-            self.items.push(item);
-            CmdResult::new(Action::Active, "Dropped.")
+            if matches!(self.floor_capacity, Some(capacity) if self.items.len() >= capacity) {
+                (
+                    CmdResult::new(
+                        Action::Failed,
+                        format!("There is no room to drop the {} here.", name),
+                    ),
+                    Some(item),
+                )
+            } else {
+                self.items.push(item);
+                (CmdResult::new(Action::Active, "Dropped."), None)
+            }
         } else {
-            CmdResult::dont_have(name)
+            (CmdResult::dont_have(name), None)
         }
     }
 
+    pub fn get_on(&mut self, name: &str) -> CmdResult {
+        if let Some((on, furniture)) = &self.position {
+            return CmdResult::new(
+                Action::Failed,
+                format!("You are already {} the {}.", furniture.prep(), on),
+            );
+        }
+        if let Some(el) = self.find_element(name) {
+            if let Some(furniture) = el.furniture() {
+                let res = CmdResult::new(
+                    Action::Active,
+                    format!("You get {} the {}.", furniture.prep(), el.name()),
+                );
+                self.position = Some((el.name().to_owned(), furniture));
+                res
+            } else {
+                CmdResult::new(Action::Failed, format!("You cannot get on the {}.", name))
+            }
+        } else {
+            CmdResult::no_item_here(name)
+        }
+    }
+
+    pub fn get_off(&mut self) -> CmdResult {
+        if let Some((name, furniture)) = self.position.take() {
+            CmdResult::new(
+                Action::Active,
+                format!("You get {} the {}.", furniture.leave(), name),
+            )
+        } else {
+            CmdResult::new(Action::Failed, "You are already standing.")
+        }
+    }
+
+    // the player must leave furniture before leaving the Room
+    pub fn stuck_on_furniture(&self) -> Option<CmdResult> {
+        self.position.as_ref().map(|(name, furniture)| {
+            CmdResult::new(
+                Action::Failed,
+                format!(
+                    "You will have to get {} the {} first.",
+                    furniture.leave(),
+                    name
+                ),
+            )
+        })
+    }
+
+    // put something out of reach unless the player is on or in the named furniture
+    pub fn set_reach(&mut self, name: &str, furniture: &str) {
+        self.reach.push((name.to_owned(), furniture.to_owned()));
+    }
+
+    pub fn out_of_reach(&self, name: &str) -> Option<CmdResult> {
+        let (_, needed) = self.reach.iter().find(|(thing, _)| named(thing, name))?;
+        match &self.position {
+            Some((on, _)) if on == needed => None,
+            _ => Some(CmdResult::new(
+                Action::Failed,
+                format!("You cannot reach the {} from here.", name),
+            )),
+        }
+    }
+
+    pub fn set_floor_capacity(&mut self, capacity: usize) {
+        self.floor_capacity = Some(capacity);
+    }
+
+    pub fn set_music(&mut self, cue: &str) {
+        self.music = Some(cue.to_owned());
+    }
+
+    pub fn set_owned(&mut self, owned: bool) {
+        self.owned = owned;
+    }
+
+    pub const fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    pub fn set_dangerous(&mut self, dangerous: bool) {
+        self.dangerous = dangerous;
+    }
+
+    pub const fn is_dangerous(&self) -> bool {
+        self.dangerous
+    }
+
+    pub fn add_dead_end(&mut self, direction: &str, message: &str) {
+        self.dead_ends
+            .push((direction.to_owned(), message.to_owned()));
+    }
+
+    pub fn dead_end(&self, direction: &str) -> Option<&str> {
+        self.dead_ends
+            .iter()
+            .find(|(known, _)| named(known, direction))
+            .map(|(_, message)| message.as_str())
+    }
+
+    // everyone here remembers what the player did, named as they are seen doing so
+    pub fn witness(&mut self, deed: Deed) -> Vec<String> {
+        let mut witnesses = Vec::new();
+        for ally in self.allies.iter_mut() {
+            ally.remember(deed);
+            witnesses.push(ally.the());
+        }
+        for enemy in self.enemies.iter_mut().filter(|enemy| enemy.is_alive()) {
+            enemy.remember(deed);
+            witnesses.push(enemy.the());
+        }
+        witnesses
+    }
+
+    // hand an Item to someone here, or get it back if there is nobody by that name
+    #[allow(clippy::result_large_err)]
+    pub fn give_to(&mut self, name: &str, item: Box<Item>) -> Result<CmdResult, Box<Item>> {
+        let item_name = item.name().to_owned();
+        let receiver = if let Some(ally) = self
+            .allies
+            .iter_mut()
+            .find(|ally| same_name(ally.name(), name))
+        {
+            ally.receive(item);
+            ally.the()
+        } else if let Some(enemy) = self
+            .enemy_key(name)
+            .and_then(|key| self.enemies.get_key_mut(key))
+        {
+            enemy.receive(item);
+            enemy.the()
+        } else {
+            return Err(item);
+        };
+        Ok(CmdResult::new(
+            Action::Active,
+            format!("You give the {} to {}.", item_name, receiver),
+        ))
+    }
+
+    pub fn set_region(&mut self, region: &str) {
+        self.region = Some(region.to_owned());
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    pub fn music(&self) -> Option<&str> {
+        self.music.as_deref()
+    }
+
+    pub fn set_pvp(&mut self) {
+        self.pvp = true;
+    }
+
+    pub const fn is_pvp(&self) -> bool {
+        self.pvp
+    }
+
     pub fn remove_item(&mut self, name: &str) -> Option<Box<Item>> {
-        if let Some(item) = self.item_pos(name) {
-            Some(self.items.remove(item))
+        let key = self.item_key(name)?;
+        self.items.remove_key(key)
+    }
+
+    // take out the Item defined under exactly this name, rather than the first one the name
+    // could mean
+    pub fn remove_exact_item(&mut self, name: &str) -> Option<Box<Item>> {
+        let key = self.items.key_of(name)?;
+        self.items.remove_key(key)
+    }
+
+    // take out a heavy Element to push it into another Room
+    pub fn remove_heavy(&mut self, name: &str) -> Option<Box<Element>> {
+        let pos = self
+            .elements
+            .iter()
+            .position(|el| el.is_heavy() && named(el.name(), name))?;
+        Some(self.elements.remove(pos))
+    }
+
+    // set down a heavy Element, pressing the first free plate or filling the first hole
+    pub fn settle(&mut self, el: Box<Element>) -> Option<String> {
+        self.elements.push(el);
+        let plate = self
+            .elements
+            .iter_mut()
+            .filter_map(|el| el.plate_mut())
+            .find(|plate| !plate.is_pressed())?;
+        let reply = plate.press().to_owned();
+        if let Some(direction) = plate.opens().map(str::to_owned) {
+            if let Some(path) = self.find_path_mut(&direction) {
+                path.swing_open();
+            }
+        }
+        Some(reply)
+    }
+
+    pub fn has_element(&self, name: &str) -> bool {
+        self.find_element(name).is_some()
+    }
+
+    pub fn is_breakable(&self, name: &str) -> bool {
+        self.elements
+            .iter()
+            .any(|el| el.is_breakable() && named(el.name(), name))
+    }
+
+    // strike a breakable floor or ceiling by name, taking it away once it gives way
+    pub fn strike(&mut self, name: &str, damage: u32) -> Option<(String, Option<Breach>)> {
+        let pos = self
+            .elements
+            .iter()
+            .position(|el| el.is_breakable() && named(el.name(), name))?;
+        let el_name = self.elements[pos].name().to_owned();
+        if self.elements[pos].breach_mut()?.strike(damage) {
+            Some((el_name, self.elements.remove(pos).into_breach()))
         } else {
-            None
+            Some((el_name, None))
+        }
+    }
+
+    // something here to gather from, by name, with the tool it needs if any
+    pub fn find_harvest(&self, name: &str) -> Option<(&str, Option<&str>)> {
+        self.elements
+            .iter()
+            .find(|el| el.harvest().is_some() && named(el.name(), name))
+            .and_then(|el| el.harvest().map(|harvest| (el.name(), harvest.tool())))
+    }
+
+    pub fn gather(&mut self, name: &str, now: u32) -> Option<Result<Items, u32>> {
+        self.elements
+            .iter_mut()
+            .find(|el| el.harvest().is_some() && named(el.name(), name))?
+            .harvest_mut()
+            .map(|harvest| harvest.gather(now))
+    }
+
+    // an Element here by name that the verb can be done to with a tool, and what it takes
+    pub fn find_tool_use(&self, name: &str, verb: &str) -> Option<(&str, &ToolUse)> {
+        self.elements
+            .iter()
+            .filter(|el| named(el.name(), name))
+            .find_map(|el| el.tool_use(verb).map(|tool_use| (el.name(), tool_use)))
+    }
+
+    // do the verb to an Element here once, leaving what it turns up and taking the Element away
+    // if it is used up
+    pub fn use_tool(&mut self, name: &str, verb: &str) -> Option<ToolUse> {
+        let pos = self
+            .elements
+            .iter()
+            .position(|el| named(el.name(), name) && el.tool_use(verb).is_some())?;
+        let tool_use = self.elements[pos].take_tool_use(verb)?;
+        if tool_use.consumes() {
+            self.elements.remove(pos);
         }
+        Some(tool_use)
+    }
+
+    // the name of an Element here that a rope can be tied to
+    pub fn find_anchor(&self, name: &str) -> Option<&str> {
+        self.elements
+            .iter()
+            .find(|el| el.is_anchor() && named(el.name(), name))
+            .map(|el| el.name())
+    }
+
+    // a rope tied to an anchor here makes any climb from this Room possible
+    pub fn is_roped(&self) -> bool {
+        self.ties.iter().any(|tie| tie.lowered().is_none())
+    }
+
+    pub fn add_tie(&mut self, tie: Tie) {
+        self.ties.push(tie);
+    }
+
+    // take away the Tie whose rope, or whatever the rope is tied to, is named
+    pub fn remove_tie(&mut self, name: &str) -> Option<Tie> {
+        let pos = self
+            .ties
+            .iter()
+            .position(|tie| named(tie.rope(), name) || named(tie.anchor(), name))?;
+        Some(self.ties.remove(pos))
+    }
+
+    pub fn add_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+
+    pub fn add_sequence(&mut self, sequence: Sequence) {
+        self.sequences.push(sequence);
+    }
+
+    pub fn sequences(&self) -> &[Sequence] {
+        &self.sequences
+    }
+
+    // do a step of a Sequence here, saying how it went and the quest it completes if that was
+    // the last step, swinging open any path it opens
+    pub fn follow_sequence(&mut self, verb: &str, obj: &str) -> Option<(String, Option<String>)> {
+        let sequence = self
+            .sequences
+            .iter_mut()
+            .find(|sequence| sequence.is_step(verb, obj))?;
+        let said = sequence.follow(verb, obj);
+        if !sequence.is_solved() {
+            return Some((said, None));
+        }
+        let quest = sequence.completes().map(str::to_owned);
+        if let Some(direction) = sequence.opens().map(str::to_owned) {
+            if let Some(path) = self.find_path_mut(&direction) {
+                path.swing_open();
+            }
+        }
+        Some((said, quest))
+    }
+
+    // the replies of Listeners here to a shout from the given number of rooms away
+    pub fn hear(&mut self, shout: &str, distance: u32) -> Vec<String> {
+        let mut replies = Vec::new();
+        let mut opened = Vec::new();
+        for listener in self.listeners.iter_mut() {
+            if let Some(reply) = listener.hear(shout, distance) {
+                replies.push(reply.to_owned());
+                opened.extend(listener.opens().map(str::to_owned));
+            }
+        }
+        for direction in opened {
+            if let Some(path) = self.find_path_mut(&direction) {
+                path.swing_open();
+            }
+        }
+        replies
     }
 
     pub fn add_element(&mut self, el: Element) {
@@ -305,79 +1585,157 @@ impl Room {
         self.enemies.push(Box::new(enemy));
     }
 
+    pub fn remove_enemy(&mut self, enemy_name: &str) -> Option<Box<Enemy>> {
+        let key = self.enemy_key(enemy_name)?;
+        self.enemies.remove_key(key)
+    }
+
+    pub fn anger_enemy(&mut self, enemy_name: &str) {
+        if let Some(enemy) = self
+            .enemy_key(enemy_name)
+            .and_then(|key| self.enemies.get_key_mut(key))
+        {
+            enemy.make_angry();
+        }
+    }
+
+    pub fn grapple(&mut self, enemy_name: &str, strength_mod: i32) -> CmdResult {
+        if let Some(enemy) = self
+            .enemy_key(enemy_name)
+            .and_then(|key| self.enemies.get_key_mut(key))
+        {
+            enemy.grapple(strength_mod)
+        } else {
+            CmdResult::no_item_here(enemy_name)
+        }
+    }
+
+    // each way out of the Room and where it leads, like "north to Small Cave"
+    pub fn exits(&self) -> Vec<String> {
+        self.paths
+            .iter()
+            .filter(|path| self.is_shown(path))
+            .map(|path| format!("{} to {}", path.direction(), path.name()))
+            .collect()
+    }
+
+    // a window or keyhole by name, with the Room it looks into
+    pub fn find_view(&self, name: &str) -> Option<(&str, &str)> {
+        self.find_element(name)
+            .and_then(|el| el.view().map(|view| (el.name(), view)))
+    }
+
+    // every Room that a window or keyhole here looks into
+    pub fn views(&self) -> Vec<&str> {
+        self.elements.iter().filter_map(|el| el.view()).collect()
+    }
+
+    // every Room that a breakable floor or ceiling here would open into
+    pub fn breaches(&self) -> Vec<&str> {
+        self.elements
+            .iter()
+            .filter_map(|el| el.breach_target())
+            .collect()
+    }
+
+    // the named directions of the ways out that show, with the Room each leads to
+    pub fn compass(&self) -> Vec<(Direction, &str)> {
+        self.paths
+            .iter()
+            .filter(|path| self.is_shown(path))
+            .flat_map(|path| {
+                path.directions()
+                    .iter()
+                    .filter(|direction| !direction.is_custom())
+                    .map(move |direction| (direction.clone(), path.name()))
+            })
+            .collect()
+    }
+
+    // names of the Rooms one Pathway away, including every place a teleporter may lead
+    pub fn neighbors(&self) -> Vec<&str> {
+        self.paths
+            .iter()
+            .flat_map(|path| {
+                std::iter::once(path.name())
+                    .chain(path.destinations().iter().map(|(room, _)| room.as_str()))
+            })
+            .collect()
+    }
+
+    pub const fn items(&self) -> &Items {
+        &self.items
+    }
+
     pub const fn enemies(&self) -> &Enemies {
         &self.enemies
     }
+    pub const fn allies(&self) -> &Allies {
+        &self.allies
+    }
     pub fn enemies_mut(&mut self) -> &mut Enemies {
         &mut self.enemies
     }
 
-    fn item_pos(&self, item_name: &str) -> Option<usize> {
-        if cfg!(target_arch = "wasm32") {
-            self.items
-                .iter()
-                .map(|item| item.name().split_whitespace().collect())
-                .position(|item: Vec<&str>| {
-                    item_name
-                        .split_whitespace()
-                        .all(|ref word| item.contains(word))
-                })
-        } else {
-            self.items
-                .par_iter()
-                .map(|item| item.name().par_split_whitespace().collect())
-                .position_any(|item: Vec<&str>| {
-                    item_name
-                        .par_split_whitespace()
-                        .all(|ref word| item.contains(word))
-                })
-        }
+    fn item_key(&self, item_name: &str) -> Option<ItemId> {
+        let pos = self
+            .items
+            .iter()
+            .position(|item| named(item.name(), item_name))?;
+        self.items.key(pos)
     }
     #[allow(clippy::borrowed_box)]
-    fn find_item(&self, item_name: &str) -> Option<&Box<Item>> {
-        if let Some(pos) = self.item_pos(item_name) {
-            self.items.get(pos)
-        } else {
-            None
-        }
+    pub fn find_item(&self, item_name: &str) -> Option<&Box<Item>> {
+        let pos = self.items.position(self.item_key(item_name)?)?;
+        self.items.get(pos)
     }
     #[allow(clippy::borrowed_box)]
     fn find_item_mut(&mut self, item_name: &str) -> Option<&mut Box<Item>> {
-        if let Some(pos) = self.item_pos(item_name) {
-            self.items.get_mut(pos)
-        } else {
-            None
-        }
+        let pos = self.items.position(self.item_key(item_name)?)?;
+        self.items.get_mut(pos)
     }
 
-    fn enemy_pos(&self, enemy_name: &str) -> Option<usize> {
-        if cfg!(target_arch = "wasm32") {
-            self.enemies
-                .iter()
-                .map(|enemy| enemy.name().split_whitespace().collect())
-                .position(|enemy: Vec<&str>| {
-                    enemy_name
-                        .split_whitespace()
-                        .all(|ref word| enemy.contains(word))
-                })
-        } else {
-            self.enemies
-                .par_iter()
-                .map(|enemy| enemy.name().par_split_whitespace().collect())
-                .position_any(|enemy: Vec<&str>| {
-                    enemy_name
-                        .par_split_whitespace()
-                        .all(|ref word| enemy.contains(word))
-                })
+    // every Enemy matching the name, in order
+    fn enemy_keys(&self, enemy_name: &str) -> Vec<EnemyId> {
+        self.enemies
+            .iter()
+            .zip(self.enemies.keys())
+            .filter(|(enemy, _)| named(enemy.name(), enemy_name))
+            .map(|(_, key)| key)
+            .collect()
+    }
+
+    fn enemy_key(&self, enemy_name: &str) -> Option<EnemyId> {
+        // "goblin 2" picks the second of several goblins
+        if let Some((name, nth)) = enemy_name.rsplit_once(' ') {
+            if let Ok(nth) = nth.parse::<usize>() {
+                return if nth > 0 {
+                    self.enemy_keys(name).get(nth - 1).copied()
+                } else {
+                    None
+                };
+            }
+        }
+
+        let pos = self
+            .enemies
+            .iter()
+            .position(|enemy| named(enemy.name(), enemy_name))?;
+        self.enemies.key(pos)
+    }
+    // the name of the only living Enemy here, if there is just one
+    pub fn lone_enemy(&self) -> Option<&str> {
+        let mut living = self.enemies.iter().filter(|enemy| enemy.is_alive());
+        match (living.next(), living.next()) {
+            (Some(enemy), None) => Some(enemy.name()),
+            _ => None,
         }
     }
+
     #[allow(clippy::borrowed_box)]
-    fn find_enemy(&self, enemy_name: &str) -> Option<&Box<Enemy>> {
-        if let Some(pos) = self.enemy_pos(enemy_name) {
-            self.enemies.get(pos)
-        } else {
-            None
-        }
+    pub fn find_enemy(&self, enemy_name: &str) -> Option<&Box<Enemy>> {
+        let pos = self.enemies.position(self.enemy_key(enemy_name)?)?;
+        self.enemies.get(pos)
     }
 }
 
@@ -1,6 +1,8 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CmdTokens {
     verb: Option<String>,
     obj: Option<String>,
@@ -59,11 +61,12 @@ impl CmdTokens {
 
     pub fn short_verb(&self) -> (Option<&str>, Option<&str>) {
         if let Some(verb) = &self.verb {
-            if verb.len() >= 6 {
-                (Some(&verb), Some(&verb[0..6]))
-            } else {
-                (Some(&verb), Some(&verb))
-            }
+            // the first six letters, however many bytes they take
+            let short = verb
+                .char_indices()
+                .nth(6)
+                .map_or(verb.as_str(), |(end, _)| &verb[..end]);
+            (Some(verb), Some(short))
         } else {
             (None, None)
         }
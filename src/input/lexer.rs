@@ -1,6 +1,7 @@
-use rayon::prelude::*;
-
-use crate::input::CmdTokens;
+use crate::{
+    input::{fold, CmdTokens},
+    types::Direction,
+};
 
 #[derive(Clone, Debug)]
 pub struct Lexer;
@@ -14,15 +15,9 @@ impl Lexer {
         } else if words.len() < 2 {
             CmdTokens::new(words[0].to_owned())
         } else {
-            let prep_pos = if cfg!(target_arch = "wasm32") {
-                words
-                    .iter()
-                    .position(|r| ["in", "inside", "from", "on", "with"].contains(&r.as_str()))
-            } else {
-                words
-                    .par_iter()
-                    .position_any(|r| ["in", "inside", "from", "on", "with"].contains(&r.as_str()))
-            };
+            let prep_pos = words
+                .iter()
+                .position(|r| ["in", "inside", "from", "on", "with"].contains(&r.as_str()));
 
             if let Some(pos) = prep_pos {
                 if pos == 0 {
@@ -54,43 +49,27 @@ impl Lexer {
     }
 
     fn filter_parts(s: &str) -> Vec<String> {
-        if cfg!(target_arch = "wasm32") {
-            s.split_whitespace()
-                .map(str::to_lowercase)
-                .filter(|w| {
-                    !([
-                        "a", "an", "around", "at", "of", "my", "that", "the", "through", "to", "'",
-                    ])
-                    .contains(&w.as_str())
-                })
-                .collect()
-        } else {
-            s.par_split_whitespace()
-                .map(str::to_lowercase)
-                .filter(|w| {
-                    !([
-                        "a", "an", "around", "at", "of", "my", "that", "the", "through", "to", "'",
-                    ])
-                    .contains(&w.as_str())
-                })
-                .collect()
-        }
+        s.split_whitespace()
+            .map(fold)
+            .filter(|w| {
+                !([
+                    "a", "an", "around", "at", "of", "my", "that", "the", "through", "to", "'",
+                ])
+                .contains(&w.as_str())
+            })
+            .collect()
     }
 
     fn mod_words(mut words: Vec<String>) -> Vec<String> {
         for w in words.iter_mut() {
+            let direction = Direction::parse(w);
+            if direction.is_bare() {
+                *w = direction.name().to_owned();
+                continue;
+            }
             match w.as_str() {
-                "n" => *w = String::from("north"),
-                "s" => *w = String::from("south"),
-                "e" => *w = String::from("east"),
-                "w" => *w = String::from("west"),
-                "ne" => *w = String::from("northeast"),
-                "nw" => *w = String::from("northwest"),
-                "se" => *w = String::from("southeast"),
-                "sw" => *w = String::from("southwest"),
-                "u" => *w = String::from("up"),
-                "d" => *w = String::from("down"),
                 "r" => *w = String::from("again"),
+                "using" => *w = String::from("with"),
                 _ => (),
             }
         }
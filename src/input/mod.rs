@@ -1,20 +1,25 @@
+#[cfg(feature = "terminal")]
 use std::io::{self, Write};
 
+#[cfg(feature = "terminal")]
 pub fn read_line(prompt: &str) -> String {
     print!("{}", prompt);
-    io::stdout().flush().expect("Error flushing stdout");
+    // a prompt that fails to show or a line that fails to read is treated as no input at all
+    let _ = io::stdout().flush();
 
     let mut input = String::new();
-    io::stdin()
-        .read_line(&mut input)
-        .expect("Error reading stdin");
+    if io::stdin().read_line(&mut input).is_err() {
+        input.clear();
+    }
     input.trim().to_owned()
 }
 
 mod cmdtokens;
 mod lexer;
+mod normalize;
 mod parser;
 
 pub use cmdtokens::CmdTokens;
 pub use lexer::Lexer;
+pub(crate) use normalize::{fold, named, normalize, same_name};
 pub use parser::Parser;
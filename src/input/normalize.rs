@@ -0,0 +1,53 @@
+// Typed input and entity names are brought to one form before they are compared, so that an
+// accent typed as one character or as two, a curly apostrophe from a phone keyboard, or a
+// capital letter never decides whether "café" finds the Café
+
+#[cfg(feature = "unicode")]
+use unicode_normalization::UnicodeNormalization;
+
+// straighten quotes and dashes and compose accents, keeping the case that was typed
+pub(crate) fn normalize(input: &str) -> String {
+    compose(input)
+        .chars()
+        .map(|c| match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' | '\u{00B4}'
+            | '\u{0060}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}'
+            | '\u{00BB}' => '"',
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
+            | '\u{2212}' => '-',
+            '\u{00A0}' | '\u{2007}' | '\u{202F}' => ' ',
+            '\u{2026}' => '.',
+            c => c,
+        })
+        .collect()
+}
+
+// the form names are compared in: normalized and without case
+pub(crate) fn fold(name: &str) -> String {
+    normalize(name).to_lowercase()
+}
+
+// every word typed is part of the name, so that "gandalf" and "grey gandalf" find "Gandalf the Grey"
+pub(crate) fn named(name: &str, input: &str) -> bool {
+    let name = fold(name);
+    let words: Vec<&str> = name.split_whitespace().collect();
+    fold(input)
+        .split_whitespace()
+        .all(|word| words.contains(&word))
+}
+
+// the same name, whatever case or composition either was written in
+pub(crate) fn same_name(a: &str, b: &str) -> bool {
+    fold(a) == fold(b)
+}
+
+#[cfg(feature = "unicode")]
+fn compose(input: &str) -> String {
+    input.nfc().collect()
+}
+
+#[cfg(not(feature = "unicode"))]
+fn compose(input: &str) -> String {
+    input.to_owned()
+}
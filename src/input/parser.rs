@@ -1,8 +1,10 @@
 use crate::{
     cli::Cli,
-    input::CmdTokens,
+    entity::Entity,
+    input::{named, CmdTokens},
     player::Player,
-    types::{Action, CmdResult},
+    prose,
+    types::{Action, CmdResult, Direction, Gear, Order},
     world::World,
 };
 
@@ -16,21 +18,42 @@ impl Parser {
         world: &mut World,
         player: &mut Player,
     ) -> CmdResult {
-        if let Some(obj) = words.obj() {
+        let target = match words.obj() {
+            Some(obj) => Some(obj.to_owned()),
+            None => Parser::default_target(world, player),
+        };
+        if let Some(obj) = target.as_deref() {
+            if world.get_curr_room().is_breakable(obj) {
+                return Parser::parse_smash(verb, words, world, player);
+            }
+            // "troll's head" aims at a body part of the troll
+            let (obj, part) = if let Some((enemy, part)) = obj.split_once("'s ") {
+                (enemy, Some(part))
+            } else {
+                (obj, None)
+            };
+
             if let Some(obj_prep) = words.obj_prep() {
                 if words.prep() == Some(&String::from("with")) {
-                    let res = world.harm_enemy(&obj, player.attack_with(&obj_prep));
+                    let obj_prep = match player.slotted(obj_prep) {
+                        Ok(obj_prep) => obj_prep,
+                        Err(res) => return res,
+                    };
+                    let res =
+                        world.harm_enemy(obj, player.attack_with(&obj_prep).with_target_part(part));
                     if res.is_active() {
-                        player.engage_combat()
+                        player.engage_combat();
+                        player.set_target(obj);
                     }
                     res
                 } else {
                     CmdResult::no_comprendo()
                 }
             } else if player.main_hand().is_some() {
-                let res = world.harm_enemy(&obj, player.attack_main());
+                let res = world.harm_enemy(obj, player.attack_main().with_target_part(part));
                 if res.is_active() {
-                    player.engage_combat()
+                    player.engage_combat();
+                    player.set_target(obj);
                 }
                 res
             } else {
@@ -42,6 +65,117 @@ impl Parser {
         }
     }
 
+    // the carried Item named after "with" or "using", by its full name; a quick slot can name it
+    // by number
+    #[allow(clippy::result_large_err)]
+    fn instrument(words: &CmdTokens, player: &Player) -> Result<Option<String>, CmdResult> {
+        match (words.prep(), words.obj_prep()) {
+            (Some("with"), Some(tool)) => {
+                let tool = player.slotted(tool)?;
+                match player.find_item(&tool) {
+                    Some(item) => Ok(Some(item.name().to_owned())),
+                    None => Err(CmdResult::dont_have(&tool)),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // "ring bell" may be a step of a puzzle here that must be done in order
+    fn parse_sequence(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> Option<CmdResult> {
+        let (res, quest) = world.follow_sequence(verb, words.obj()?)?;
+        if let Some(quest) = quest {
+            player.complete_quest(&quest);
+        }
+        Some(res)
+    }
+
+    // "cut rope with knife" does to an Element here what only one of its tools can, picking a
+    // carried tool when none is named
+    fn parse_tool_use(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &Player,
+    ) -> Option<CmdResult> {
+        let obj = words.obj()?;
+        let (el_name, tool_use) = world.get_curr_room().find_tool_use(obj, verb)?;
+        let refusal = match Parser::instrument(words, player) {
+            Ok(Some(tool)) if tool_use.accepts(&tool) => None,
+            Ok(Some(tool)) => Some(format!(
+                "You cannot {} the {} with the {}.",
+                verb, el_name, tool
+            )),
+            Ok(None)
+                if tool_use
+                    .tools()
+                    .iter()
+                    .any(|tool| player.find_item(tool).is_some()) =>
+            {
+                None
+            }
+            Ok(None) => {
+                let tools: Vec<String> = tool_use.tools().iter().map(|t| prose::a(t)).collect();
+                Some(format!(
+                    "You need {} to {} the {}.",
+                    prose::list_with(&tools, "or"),
+                    verb,
+                    el_name
+                ))
+            }
+            Err(res) => return Some(res),
+        };
+        Some(match refusal {
+            Some(refusal) => CmdResult::new(Action::Failed, refusal),
+            None => world.use_tool(obj, verb),
+        })
+    }
+
+    // a bare "attack" goes after the Enemy last fought, or the only one here
+    fn default_target(world: &World, player: &Player) -> Option<String> {
+        let room = world.get_curr_room();
+        player
+            .target()
+            .filter(|target| {
+                room.find_enemy(target)
+                    .is_some_and(|enemy| enemy.is_alive())
+            })
+            .or_else(|| room.lone_enemy())
+            .map(str::to_owned)
+    }
+
+    // "smash floorboards" or "smash floorboards with hammer" breaks through a floor or ceiling
+    fn parse_smash(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match words.obj() {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        let attack = match Parser::instrument(words, player) {
+            Ok(Some(weapon)) => player.attack_with(&weapon),
+            Ok(None) => player.attack_main(),
+            Err(res) => return res,
+        };
+        // bare hands barely dent it
+        let damage = attack.damage().unwrap_or(1);
+        if let Some(res) = world.smash(obj, damage) {
+            res
+        } else if world.get_curr_room().has_element(obj) {
+            CmdResult::new(Action::Passive, format!("The {} will not break.", obj))
+        } else {
+            CmdResult::no_item_here(obj)
+        }
+    }
+
     fn parse_close(
         verb: &str,
         words: &CmdTokens,
@@ -59,6 +193,42 @@ impl Parser {
         }
     }
 
+    fn parse_compare(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let names = match (words.obj(), words.prep(), words.obj_prep()) {
+            (Some(first), Some(_), Some(second)) => Some((first, second)),
+            (Some(obj), None, None) => obj.split_once(" and "),
+            _ => None,
+        };
+        if let Some((first, second)) = names {
+            let find = |name: &str| player.find_item(name).or_else(|| world.find_item(name));
+            match (find(first), find(second)) {
+                (Some(first), Some(second)) => {
+                    CmdResult::new(Action::Passive, first.compare(second))
+                }
+                (None, _) => CmdResult::no_item_here(first),
+                (_, None) => CmdResult::no_item_here(second),
+            }
+        } else if let Some(obj) = words.obj() {
+            CmdResult::do_what(&format!("{} the {} with", verb, obj))
+                .with_request_input(CmdTokens::new(verb).with_obj(obj).with_prep("with"))
+        } else {
+            CmdResult::do_what(verb)
+        }
+    }
+
+    fn parse_dismiss(verb: &str, words: &CmdTokens, world: &mut World) -> CmdResult {
+        if let Some(obj) = words.obj() {
+            world.dismiss(obj)
+        } else {
+            CmdResult::do_what(verb)
+        }
+    }
+
     fn parse_don(verb: &str, words: &CmdTokens, player: &mut Player) -> CmdResult {
         if let Some(obj) = words.obj() {
             player.don_armor(obj)
@@ -74,7 +244,16 @@ impl Parser {
         player: &mut Player,
     ) -> CmdResult {
         if let Some(obj) = words.obj() {
-            world.insert(&obj, player.remove(&obj))
+            match player.release(obj) {
+                Ok(item) => {
+                    let (res, rejected_item) = world.insert(obj, Some(item));
+                    if let Some(item) = rejected_item {
+                        player.take_back(item);
+                    }
+                    res
+                }
+                Err(res) => res,
+            }
         } else {
             CmdResult::do_what(verb)
         }
@@ -82,7 +261,31 @@ impl Parser {
 
     fn parse_equip(verb: &str, words: &CmdTokens, player: &mut Player) -> CmdResult {
         if let Some(obj) = words.obj() {
-            player.equip(obj)
+            if obj == "best" {
+                player.equip_best()
+            } else {
+                match player.slotted(obj) {
+                    Ok(obj) => player.equip(&obj),
+                    Err(res) => res,
+                }
+            }
+        } else {
+            CmdResult::do_what(verb)
+        }
+    }
+
+    fn parse_grapple(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        if let Some(obj) = words.obj() {
+            let res = world.grapple(obj, player.strength_mod());
+            if res.is_active() {
+                player.engage_combat()
+            }
+            res
         } else {
             CmdResult::do_what(verb)
         }
@@ -90,15 +293,68 @@ impl Parser {
 
     fn parse_hail(words: &CmdTokens, world: &mut World) -> CmdResult {
         if let Some(obj) = words.obj() {
-            world.hail(&obj)
+            world.hail(obj)
         } else {
             CmdResult::new(Action::Passive, "Hello, sailor!")
         }
     }
 
+    // "give apple to squire" hands the Item over, and they remember the gift; with "to" dropped
+    // by the lexer, the receiver is whoever here the end of the command names
+    fn parse_give(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match (words.obj(), words.obj_prep()) {
+            (Some(obj), Some(obj_prep)) => format!("{} {}", obj, obj_prep),
+            (Some(obj), None) => obj.to_owned(),
+            _ => return CmdResult::do_what(verb),
+        };
+        let obj: Vec<&str> = obj.split_whitespace().collect();
+        let room = world.get_curr_room();
+        let split = (1..obj.len())
+            .find(|&split| {
+                let name = obj[split..].join(" ");
+                room.find_ally(&name).is_some() || room.find_enemy(&name).is_some()
+            })
+            .or_else(|| {
+                (1..obj.len())
+                    .rev()
+                    .find(|&split| player.has(&obj[..split].join(" ")))
+            });
+        let (item, receiver) = match split {
+            Some(split) => (obj[..split].join(" "), obj[split..].join(" ")),
+            None if player.has(&obj.join(" ")) => {
+                return CmdResult::do_what(&format!("{} the {} to", verb, obj.join(" ")))
+            }
+            None => return CmdResult::dont_have(&obj.join(" ")),
+        };
+        match player.release(&item) {
+            Ok(item) => match world.give_to(&receiver, item) {
+                Ok(res) => res,
+                Err(item) => {
+                    player.take_back(item);
+                    CmdResult::no_item_here(&receiver)
+                }
+            },
+            Err(res) => res,
+        }
+    }
+
+    fn parse_talk(verb: &str, words: &CmdTokens, world: &mut World, player: &Player) -> CmdResult {
+        // "talk with the squire" leaves the name in obj_prep
+        if let Some(obj) = words.obj().or_else(|| words.obj_prep()) {
+            world.talk(obj, player.karma())
+        } else {
+            CmdResult::do_what(verb)
+        }
+    }
+
     fn parse_increase(words: &CmdTokens, player: &mut Player) -> CmdResult {
         if let Some(obj) = words.obj() {
-            player.increase_ability_score(&obj)
+            player.increase_ability_score(obj)
         } else {
             CmdResult::do_what(
                 "increase?
@@ -107,9 +363,36 @@ impl Parser {
         }
     }
 
-    fn parse_move(verb: &str, words: &CmdTokens, world: &mut World) -> CmdResult {
+    // the player must bring themselves to go the way, and guards on it must first be
+    // satisfied that the player belongs there
+    fn go(direction: &str, world: &mut World, player: &mut Player) -> CmdResult {
+        let path = world.get_curr_room().find_path(direction);
+        if let Some(karma) = path.and_then(|path| path.karma()) {
+            if !karma.allows(player.karma()) {
+                return CmdResult::new(Action::Failed, karma.refusal());
+            }
+        }
+        let guard = match path.and_then(|path| path.guard()) {
+            Some(guard) => guard.clone(),
+            None => return world.move_room(direction),
+        };
+        match player.pass_guard(&guard) {
+            Ok(Some(waved)) => world.move_room(direction).with_preface(&waved),
+            Ok(None) => world.move_room(direction),
+            Err(res) => res,
+        }
+    }
+
+    fn parse_move(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
         if let Some(obj) = words.obj() {
-            world.move_room(&obj)
+            Parser::go(obj, world, player)
+        } else if world.get_curr_room().is_camp() {
+            Parser::go("out", world, player)
         } else {
             CmdResult::new(Action::Passive, format!("Where do you want to {}?", verb))
                 .with_request_input(CmdTokens::new(verb))
@@ -133,14 +416,515 @@ impl Parser {
         if let Some(obj) = words.obj() {
             if let Some(res) = player.open(obj) {
                 res
+            } else if let Some(res) = world.reveal(obj) {
+                res
+            } else {
+                let res = world.open(obj);
+                if !res.is_active() {
+                    return res;
+                }
+                // a trap is sprung the moment its Container is opened
+                if let Some(trap) = world.take_trap(obj) {
+                    CmdResult::new(
+                        Action::Active,
+                        format!("{}{}", res.output(), player.save_against(&trap)),
+                    )
+                } else {
+                    res
+                }
+            }
+        } else {
+            CmdResult::do_what(verb)
+        }
+    }
+
+    fn parse_push(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        if let Some(obj) = words.obj() {
+            let obj = match words.obj_prep() {
+                Some(obj_prep) => format!("{} {}", obj, obj_prep),
+                None => obj.to_owned(),
+            };
+            if let Some((res, items)) = world.haul(&obj) {
+                for item in items {
+                    player.take_back(item);
+                }
+                res
+            } else if let Some(res) = world.push_object(verb, &obj) {
+                res
             } else {
-                world.open(obj)
+                world.push_enemy(&obj, player.strength_mod())
             }
         } else {
             CmdResult::do_what(verb)
         }
     }
 
+    // "tie rope to post" ties a carried rope to an anchor here
+    fn parse_tie(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match (words.obj(), words.obj_prep()) {
+            (Some(obj), Some(obj_prep)) => format!("{} {}", obj, obj_prep),
+            (Some(obj), None) => obj.to_owned(),
+            _ => return CmdResult::do_what(verb),
+        };
+        let rope = match player.rope() {
+            Some(rope) => rope.to_owned(),
+            None => return CmdResult::new(Action::Failed, "You have nothing to tie."),
+        };
+
+        let obj: Vec<&str> = obj.split_whitespace().collect();
+        let anchor = (0..obj.len()).find_map(|split| {
+            world
+                .get_curr_room()
+                .find_anchor(&obj[split..].join(" "))
+                .map(str::to_owned)
+        });
+        match anchor {
+            Some(anchor) => match player.release(&rope) {
+                Ok(rope) => world.tie(rope, &anchor),
+                Err(res) => res,
+            },
+            None => CmdResult::new(
+                Action::Passive,
+                format!("There is nothing here to tie the {} to.", rope),
+            ),
+        }
+    }
+
+    fn parse_untie(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match words.obj() {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        if let Some((res, items)) = world.haul(obj) {
+            for item in items {
+                player.take_back(item);
+            }
+            res
+        } else if let Some(rope) = world.untie(obj) {
+            let res = format!("You untie the {}.", rope.name());
+            player.take_back(rope);
+            CmdResult::new(Action::Active, res)
+        } else {
+            CmdResult::no_item_here(obj)
+        }
+    }
+
+    // "lower lantern down" ties a carried rope to the lantern and lets it down the path
+    fn parse_lower(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match words.obj() {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        let rope = match player.rope() {
+            Some(rope) => rope.to_owned(),
+            None => return CmdResult::new(Action::Failed, "You have no rope to lower it on."),
+        };
+
+        let obj: Vec<&str> = obj.split_whitespace().collect();
+        for split in 1..obj.len() {
+            let (name, direction) = (obj[..split].join(" "), obj[split..].join(" "));
+            if player.find_item(&name).is_none()
+                || world.get_curr_room().find_path(&direction).is_none()
+            {
+                continue;
+            }
+            if player.find_item(&name).map(|item| item.name()) == Some(rope.as_str()) {
+                return CmdResult::new(
+                    Action::Passive,
+                    format!("You need something to tie the {} to.", rope),
+                );
+            }
+            let item = match player.release(&name) {
+                Ok(item) => item,
+                Err(res) => return res,
+            };
+            let rope = match player.release(&rope) {
+                Ok(rope) => rope,
+                Err(res) => {
+                    player.take_back(item);
+                    return res;
+                }
+            };
+            let (res, rejected) = world.lower(rope, item, &direction);
+            for item in rejected {
+                player.take_back(item);
+            }
+            return res;
+        }
+        let obj = obj.join(" ");
+        if player.find_item(&obj).is_some() {
+            CmdResult::new(
+                Action::Passive,
+                format!("Which way do you want to {} the {}?", verb, obj),
+            )
+        } else {
+            CmdResult::new(Action::Passive, "You have nothing like that to lower.")
+        }
+    }
+
+    // a tent to sleep in and firewood, which burns away, for a fire to cook over
+    fn parse_camp(world: &mut World, player: &mut Player) -> CmdResult {
+        if let Some(res) = world.camp_blocked() {
+            return res;
+        }
+        let tent = player.gear(Gear::Tent).is_some();
+        let firewood = player.gear(Gear::Firewood).map(str::to_owned);
+        if !tent && firewood.is_none() {
+            return CmdResult::new(Action::Passive, "You have nothing to make camp with.");
+        }
+        let fire = match firewood {
+            Some(firewood) => match player.release(&firewood) {
+                Ok(_) => true,
+                Err(res) => return res,
+            },
+            None => false,
+        };
+        world.make_camp(tent, fire)
+    }
+
+    // "brew healing potion" at a cauldron, or "cook stew" over a campfire
+    fn parse_craft(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match words.obj() {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        match world.recipe(obj) {
+            Some((name, recipe)) if !world.get_curr_room().has_station(recipe.station()) => {
+                CmdResult::new(
+                    Action::Failed,
+                    format!(
+                        "You need {} to make {}.",
+                        prose::a(recipe.station()),
+                        prose::a(name)
+                    ),
+                )
+            }
+            Some((name, recipe)) => player.craft(name, recipe),
+            None => CmdResult::new(
+                Action::Failed,
+                format!("You do not know how to make {}.", prose::a(obj)),
+            ),
+        }
+    }
+
+    // "dial 7-2-9" or "dial 7 2 9" splits into the dial and the numbers to turn it to
+    fn split_numbers(obj: &str) -> (String, Vec<u32>) {
+        let words: Vec<&str> = obj.split_whitespace().collect();
+        let pos = words
+            .iter()
+            .position(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+            .unwrap_or(words.len());
+        let numbers = words[pos..]
+            .iter()
+            .flat_map(|word| word.split(['-', ',']))
+            .filter_map(|number| number.parse().ok())
+            .collect();
+        (words[..pos].join(" "), numbers)
+    }
+
+    // whether "set" is meant for a combination lock here rather than a setting
+    pub fn turns_dial(words: &CmdTokens, world: &World) -> bool {
+        words
+            .obj()
+            .map(Parser::split_numbers)
+            .filter(|(_, numbers)| !numbers.is_empty())
+            .and_then(|(dial, _)| world.get_curr_room().find_dial(&dial).map(|_| ()))
+            .is_some()
+    }
+
+    // "turn dial to 3" enters one number, and "set dial to 7-2-9" a whole combination
+    fn parse_dial(verb: &str, words: &CmdTokens, world: &mut World) -> CmdResult {
+        let (dial, numbers) = match words.obj().map(Parser::split_numbers) {
+            Some((dial, _)) if dial.is_empty() => return CmdResult::do_what(verb),
+            Some(split) => split,
+            None => return CmdResult::do_what(verb),
+        };
+        if numbers.is_empty() {
+            CmdResult::new(
+                Action::Passive,
+                format!("What do you want to {} the {} to?", verb, dial),
+            )
+        } else {
+            world.turn_dial(&dial, &numbers)
+        }
+    }
+
+    // "assign potion to 1" binds an Item to a quick slot
+    fn parse_assign(verb: &str, words: &CmdTokens, player: &mut Player) -> CmdResult {
+        let obj = match words.obj() {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        match obj.rsplit_once(' ') {
+            Some((item, slot)) => match slot.parse() {
+                Ok(slot) => player.assign_slot(item, slot),
+                Err(_) => CmdResult::new(
+                    Action::Passive,
+                    format!("Which quick slot do you want to {} the {} to?", verb, obj),
+                ),
+            },
+            None => CmdResult::new(
+                Action::Passive,
+                format!("Which quick slot do you want to {} the {} to?", verb, obj),
+            ),
+        }
+    }
+
+    // "play flute" plays a simple tune, and "play song of opening on lute" a learned song
+    fn parse_play(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match words.obj() {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        let (song, instrument) = match words.obj_prep() {
+            Some(instrument) => (Some(obj), Some(instrument)),
+            None if player.known_song(obj).is_some() => (Some(obj), None),
+            None => (None, Some(obj)),
+        };
+        let instrument = match player.instrument(instrument) {
+            Ok(instrument) => instrument.to_owned(),
+            Err(res) => return res,
+        };
+        match song {
+            Some(song) => match player.known_song(song) {
+                Some(song) => world.play_song(song, &instrument),
+                None => CmdResult::new(Action::Failed, "You don't know that song."),
+            },
+            None => CmdResult::new(
+                Action::Active,
+                format!("You play a simple tune on the {}.", instrument),
+            ),
+        }
+    }
+
+    // "learn song of opening from bard", or every song a teacher or carried scroll has
+    fn parse_learn(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let (wanted, source) = match (words.obj(), words.obj_prep()) {
+            (wanted, Some(source)) => (wanted, source),
+            (Some(obj), None) if player.find_item(obj).is_some() => (None, obj),
+            (Some(obj), None) => {
+                return CmdResult::new(
+                    Action::Passive,
+                    format!("What do you want to learn the {} from?", obj),
+                )
+            }
+            (None, None) => return CmdResult::do_what(verb),
+        };
+        if let Some(item) = player.find_item(source) {
+            let teacher = format!("the {}", item.name());
+            let songs: Vec<String> = item.song().map(str::to_owned).into_iter().collect();
+            player.learn_from(&teacher, &songs, wanted)
+        } else if let Some((teacher, songs)) = world.teacher(source) {
+            player.learn_from(&teacher, &songs, wanted)
+        } else {
+            CmdResult::no_item_here(source)
+        }
+    }
+
+    // "feed dog", so long as the player carries the treat it likes
+    fn parse_feed(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj = match words.obj() {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        let treat = match world.get_curr_room().find_pet(obj) {
+            Some(pet) => pet.treat().to_owned(),
+            None => return CmdResult::no_item_here(obj),
+        };
+        match player.release(&treat) {
+            Ok(_) => world.feed_pet(obj),
+            Err(_) => CmdResult::new(
+                Action::Failed,
+                format!("You have no {} to feed the {}.", treat, obj),
+            ),
+        }
+    }
+
+    // "name dog rex" splits into the Pet being named and the name it is given
+    fn parse_name(verb: &str, words: &CmdTokens, world: &mut World) -> CmdResult {
+        let obj: Vec<&str> = match words.obj() {
+            Some(obj) => obj.split_whitespace().collect(),
+            None => return CmdResult::do_what(verb),
+        };
+        for split in 1..obj.len() {
+            let pet = obj[..split].join(" ");
+            if world.get_curr_room().find_pet(&pet).is_some() {
+                return world.name_pet(&pet, &obj[split..].join(" "));
+            }
+        }
+        CmdResult::new(Action::Passive, "What do you want to name, and what to?")
+    }
+
+    // "command dog to search", "command rex to carry sword", or "command rex to drop"
+    fn parse_command(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        let obj: Vec<&str> = match words.obj() {
+            Some(obj) => obj.split_whitespace().collect(),
+            None => return CmdResult::do_what(verb),
+        };
+        if let Some(split) = (1..obj.len()).find(|split| {
+            world
+                .get_curr_room()
+                .find_ally(&obj[..*split].join(" "))
+                .is_some()
+        }) {
+            return Parser::command_ally(&obj[..split].join(" "), &obj[split..], words, world);
+        }
+        let split = match (1..obj.len()).find(|split| {
+            world
+                .get_curr_room()
+                .find_pet(&obj[..*split].join(" "))
+                .is_some()
+        }) {
+            Some(split) => split,
+            None => return CmdResult::new(Action::Passive, "There is no animal here to command."),
+        };
+        let pet = obj[..split].join(" ");
+        let item = obj[split + 1..].join(" ");
+        match obj[split] {
+            "search" | "sniff" | "dig" | "hunt" => world.pet_search(&pet),
+            "drop" | "unload" => world.pet_drop(&pet),
+            "carry" | "hold" | "take" if !item.is_empty() => match player.release(&item) {
+                Ok(item) => {
+                    let (res, refused) = world.pet_carry(&pet, item);
+                    if let Some(item) = refused {
+                        player.take_back(item);
+                    }
+                    res
+                }
+                Err(res) => res,
+            },
+            _ => CmdResult::new(
+                Action::Passive,
+                format!(
+                    "The {} can be told to search, carry something, or drop what it carries.",
+                    pet
+                ),
+            ),
+        }
+    }
+
+    // "tell squire to attack troll" or "tell thief to pick lock on gate"
+    fn command_ally(ally: &str, order: &[&str], words: &CmdTokens, world: &mut World) -> CmdResult {
+        let obj = match words.obj_prep() {
+            Some(obj_prep) => obj_prep.to_owned(),
+            None => order[1..].join(" "),
+        };
+        match Order::parse(order[0], &obj) {
+            Some(order) => world.command_ally(ally, order),
+            None => {
+                let ally = match world.get_curr_room().find_ally(ally) {
+                    Some(ally) => ally,
+                    None => return CmdResult::no_item_here(ally),
+                };
+                let orders: Vec<String> = ally
+                    .orders()
+                    .into_iter()
+                    .map(|order| Order::describe(order).to_owned())
+                    .collect();
+                CmdResult::new(
+                    Action::Passive,
+                    format!(
+                        "{} can be told to {}.",
+                        prose::capitalize(&ally.the()),
+                        prose::list_with(&orders, "or")
+                    ),
+                )
+            }
+        }
+    }
+
+    // "fish pond" or "mine vein", so long as the player carries any tool it needs
+    fn parse_gather(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        // "fish with rod" and "mine vein with pickaxe" name the tool, "gather from bush" the
+        // place
+        let instrument = match Parser::instrument(words, player) {
+            Ok(instrument) => instrument,
+            Err(res) => return res,
+        };
+        let obj = match instrument {
+            Some(_) => words.obj(),
+            None => words.obj_prep().or_else(|| words.obj()),
+        };
+        let obj = match obj {
+            Some(obj) => obj,
+            None => return CmdResult::do_what(verb),
+        };
+        let tool = world
+            .get_curr_room()
+            .find_harvest(obj)
+            .and_then(|(_, tool)| tool)
+            .map(str::to_owned);
+        if let Some(tool) = tool {
+            if let Some(instrument) = instrument.filter(|instrument| !named(instrument, &tool)) {
+                return CmdResult::new(
+                    Action::Failed,
+                    format!("You cannot {} with the {}.", verb, instrument),
+                );
+            }
+            if !player.has(&tool) && player.find_item(&tool).is_none() {
+                return CmdResult::new(
+                    Action::Failed,
+                    format!("You need {} for that.", prose::a(&tool)),
+                );
+            }
+        }
+        let (res, items) = world.gather(obj);
+        for item in items {
+            player.take_back(item);
+        }
+        res
+    }
+
     fn parse_put(
         words: &CmdTokens,
         verb: &str,
@@ -152,16 +936,19 @@ impl Parser {
                 "in" | "inside" => {
                     if let Some(obj) = words.obj() {
                         if let Some(obj_prep) = words.obj_prep() {
-                            if player.has(&obj_prep) {
-                                player.insert_into(&obj, &obj_prep)
+                            if player.has(obj_prep) {
+                                player.insert_into(obj, obj_prep)
                             } else {
-                                let (res, rejected_item) =
-                                    world.insert_into(&obj, &obj_prep, player.remove(&obj));
-                                if let Some(item) = rejected_item {
-                                    player.take_back(item);
-                                    res
-                                } else {
-                                    res
+                                match player.release(obj) {
+                                    Ok(item) => {
+                                        let (res, rejected_item) =
+                                            world.insert_into(obj, obj_prep, Some(item));
+                                        if let Some(item) = rejected_item {
+                                            player.take_back(item);
+                                        }
+                                        res
+                                    }
+                                    Err(res) => res,
                                 }
                             }
                         } else {
@@ -175,7 +962,7 @@ impl Parser {
                 }
                 "on" => {
                     if let Some(obj_prep) = words.obj_prep() {
-                        player.don_armor(&obj_prep)
+                        player.don_armor(obj_prep)
                     } else {
                         CmdResult::do_what(&format!("{} on", verb))
                     }
@@ -190,20 +977,43 @@ impl Parser {
         }
     }
 
+    // taking what belongs to someone in front of anyone is remembered
+    fn taken(res: CmdResult, world: &mut World) -> CmdResult {
+        if !res.is_active() {
+            return res;
+        }
+        match world.theft() {
+            Some(seen) => res.with_postscript(&seen),
+            None => res,
+        }
+    }
+
     fn parse_take(
         verb: &str,
         words: &CmdTokens,
         world: &mut World,
         player: &mut Player,
     ) -> CmdResult {
+        if let (None, Some("on" | "in"), Some(obj_prep)) =
+            (words.obj(), words.prep(), words.obj_prep())
+        {
+            return world.get_on(obj_prep);
+        }
         if let Some(obj) = words.obj() {
+            if matches!(obj, "off" | "out" | "down" | "up") || obj.starts_with("off ") {
+                return world.get_off();
+            }
+            if let Some(res) = world.out_of_reach(obj) {
+                return res;
+            }
             if let Some(prep) = words.prep() {
                 if prep == "from" || prep == "out" || prep == "in" {
                     if let Some(obj_prep) = words.obj_prep() {
                         if player.has(obj_prep) {
                             player.take_from_self(obj, obj_prep)
                         } else {
-                            player.take_item_from(world.give_from(obj, obj_prep))
+                            let res = player.take_item_from(world.give_from(obj, obj_prep));
+                            Parser::taken(res, world)
                         }
                     } else {
                         CmdResult::do_what(&format!("{} the {} from", verb, obj))
@@ -215,20 +1025,100 @@ impl Parser {
                     CmdResult::no_comprendo()
                 }
             } else if obj == "all" || obj.len() >= 4 && obj.starts_with("all ") {
-                player.take_all(world.give_all())
+                if let Some(res) = world.reveal_all() {
+                    res
+                } else {
+                    let res = player.take_all(world.give_all());
+                    Parser::taken(res, world)
+                }
+            } else if let Some(res) = world.reveal(obj) {
+                res
+            } else if let Some(res) = world.scenery(obj) {
+                res
             } else {
-                player.take(obj, world.give(obj))
+                let res = player.take(obj, world.give(obj));
+                Parser::taken(res, world)
             }
         } else {
             CmdResult::do_what_prep(verb, words.prep(), words.obj_prep())
         }
     }
 
+    fn parse_use(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        if let Some(obj) = words.obj() {
+            let obj = match player.slotted(obj) {
+                Ok(obj) => obj,
+                Err(res) => return res,
+            };
+            let (res, ally) = player.use_item(&obj);
+            if let Some(ally) = ally {
+                world.summon(ally);
+            }
+            res
+        } else {
+            CmdResult::do_what(verb)
+        }
+    }
+
+    // "sit on the chair", "sit in the cage", or "sit chair"
+    fn parse_sit(verb: &str, words: &CmdTokens, world: &mut World) -> CmdResult {
+        if let Some(obj) = words.obj_prep().or_else(|| words.obj()) {
+            world.get_on(obj)
+        } else {
+            CmdResult::do_what(&format!("{} on", verb))
+        }
+    }
+
+    fn parse_stand(words: &CmdTokens, world: &mut World) -> CmdResult {
+        if let (Some("on" | "in"), Some(obj_prep)) = (words.prep(), words.obj_prep()) {
+            world.get_on(obj_prep)
+        } else {
+            world.get_off()
+        }
+    }
+
+    // "look north" peers along an exit by lamplight, and "look lamp" examines the lamp
+    fn parse_look(
+        verb: &str,
+        words: &CmdTokens,
+        world: &mut World,
+        player: &mut Player,
+    ) -> CmdResult {
+        if let Some(res) = words.obj().and_then(|obj| world.look_through(obj)) {
+            return res;
+        }
+        if let Some(obj) = words.obj().and_then(|obj| obj.strip_prefix("under ")) {
+            return world.look_under(obj);
+        }
+        match words.obj() {
+            Some(obj) if world.get_curr_room().find_path(obj).is_some() => {
+                world.look_toward(obj, player.light_radius())
+            }
+            Some(_) => Parser::parse_x(verb, words, world, player),
+            None => world.look(),
+        }
+    }
+
     fn parse_x(verb: &str, words: &CmdTokens, world: &mut World, player: &mut Player) -> CmdResult {
         if let Some(obj) = words.obj() {
             if let Some(s) = player.inspect(obj) {
-                s
-            } else if let Some(s) = world.inspect(obj) {
+                // reading a scroll of music teaches the song written on it
+                match player.find_item(obj).and_then(|item| item.song()) {
+                    Some(song) if verb == "read" => {
+                        let song = song.to_owned();
+                        let learned = player.learn_song(&song);
+                        CmdResult::new(Action::Active, format!("{}\n{}", s.output(), learned))
+                    }
+                    _ => s,
+                }
+            } else if let Some(res) = world.out_of_reach(obj) {
+                res
+            } else if let Some(s) = world.inspect(obj, player.bestiary()) {
                 s
             } else {
                 CmdResult::no_item_here(obj)
@@ -239,41 +1129,168 @@ impl Parser {
     }
 
     pub fn parse(words: &CmdTokens, world: &mut World, player: &mut Player) -> CmdResult {
+        // what the world says for this verb done to this thing goes in place of the engine's,
+        // found before the command can carry the player elsewhere
+        let reply = match (words.short_verb(), words.obj()) {
+            ((_, Some(short_verb)), Some(obj)) => world.reply(short_verb, obj).cloned(),
+            _ => None,
+        };
+        match reply {
+            Some(reply) => match reply.refusal() {
+                Some(refusal) => CmdResult::new(Action::Failed, refusal),
+                None => reply.reword(Parser::act(words, world, player)),
+            },
+            None => Parser::act(words, world, player),
+        }
+    }
+
+    fn act(words: &CmdTokens, world: &mut World, player: &mut Player) -> CmdResult {
         if let (Some(verb), Some(short_verb)) = words.short_verb() {
+            if let Some(res) = Parser::parse_sequence(verb, words, world, player) {
+                return res;
+            }
+            if let Some(res) = Parser::parse_tool_use(verb, words, world, player) {
+                return res;
+            }
+            if Direction::parse(verb).is_bare() {
+                return Parser::go(verb, world, player);
+            }
             match short_verb {
-                "north" | "south" | "east" | "west" | "northeast" | "northwest" | "southeast"
-                | "southwest" | "up" | "down" => world.move_room(verb),
-                "enter" | "go" | "move" | "exit" => Parser::parse_move(verb, &words, world),
+                "enter" | "go" | "move" | "exit" | "leave" => {
+                    Parser::parse_move(verb, words, world, player)
+                }
                 "c" | "stat" | "stats" => player.info(),
+                "karma" if world.shows_karma() => player.show_karma(),
+                "date" | "calend" => world.date(),
+                "bestia" => CmdResult::new(
+                    Action::Passive,
+                    player.bestiary().list(&world.enemy_names()),
+                ),
+                "codex" => {
+                    CmdResult::new(Action::Passive, player.codex().list(&world.item_names()))
+                }
                 "i" | "invent" => player.print_inventory(),
-                "l" | "look" => world.look(),
-                "attack" | "cut" | "hit" | "kill" | "slay" => {
-                    Parser::parse_attack(verb, &words, world, player)
+                "l" | "look" | "peek" | "peer" => Parser::parse_look(verb, words, world, player),
+                "attack" | "cut" | "fight" | "hit" | "kill" | "slay" => {
+                    Parser::parse_attack(verb, words, world, player)
+                }
+                "grab" | "grappl" | "wrestl" | "tackle" => {
+                    Parser::parse_grapple(verb, words, world, player)
                 }
-                "heal" | "rest" | "sleep" => player.rest(),
-                "hail" | "talk" | "hi" | "hello" | "greet" => Parser::parse_hail(&words, world),
-                "cast" | "use" => {
-                    CmdResult::new(Action::Passive, String::from("TODO: cast something"))
+                "push" | "shove" | "pull" | "drag" | "roll" | "haul" | "hoist" => {
+                    Parser::parse_push(verb, words, world, player)
                 }
-                "close" => Parser::parse_close(verb, &words, world, player),
-                "don" | "wear" => Parser::parse_don(verb, &words, player),
-                "draw" | "equip" | "hold" => Parser::parse_equip(verb, &words, player),
-                "drop" | "remove" | "throw" => Parser::parse_drop(verb, &words, world, player),
+                "heal" | "rest" | "sleep" if world.get_curr_room().is_camp() => player.sleep(),
+                "heal" | "rest" | "sleep" => world.wander().unwrap_or_else(|| player.rest()),
+                "camp" | "pitch" => Parser::parse_camp(world, player),
+                "cook" | "roast" | "bake"
+                    if words.obj().and_then(|obj| world.recipe(obj)).is_some() =>
+                {
+                    Parser::parse_craft(verb, words, world, player)
+                }
+                "craft" | "make" | "brew" | "mix" | "prepar" => {
+                    Parser::parse_craft(verb, words, world, player)
+                }
+                "recipe" => world.recipes_here(),
+                "feed" | "tame" => Parser::parse_feed(verb, words, world, player),
+                "name" | "rename" => Parser::parse_name(verb, words, world),
+                "comman" | "order" | "tell" => Parser::parse_command(verb, words, world, player),
+                "cook" | "roast" | "bake" => match words.obj() {
+                    Some(_) if !world.get_curr_room().has_fire() => {
+                        CmdResult::new(Action::Passive, "You need a campfire to cook over.")
+                    }
+                    Some(obj) => player.cook(obj),
+                    None => CmdResult::do_what(verb),
+                },
+                "pray" | "kneel" => world.pray(),
+                "hail" | "hi" | "hello" | "greet" => Parser::parse_hail(words, world),
+                "talk" | "speak" | "conver" => Parser::parse_talk(verb, words, world, player),
+                "give" | "offer" | "hand" => Parser::parse_give(verb, words, world, player),
+                "turn" | "spin" | "rotate" | "dial" | "set" => {
+                    Parser::parse_dial(verb, words, world)
+                }
+                "reset" | "clear" => match words.obj() {
+                    Some(obj) => world.reset_dial(obj),
+                    None => CmdResult::do_what(verb),
+                },
+                "answer" | "solve" | "riddle" => match words.obj() {
+                    Some(obj) => world.pose_riddle(obj),
+                    None => CmdResult::do_what(verb),
+                },
+                "cast" | "use" | "summon" => Parser::parse_use(verb, words, world, player),
+                "knock" | "rap" | "bang" => match words.obj_prep().or_else(|| words.obj()) {
+                    Some(obj) => world.knock(obj),
+                    None => CmdResult::do_what(&format!("{} on", verb)),
+                },
+                "listen" => match words.obj_prep().or_else(|| words.obj()) {
+                    Some(obj) => world.listen(obj),
+                    None => CmdResult::new(Action::Passive, "You hear nothing unusual."),
+                },
+                "shout" | "yell" | "scream" | "call" => match words.obj() {
+                    Some(obj) => world.shout(obj),
+                    None => CmdResult::do_what(verb),
+                },
+                "light" | "ignite" | "kindle" => match words.obj() {
+                    Some(obj) => player.tend_light(obj, true),
+                    None => CmdResult::do_what(verb),
+                },
+                "douse" | "exting" | "snuff" => match words.obj() {
+                    Some(obj) => player.tend_light(obj, false),
+                    None => CmdResult::do_what(verb),
+                },
+                "smash" | "break" | "bash" | "blast" | "shatte" => {
+                    Parser::parse_smash(verb, words, world, player)
+                }
+                "contin" | "resume" => world.resume(),
+                "gather" | "forage" | "harves" | "collec" | "fish" | "mine" => {
+                    Parser::parse_gather(verb, words, world, player)
+                }
+                "close" => Parser::parse_close(verb, words, world, player),
+                "compar" => Parser::parse_compare(verb, words, world, player),
+                "dismis" => Parser::parse_dismiss(verb, words, world),
+                "don" | "wear" => Parser::parse_don(verb, words, player),
+                "doff" | "unmask" => player.doff_disguise(),
+                "play" | "strum" => Parser::parse_play(verb, words, world, player),
+                "learn" | "study" => Parser::parse_learn(verb, words, world, player),
+                "songs" => player.print_songs(),
+                "assign" | "bind" => Parser::parse_assign(verb, words, player),
+                "unassi" | "unbind" => match words.obj().map(str::parse) {
+                    Some(Ok(slot)) => player.clear_slot(slot),
+                    Some(Err(_)) => CmdResult::new(
+                        Action::Passive,
+                        format!("Which quick slot do you want to {}?", verb),
+                    ),
+                    None => CmdResult::do_what(verb),
+                },
+                "draw" | "equip" | "hold" => Parser::parse_equip(verb, words, player),
+                "drop" | "remove" | "throw" => Parser::parse_drop(verb, words, world, player),
                 "examin" | "inspec" | "read" | "search" | "x" => {
-                    Parser::parse_x(verb, &words, world, player)
+                    Parser::parse_x(verb, words, world, player)
+                }
+                "get" | "take" => Parser::parse_take(verb, words, world, player),
+                "climb"
+                    if words
+                        .obj()
+                        .and_then(|obj| world.get_curr_room().find_path(obj))
+                        .is_some() =>
+                {
+                    Parser::go(words.obj().unwrap_or_default(), world, player)
                 }
-                "get" | "take" => Parser::parse_take(verb, &words, world, player),
-                "increa" => Parser::parse_increase(&words, player),
+                "sit" | "lie" | "climb" => Parser::parse_sit(verb, words, world),
+                "tie" | "fasten" | "knot" => Parser::parse_tie(verb, words, world, player),
+                "untie" | "unfast" => Parser::parse_untie(verb, words, world, player),
+                "lower" => Parser::parse_lower(verb, words, world, player),
+                "stand" => Parser::parse_stand(words, world),
+                "increa" => Parser::parse_increase(words, player),
                 "lock" => CmdResult::new(Action::Passive, String::from("TODO: lock something")),
-                "open" => Parser::parse_open(verb, &words, world, player),
-                "insert" | "place" | "put" => Parser::parse_put(&words, verb, world, player),
-                "unlock" | "pick" => Parser::parse_unlock(verb, &words, world),
-                "wait" | "z" => Player::wait(),
+                "open" => Parser::parse_open(verb, words, world, player),
+                "insert" | "place" | "put" => Parser::parse_put(words, verb, world, player),
+                "unlock" | "pick" => Parser::parse_unlock(verb, words, world),
+                "wait" | "z" => world.wander().unwrap_or_else(Player::wait),
                 "help" => Cli::help(),
-                _ => CmdResult::new(
-                    Action::Failed,
-                    format!("I do not know the word \"{}\".", verb),
-                ),
+                _ => world
+                    .respond(short_verb)
+                    .unwrap_or_else(|| CmdResult::unknown_word(verb)),
             }
         } else {
             CmdResult::no_comprendo()
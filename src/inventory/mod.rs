@@ -1,16 +1,19 @@
-use rayon::prelude::*;
-
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::{
     entity::{
+        item::Revival,
         Closeable, Entity,
-        Item::{self, Container, Gold},
+        Item::{self, Container, Gold, Weapon},
     },
-    types::{Action, CmdResult, Items},
+    input::named,
+    types::{Action, Category, CmdResult, InventoryFormat, Items, Stir},
+    world::namespace,
 };
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Inventory {
     items: Items,
     gold: u32,
@@ -36,26 +39,15 @@ impl Inventory {
         }
     }
 
+    // a name that starts with a content pack's name, like a recipe's ingredient, only finds the
+    // Item defined under it
     pub fn item_pos(&self, item_name: &str) -> Option<usize> {
-        if cfg!(target_arch = "wasm32") {
-            self.items
-                .iter()
-                .map(|item| item.name().split_whitespace().collect())
-                .position(|item: Vec<&str>| {
-                    item_name
-                        .split_whitespace()
-                        .all(|ref word| item.contains(word))
-                })
-        } else {
-            self.items
-                .par_iter()
-                .map(|item| item.name().par_split_whitespace().collect())
-                .position_any(|item: Vec<&str>| {
-                    item_name
-                        .par_split_whitespace()
-                        .all(|ref word| item.contains(word))
-                })
+        if namespace::pack_of(item_name).is_some() {
+            return self.items.position_of(item_name);
         }
+        self.items
+            .iter()
+            .position(|item| named(item.name(), item_name))
     }
 
     #[allow(clippy::borrowed_box)]
@@ -75,6 +67,36 @@ impl Inventory {
         }
     }
 
+    // use up the first revival Item being carried
+    pub fn take_revival(&mut self) -> Option<Revival> {
+        let pos = self
+            .items
+            .iter()
+            .position(|item| matches!(**item, Item::Revival(_)))?;
+        if let Item::Revival(revival) = *self.items.remove(pos) {
+            Some(revival)
+        } else {
+            None
+        }
+    }
+
+    // let turns pass for the daemons of carried Things, dropping any that go off
+    pub fn stir(&mut self, turns: u32) -> Vec<(String, Stir)> {
+        let mut stirs = Vec::new();
+        self.items.retain_mut(|item| {
+            let name = item.name().to_owned();
+            match item.daemon_mut().and_then(|daemon| daemon.stir(turns)) {
+                Some(stir) => {
+                    let gone = matches!(stir, Stir::GoOff(..));
+                    stirs.push((name, stir));
+                    !gone
+                }
+                None => true,
+            }
+        });
+        stirs
+    }
+
     pub fn has(&self, name: &str) -> bool {
         self.find_item(name).is_some()
     }
@@ -117,21 +139,90 @@ impl Inventory {
         }
     }
 
-    pub fn print(&self) -> String {
+    pub fn print(&self, format: InventoryFormat) -> String {
         if self.items.is_empty() {
             String::from("Your inventory is empty.")
         } else {
-            self.items.iter().fold(
+            self.sorted(format).iter().fold(
                 format!("Gold: {}\nYou are carrying:", self.gold),
                 |res, item| format!("{}\n  {}", res, item.long_name()),
             )
         }
     }
 
+    // list carried Items under their categories, with equipped Items marked alongside them
+    pub fn print_categorized(&self, format: InventoryFormat, equipped: &[(&Item, &str)]) -> String {
+        if self.items.is_empty() && equipped.is_empty() {
+            return String::from("Your inventory is empty.");
+        }
+        let mut lines: Vec<(Category, String)> = equipped
+            .iter()
+            .map(|(item, marker)| {
+                (
+                    item.category(),
+                    format!("{} ({})", item.long_name(), marker),
+                )
+            })
+            .collect();
+        lines.extend(
+            self.sorted(format)
+                .iter()
+                .map(|item| (item.category(), item.long_name())),
+        );
+
+        let mut res = format!("Gold: {}", self.gold);
+        for category in Category::ALL.iter() {
+            let mut names = lines
+                .iter()
+                .filter(|(c, _)| c == category)
+                .map(|(_, name)| name.as_str())
+                .peekable();
+            if names.peek().is_some() {
+                res.push_str(&format!("\n{}:", category.name()));
+                for name in names {
+                    res.push_str(&format!("\n  {}", name));
+                }
+            }
+        }
+        res
+    }
+
+    // Items in the order they were picked up, or by name
+    fn sorted(&self, format: InventoryFormat) -> Vec<&Item> {
+        let mut items: Vec<&Item> = self.items.iter().map(|item| &**item).collect();
+        if format.is_alphabetical() {
+            items.sort_by(|a, b| a.name().cmp(b.name()));
+        }
+        items
+    }
+
+    pub const fn items(&self) -> &Items {
+        &self.items
+    }
+
     pub fn push(&mut self, item: Box<Item>) {
         self.items.push(item);
     }
 
+    // remove the highest-damage usable weapon if it hits harder than the given die
+    pub fn take_best_weapon<F>(&mut self, better_than: u32, usable: F) -> Option<Box<Item>>
+    where
+        F: Fn(&Item) -> bool,
+    {
+        let best = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| usable(item))
+            .filter_map(|(pos, item)| match **item {
+                Weapon(ref weapon) => Some((pos, weapon.max_damage())),
+                _ => None,
+            })
+            .filter(|(_, damage)| *damage > better_than)
+            .max_by_key(|(pos, damage)| (*damage, std::cmp::Reverse(*pos)));
+        best.map(|(pos, _)| self.items.remove(pos))
+    }
+
     pub fn remove_item(&mut self, item_name: &str) -> Option<Box<Item>> {
         if let Some(item) = self.item_pos(item_name) {
             Some(self.items.remove(item))
@@ -158,11 +249,7 @@ impl Inventory {
             CmdResult::new(Action::Passive, "There is nothing to take.")
         } else {
             let times = items.len();
-            if cfg!(target_arch = "wasm32") {
-                self.items.extend(items);
-            } else {
-                self.items.par_extend(items);
-            }
+            self.items.extend(items);
 
             let mut gold = 0;
             self.items.retain(|x| {
@@ -181,6 +268,7 @@ impl Inventory {
         }
     }
 
+    #[allow(clippy::result_large_err)]
     fn take_out_of(
         &mut self,
         item_name: &str,
@@ -1,12 +1,47 @@
 //! Kingslayer is a text-based dungeon crawler adventure game and game engine
 
-pub use cli::Cli;
+pub use campaign::{Campaign, Chapter, Record};
+#[cfg(feature = "serde")]
+pub use cli::SaveInfo;
+#[cfg(feature = "sqlite")]
+pub use cli::SqliteGame;
+pub use cli::{
+    Audio, Behavior, Cli, FileStore, Haptics, Metrics, NoDelay, RealTime, SaveStore, Speech,
+    Timing, Transcript,
+};
 pub use entity::{
-    item::{Armor, Container, Gold, Thing, Weapon},
-    Element, Enemy, Item,
+    item::{Armor, Container, Gold, Key, Light, Mimic, Revival, Rope, Summon, Thing, Weapon},
+    Ally, DoorLock, Durability, Element, Enemy, Item, Opening, Pathway, Pet,
 };
 pub use input::{CmdTokens, Lexer};
-pub use types::EnemyStatus;
+#[cfg(feature = "pack")]
+pub use pack::Archive;
+pub use session::{AsyncGameSession, Chat, Filter, Recv, SessionManager, WordList};
+pub use types::{
+    Ability, Answer, Approach, BodyPart, Breach, Calendar, Category, Check, Class, Condition,
+    Consequence, Cue, Daemon, DamageType, Deck, Deed, Dial, Direction, Director, Encounter,
+    EnemyStatus, Finding, Furniture, Gear, Guard, Happening, Harvest, Hazard, HpDisplay,
+    InventoryFormat, Karma, Listener, LoadError, LootTable, Memory, Metadata, Order, PartEffect,
+    Pause, Plate, Prompt, Pronouns, Quest, QuestGraph, Race, Recipe, Region, Reply, Requirements,
+    Responses, Riddle, Role, Rumble, SavingThrow, Sequence, Severity, StatusLine, Step, StoryEvent,
+    ToolUse, Tune, WorldFormat, WorldStats,
+};
+
+// log at debug level when the log feature is on; otherwise the arguments are still checked but
+// nothing is kept
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::debug!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+/// Worlds played one after another by the same character
+mod campaign;
 
 /// A command line interface for controlling interactions between objects in a game
 mod cli;
@@ -20,19 +55,185 @@ mod input;
 /// A Player's inventory
 mod inventory;
 
+/// World files packed into one scrambled archive for shipping
+#[cfg(feature = "pack")]
+mod pack;
+
 /// An abstraction of the player's interactions with the World
 mod player;
 
+/// Articles, plurals, capitals, and lists for writing names into sentences
+mod prose;
+
+/// Adapters for hosting games from async servers
+mod session;
+
+/// Golden transcript tests that play scripts against worlds and compare the output
+#[cfg(feature = "serde")]
+pub mod testing;
+
 /// Useful types used throughout the crate
 mod types;
 
 /// Manages the map of Rooms
 mod world;
 
-use rand::Rng;
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
 
-fn dice_roll(num_rolls: u32, num_sides: u32) -> u32 {
-    (0..num_rolls).fold(0, |sum, _| {
-        sum + rand::thread_rng().gen_range(1..=num_sides)
+/// A source of random numbers for dice rolls, which hosts can replace to control randomness
+pub trait RandomSource: Send {
+    fn next_u64(&mut self) -> u64;
+}
+
+/// A small deterministic generator that needs no external crates or operating system entropy
+#[derive(Clone, Debug)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl RandomSource for rand::rngs::StdRng {
+    fn next_u64(&mut self) -> u64 {
+        rand::RngCore::next_u64(self)
+    }
+}
+
+#[cfg(feature = "rand")]
+fn default_source() -> Box<dyn RandomSource> {
+    use rand::SeedableRng;
+    Box::new(rand::rngs::StdRng::from_entropy())
+}
+
+// without rand, fall back to the clock for a seed where one is available
+#[cfg(not(feature = "rand"))]
+fn default_source() -> Box<dyn RandomSource> {
+    let seed = if cfg!(target_arch = "wasm32") {
+        0
+    } else {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or(0)
+    };
+    Box::new(SplitMix64::new(seed))
+}
+
+// a game's dice, shared by its clones; rolls made while the game handles a call come from here,
+// so games never draw from each other's sequence however threads are shared between them
+#[derive(Clone)]
+pub(crate) struct Dice(Arc<Mutex<Box<dyn RandomSource>>>);
+
+impl Dice {
+    fn set(&self, source: Box<dyn RandomSource>) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = source;
+    }
+
+    // reseed so that rolls can be replayed the same way whatever features are enabled
+    pub(crate) fn seed(&self, seed: u64) {
+        self.set(Box::new(SplitMix64::new(seed)));
+    }
+
+    pub(crate) fn set_source<R: RandomSource + 'static>(&self, source: R) {
+        self.set(Box::new(source));
+    }
+
+    // make every roll during the call come from these dice
+    pub(crate) fn rolling<T>(&self, call: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Dice>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                ROLLING.with(|rolling| *rolling.borrow_mut() = previous);
+            }
+        }
+
+        let _restore = Restore(ROLLING.with(|rolling| rolling.replace(Some(self.clone()))));
+        call()
+    }
+}
+
+impl Default for Dice {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(default_source())))
+    }
+}
+
+impl fmt::Debug for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Dice")
+    }
+}
+
+thread_local! {
+    // the dice of the game handling a call on this thread, if any
+    static ROLLING: RefCell<Option<Dice>> = const { RefCell::new(None) };
+    // rolls made outside of any game, as by a loot table on its own
+    static LOOSE: RefCell<Box<dyn RandomSource>> = RefCell::new(default_source());
+}
+
+fn next_u64() -> u64 {
+    ROLLING.with(|rolling| match &*rolling.borrow() {
+        Some(dice) => dice
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .next_u64(),
+        None => LOOSE.with(|rng| rng.borrow_mut().next_u64()),
     })
 }
+
+fn dice_roll(num_rolls: u32, num_sides: u32) -> u32 {
+    if num_sides == 0 {
+        return 0;
+    }
+    let roll = (0..num_rolls).fold(0, |sum, _| {
+        sum + 1 + (next_u64() % u64::from(num_sides)) as u32
+    });
+    debug!("rolled {}d{}: {}", num_rolls, num_sides, roll);
+    roll
+}
+
+// an opposed d20 check that the first side wins only by beating the second
+fn opposed_roll(attacker_mod: i32, defender_mod: i32) -> bool {
+    dice_roll(1, 20) as i32 + attacker_mod > dice_roll(1, 20) as i32 + defender_mod
+}
+
+// pick an index from a list of weights, or None if every weight is zero
+fn weighted_pick<I>(weights: I) -> Option<usize>
+where
+    I: IntoIterator<Item = u32>,
+{
+    let weights: Vec<u32> = weights.into_iter().collect();
+    let total: u32 = weights.iter().sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut roll = dice_roll(1, total);
+    for (i, weight) in weights.iter().enumerate() {
+        if roll <= *weight {
+            return Some(i);
+        }
+        roll -= weight;
+    }
+    None
+}
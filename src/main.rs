@@ -1,16 +1,127 @@
-use std::env;
+use std::{env, fs, process};
 
 use kingslayer::{Armor, Cli, Element, Enemy, EnemyStatus, Gold, Item, Thing, Weapon};
 
+// `kingslayer lint <world-file>` prints one tab-separated finding per line
+// and fails if any of them are errors
+fn lint(path: &str) {
+    let findings = load(path).lint();
+    for finding in &findings {
+        println!("{}", finding);
+    }
+    if findings.iter().any(|finding| finding.is_error()) {
+        process::exit(1);
+    }
+}
+
+// `kingslayer quests <world-file>` prints how the quests hang together as a Graphviz graph,
+// and `kingslayer quests-json <world-file>` the same as JSON
+fn quests(path: &str) {
+    println!("{}", load(path).quest_graph().to_dot());
+}
+
+fn quests_json(path: &str) {
+    println!("{}", load(path).quest_graph().to_json());
+}
+
+// `kingslayer import <map.trizbort>` prints a world file to fill in
+fn import(path: &str) {
+    let imported = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|xml| Cli::from_trizbort(&xml).map_err(|e| e.to_string()));
+    match imported {
+        Ok(cli) => println!("{}", cli.to_ron()),
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+// the key packed worlds are scrambled with, which a shipped game would build in instead
+#[cfg(feature = "pack")]
+fn pack_key() -> String {
+    env::var("KINGSLAYER_PACK_KEY").unwrap_or_else(|_| {
+        eprintln!("set KINGSLAYER_PACK_KEY to the key for packed worlds");
+        process::exit(2);
+    })
+}
+
+// `kingslayer pack <world-file>` writes the world next to it as a scrambled .pack archive
+#[cfg(feature = "pack")]
+fn pack(path: &str) {
+    let packed = fs::read_to_string(path).map(|world| {
+        let mut archive = kingslayer::Archive::new();
+        archive.add(path, &world);
+        archive.pack(pack_key().as_bytes())
+    });
+    let out = std::path::Path::new(path).with_extension("pack");
+    if let Err(e) = packed.and_then(|packed| fs::write(&out, packed)) {
+        eprintln!("{}: {}", path, e);
+        process::exit(1);
+    }
+    println!("{}", out.display());
+}
+
+// without pack support `kingslayer pack` says so, rather than loading a world file named "pack"
+#[cfg(not(feature = "pack"))]
+fn pack(_: &str) {
+    eprintln!("kingslayer was built without pack support");
+    process::exit(2);
+}
+
+// a packed world is read with the key, and any other file as RON
+fn load(path: &str) -> Cli {
+    #[cfg(feature = "pack")]
+    if path.ends_with(".pack") {
+        let loaded = fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|packed| {
+                Cli::from_packed(&packed, pack_key().as_bytes()).map_err(|e| e.to_string())
+            });
+        return loaded.unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        });
+    }
+    Cli::from_file(path).unwrap_or_else(|e| {
+        eprintln!("{}: {}", path, e);
+        process::exit(1);
+    })
+}
+
 fn main() {
+    let subcommand: Option<fn(&str)> = match env::args().nth(1).as_deref() {
+        Some("lint") => Some(lint),
+        Some("import") => Some(import),
+        Some("quests") => Some(quests),
+        Some("quests-json") => Some(quests_json),
+        Some("pack") => Some(pack),
+        _ => None,
+    };
+    if let Some(subcommand) = subcommand {
+        match env::args().nth(2) {
+            Some(path) => subcommand(&path),
+            None => {
+                eprintln!(
+                    "usage: kingslayer lint <world-file> | kingslayer quests[-json] <world-file> \
+                     | kingslayer import <map.trizbort> | kingslayer pack <world-file>"
+                );
+                process::exit(2);
+            }
+        }
+        return;
+    }
+
     let cli = if let Some(path) = env::args().nth(1) {
-        Cli::from_file(&path)
+        load(&path)
     } else {
         let cli = Cli::from_ron_str(
             r#"
 (world:(curr_room:"Brig",rooms:{"Brig":(name:"Brig",desc:"You are in a small wooden room with a wood pillar in the middle. The ground slowly creaks and rocks beneath you.",paths:[(directions:["door","north"],target:"Hold 1",desc:"There is a door on the north side.",inspect:"The door is plain and wooden.",opening:Some(Closed),lock:Some(Locked("")))],enemies:[],allies:[],elements:[],items:[]),"Crows Nest":(name:"Crows Nest",desc:"You are in a crows nest overlooking the entire ship and sea.",paths:[(directions:["mast","platform","down"],target:"Platform",desc:"There is a platform below you on the central mast.",inspect:"The platform can be reached through holds on the mast.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Platform":(name:"Platform",desc:"You stand on a platform several feet above the main deck.",paths:[(directions:["down"],target:"Main Deck",desc:"The main deck is below you.",inspect:"The main deck is several feet below.",opening:None,lock:None),(directions:["crows nest","up"],target:"Crows Nest",desc:"There is a crows nest above you on the central mast.",inspect:"The crows nest can be reached through holds and rigging on the mast.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Cannon Deck 2":(name:"Cannon Deck",desc:"This deck has cannons lining each side.",paths:[(directions:["north"],target:"Cannon Deck 1",desc:"The deck continues to the north.",inspect:"This ship sure has a lot of cannons.",opening:None,lock:None),(directions:["doorway","south"],target:"Empty Room",desc:"There is a doorway to the south.",inspect:"The doorway is completely dark.",opening:None,lock:None),(directions:["opening","down"],target:"Crew Deck 2",desc:"There is an opening below you.",inspect:"You can see hammocks through the opening.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Captains Cabin":(name:"Captains Cabin",desc:"You stand in a large cabin with the captains belongings littering the ground and hanging on the walls.",paths:[(directions:["door","north"],target:"Main Deck",desc:"There is door at the north end of the room.",inspect:"The door is large with a small dim window in the center.",opening:Some(Open),lock:None)],enemies:[],allies:[],elements:[],items:[]),"Hold 1":(name:"Hold",desc:"You are in the middle of a spacious hold. Crates and barrels array the sides.",paths:[(directions:["door","south"],target:"Brig",desc:"There is a door at the south end of the hold.",inspect:"The door is plain and wooden.",opening:Some(Open),lock:None),(directions:["north"],target:"Hold 2",desc:"The hold continues to the north.",inspect:"This end of the hold is too dark to see into.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Crew Deck 1":(name:"Crew Deck",desc:"You are on a deck with dirty hammocks hanging everywhere.",paths:[(directions:["hatch","down"],target:"Hold 2",desc:"There is a hatch on the ground",inspect:"It looks like a metal grate.",opening:Some(Open),lock:None),(directions:["south"],target:"Crew Deck 2",desc:"The deck continues to the south.",inspect:"There is a doorway at the south end of the deck.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Crew Deck 2":(name:"Crew Deck",desc:"You are on a deck with dirty hammocks hanging everywhere.",paths:[(directions:["opening","up"],target:"Cannon Deck 2",desc:"There is an opening above you.",inspect:"You think you might be able to see the light of day.",opening:None,lock:None),(directions:["north"],target:"Crew Deck 1",desc:"The deck continues to the north.",inspect:"There is a hatch at the north end of the deck.",opening:None,lock:None),(directions:["empty doorway","south"],target:"Infirmary",desc:"There is an empty doorway to the south.",inspect:"The doorway flickers with lantern light.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Cannon Deck 1":(name:"Cannon Deck",desc:"This deck has cannons lining each side.",paths:[(directions:["south"],target:"Cannon Deck 2",desc:"The deck continues to the south.",inspect:"This ship sure has a lot of cannons.",opening:None,lock:None),(directions:["hatch","up"],target:"Main Deck",desc:"A hatch above you bring in bright sunlight.",inspect:"The hatch is a double-hinged grate made of old rusty metal.",opening:Some(Closed),lock:None)],enemies:[],allies:[],elements:[],items:[]),"Infirmary":(name:"Infirmary",desc:"You are in a room with a few empty beds against one wall.",paths:[(directions:["doorway","north"],target:"Crew Deck 2",desc:"The doorway leads back into the crew\'s quarters.",inspect:"The doorway flickers with lantern light.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Hold 2":(name:"Hold",desc:"You are at the end of a spacious hold. Crates and barrels array the sides.",paths:[(directions:["hatch","up"],target:"Crew Deck 1",desc:"There is a hatch above you.",inspect:"It looks like a metal grate.",opening:Some(Closed),lock:None),(directions:["south"],target:"Hold 1",desc:"The hold continues to the south.",inspect:"The middle of the hold is lit by a lantern.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Main Deck":(name:"Main Deck",desc:"The vast open sea surrounds the ship you stand on.",paths:[(directions:["hatch","down"],target:"Cannon Deck 1",desc:"There is a hatch below you.",inspect:"The hatch is a double-hinged grate made of old rusty metal.",opening:Some(Open),lock:None),(directions:["mast","platform","up"],target:"Platform",desc:"There is a platform above you on the central mast.",inspect:"The platform can be reached through holds on the mast.",opening:None,lock:None),(directions:["stairs","south"],target:"Sterncastle",desc:"Stairs towards the south lead upwards onto the sterncastle.",inspect:"The stairs are old and dirty.",opening:None,lock:None),(directions:["door"],target:"Captains Cabin",desc:"There is door on the wall beneath the sterncastle of the ship.",inspect:"The door is large with a small dim window in the center.",opening:Some(Closed),lock:None)],enemies:[],allies:[],elements:[],items:[]),"Sterncastle":(name:"Sterncastle",desc:"You are on the sterncastle of the ship. There is another mast in the center and the ships wheel.",paths:[(directions:["stairs","north","down"],target:"Main Deck",desc:"The stairs lead back onto the main deck.",inspect:"The stairs are old and dirty.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[]),"Empty Room":(name:"Empty Room",desc:"The room is dark and empty.",paths:[(directions:["doorway","north"],target:"Cannon Deck 2",desc:"The doorway leads back into the cannon deck.",inspect:"You see many cannons.",opening:None,lock:None)],enemies:[],allies:[],elements:[],items:[])}))
             "#,
-        );
+        )
+        .expect("the built-in world is valid");
 
         cli.add_item(
             "Brig",
@@ -112,5 +223,6 @@ fn main() {
         cli
     };
 
+    cli.set_page_height(24);
     cli.start();
 }
@@ -0,0 +1,121 @@
+use std::io::{Read, Write};
+
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+
+use crate::{RandomSource, SplitMix64};
+
+// marks a packed archive, and again inside it to tell a wrong key from a damaged file
+const MAGIC: &[u8; 4] = b"KSPK";
+const VERSION: u8 = 1;
+
+/// World files packed into one archive, compressed and scrambled with a key so that players
+/// cannot read the puzzle solutions by opening the file; this keeps honest players honest but
+/// is not encryption, since the key has to ship inside the game to load it
+///
+/// The first file added is the world that `Cli::from_packed` loads.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Archive {
+    files: Vec<(String, String)>,
+}
+
+impl Archive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file under a name, replacing any file of the same name
+    pub fn add(&mut self, name: &str, contents: &str) {
+        match self.files.iter_mut().find(|(file, _)| file == name) {
+            Some(file) => file.1 = contents.to_owned(),
+            None => self.files.push((name.to_owned(), contents.to_owned())),
+        }
+    }
+
+    /// The contents of the file added under a name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|(file, _)| file == name)
+            .map(|(_, contents)| contents.as_str())
+    }
+
+    /// The name and contents of each file in the order they were added
+    pub fn files(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.files
+            .iter()
+            .map(|(name, contents)| (name.as_str(), contents.as_str()))
+    }
+
+    /// The archive as bytes to ship, scrambled with the key
+    pub fn pack(&self, key: &[u8]) -> Vec<u8> {
+        let mut plain = MAGIC.to_vec();
+        for (name, contents) in &self.files {
+            for field in [name, contents] {
+                plain.extend_from_slice(&(field.len() as u32).to_le_bytes());
+                plain.extend_from_slice(field.as_bytes());
+            }
+        }
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        let compressed = encoder
+            .write_all(&plain)
+            .and_then(|_| encoder.finish())
+            .expect("Compressing into memory does not fail");
+
+        let mut packed = MAGIC.to_vec();
+        packed.push(VERSION);
+        packed.extend(scramble(compressed, key));
+        packed
+    }
+
+    /// Read an archive packed with the same key
+    pub fn unpack(packed: &[u8], key: &[u8]) -> Result<Self, String> {
+        let body = match packed.strip_prefix(MAGIC.as_slice()) {
+            Some([VERSION, body @ ..]) => body,
+            Some(_) => return Err(String::from("The archive is from another version.")),
+            None => return Err(String::from("This is not a packed world.")),
+        };
+
+        let mut plain = Vec::new();
+        DeflateDecoder::new(scramble(body.to_vec(), key).as_slice())
+            .read_to_end(&mut plain)
+            .map_err(|_| String::from("The archive cannot be read with this key."))?;
+        let mut rest = plain
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| String::from("The archive cannot be read with this key."))?;
+
+        let mut archive = Self::new();
+        while !rest.is_empty() {
+            let name = field(&mut rest)?;
+            let contents = field(&mut rest)?;
+            archive.add(&name, &contents);
+        }
+        Ok(archive)
+    }
+}
+
+// one length-prefixed string from the front of the archive
+fn field(rest: &mut &[u8]) -> Result<String, String> {
+    let damaged = || String::from("The archive is damaged.");
+    let (len, tail) = rest.split_first_chunk::<4>().ok_or_else(damaged)?;
+    let len = u32::from_le_bytes(*len) as usize;
+    let bytes = tail.get(..len).ok_or_else(damaged)?;
+    *rest = &tail[len..];
+    String::from_utf8(bytes.to_vec()).map_err(|_| damaged())
+}
+
+// xor the bytes with a stream drawn from the key, which undoes itself when done twice
+fn scramble(mut bytes: Vec<u8>, key: &[u8]) -> Vec<u8> {
+    // FNV-1a, so every byte of the key changes the whole stream
+    let seed = key.iter().fold(0xCBF2_9CE4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01B3)
+    });
+    let mut stream = SplitMix64::new(seed);
+    for chunk in bytes.chunks_mut(8) {
+        let mask = stream.next_u64().to_le_bytes();
+        for (byte, mask) in chunk.iter_mut().zip(mask) {
+            *byte ^= mask;
+        }
+    }
+    bytes
+}
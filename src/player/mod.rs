@@ -1,18 +1,31 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use rayon::prelude::*;
-
 use crate::{
     dice_roll,
     entity::{
-        Entity,
-        Item::{self, Armor, Weapon},
+        Ally, Entity,
+        Item::{self, Armor, Light, Summon, Weapon},
+        Room,
     },
+    input::{named, same_name},
     inventory::Inventory,
-    types::{Action, Attack, Class, CmdResult, CombatStatus, Items, Race, Stats},
+    prose,
+    types::{
+        Action, Attack, Bestiary, Check, Class, CmdResult, Codex, CombatStatus, Gear, Guard,
+        Hazard, HpDisplay, InventoryFormat, Items, Karma, Pause, Pronouns, Race, Recipe,
+        SavingThrow, Stats, Stir,
+    },
+    world::namespace,
 };
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+const QUICK_SLOTS: u32 = 9;
+
+// points for each quest completed, on top of the experience earned along the way
+const QUEST_POINTS: u32 = 25;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Player {
     lvl: u32,
     race: Race,
@@ -24,6 +37,49 @@ pub struct Player {
     main_hand: Option<Box<Item>>,
     armor: Option<Box<Item>>,
     inventory: Inventory,
+    #[cfg_attr(feature = "serde", serde(default))]
+    death: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hp_display: HpDisplay,
+    #[cfg_attr(feature = "serde", serde(default))]
+    inventory_format: InventoryFormat,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quests_done: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    auto_equip: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    bestiary: Bestiary,
+    #[cfg_attr(feature = "serde", serde(default))]
+    codex: Codex,
+    // experience earned and quests completed, kept as a running total
+    #[cfg_attr(feature = "serde", serde(default))]
+    score: u32,
+    #[cfg_attr(feature = "serde", serde(default = "Pronouns::they"))]
+    pronouns: Pronouns,
+    // the factions the player truly belongs to
+    #[cfg_attr(feature = "serde", serde(default))]
+    factions: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    disguise: Option<Box<Item>>,
+    // factions whose guards have seen through a disguise and know the player's face
+    #[cfg_attr(feature = "serde", serde(default))]
+    exposed: Vec<String>,
+    // songs learned from scrolls and teachers, played on an instrument
+    #[cfg_attr(feature = "serde", serde(default))]
+    songs: Vec<String>,
+    // favorite Items bound to numbered slots, remembered by name so a used-up potion's slot
+    // finds the next one carried
+    #[cfg_attr(feature = "serde", serde(default))]
+    quick_slots: Vec<(u32, String)>,
+    // the Enemy last attacked in the current fight, which a bare "attack" goes after
+    #[cfg_attr(feature = "serde", serde(default))]
+    target: Option<String>,
+    // how good or wicked the player's deeds have been, apart from any faction's opinion
+    #[cfg_attr(feature = "serde", serde(default))]
+    karma: i32,
+    // a debug cheat that keeps the player from losing any HP
+    #[cfg_attr(feature = "serde", serde(skip))]
+    god: bool,
 }
 
 impl Default for Player {
@@ -39,11 +95,36 @@ impl Default for Player {
             main_hand: None,
             armor: None,
             inventory: Inventory::new(),
+            death: None,
+            hp_display: HpDisplay::default(),
+            inventory_format: InventoryFormat::default(),
+            quests_done: Vec::new(),
+            auto_equip: false,
+            bestiary: Bestiary::default(),
+            codex: Codex::default(),
+            score: 0,
+            pronouns: Pronouns::they(),
+            factions: Vec::new(),
+            disguise: None,
+            exposed: Vec::new(),
+            songs: Vec::new(),
+            quick_slots: Vec::new(),
+            target: None,
+            karma: 0,
+            god: false,
         }
     }
 }
 
 impl Player {
+    pub fn set_pronouns(&mut self, pronouns: Pronouns) {
+        self.pronouns = pronouns;
+    }
+
+    pub const fn pronouns(&self) -> &Pronouns {
+        &self.pronouns
+    }
+
     pub fn set_race(&mut self, race: Race) {
         self.race = race;
     }
@@ -64,6 +145,9 @@ impl Player {
         if let Some(weapon) = &self.main_hand {
             if let Weapon(ref weapon) = **weapon {
                 Attack::new(weapon.name(), Some(self.deal_damage(weapon.damage())))
+                    .with_area(weapon.is_area())
+                    .with_damage_type(weapon.damage_type())
+                    .with_sound(weapon.sound())
             } else {
                 Attack::new(weapon.name(), Some(self.default_damage()))
             }
@@ -76,6 +160,9 @@ impl Player {
         if let Some(weapon) = self.inventory.find_item(weapon_name) {
             if let Weapon(ref weapon) = **weapon {
                 Attack::new(weapon_name, Some(self.deal_damage(weapon.damage())))
+                    .with_area(weapon.is_area())
+                    .with_damage_type(weapon.damage_type())
+                    .with_sound(weapon.sound())
             } else {
                 Attack::new(weapon_name, Some(self.default_damage()))
             }
@@ -83,6 +170,9 @@ impl Player {
             if let Some(weapon) = &self.main_hand {
                 if let Weapon(ref weapon) = **weapon {
                     Attack::new(weapon_name, Some(self.deal_damage(weapon.damage())))
+                        .with_area(weapon.is_area())
+                        .with_damage_type(weapon.damage_type())
+                        .with_sound(weapon.sound())
                 } else {
                     Attack::new(weapon_name, Some(self.default_damage()))
                 }
@@ -99,14 +189,26 @@ impl Player {
     }
 
     pub fn disengage_combat(&mut self) {
+        if let CombatStatus::InCombat = self.in_combat {
+            debug!("combat ended");
+        }
         self.in_combat = CombatStatus::Resting;
+        self.target = None;
+    }
+
+    pub fn set_target(&mut self, enemy_name: &str) {
+        self.target = Some(enemy_name.to_owned());
+    }
+
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
     }
 
     fn set_armor(&mut self, armor_name: &str, item: Box<Item>) -> CmdResult {
         if let Armor(_) = *item {
             // move old armor back to inventory
             if let Some(armor) = self.armor.take() {
-                self.take(armor_name, Some(armor));
+                self.inventory.take(armor_name, Some(armor));
             }
             self.armor = Some(item);
             CmdResult::new(Action::Active, "Donned.")
@@ -122,28 +224,124 @@ impl Player {
         }
     }
 
+    // whether the player meets an Item's class and ability requirements
+    #[allow(clippy::result_large_err)]
+    pub fn can_use(&self, item: &Item) -> Result<(), CmdResult> {
+        meets_requirements(item, &self.class, &self.stats)
+    }
+
     pub fn don_armor(&mut self, armor_name: &str) -> CmdResult {
+        if let Some(item) = self.inventory.find_item(armor_name) {
+            if item.disguise().is_some() {
+                return self.wear_disguise(armor_name);
+            }
+            if let Err(res) = self.can_use(item) {
+                return res;
+            }
+        }
         if let Some(item) = self.inventory.remove_item(armor_name) {
-            self.set_armor(&armor_name, item)
+            self.set_armor(armor_name, item)
         } else {
             CmdResult::dont_have(armor_name)
         }
     }
 
+    pub fn join_faction(&mut self, faction: &str) {
+        if !self.factions.iter().any(|joined| joined == faction) {
+            self.factions.push(faction.to_owned());
+        }
+    }
+
+    // put on a disguise, taking off any other one
+    fn wear_disguise(&mut self, name: &str) -> CmdResult {
+        let item = match self.inventory.remove_item(name) {
+            Some(item) => item,
+            None => return CmdResult::dont_have(name),
+        };
+        let res = format!(
+            "You put on the {} and pass for one of the {}.",
+            item.name(),
+            item.disguise().unwrap_or_default()
+        );
+        if let Some(old) = self.disguise.replace(item) {
+            self.inventory.push(old);
+        }
+        CmdResult::new(Action::Active, res)
+    }
+
+    pub fn doff_disguise(&mut self) -> CmdResult {
+        match self.disguise.take() {
+            Some(item) => {
+                let res = format!("You take off the {}.", item.name());
+                self.inventory.push(item);
+                CmdResult::new(Action::Active, res)
+            }
+            None => CmdResult::new(Action::Passive, "You are not wearing a disguise."),
+        }
+    }
+
+    // whom the player seems to be, by their disguise if they wear one
+    pub fn perceived_faction(&self) -> Option<&str> {
+        self.disguise.as_ref().and_then(|item| item.disguise())
+    }
+
+    // roll d20 + ability modifier to sway someone in conversation
+    pub fn passes(&self, check: &Check) -> bool {
+        dice_roll(1, 20) as i32 + self.stats.modifier(check.approach().ability()) >= check.dc()
+    }
+
+    // the guards let members through, and anyone whose disguise stands up to a Charisma check;
+    // a disguise that fails is seen through and no use against that faction again
+    #[allow(clippy::result_large_err)]
+    pub fn pass_guard(&mut self, guard: &Guard) -> Result<Option<String>, CmdResult> {
+        let faction = guard.faction();
+        if self.factions.iter().any(|joined| joined == faction) {
+            return Ok(None);
+        }
+        if self.perceived_faction() != Some(faction) {
+            return Err(CmdResult::new(Action::Failed, guard.refusal()));
+        }
+        if self.exposed.iter().any(|exposed| exposed == faction) {
+            return Err(CmdResult::new(
+                Action::Failed,
+                format!("The guards know your face. {}", guard.refusal()),
+            ));
+        }
+        if dice_roll(1, 20) as i32 + self.stats.cha_mod() >= guard.dc() {
+            Ok(Some(String::from("The guards wave you through.")))
+        } else {
+            self.exposed.push(faction.to_owned());
+            if let Some(item) = self.disguise.take() {
+                self.inventory.push(item);
+            }
+            Err(CmdResult::new(
+                Action::Failed,
+                format!(
+                    "The guards see through your disguise and tear it off you. {}",
+                    guard.refusal()
+                ),
+            ))
+        }
+    }
+
     pub fn engage_combat(&mut self) {
+        if let CombatStatus::Resting = self.in_combat {
+            debug!("combat started");
+        }
         self.in_combat = CombatStatus::InCombat;
     }
 
     fn set_equipped(&mut self, item_name: &str, item: Box<Item>) -> CmdResult {
         match *item {
             Armor(_) => {
-                self.take(item_name, Some(item));
+                self.inventory.take(item_name, Some(item));
                 self.don_armor(item_name);
                 CmdResult::new(Action::Active, "Donned.")
             }
             Weapon(_) => {
                 if let Some(weapon) = self.main_hand.take() {
-                    self.take(&weapon.name().to_owned(), Some(weapon));
+                    let weapon_name = weapon.name().to_owned();
+                    self.inventory.take(&weapon_name, Some(weapon));
                 }
                 self.main_hand = Some(item);
                 CmdResult::new(Action::Active, "Equipped.")
@@ -160,6 +358,11 @@ impl Player {
 
     // equip an Item into main_hand to simplify fighting
     pub fn equip(&mut self, weapon_name: &str) -> CmdResult {
+        if let Some(item) = self.inventory.find_item(weapon_name) {
+            if let Err(res) = self.can_use(item) {
+                return res;
+            }
+        }
         if let Some(item) = self.inventory.remove_item(weapon_name) {
             self.set_equipped(weapon_name, item)
         } else {
@@ -167,14 +370,112 @@ impl Player {
         }
     }
 
+    fn main_hand_damage(&self) -> u32 {
+        match self.main_hand.as_deref() {
+            Some(Weapon(weapon)) => weapon.max_damage(),
+            _ => 0,
+        }
+    }
+
+    fn take_best_weapon(&mut self) -> Option<Box<Item>> {
+        let better_than = self.main_hand_damage();
+        let (class, stats) = (&self.class, &self.stats);
+        self.inventory.take_best_weapon(better_than, |item| {
+            meets_requirements(item, class, stats).is_ok()
+        })
+    }
+
+    // equip the highest-damage usable weapon carried, if it beats the one in hand
+    pub fn equip_best(&mut self) -> CmdResult {
+        if let Some(item) = self.take_best_weapon() {
+            let name = item.name().to_owned();
+            self.set_equipped(&name, item);
+            CmdResult::new(Action::Active, format!("You equip the {}.", name))
+        } else if let Some(weapon) = &self.main_hand {
+            CmdResult::new(
+                Action::Passive,
+                format!("The {} is already your best weapon.", weapon.name()),
+            )
+        } else {
+            CmdResult::new(Action::Passive, "You have no weapon to equip.")
+        }
+    }
+
+    pub const fn auto_equip(&self) -> bool {
+        self.auto_equip
+    }
+
+    pub fn set_auto_equip(&mut self, auto_equip: bool) {
+        self.auto_equip = auto_equip;
+    }
+
+    // with auto-equip on, switch to a better weapon that was just picked up
+    fn equip_pickup(&mut self, res: CmdResult) -> CmdResult {
+        if self.auto_equip && res.is_active() {
+            if let Some(item) = self.take_best_weapon() {
+                let name = item.name().to_owned();
+                self.set_equipped(&name, item);
+                return CmdResult::new(
+                    Action::Active,
+                    format!("{} You equip the {}.", res.output().trim_end(), name),
+                );
+            }
+        }
+        res
+    }
+
+    pub fn strength_mod(&self) -> i32 {
+        self.stats.strngth_mod()
+    }
+
+    pub const fn bestiary(&self) -> &Bestiary {
+        &self.bestiary
+    }
+
+    pub fn record_kill(&mut self, enemy_name: &str) {
+        self.bestiary.record(enemy_name);
+    }
+
+    pub const fn codex(&self) -> &Codex {
+        &self.codex
+    }
+
+    // note every Enemy and Item in the Room, and everything carried, as encountered
+    pub fn discover(&mut self, room: &Room) {
+        for enemy in room.enemies() {
+            self.bestiary.discover(enemy.name(), enemy.inspect());
+        }
+        let carried = self
+            .inventory
+            .items()
+            .iter()
+            .chain(self.main_hand.iter())
+            .chain(self.armor.iter());
+        for item in room.items().iter().chain(carried) {
+            self.codex.discover(item.name(), item.inspect());
+        }
+    }
+
     pub fn gain_xp(&mut self, gained: u32) {
         self.xp.0 += gained;
+        self.score += gained;
+    }
+
+    // all experience ever earned, which is never lost to levelling up or death, and points for
+    // every quest completed
+    pub const fn score(&self) -> u32 {
+        self.score
     }
 
     pub fn has(&self, name: &str) -> bool {
         self.inventory.has(name)
     }
 
+    // let turns pass for whatever carried Things do on their own
+    pub fn stir_daemons(&mut self, turns: u32) -> Vec<(String, Stir)> {
+        self.inventory.stir(turns)
+    }
+
     pub const fn hp(&self) -> i32 {
         self.hp.0
     }
@@ -183,6 +484,18 @@ impl Player {
         self.hp.1
     }
 
+    pub const fn hp_display(&self) -> HpDisplay {
+        self.hp_display
+    }
+
+    pub fn set_hp_display(&mut self, hp_display: HpDisplay) {
+        self.hp_display = hp_display;
+    }
+
+    fn hp_report(&self) -> String {
+        self.hp_display.report(self.hp(), self.hp_cap())
+    }
+
     pub fn increase_ability_score(&mut self, ability_score: &str) -> CmdResult {
         self.stats.increase_ability_score(ability_score)
     }
@@ -195,19 +508,24 @@ impl Player {
         CmdResult::new(
             Action::Passive,
             format!(
-                "Level {} {} {}\
-                 \nHP: ({} / {})\
+                "Level {} {} {} ({})\
+                 \nHP: {}\
                  \nAC: {}\
                  \nXP: ({} / {})\
+                 {}\
                  \n{}",
                 self.lvl,
                 self.race,
                 self.class,
-                self.hp(),
-                self.hp_cap(),
+                self.pronouns,
+                self.hp_display.render(self.hp(), self.hp_cap()),
                 self.ac(),
                 self.xp.0,
                 self.xp.1,
+                match self.perceived_faction() {
+                    Some(faction) => format!("\nDisguised as one of the {}", faction),
+                    None => String::new(),
+                },
                 self.stats.print_stats()
             ),
         )
@@ -217,10 +535,10 @@ impl Player {
         if name == "me" || name == "self" || name == "myself" {
             Some(self.info())
         } else if let Some(item) = self.inventory.find_item(name) {
-            Some(CmdResult::new(Action::Active, item.inspect()))
+            Some(CmdResult::new(Action::Active, item.details()))
         } else if let Some(item) = &self.main_hand {
-            if item.name() == name {
-                Some(CmdResult::new(Action::Active, item.inspect()))
+            if named(item.name(), name) {
+                Some(CmdResult::new(Action::Active, item.details()))
             } else {
                 None
             }
@@ -229,6 +547,18 @@ impl Player {
         }
     }
 
+    // an Item carried, held, or worn
+    #[allow(clippy::borrowed_box)]
+    pub fn find_item(&self, name: &str) -> Option<&Box<Item>> {
+        if let Some(item) = self.inventory.find_item(name) {
+            return Some(item);
+        }
+        [&self.main_hand, &self.armor]
+            .iter()
+            .filter_map(|item| item.as_ref())
+            .find(|item| named(item.name(), name))
+    }
+
     pub const fn is_alive(&self) -> bool {
         self.hp.0 > 0
     }
@@ -253,11 +583,37 @@ impl Player {
         self.inventory.open(item_name)
     }
 
+    pub const fn inventory_format(&self) -> InventoryFormat {
+        self.inventory_format
+    }
+
+    pub fn set_inventory_format(&mut self, format: InventoryFormat) {
+        self.inventory_format = format;
+    }
+
     pub fn print_inventory(&self) -> CmdResult {
+        if self.inventory_format.is_categorized() {
+            let mut equipped = Vec::new();
+            if let Some(weapon) = &self.main_hand {
+                equipped.push((&**weapon, "equipped"));
+            }
+            if let Some(armor) = &self.armor {
+                equipped.push((&**armor, "worn"));
+            }
+            return CmdResult::new(
+                Action::Active,
+                format!(
+                    "{}{}",
+                    self.inventory
+                        .print_categorized(self.inventory_format, &equipped),
+                    self.print_quick_slots()
+                ),
+            );
+        }
         CmdResult::new(
             Action::Active,
             format!(
-                "{}{}{}",
+                "{}{}{}{}",
                 if let Some(weapon) = &self.main_hand {
                     format!("Main hand: {}\n", weapon.name())
                 } else {
@@ -268,19 +624,77 @@ impl Player {
                 } else {
                     String::new()
                 },
-                self.inventory.print()
+                self.inventory.print(self.inventory_format),
+                self.print_quick_slots()
             ),
         )
     }
 
+    fn print_quick_slots(&self) -> String {
+        if self.quick_slots.is_empty() {
+            return String::new();
+        }
+        let mut slots = self.quick_slots.clone();
+        slots.sort();
+        slots
+            .iter()
+            .fold(String::from("\nQuick slots:"), |res, (slot, name)| {
+                format!("{}\n  {}. {}", res, slot, name)
+            })
+    }
+
+    // bind a carried Item to a numbered slot so "use 1" reaches it
+    pub fn assign_slot(&mut self, item_name: &str, slot: u32) -> CmdResult {
+        if !(1..=QUICK_SLOTS).contains(&slot) {
+            return CmdResult::new(
+                Action::Failed,
+                format!("Quick slots are numbered 1 to {}.", QUICK_SLOTS),
+            );
+        }
+        let name = match self.find_item(item_name) {
+            Some(item) => item.name().to_owned(),
+            None => return CmdResult::dont_have(item_name),
+        };
+        self.quick_slots.retain(|(s, _)| *s != slot);
+        let res = format!("The {} is now in quick slot {}.", name, slot);
+        self.quick_slots.push((slot, name));
+        CmdResult::new(Action::Passive, res)
+    }
+
+    pub fn clear_slot(&mut self, slot: u32) -> CmdResult {
+        match self.quick_slots.iter().position(|(s, _)| *s == slot) {
+            Some(pos) => {
+                let (_, name) = self.quick_slots.remove(pos);
+                CmdResult::new(
+                    Action::Passive,
+                    format!("The {} is no longer in quick slot {}.", name, slot),
+                )
+            }
+            None => CmdResult::new(Action::Failed, format!("Quick slot {} is empty.", slot)),
+        }
+    }
+
+    // the name of the Item in a quick slot when given a slot number, or else the name as given
+    #[allow(clippy::result_large_err)]
+    pub fn slotted(&self, name: &str) -> Result<String, CmdResult> {
+        match name.parse::<u32>() {
+            Ok(slot) => self
+                .quick_slots
+                .iter()
+                .find(|(s, _)| *s == slot)
+                .map(|(_, name)| name.to_owned())
+                .ok_or_else(|| {
+                    CmdResult::new(Action::Failed, format!("Quick slot {} is empty.", slot))
+                }),
+            Err(_) => Ok(name.to_owned()),
+        }
+    }
+
     fn is_main_hand(&self, name: &str) -> bool {
         if let Some(main_hand) = &self.main_hand {
-            let main_hand = main_hand
-                .name()
-                .par_split_whitespace()
-                .collect::<Vec<&str>>();
+            let main_hand = main_hand.name().split_whitespace().collect::<Vec<&str>>();
 
-            name.par_split_whitespace()
+            name.split_whitespace()
                 .all(|ref word| main_hand.contains(word))
         } else {
             false
@@ -297,10 +711,9 @@ impl Player {
 
     fn is_armor(&self, name: &str) -> bool {
         if let Some(armor) = &self.armor {
-            let armor = armor.name().par_split_whitespace().collect::<Vec<&str>>();
+            let armor = armor.name().split_whitespace().collect::<Vec<&str>>();
 
-            name.par_split_whitespace()
-                .all(|ref word| armor.contains(word))
+            name.split_whitespace().all(|ref word| armor.contains(word))
         } else {
             false
         }
@@ -314,8 +727,56 @@ impl Player {
         }
     }
 
+    // give up an Item to the world, unless it is still needed for a quest
+    #[allow(clippy::result_large_err)]
+    pub fn release(&mut self, item_name: &str) -> Result<Box<Item>, CmdResult> {
+        if self.needs(item_name) {
+            Err(CmdResult::new(
+                Action::Failed,
+                "You feel you will need this.",
+            ))
+        } else {
+            self.remove(item_name)
+                .ok_or_else(|| CmdResult::dont_have(item_name))
+        }
+    }
+
+    // whether the Item, or anything inside it, is still needed for an unfinished quest
+    fn needs(&self, item_name: &str) -> bool {
+        self.inventory.find_item(item_name).is_some_and(|item| {
+            item.quests()
+                .iter()
+                .any(|quest| !self.quests_done.iter().any(|done| done == quest))
+        })
+    }
+
+    pub const fn karma(&self) -> i32 {
+        self.karma
+    }
+
+    pub fn adjust_karma(&mut self, change: i32) {
+        if change != 0 {
+            self.karma = self.karma.saturating_add(change);
+            debug!("karma changed by {} to {}", change, self.karma);
+        }
+    }
+
+    pub fn show_karma(&self) -> CmdResult {
+        CmdResult::new(
+            Action::Passive,
+            format!("Karma: {} ({})", self.karma, Karma::describe(self.karma)),
+        )
+    }
+
+    pub fn complete_quest(&mut self, quest: &str) {
+        if !self.quests_done.iter().any(|done| done == quest) {
+            self.quests_done.push(quest.to_owned());
+            self.score += QUEST_POINTS;
+        }
+    }
+
     // remove an item from inventory and into the current Room
-    pub fn remove(&mut self, item_name: &str) -> Option<Box<Item>> {
+    fn remove(&mut self, item_name: &str) -> Option<Box<Item>> {
         if let Some(item) = self.inventory.remove_item(item_name) {
             Some(item)
         } else if let Some(item) = self.remove_main_hand(item_name) {
@@ -338,13 +799,18 @@ impl Player {
                 }
                 CmdResult::new(
                     Action::Active,
-                    format!(
-                        "You regained {} HP for a total of ({} / {}) HP.",
-                        regained_hp,
-                        self.hp(),
-                        self.hp_cap()
-                    ),
+                    if self.hp_display == HpDisplay::Numeric {
+                        format!(
+                            "You regained {} HP for a total of ({} / {}) HP.",
+                            regained_hp,
+                            self.hp(),
+                            self.hp_cap()
+                        )
+                    } else {
+                        format!("You rest for a while. {}", self.hp_report())
+                    },
                 )
+                .with_pause(Pause::Rest)
             } else {
                 CmdResult::new(Action::Passive, "You cannot rest while in combat.")
             }
@@ -365,39 +831,127 @@ impl Player {
         }
     }
 
-    pub fn take_damage(&mut self, enemy_name: &str, damage: u32) -> String {
-        if dice_roll(1, 20) as i32 >= self.ac() {
+    pub fn set_god(&mut self, god: bool) {
+        self.god = god;
+    }
+
+    fn lose_hp(&mut self, damage: u32) {
+        if !self.god {
             self.hp = (self.hp.0 - damage as i32, self.hp.1);
-            format!(
-                "\nThe {} hit you for {} damage. You have {} HP left.",
-                enemy_name,
+        }
+    }
+
+    // the attacker as it is referred to in a sentence, like "the goblin" or "Gandalf"
+    pub fn take_damage(&mut self, attacker: &str, damage: u32) -> String {
+        self.take_blow(attacker, damage).1
+    }
+
+    // a blow rolled against the player's armor class, from an Enemy or another player, and
+    // whether it landed
+    pub fn take_blow(&mut self, attacker: &str, damage: u32) -> (bool, String) {
+        if dice_roll(1, 20) as i32 >= self.ac() {
+            self.lose_hp(damage);
+            let report = format!(
+                "\n{} hit you for {} damage. {}",
+                prose::capitalize(attacker),
                 damage,
-                self.hp()
-            )
+                self.hp_report()
+            );
+            (true, report)
         } else {
-            match dice_roll(1, 3) {
+            let report = match dice_roll(1, 3) {
                 0 => format!(
-                    "\nThe {} swung at you, but you dodged out of the way.",
-                    enemy_name
+                    "\n{} swung at you, but you dodged out of the way.",
+                    prose::capitalize(attacker)
                 ),
                 1 => format!(
-                    "\nThe {} hit you, but your armor deflected the blow.",
-                    enemy_name
+                    "\n{} hit you, but your armor deflected the blow.",
+                    prose::capitalize(attacker)
                 ),
                 _ => format!(
-                    "\nThe {} struck at you, but you deftly blocked the blow.",
-                    enemy_name
+                    "\n{} struck at you, but you deftly blocked the blow.",
+                    prose::capitalize(attacker)
                 ),
-            }
+            };
+            (false, report)
+        }
+    }
+
+    // give up roughly the given percent of the inventory, keeping quest items
+    pub fn forfeit(&mut self, percent: u32) -> Items {
+        let names: Vec<String> = self
+            .inventory
+            .items()
+            .iter()
+            .map(|item| item.name().to_owned())
+            .collect();
+        let count = (f64::from(names.len() as u32 * percent) / 100.0).ceil() as usize;
+        names
+            .iter()
+            .filter_map(|name| self.release(name).ok())
+            .take(count)
+            .collect()
+    }
+
+    // hurt by something other than a blow, like a sphinx's wrath at a wrong answer
+    pub fn suffer(&mut self, damage: u32) -> String {
+        self.lose_hp(damage);
+        format!("\nYou take {} damage. {}", damage, self.hp_report())
+    }
+
+    pub fn suffer_hazard(&mut self, hazard: &Hazard) -> String {
+        if let Some(save) = hazard.save() {
+            return self.save_against(save);
+        }
+        self.lose_hp(hazard.damage());
+        format!(
+            "\nYou are caught in {} and take {} damage. {}",
+            hazard.desc(),
+            hazard.damage(),
+            self.hp_report()
+        )
+    }
+
+    // worn armor offers a little protection against every kind of deadly effect
+    fn save_bonus(&self) -> i32 {
+        if self.armor.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
+    // roll d20 + ability modifier against a save-or-die effect
+    pub fn save_against(&mut self, save: &SavingThrow) -> String {
+        let roll =
+            dice_roll(1, 20) as i32 + self.stats.modifier(save.ability()) + self.save_bonus();
+        if roll >= save.dc() {
+            format!("\nYou narrowly escape {}.", save.desc())
+        } else if self.god {
+            format!(
+                "\nYou fail to escape {}, but nothing can harm you.",
+                save.desc()
+            )
+        } else {
+            self.hp = (0, self.hp.1);
+            self.death = Some(save.death().to_owned());
+            format!("\nYou fail to escape {}.", save.desc())
         }
     }
 
+    // how the player died, if it was anything more unusual than losing all their HP
+    pub fn death(&self) -> Option<&str> {
+        self.death.as_deref()
+    }
+
     pub fn take(&mut self, name: &str, item: Option<Box<Item>>) -> CmdResult {
-        self.inventory.take(name, item)
+        let res = self.inventory.take(name, item);
+        self.equip_pickup(res)
     }
 
     pub fn take_all(&mut self, items: Items) -> CmdResult {
-        self.inventory.take_all(items)
+        let res = self.inventory.take_all(items);
+        self.equip_pickup(res)
     }
 
     pub fn take_back(&mut self, item: Box<Item>) {
@@ -412,7 +966,294 @@ impl Player {
         self.inventory.take_item_from(item)
     }
 
+    // use up an Item from the inventory, returning any Ally it calls forth
+    pub fn use_item(&mut self, item_name: &str) -> (CmdResult, Option<Ally>) {
+        if let Some(item) = self.inventory.remove_item(item_name) {
+            if let Summon(summon) = *item {
+                let ally = summon.into_ally();
+                (
+                    CmdResult::new(
+                        Action::Active,
+                        format!(
+                            "You use the {}. {} appears at your side.",
+                            item_name,
+                            prose::capitalize(&prose::a(ally.name()))
+                        ),
+                    ),
+                    Some(ally),
+                )
+            } else {
+                self.inventory.push(item);
+                (
+                    CmdResult::new(
+                        Action::Passive,
+                        format!("You cannot use the {}.", item_name),
+                    ),
+                    None,
+                )
+            }
+        } else {
+            (CmdResult::dont_have(item_name), None)
+        }
+    }
+
+    // "light lamp" or "douse lamp"
+    pub fn tend_light(&mut self, item_name: &str, lit: bool) -> CmdResult {
+        match self.inventory.find_item_mut(item_name) {
+            Some(item) => match &mut **item {
+                Light(light) if lit => light.light(),
+                Light(light) => light.douse(),
+                _ if lit => {
+                    CmdResult::new(Action::Passive, format!("The {} cannot be lit.", item_name))
+                }
+                _ => CmdResult::new(Action::Passive, format!("The {} is not lit.", item_name)),
+            },
+            None => CmdResult::dont_have(item_name),
+        }
+    }
+
+    // the name of the first Thing carried that is good for making camp in the given way
+    pub fn gear(&self, gear: Gear) -> Option<&str> {
+        self.inventory
+            .items()
+            .iter()
+            .find(|item| item.gear() == Some(gear))
+            .map(|item| item.name())
+    }
+
+    // turn raw food into cooked food over a campfire
+    pub fn cook(&mut self, item_name: &str) -> CmdResult {
+        let cooked = match self.inventory.find_item(item_name) {
+            Some(item) => match item.cooked() {
+                Some(cooked) => cooked.clone(),
+                None => {
+                    return CmdResult::new(
+                        Action::Passive,
+                        format!("You cannot cook the {}.", item.name()),
+                    )
+                }
+            },
+            None => return CmdResult::dont_have(item_name),
+        };
+        match self.release(item_name) {
+            Ok(raw) => {
+                let res = format!(
+                    "You cook the {} into {}.",
+                    raw.name(),
+                    prose::a(cooked.name())
+                );
+                self.inventory.push(Box::new(cooked));
+                CmdResult::new(Action::Active, res)
+            }
+            Err(res) => res,
+        }
+    }
+
+    // make a Recipe at its station, using up the ingredients however it turns out
+    pub fn craft(&mut self, name: &str, recipe: &Recipe) -> CmdResult {
+        let mut needed: Vec<(&str, usize)> = Vec::new();
+        for ingredient in recipe.ingredients() {
+            match needed.iter_mut().find(|(name, _)| name == ingredient) {
+                Some(entry) => entry.1 += 1,
+                None => needed.push((ingredient, 1)),
+            }
+        }
+        let missing = needed
+            .iter()
+            .any(|(ingredient, count)| self.inventory.items().count_of(ingredient) < *count);
+        if missing {
+            let needed: Vec<String> = needed
+                .iter()
+                .map(|(ingredient, count)| {
+                    let ingredient = namespace::local(ingredient);
+                    if *count > 1 {
+                        prose::count(*count, ingredient)
+                    } else {
+                        prose::a(ingredient)
+                    }
+                })
+                .collect();
+            return CmdResult::new(
+                Action::Failed,
+                format!(
+                    "You need {} to make {}.",
+                    prose::list(&needed),
+                    prose::a(name)
+                ),
+            );
+        }
+        let mut used = Vec::new();
+        for ingredient in recipe.ingredients() {
+            match self.release(ingredient) {
+                Ok(item) => used.push(item),
+                Err(res) => {
+                    // a quest item cannot go into the pot, so put back what already went in
+                    for item in used {
+                        self.inventory.push(item);
+                    }
+                    return res;
+                }
+            }
+        }
+
+        let check = dice_roll(1, 20) as i32 + self.stats.modifier(recipe.ability());
+        match recipe.outcome(check) {
+            Some(made) => {
+                let res = format!("You make {}.", prose::a(made.name()));
+                self.inventory.push(Box::new(made.clone()));
+                CmdResult::new(Action::Active, res)
+            }
+            None => CmdResult::new(
+                Action::Active,
+                format!("You botch the {}, and the ingredients are wasted.", name),
+            ),
+        }
+    }
+
+    pub fn learn_song(&mut self, song: &str) -> String {
+        if self.songs.iter().any(|known| same_name(known, song)) {
+            format!("You already know the {}.", song)
+        } else {
+            self.songs.push(song.to_owned());
+            format!("You learn the {}.", song)
+        }
+    }
+
+    // learn the songs a teacher or scroll offers, or just the one asked for
+    pub fn learn_from(
+        &mut self,
+        teacher: &str,
+        songs: &[String],
+        wanted: Option<&str>,
+    ) -> CmdResult {
+        let songs: Vec<&String> = songs
+            .iter()
+            .filter(|song| wanted.is_none_or(|wanted| song_named(song, wanted)))
+            .collect();
+        if songs.is_empty() {
+            return CmdResult::new(
+                Action::Failed,
+                match wanted {
+                    Some(_) => format!("There is no such song to learn from {}.", teacher),
+                    None => format!("There are no songs to learn from {}.", teacher),
+                },
+            );
+        }
+        let learned: Vec<String> = songs.iter().map(|song| self.learn_song(song)).collect();
+        CmdResult::new(Action::Active, learned.join("\n"))
+    }
+
+    // the name of a song the player knows
+    pub fn known_song(&self, name: &str) -> Option<&str> {
+        self.songs
+            .iter()
+            .find(|song| song_named(song, name))
+            .map(String::as_str)
+    }
+
+    pub fn print_songs(&self) -> CmdResult {
+        CmdResult::new(
+            Action::Passive,
+            if self.songs.is_empty() {
+                String::from("You don't know any songs.")
+            } else {
+                format!("Songs you know:\n  {}", self.songs.join("\n  "))
+            },
+        )
+    }
+
+    // the name of the instrument asked for, or of the first one carried when none is named
+    #[allow(clippy::result_large_err)]
+    pub fn instrument(&self, name: Option<&str>) -> Result<&str, CmdResult> {
+        match name {
+            Some(name) => match self.find_item(name) {
+                Some(item) if item.is_instrument() => Ok(item.name()),
+                Some(item) => Err(CmdResult::new(
+                    Action::Failed,
+                    format!("You can't play the {}.", item.name()),
+                )),
+                None => Err(CmdResult::dont_have(name)),
+            },
+            None => self
+                .inventory
+                .items()
+                .iter()
+                .find(|item| item.is_instrument())
+                .map(|item| item.name())
+                .ok_or_else(|| CmdResult::new(Action::Failed, "You have nothing to play it on.")),
+        }
+    }
+
+    // a night's sleep somewhere safe brings back every HP
+    pub fn sleep(&mut self) -> CmdResult {
+        self.hp = (self.hp_cap() as i32, self.hp_cap());
+        CmdResult::new(Action::Active, "You sleep soundly and wake fully rested.")
+    }
+
+    // the name of the first rope or chain carried
+    pub fn rope(&self) -> Option<&str> {
+        self.inventory
+            .items()
+            .iter()
+            .find(|item| matches!(***item, Item::Rope(_)))
+            .map(|item| item.name())
+    }
+
+    // how many rooms away the player can see along an exit by the brightest lit Light carried
+    pub fn light_radius(&self) -> u32 {
+        self.inventory
+            .items()
+            .iter()
+            .filter_map(|item| match &**item {
+                Light(light) if light.is_lit() => Some(light.radius()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    // bring the player back from death at a fraction of their HP, losing all progress
+    // toward the next level
+    pub fn revive(&mut self, hp_percent: u32) -> String {
+        self.hp = (((self.hp.1 * hp_percent) / 100).max(1) as i32, self.hp.1);
+        self.xp.0 = 0;
+        self.death = None;
+        format!(
+            "\nYou return from death, but all your experience toward the next level is lost. {}",
+            self.hp_report()
+        )
+    }
+
+    // return to full health, ready for a fresh adventure
+    pub fn restore(&mut self) {
+        self.hp = (self.hp.1 as i32, self.hp.1);
+        self.in_combat = CombatStatus::Resting;
+        self.death = None;
+    }
+
+    pub fn revive_with_item(&mut self) -> Option<String> {
+        let revival = self.inventory.take_revival()?;
+        Some(format!(
+            "\n\nThe {} crumbles to ash and you are pulled back from death.{}",
+            revival.name(),
+            self.revive(revival.hp_percent())
+        ))
+    }
+
     pub fn wait() -> CmdResult {
         CmdResult::new(Action::Active, "Time passes...")
     }
 }
+
+// whether every word given is in the song's name, so "song opening" finds "song of opening"
+fn song_named(song: &str, name: &str) -> bool {
+    named(song, name)
+}
+
+#[allow(clippy::result_large_err)]
+fn meets_requirements(item: &Item, class: &Class, stats: &Stats) -> Result<(), CmdResult> {
+    match item.requirements() {
+        Some(requirements) => requirements.check(item.name(), class, stats),
+        None => Ok(()),
+    }
+}
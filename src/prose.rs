@@ -0,0 +1,128 @@
+// Small helpers for writing names into sentences, so that every message gets its articles,
+// plurals, capitals, and lists right the same way
+
+// words whose first letter is a vowel but whose first sound is not, and the other way around
+const VOWEL_LETTER_CONSONANT_SOUND: [&str; 5] = ["uni", "use", "usu", "one", "eu"];
+const SILENT_H: [&str; 4] = ["hour", "honest", "honor", "heir"];
+
+const IRREGULAR_PLURALS: [(&str, &str); 14] = [
+    ("child", "children"),
+    ("dwarf", "dwarves"),
+    ("elf", "elves"),
+    ("foot", "feet"),
+    ("goose", "geese"),
+    ("knife", "knives"),
+    ("louse", "lice"),
+    ("man", "men"),
+    ("mouse", "mice"),
+    ("ox", "oxen"),
+    ("person", "people"),
+    ("thief", "thieves"),
+    ("tooth", "teeth"),
+    ("wolf", "wolves"),
+];
+const UNCHANGED_PLURALS: [&str; 4] = ["sheep", "fish", "deer", "moose"];
+
+// "a" or "an" by how the name sounds
+pub(crate) fn article(name: &str) -> &'static str {
+    let word = name.to_lowercase();
+    if SILENT_H.iter().any(|prefix| word.starts_with(prefix)) {
+        "an"
+    } else if VOWEL_LETTER_CONSONANT_SOUND
+        .iter()
+        .any(|prefix| word.starts_with(prefix))
+    {
+        "a"
+    } else if word.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+// "an axe", "a unicorn"
+pub(crate) fn a(name: &str) -> String {
+    format!("{} {}", article(name), name)
+}
+
+fn plural_word(word: &str) -> String {
+    for (singular, plural) in IRREGULAR_PLURALS.iter() {
+        if let Some(stem) = word.strip_suffix(singular) {
+            // "woman" and "werewolf" follow their last part, but "human" and "shaman" do not
+            if stem.is_empty()
+                || (matches!(*singular, "man" | "wolf") && !["hu", "sha"].contains(&stem))
+            {
+                return format!("{}{}", stem, plural);
+            }
+        }
+    }
+    if UNCHANGED_PLURALS.contains(&word) {
+        word.to_owned()
+    } else if word.ends_with(['s', 'x', 'z']) || word.ends_with("ch") || word.ends_with("sh") {
+        format!("{}es", word)
+    } else if word.ends_with('y') && !word[..word.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u']) {
+        format!("{}ies", &word[..word.len() - 1])
+    } else {
+        format!("{}s", word)
+    }
+}
+
+// "the goblin", but a proper noun such as "Gandalf" goes without an article
+pub(crate) fn the(name: &str, proper: bool) -> String {
+    if proper {
+        name.to_owned()
+    } else {
+        format!("the {}", name)
+    }
+}
+
+// "Gandalf's", or "Boris'" for a name that already ends in s
+pub(crate) fn possessive(name: &str) -> String {
+    if name.ends_with('s') {
+        format!("{}'", name)
+    } else {
+        format!("{}'s", name)
+    }
+}
+
+// pluralize the head of the name: the word before "of" in "swarm of rats", otherwise the last
+pub(crate) fn plural(name: &str) -> String {
+    if let Some((head, rest)) = name.split_once(" of ") {
+        return format!("{} of {}", plural(head), rest);
+    }
+    match name.rsplit_once(' ') {
+        Some((first, last)) => format!("{} {}", first, plural_word(last)),
+        None => plural_word(name),
+    }
+}
+
+// "1 goblin" or "3 goblins"
+pub(crate) fn count(n: usize, name: &str) -> String {
+    if n == 1 {
+        format!("1 {}", name)
+    } else {
+        format!("{} {}", n, plural(name))
+    }
+}
+
+pub(crate) fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// "a, b, and c", or with another conjunction such as "or"
+pub(crate) fn list_with(names: &[String], conjunction: &str) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.to_owned(),
+        [first, second] => format!("{} {} {}", first, conjunction, second),
+        [rest @ .., last] => format!("{}, {} {}", rest.join(", "), conjunction, last),
+    }
+}
+
+pub(crate) fn list(names: &[String]) -> String {
+    list_with(names, "and")
+}
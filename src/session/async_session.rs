@@ -0,0 +1,113 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+};
+
+use crate::Cli;
+
+#[derive(Debug, Default)]
+struct Outbox {
+    lines: VecDeque<String>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+/// An async front for a game: input lines go in with `send` and output comes back out of `recv`,
+/// so a host can run many players on one executor without a blocking thread for each
+///
+/// The game rolls its own dice, so a seeded game replays the same whichever thread polls it.
+#[derive(Debug)]
+pub struct AsyncGameSession {
+    cli: Mutex<Cli>,
+    outbox: Arc<Mutex<Outbox>>,
+}
+
+impl AsyncGameSession {
+    pub fn new(cli: Cli) -> Self {
+        Self {
+            cli: Mutex::new(cli),
+            outbox: Arc::default(),
+        }
+    }
+
+    /// Handle one line of input and return its output
+    pub async fn ask(&self, input: &str) -> String {
+        self.lock_cli().ask(input)
+    }
+
+    /// Handle one line of input, queueing its output for `recv`; the stream ends when the game does
+    pub fn send(&self, input: &str) {
+        let (output, over) = {
+            let cli = self.lock_cli();
+            let output = cli.ask(input);
+            (output, cli.is_over())
+        };
+
+        let mut outbox = self.lock_outbox();
+        if outbox.closed {
+            return;
+        }
+        outbox.lines.push_back(output);
+        outbox.closed = over;
+        if let Some(waker) = outbox.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Wait for the next output, or None once the game has ended and every output has been read
+    pub fn recv(&self) -> Recv<'_> {
+        Recv { session: self }
+    }
+
+    /// Poll for the next output in the style of a stream
+    pub fn poll_recv(&self, cx: &mut Context) -> Poll<Option<String>> {
+        let mut outbox = self.lock_outbox();
+        if let Some(line) = outbox.lines.pop_front() {
+            Poll::Ready(Some(line))
+        } else if outbox.closed {
+            Poll::Ready(None)
+        } else {
+            outbox.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    /// End the output stream early, for example when a player disconnects
+    pub fn close(&self) {
+        let mut outbox = self.lock_outbox();
+        outbox.closed = true;
+        if let Some(waker) = outbox.waker.take() {
+            waker.wake();
+        }
+    }
+
+    pub fn into_inner(self) -> Cli {
+        self.cli.into_inner().unwrap_or_else(|err| err.into_inner())
+    }
+
+    // a panic in another task must not lock the player out of their game
+    fn lock_cli(&self) -> MutexGuard<'_, Cli> {
+        self.cli.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn lock_outbox(&self) -> MutexGuard<'_, Outbox> {
+        self.outbox.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Future returned by `AsyncGameSession::recv`
+#[derive(Debug)]
+pub struct Recv<'a> {
+    session: &'a AsyncGameSession,
+}
+
+impl Future for Recv<'_> {
+    type Output = Option<String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.session.poll_recv(cx)
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+/// Something one player said or did, as heard by another player
+#[derive(Clone, Debug, PartialEq)]
+pub enum Chat {
+    Say {
+        from: String,
+        text: String,
+    },
+    /// `nearby` is set for players in an adjacent room, who hear the words but not who shouted
+    Shout {
+        from: String,
+        text: String,
+        nearby: bool,
+    },
+    Emote {
+        from: String,
+        action: String,
+    },
+}
+
+impl fmt::Display for Chat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Say { from, text } => write!(f, "{} says, \"{}\"", from, text),
+            Self::Shout {
+                text, nearby: true, ..
+            } => write!(f, "Someone shouts nearby, \"{}\"", text),
+            Self::Shout { from, text, .. } => write!(f, "{} shouts, \"{}\"", from, text),
+            Self::Emote { from, action } => write!(f, "{} {}", from, sentence(action)),
+        }
+    }
+}
+
+// end an emote with a full stop unless it already has one
+pub(crate) fn sentence(action: &str) -> String {
+    if action.ends_with(['.', '!', '?']) {
+        action.to_owned()
+    } else {
+        format!("{}.", action)
+    }
+}
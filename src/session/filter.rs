@@ -0,0 +1,58 @@
+/// Screens what passes between players and their games in a public deployment, such as masking
+/// rude words in what players say or name their pets; the game itself leaves words alone
+pub trait Filter: Send {
+    /// A line a player typed, before their game or other players see it
+    fn input(&mut self, user: &str, text: &str) -> String;
+
+    /// What is about to be sent to a player
+    fn output(&mut self, _user: &str, text: &str) -> String {
+        text.to_owned()
+    }
+}
+
+/// A Filter that masks the words on a list with asterisks wherever players type them,
+/// in any case
+#[derive(Clone, Debug, Default)]
+pub struct WordList {
+    words: Vec<String>,
+}
+
+impl WordList {
+    pub fn new(words: &[&str]) -> Self {
+        Self {
+            words: words.iter().map(|word| word.to_lowercase()).collect(),
+        }
+    }
+
+    pub fn with_word(mut self, word: &str) -> Self {
+        self.words.push(word.to_lowercase());
+        self
+    }
+
+    // the text with each listed word starred out, leaving the words around it be
+    pub fn mask(&self, text: &str) -> String {
+        let mut masked = String::with_capacity(text.len());
+        let mut word = String::new();
+        for c in text.chars().chain(std::iter::once(' ')) {
+            if c.is_alphanumeric() || c == '\'' {
+                word.push(c);
+                continue;
+            }
+            if self.words.contains(&word.to_lowercase()) {
+                masked.extend(word.chars().map(|_| '*'));
+            } else {
+                masked.push_str(&word);
+            }
+            word.clear();
+            masked.push(c);
+        }
+        masked.pop();
+        masked
+    }
+}
+
+impl Filter for WordList {
+    fn input(&mut self, _user: &str, text: &str) -> String {
+        self.mask(text)
+    }
+}
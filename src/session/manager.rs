@@ -0,0 +1,656 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    time::{Duration, Instant},
+};
+
+use super::{
+    chat::{sentence, Chat},
+    filter::Filter,
+};
+use crate::{entity::Entity, prose, Cli, Item, Role};
+
+type NewGame = Box<dyn Fn(&str) -> Cli + Send>;
+type Autosave = Box<dyn FnMut(&str, &Cli) + Send>;
+type Broadcast = Box<dyn FnMut(&str, &str) + Send>;
+type ChatHook = Box<dyn FnMut(&str, &Chat) + Send>;
+type Spectate = Box<dyn FnMut(&str, &str, &str) + Send>;
+
+// one player's game and when they last touched it
+struct Session {
+    cli: Cli,
+    last_active: Instant,
+    // when recent commands arrived, for the rate limit
+    recent: VecDeque<Instant>,
+}
+
+// an item a slain player dropped, lying in that room in every game until someone takes it
+struct Dropped {
+    room: String,
+    item: Item,
+}
+
+// an item held back from one player's game until another accepts or declines it
+struct Trade {
+    from: String,
+    to: String,
+    item: Item,
+}
+
+/// Many independent games keyed by user id, for bots and servers that host a game per player
+///
+/// Every game keeps its own copy of the world. Players meet only through what the manager
+/// passes between games: chat, items offered from one to another, and fights where allowed
+pub struct SessionManager {
+    sessions: HashMap<String, Session>,
+    idle_timeout: Duration,
+    max_input_len: usize,
+    rate_limit: Option<(usize, Duration)>,
+    new_game: NewGame,
+    autosave: Option<Autosave>,
+    broadcast: Option<Broadcast>,
+    chat: Option<ChatHook>,
+    pvp_drop: Option<u32>,
+    ground: Vec<Dropped>,
+    trades: Vec<Trade>,
+    spectate: Option<Spectate>,
+    // each watched user and who is watching them
+    spectators: HashMap<String, Vec<String>>,
+    filter: Option<Box<dyn Filter>>,
+}
+
+impl SessionManager {
+    /// Build a new game with `new_game` the first time each user speaks; a game seeded there
+    /// rolls its own dice, so seeding one user's game never reseeds anyone else's
+    pub fn new<F>(new_game: F) -> Self
+    where
+        F: Fn(&str) -> Cli + Send + 'static,
+    {
+        Self {
+            sessions: HashMap::new(),
+            idle_timeout: Duration::from_secs(30 * 60),
+            max_input_len: 256,
+            rate_limit: None,
+            new_game: Box::new(new_game),
+            autosave: None,
+            broadcast: None,
+            chat: None,
+            pvp_drop: None,
+            ground: Vec::new(),
+            trades: Vec::new(),
+            spectate: None,
+            spectators: HashMap::new(),
+            filter: None,
+        }
+    }
+
+    /// How long a session may go without input before `expire_idle` ends it
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// The longest line of input accepted, in characters; 256 by default
+    pub fn with_max_input_len(mut self, max_input_len: usize) -> Self {
+        self.max_input_len = max_input_len;
+        self
+    }
+
+    /// Turn away input from a user who sends more than `commands` lines within `per`
+    pub fn with_rate_limit(mut self, commands: usize, per: Duration) -> Self {
+        self.rate_limit = Some((commands, per));
+        self
+    }
+
+    /// Called with a user's game after every turn they take and when their session ends
+    pub fn with_autosave<F>(mut self, autosave: F) -> Self
+    where
+        F: FnMut(&str, &Cli) + Send + 'static,
+    {
+        self.autosave = Some(Box::new(autosave));
+        self
+    }
+
+    /// Called once per user by `broadcast` to deliver a message to them
+    pub fn with_broadcast<F>(mut self, broadcast: F) -> Self
+    where
+        F: FnMut(&str, &str) + Send + 'static,
+    {
+        self.broadcast = Some(Box::new(broadcast));
+        self
+    }
+
+    /// Called for each user who hears another player's say, shout, or emote, with the words
+    /// already passed through the output filter; without it, chat is delivered as text through
+    /// the broadcast hook
+    pub fn with_chat<F>(mut self, chat: F) -> Self
+    where
+        F: FnMut(&str, &Chat) + Send + 'static,
+    {
+        self.chat = Some(Box::new(chat));
+        self
+    }
+
+    /// Called with a spectator, the user they watch, and each piece of that user's output
+    pub fn with_spectate<F>(mut self, spectate: F) -> Self
+    where
+        F: FnMut(&str, &str, &str) + Send + 'static,
+    {
+        self.spectate = Some(Box::new(spectate));
+        self
+    }
+
+    /// Pass what players type and what they are sent through a filter, such as a `WordList`
+    /// of words to mask in public games
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Filter + 'static,
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Let users fight with "attack <user>" in rooms that allow it, a slain player dropping
+    /// the given percent of their inventory in the room
+    ///
+    /// Blows are rolled the way they are against enemies, and an enemy that shares a user's name
+    /// is the one attacked. Every game has its own copy of the world, so what a slain player
+    /// drops is put in that room in every game, and taken from all of them once one player
+    /// picks it up
+    pub fn with_pvp(mut self, drop_percent: u32) -> Self {
+        self.pvp_drop = Some(drop_percent.min(100));
+        self
+    }
+
+    /// Handle a line of input from a user, starting a game for them if they have none
+    ///
+    /// "give <item> to <user>" offers an item to another player, who can "accept" or "decline" it,
+    /// and "say", "shout", and "emote" reach other players in the same room
+    ///
+    /// Only the item changes hands, from one inventory to the other; neither world changes with
+    /// it. A traded key opens whatever lock of its name the receiver's world has, and the
+    /// receiver can still find their own copy of the item wherever it lay in their world
+    ///
+    /// Control characters are stripped, and overlong or too frequent input is turned away
+    pub fn ask(&mut self, user: &str, input: &str) -> String {
+        if input.chars().count() > self.max_input_len {
+            return String::from("That is too long to understand.");
+        }
+        if self.rate_limited(user) {
+            return String::from("You are acting too quickly. Wait a moment and try again.");
+        }
+        let input: String = input
+            .chars()
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .filter(|c| !c.is_control())
+            .collect();
+        let input = match &mut self.filter {
+            Some(filter) => filter.input(user, &input),
+            None => input,
+        };
+
+        let output = self.respond(user, &input);
+        let output = self.filtered(user, &output);
+        self.spectate(user, &format!("> {}\n{}", input.trim(), output));
+        output
+    }
+
+    // note the time of a command, reporting whether the user is over their rate limit
+    fn rate_limited(&mut self, user: &str) -> bool {
+        let (commands, per) = match self.rate_limit {
+            Some(limit) => limit,
+            None => return false,
+        };
+        let session = match self.sessions.get_mut(user) {
+            Some(session) => session,
+            None => return false,
+        };
+        let now = Instant::now();
+        while matches!(session.recent.front(), Some(time) if now.duration_since(*time) >= per) {
+            session.recent.pop_front();
+        }
+        if session.recent.len() >= commands {
+            true
+        } else {
+            session.recent.push_back(now);
+            false
+        }
+    }
+
+    fn respond(&mut self, user: &str, input: &str) -> String {
+        // trading, talking, and fighting other players count as activity as much as commands do
+        if let Some(session) = self.sessions.get_mut(user) {
+            session.last_active = Instant::now();
+        }
+        if let Some(output) = self.trade(user, input.trim()) {
+            return output;
+        }
+        if let Some(output) = self.talk(user, input.trim()) {
+            return output;
+        }
+        if let Some(output) = self.fight(user, input.trim()) {
+            return output;
+        }
+
+        let (new_game, ground) = (&self.new_game, &self.ground);
+        let session = self.sessions.entry(user.to_owned()).or_insert_with(|| {
+            let cli = new_game(user);
+            for dropped in ground {
+                cli.add_item(&dropped.room, dropped.item.clone());
+            }
+            Session {
+                cli,
+                last_active: Instant::now(),
+                recent: vec![Instant::now()].into(),
+            }
+        });
+
+        let lying = count_ground(&self.ground, &session.cli);
+        let turns = session.cli.status().turns();
+        let output = session.cli.ask(input);
+        let took_turn = session.cli.status().turns() != turns;
+        let over = session.cli.is_over();
+        self.clear_ground(user, &lying);
+
+        if over {
+            self.end(user);
+        } else if took_turn {
+            if let (Some(autosave), Some(session)) = (&mut self.autosave, self.sessions.get(user)) {
+                // a game that restricts saves is only saved once the player is safe
+                if session.cli.can_save().is_ok() {
+                    autosave(user, &session.cli);
+                }
+            }
+        }
+        output
+    }
+
+    // offers and answers to offers between players
+    fn trade(&mut self, user: &str, input: &str) -> Option<String> {
+        if let Some((item_name, to)) = input
+            .strip_prefix("give ")
+            .and_then(|rest| rest.rsplit_once(" to "))
+        {
+            if to == user || !self.sessions.contains_key(to) {
+                return None;
+            }
+            let cli = &self.sessions.get(user)?.cli;
+            let item = match cli.release_item(item_name) {
+                Ok(item) => item,
+                Err(output) => return Some(output),
+            };
+            let output = format!("You offer the {} to {}.", item.name(), to);
+            self.notify(
+                to,
+                &format!(
+                    "{} offers you the {}. Type \"accept\" or \"decline\".",
+                    user,
+                    item.name()
+                ),
+            );
+            self.trades.push(Trade {
+                from: user.to_owned(),
+                to: to.to_owned(),
+                item,
+            });
+            return Some(output);
+        }
+
+        let accept = match input {
+            "accept" => true,
+            "decline" => false,
+            _ => return None,
+        };
+        let pos = self.trades.iter().position(|trade| trade.to == user)?;
+        let trade = self.trades.remove(pos);
+        let name = trade.item.name().to_owned();
+        if accept {
+            self.sessions.get(user)?.cli.receive_item(trade.item);
+            self.notify(&trade.from, &format!("{} accepts the {}.", user, name));
+            Some(format!("You accept the {} from {}.", name, trade.from))
+        } else {
+            self.notify(&trade.from, &format!("{} declines the {}.", user, name));
+            self.give_back(trade);
+            Some(format!("You decline the {}.", name))
+        }
+    }
+
+    // say, shout, and emote to players in the same room; shouts also carry one room away
+    fn talk(&mut self, user: &str, input: &str) -> Option<String> {
+        let (verb, text) = input.split_once(' ').unwrap_or((input, ""));
+        let text = text.trim();
+        if !matches!(verb, "say" | "shout" | "emote") {
+            return None;
+        }
+        let speaker = &self.sessions.get(user)?.cli;
+        if text.is_empty() {
+            return Some(format!("What do you want to {}?", verb));
+        }
+
+        let room = speaker.status().room().to_owned();
+        let neighbors = speaker.neighbors();
+        let listeners: Vec<(String, bool)> = self
+            .sessions
+            .iter()
+            .filter(|(other, _)| *other != user)
+            .filter_map(|(other, session)| {
+                let other_room = session.cli.status().room().to_owned();
+                if other_room == room {
+                    Some((other.to_owned(), false))
+                } else if verb == "shout" && neighbors.contains(&other_room) {
+                    Some((other.to_owned(), true))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        for (listener, nearby) in listeners {
+            // the hook is handed the words as the filter lets them through to the listener
+            let text = match self.chat {
+                Some(_) => self.filtered(&listener, text),
+                None => text.to_owned(),
+            };
+            let chat = match verb {
+                "say" => Chat::Say {
+                    from: user.to_owned(),
+                    text,
+                },
+                "shout" => Chat::Shout {
+                    from: user.to_owned(),
+                    text,
+                    nearby,
+                },
+                _ => Chat::Emote {
+                    from: user.to_owned(),
+                    action: text,
+                },
+            };
+            if let Some(hook) = &mut self.chat {
+                hook(&listener, &chat);
+                self.spectate(&listener, &chat.to_string());
+            } else {
+                self.notify(&listener, &chat.to_string());
+            }
+        }
+
+        Some(match verb {
+            "say" => format!("You say, \"{}\"", text),
+            "shout" => self
+                .sessions
+                .get(user)?
+                .cli
+                .hear(text)
+                .iter()
+                .fold(format!("You shout, \"{}\"", text), |res, reply| {
+                    format!("{}\n{}", res, reply)
+                }),
+            _ => format!("{} {}", user, sentence(text)),
+        })
+    }
+
+    // an attack on another player in the same room
+    fn fight(&mut self, user: &str, input: &str) -> Option<String> {
+        let (verb, rest) = input.split_once(' ')?;
+        if !matches!(verb, "attack" | "hit" | "kill" | "slay" | "cut") {
+            return None;
+        }
+        let (target, weapon) = match rest.split_once(" with ") {
+            Some((target, weapon)) => (target, Some(weapon)),
+            None => (rest, None),
+        };
+        let attacker = &self.sessions.get(user)?.cli;
+        // an enemy is fought as usual, even one that shares a player's name
+        if target == user || attacker.has_enemy_here(target) {
+            return None;
+        }
+        let room = attacker.room_key();
+        let defender = &self.sessions.get(target)?.cli;
+        // a player elsewhere is out of reach, without giving away where they are
+        if defender.room_key() != room {
+            return Some(String::from("There is no one like that here."));
+        }
+
+        let drop_percent = match self.pvp_drop {
+            Some(drop_percent) if attacker.in_pvp_room() => drop_percent,
+            _ => return Some(String::from("You cannot fight other players here.")),
+        };
+        let damage = match attacker.attack_player(weapon) {
+            Ok(damage) => damage,
+            Err(output) => return Some(output),
+        };
+
+        let (landed, report) = defender.defend(user, damage);
+        self.notify(target, &report);
+        let mut output = if landed {
+            format!("You hit {} for {} damage.", target, damage)
+        } else {
+            format!("You miss {}.", target)
+        };
+
+        let defender = &self.sessions.get(target)?.cli;
+        if defender.is_over() {
+            let dropped = defender.forfeit_items(drop_percent);
+            let possessive = defender.pronouns().possessive().to_owned();
+            let names: Vec<String> = dropped
+                .iter()
+                .map(|item| format!("{} {}", possessive, item.name()))
+                .collect();
+            if names.is_empty() {
+                output.push_str(&format!("\n{} falls.", target));
+            } else {
+                output.push_str(&format!(
+                    "\n{} falls, dropping {}.",
+                    target,
+                    prose::list(&names)
+                ));
+            }
+            self.notify(target, &format!("You were slain by {}.", user));
+            self.end(target);
+            for item in dropped {
+                for session in self.sessions.values() {
+                    session.cli.add_item(&room, item.clone());
+                }
+                self.ground.push(Dropped {
+                    room: room.clone(),
+                    item,
+                });
+            }
+        }
+        Some(output)
+    }
+
+    // whatever a user's turn took off the shared ground is taken out of every other game too;
+    // several drops alike in one room are counted together, so as many of them are cleared as
+    // the turn took
+    fn clear_ground(&mut self, user: &str, before: &[usize]) {
+        let after = match self.sessions.get(user) {
+            Some(session) => count_ground(&self.ground, &session.cli),
+            None => return,
+        };
+        let mut taken: Vec<usize> = Vec::new();
+        for (i, dropped) in self.ground.iter().enumerate() {
+            let alike = taken
+                .iter()
+                .filter(|&&j| {
+                    self.ground[j].room == dropped.room
+                        && self.ground[j].item.id() == dropped.item.id()
+                })
+                .count();
+            if alike < before[i].saturating_sub(after[i]) {
+                taken.push(i);
+            }
+        }
+        for i in taken.into_iter().rev() {
+            let dropped = self.ground.remove(i);
+            for (other, session) in &self.sessions {
+                if other != user {
+                    session.cli.remove_item(&dropped.room, dropped.item.id());
+                }
+            }
+        }
+    }
+
+    // return an offered item to the player who offered it
+    fn give_back(&mut self, trade: Trade) {
+        if let Some(session) = self.sessions.get(&trade.from) {
+            session.cli.receive_item(trade.item);
+        }
+    }
+
+    fn notify(&mut self, user: &str, message: &str) {
+        let message = self.filtered(user, message);
+        if let Some(broadcast) = &mut self.broadcast {
+            broadcast(user, &message);
+        }
+        self.spectate(user, &message);
+    }
+
+    // text bound for a user, as the filter lets it through
+    fn filtered(&mut self, user: &str, text: &str) -> String {
+        match &mut self.filter {
+            Some(filter) => filter.output(user, text),
+            None => text.to_owned(),
+        }
+    }
+
+    /// Follow everything a user sees through the spectate hook, without being able to play;
+    /// returns false if the user has no game to watch
+    pub fn watch(&mut self, spectator: &str, user: &str) -> bool {
+        if !self.sessions.contains_key(user) {
+            return false;
+        }
+        let spectators = self.spectators.entry(user.to_owned()).or_default();
+        if !spectators.iter().any(|watching| watching == spectator) {
+            spectators.push(spectator.to_owned());
+        }
+        true
+    }
+
+    pub fn stop_watching(&mut self, spectator: &str, user: &str) {
+        if let Some(spectators) = self.spectators.get_mut(user) {
+            spectators.retain(|watching| watching != spectator);
+            if spectators.is_empty() {
+                self.spectators.remove(user);
+            }
+        }
+    }
+
+    fn spectate(&mut self, user: &str, output: &str) {
+        if let (Some(spectate), Some(spectators)) = (&mut self.spectate, self.spectators.get(user))
+        {
+            for spectator in spectators {
+                spectate(spectator, user, output);
+            }
+        }
+    }
+
+    /// Give a user access to debug commands; returns false if the user has no game
+    pub fn set_role(&self, user: &str, role: Role) -> bool {
+        match self.get(user) {
+            Some(cli) => {
+                cli.set_role(role);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get(&self, user: &str) -> Option<&Cli> {
+        self.sessions.get(user).map(|session| &session.cli)
+    }
+
+    pub fn contains(&self, user: &str) -> bool {
+        self.sessions.contains_key(user)
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Save and end a user's session, handing back their game
+    pub fn end(&mut self, user: &str) -> Option<Cli> {
+        // call off any offers to or from the user before their game is saved
+        let (open, trades) = self
+            .trades
+            .drain(..)
+            .partition(|trade| trade.from == user || trade.to == user);
+        self.trades = trades;
+        for trade in open {
+            self.give_back(trade);
+        }
+
+        let session = self.sessions.remove(user)?;
+        if let Some(autosave) = &mut self.autosave {
+            autosave(user, &session.cli);
+        }
+        Some(session.cli)
+    }
+
+    /// Save and end every session that has been idle past the timeout, returning their user ids
+    pub fn expire_idle(&mut self) -> Vec<String> {
+        let idle_timeout = self.idle_timeout;
+        let expired: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.last_active.elapsed() >= idle_timeout)
+            .map(|(user, _)| user.to_owned())
+            .collect();
+
+        for user in &expired {
+            self.end(user);
+        }
+        expired
+    }
+
+    /// Let real time go by in every game, returning what happens in each where anything does
+    pub fn tick(&mut self, elapsed: Duration) -> Vec<(String, String)> {
+        let events: Vec<(String, String)> = self
+            .sessions
+            .iter()
+            .map(|(user, session)| (user.to_owned(), session.cli.tick(elapsed)))
+            .filter(|(_, events)| !events.is_empty())
+            .collect();
+        events
+            .into_iter()
+            .map(|(user, events)| {
+                let events = self.filtered(&user, &events);
+                (user, events)
+            })
+            .collect()
+    }
+
+    /// Send a message to every user with a session through the broadcast hook
+    pub fn broadcast(&mut self, message: &str) {
+        if let Some(broadcast) = &mut self.broadcast {
+            for user in self.sessions.keys() {
+                let message = match &mut self.filter {
+                    Some(filter) => filter.output(user, message),
+                    None => message.to_owned(),
+                };
+                broadcast(user, &message);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SessionManager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SessionManager")
+            .field("users", &self.sessions.keys().collect::<Vec<_>>())
+            .field("idle_timeout", &self.idle_timeout)
+            .finish()
+    }
+}
+
+// how many of each item on the shared ground lie where it was dropped in one game
+fn count_ground(ground: &[Dropped], cli: &Cli) -> Vec<usize> {
+    ground
+        .iter()
+        .map(|dropped| cli.count_items(&dropped.room, dropped.item.id()))
+        .collect()
+}
@@ -0,0 +1,9 @@
+mod async_session;
+mod chat;
+mod filter;
+mod manager;
+
+pub use async_session::{AsyncGameSession, Recv};
+pub use chat::Chat;
+pub use filter::{Filter, WordList};
+pub use manager::SessionManager;
@@ -0,0 +1,98 @@
+use std::{env, fs};
+
+use crate::Cli;
+
+/// Set this environment variable to rewrite golden transcripts instead of checking them
+pub const BLESS_VAR: &str = "KINGSLAYER_BLESS";
+
+/// Play each command in a script and record it as "> command" followed by the output
+pub fn record(cli: &Cli, script: &str) -> String {
+    let mut transcript = String::new();
+    for command in script
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        transcript.push_str(&format!("> {}\n{}\n\n", command, cli.ask(command)));
+    }
+    transcript
+}
+
+/// A regression test that plays a script of commands against a world with a fixed seed
+/// and compares the result to a stored golden transcript
+#[derive(Clone, Debug)]
+pub struct Golden {
+    world: String,
+    script: String,
+    golden: String,
+    seed: u64,
+    bless: bool,
+}
+
+impl Golden {
+    /// Paths to the world file, the script with one command per line, and the golden transcript
+    pub fn new(world: &str, script: &str, golden: &str) -> Self {
+        Self {
+            world: world.to_owned(),
+            script: script.to_owned(),
+            golden: golden.to_owned(),
+            seed: 0,
+            bless: env::var_os(BLESS_VAR).is_some(),
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Write the transcript as the new golden file rather than comparing against it
+    pub fn with_bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Play the script, failing with the first line that differs from the golden transcript
+    pub fn run(&self) -> Result<(), String> {
+        let script =
+            fs::read_to_string(&self.script).map_err(|e| format!("{}: {}", self.script, e))?;
+        let cli = Cli::from_file(&self.world).map_err(|e| e.to_string())?;
+        cli.seed(self.seed);
+        let actual = record(&cli, &script);
+
+        if self.bless {
+            return fs::write(&self.golden, actual).map_err(|e| format!("{}: {}", self.golden, e));
+        }
+
+        let expected = fs::read_to_string(&self.golden)
+            .map_err(|e| format!("{}: {} (set {} to create it)", self.golden, e, BLESS_VAR))?;
+        if actual == expected {
+            return Ok(());
+        }
+        let mut expected_lines = expected.lines();
+        for (i, line) in actual.lines().enumerate() {
+            let wanted = expected_lines.next();
+            if wanted != Some(line) {
+                return Err(format!(
+                    "{} differs at line {}:\n  expected: {}\n  actual:   {}",
+                    self.golden,
+                    i + 1,
+                    wanted.unwrap_or("<end of file>"),
+                    line
+                ));
+            }
+        }
+        Err(format!(
+            "{} continues past the end of the transcript:\n  expected: {}",
+            self.golden,
+            expected_lines.next().unwrap_or_default()
+        ))
+    }
+
+    /// Run, panicking with the difference if the transcript has changed
+    pub fn assert(&self) {
+        if let Err(e) = self.run() {
+            panic!("{}", e);
+        }
+    }
+}
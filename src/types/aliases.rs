@@ -1,10 +1,28 @@
 use std::collections::HashMap;
 
-use crate::entity::{Ally, Element, Enemy, Item, Pathway, Room};
+use super::{
+    arena::{Arena, Id},
+    slot_map::{Key, SlotMap},
+    LootTable, Quest, Recipe, Region,
+};
+use crate::entity::{Ally, Element, Enemy, Item, Pathway, Pet, Room};
 
-pub type Allies = Vec<Box<Ally>>;
+pub type Allies = SlotMap<Ally>;
 pub type Elements = Vec<Box<Element>>;
-pub type Enemies = Vec<Box<Enemy>>;
-pub type Items = Vec<Box<Item>>;
+pub type Enemies = SlotMap<Enemy>;
+pub type Items = SlotMap<Item>;
+pub type LootTables = HashMap<String, LootTable>;
 pub type Paths = Vec<Box<Pathway>>;
-pub type Rooms = HashMap<String, Box<Room>>;
+pub type Pets = SlotMap<Pet>;
+pub type Quests = HashMap<String, Quest>;
+pub type Recipes = HashMap<String, Recipe>;
+pub type Regions = HashMap<String, Region>;
+// Rooms have names unique across a World, so they are kept in one Arena for all of it; Items,
+// Enemies, and NPCs are kept in a SlotMap in the Room or inventory that holds them, since
+// several can share a name
+pub type AllyId = Key<Ally>;
+pub type EnemyId = Key<Enemy>;
+pub type ItemId = Key<Item>;
+pub type PetId = Key<Pet>;
+pub type RoomId = Id<Room>;
+pub type Rooms = Arena<Room>;
@@ -0,0 +1,342 @@
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    sync::{Arc, OnceLock},
+};
+
+#[cfg(feature = "serde")]
+use serde::{
+    ser::{Error, SerializeMap},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+// A typed index into an Arena
+pub struct Id<T> {
+    index: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Id<T> {
+    const fn new(index: usize) -> Self {
+        Self {
+            index,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Id({})", self.index)
+    }
+}
+
+const CHUNK_SIZE: usize = 64;
+
+// reads an entry from its text
+#[cfg(feature = "serde")]
+type Reader<T> = fn(&str) -> ron::Result<T>;
+// without serde, entries are never held as text
+#[cfg(not(feature = "serde"))]
+type Reader<T> = fn(&str) -> T;
+
+// An entry, or the text it is read from the first time it is needed
+#[derive(Clone, Debug)]
+struct Slot<T> {
+    value: OnceLock<T>,
+    // the text the entry was read from, kept until the entry is changed
+    source: Option<Arc<str>>,
+    // why the text could not be read, if it could not, in which case the entry is left empty
+    #[cfg(feature = "serde")]
+    error: OnceLock<ron::Error>,
+}
+
+impl<T> Slot<T> {
+    fn new(entry: T) -> Self {
+        Self {
+            value: OnceLock::from(entry),
+            source: None,
+            #[cfg(feature = "serde")]
+            error: OnceLock::new(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn unread(source: Arc<str>) -> Self {
+        Self {
+            value: OnceLock::new(),
+            source: Some(source),
+            error: OnceLock::new(),
+        }
+    }
+
+    // the entry read from its text, or an empty one if there is no way to read it, noting why
+    // if the text is broken
+    #[cfg(feature = "serde")]
+    fn read(&self, read: Option<Reader<T>>) -> T
+    where
+        T: Default,
+    {
+        match (read, &self.source) {
+            (Some(read), Some(source)) => read(source).unwrap_or_else(|err| {
+                let _ = self.error.set(err);
+                T::default()
+            }),
+            _ => T::default(),
+        }
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn read(&self, _: Option<Reader<T>>) -> T
+    where
+        T: Default,
+    {
+        T::default()
+    }
+}
+
+// Storage for named entities that are addressed by typed Ids,
+// with a name index for the parser and for world files; Rooms are kept this way, their names
+// being unique across a World, and the rest in a SlotMap.
+// Entries are shared copy-on-write in fixed-size chunks, so cloning an Arena
+// only copies a pointer per chunk, and a chunk and entry are duplicated the
+// first time one of the clones changes them.
+// An Arena with a reader can also hold entries as unread text, which is read
+// the first time the entry is needed and can be put back into text to save memory.
+#[derive(Clone, Debug)]
+pub struct Arena<T> {
+    chunks: Vec<Arc<Vec<Arc<Slot<T>>>>>,
+    names: Arc<Vec<String>>,
+    index: Arc<HashMap<String, Id<T>>>,
+    read: Option<Reader<T>>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self {
+            chunks: Vec::new(),
+            names: Arc::default(),
+            index: Arc::default(),
+            read: None,
+        }
+    }
+}
+
+impl<T: Clone + Default> Arena<T> {
+    // add an entry, replacing any existing entry of the same name
+    pub fn insert(&mut self, name: &str, entry: T) -> Id<T> {
+        if let Some(id) = self.id(name) {
+            *self.get_mut(id) = entry;
+            id
+        } else {
+            let id = Id::new(self.names.len());
+            if id.index % CHUNK_SIZE == 0 {
+                self.chunks.push(Arc::new(Vec::with_capacity(CHUNK_SIZE)));
+            }
+            if let Some(chunk) = self.chunks.last_mut() {
+                Arc::make_mut(chunk).push(Arc::new(Slot::new(entry)));
+            }
+            Arc::make_mut(&mut self.names).push(name.to_owned());
+            Arc::make_mut(&mut self.index).insert(name.to_owned(), id);
+            id
+        }
+    }
+
+    pub fn get_mut(&mut self, id: Id<T>) -> &mut T {
+        let read = self.read;
+        let chunk = Arc::make_mut(&mut self.chunks[id.index / CHUNK_SIZE]);
+        let slot = Arc::make_mut(&mut chunk[id.index % CHUNK_SIZE]);
+        if slot.value.get().is_none() {
+            let _ = slot.value.set(slot.read(read));
+        }
+        // once changed, the entry no longer matches the text it was read from
+        slot.source = None;
+        slot.value.get_mut().expect("The entry was read above")
+    }
+
+    // read entries from their text the first time each is needed; an entry whose text is
+    // broken is read as an empty one, and why is kept with it
+    #[cfg(feature = "serde")]
+    pub fn read_with(&mut self, read: Reader<T>) {
+        self.read = Some(read);
+    }
+
+    // hold an entry as text to be read when it is first needed
+    #[cfg(feature = "serde")]
+    pub fn set_source(&mut self, id: Id<T>, source: &str) {
+        let chunk = Arc::make_mut(&mut self.chunks[id.index / CHUNK_SIZE]);
+        chunk[id.index % CHUNK_SIZE] = Arc::new(Slot::unread(source.into()));
+    }
+
+    // put an entry that has been read back into text, writing it out again if it has changed;
+    // copies of the Arena that share the entry keep it as it is
+    #[cfg(feature = "serde")]
+    pub fn unread(&mut self, id: Id<T>, write: impl FnOnce(&T) -> Option<String>) {
+        let slot = &self.chunks[id.index / CHUNK_SIZE][id.index % CHUNK_SIZE];
+        let source = match (&slot.source, slot.value.get()) {
+            (_, None) => return,
+            (Some(source), Some(_)) => Arc::clone(source),
+            (None, Some(entry)) => match write(entry) {
+                Some(source) => source.into(),
+                None => return,
+            },
+        };
+        let chunk = Arc::make_mut(&mut self.chunks[id.index / CHUNK_SIZE]);
+        chunk[id.index % CHUNK_SIZE] = Arc::new(Slot::unread(source));
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn id(&self, name: &str) -> Option<Id<T>> {
+        self.index.get(name).copied()
+    }
+
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    pub fn name(&self, id: Id<T>) -> &str {
+        &self.names[id.index]
+    }
+}
+
+impl<T: Default> Arena<T> {
+    #[cfg_attr(all(feature = "parallel", not(feature = "serde")), allow(dead_code))]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.chunks
+            .iter()
+            .flat_map(move |chunk| chunk.iter().map(move |slot| self.entry(slot)))
+    }
+
+    // every entry with the name it is stored under
+    pub fn iter_named(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.names.iter().map(String::as_str).zip(self.iter())
+    }
+
+    pub fn get(&self, id: Id<T>) -> &T {
+        self.entry(&self.chunks[id.index / CHUNK_SIZE][id.index % CHUNK_SIZE])
+    }
+
+    // an entry that is read here stays read, for callers that only borrow the Arena cannot
+    // put it back; only `unread` bounds how many entries are held read
+    fn entry<'a>(&self, slot: &'a Slot<T>) -> &'a T {
+        slot.value.get_or_init(|| slot.read(self.read))
+    }
+
+    // whether entries can be held as text to be read when first needed
+    #[cfg(feature = "serde")]
+    pub fn reads_lazily(&self) -> bool {
+        self.read.is_some()
+    }
+
+    // why an entry's text could not be read, once it has been tried
+    #[cfg(feature = "serde")]
+    pub fn error(&self, id: Id<T>) -> Option<&ron::Error> {
+        self.chunks[id.index / CHUNK_SIZE][id.index % CHUNK_SIZE]
+            .error
+            .get()
+    }
+
+    // every entry tried so far whose text could not be read, with why
+    #[cfg(feature = "serde")]
+    pub fn errors(&self) -> impl Iterator<Item = (&str, &ron::Error)> {
+        let slots = self.chunks.iter().flat_map(|chunk| chunk.iter());
+        self.names
+            .iter()
+            .zip(slots)
+            .filter_map(|(name, slot)| Some((name.as_str(), slot.error.get()?)))
+    }
+
+    // every entry no longer shared with an earlier clone of this Arena, which are the ones
+    // added or changed since it was taken
+    #[cfg(feature = "sqlite")]
+    pub fn changed_since<'a>(
+        &'a self,
+        earlier: &'a Self,
+    ) -> impl Iterator<Item = (&'a str, &'a T)> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| {
+                let (chunk, entry) = (index / CHUNK_SIZE, index % CHUNK_SIZE);
+                match earlier.chunks.get(chunk) {
+                    Some(old) if Arc::ptr_eq(old, &self.chunks[chunk]) => false,
+                    Some(old) => old
+                        .get(entry)
+                        .is_none_or(|old| !Arc::ptr_eq(old, &self.chunks[chunk][entry])),
+                    None => true,
+                }
+            })
+            .map(move |(index, name)| (name.as_str(), self.get(Id::new(index))))
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: Default + Send + Sync> Arena<T> {
+    pub fn par_iter(&self) -> impl rayon::iter::ParallelIterator<Item = &T> {
+        use rayon::prelude::*;
+        self.chunks
+            .par_iter()
+            .flat_map_iter(move |chunk| chunk.iter().map(move |slot| self.entry(slot)))
+    }
+}
+
+// entries that have not been read are read for the moment they are written, and left unread;
+// an entry whose text is broken cannot be written
+#[cfg(feature = "serde")]
+impl<T: Default + Serialize> Serialize for Arena<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.names.len()))?;
+        let slots = self.chunks.iter().flat_map(|chunk| chunk.iter());
+        for (name, slot) in self.names.iter().zip(slots) {
+            let unread;
+            let entry = match slot.value.get() {
+                Some(entry) => entry,
+                None => {
+                    unread = slot.read(self.read);
+                    &unread
+                }
+            };
+            if let Some(err) = slot.error.get() {
+                return Err(S::Error::custom(format!("{}: {}", name, err)));
+            }
+            map.serialize_entry(name, entry)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Clone + Default + Deserialize<'de>> Deserialize<'de> for Arena<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = BTreeMap::<String, T>::deserialize(deserializer)?;
+        let mut arena = Arena::default();
+        for (name, entry) in entries {
+            arena.insert(&name, entry);
+        }
+        Ok(arena)
+    }
+}
@@ -1,7 +1,13 @@
-#[derive(Debug, Default)]
+use super::DamageType;
+
+#[derive(Clone, Debug, Default)]
 pub struct Attack {
     weapon_name: String,
     damage: Option<u32>,
+    area: bool,
+    damage_type: Option<DamageType>,
+    target_part: Option<String>,
+    sound: Option<String>,
 }
 
 impl Attack {
@@ -12,9 +18,51 @@ impl Attack {
         Self {
             weapon_name: weapon_name.into(),
             damage,
+            area: false,
+            damage_type: None,
+            target_part: None,
+            sound: None,
         }
     }
 
+    pub fn with_sound(mut self, sound: Option<&str>) -> Self {
+        self.sound = sound.map(str::to_owned);
+        self
+    }
+
+    pub fn sound(&self) -> Option<&str> {
+        self.sound.as_deref()
+    }
+
+    // an area attack hits every Enemy sharing the target's name
+    pub fn with_area(mut self, area: bool) -> Self {
+        self.area = area;
+        self
+    }
+
+    pub fn is_area(&self) -> bool {
+        self.area
+    }
+
+    pub fn with_damage_type(mut self, damage_type: Option<DamageType>) -> Self {
+        self.damage_type = damage_type;
+        self
+    }
+
+    pub fn damage_type(&self) -> Option<DamageType> {
+        self.damage_type
+    }
+
+    // aim the attack at a specific part of the Enemy
+    pub fn with_target_part(mut self, part: Option<&str>) -> Self {
+        self.target_part = part.map(str::to_owned);
+        self
+    }
+
+    pub fn target_part(&self) -> Option<&str> {
+        self.target_part.as_deref()
+    }
+
     pub fn damage(&self) -> Option<u32> {
         self.damage
     }
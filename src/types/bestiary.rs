@@ -0,0 +1,84 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Every kind of Enemy the player has met, and how many of each they have killed
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Bestiary {
+    kills: Vec<(String, u32)>,
+    seen: Codex,
+}
+
+impl Bestiary {
+    pub fn record(&mut self, enemy_name: &str) {
+        if let Some((_, kills)) = self.kills.iter_mut().find(|(name, _)| name == enemy_name) {
+            *kills += 1;
+        } else {
+            self.kills.push((enemy_name.to_owned(), 1));
+        }
+    }
+
+    pub fn kills(&self, enemy_name: &str) -> u32 {
+        self.kills
+            .iter()
+            .find(|(name, _)| name == enemy_name)
+            .map_or(0, |(_, kills)| *kills)
+    }
+
+    pub fn discover(&mut self, enemy_name: &str, desc: &str) {
+        self.seen.discover(enemy_name, desc);
+    }
+
+    // met enemies with their kill counts, then "???" for each kind still unmet
+    pub fn list(&self, all: &[String]) -> String {
+        self.seen
+            .list_with("Bestiary", all, |name| match self.kills(name) {
+                0 => String::new(),
+                kills => format!(" ({} killed)", kills),
+            })
+    }
+}
+
+// Every kind of Item the player has come across
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Codex {
+    seen: Vec<(String, String)>,
+}
+
+impl Codex {
+    pub fn discover(&mut self, name: &str, desc: &str) {
+        if !self.is_known(name) {
+            self.seen.push((name.to_owned(), desc.to_owned()));
+        }
+    }
+
+    pub fn is_known(&self, name: &str) -> bool {
+        self.seen.iter().any(|(seen, _)| seen == name)
+    }
+
+    pub fn list(&self, all: &[String]) -> String {
+        self.list_with("Codex", all, |_| String::new())
+    }
+
+    fn list_with<F>(&self, title: &str, all: &[String], note: F) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        let mut list = format!("{}:", title);
+        for (name, desc) in &self.seen {
+            list.push_str(&format!("\n  {}{}: {}", name, note(name), desc));
+        }
+        for name in all {
+            if !self.is_known(name) {
+                list.push_str("\n  ???");
+            }
+        }
+        if list == format!("{}:", title) {
+            list.push_str("\n  (empty)");
+        }
+        list
+    }
+}
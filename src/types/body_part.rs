@@ -0,0 +1,47 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum PartEffect {
+    Stun,
+    Slow,
+}
+
+// A part of an Enemy that can be aimed at for a different chance to hit and a special effect
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct BodyPart {
+    name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hit_modifier: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    effect: Option<PartEffect>,
+}
+
+impl BodyPart {
+    pub fn new(name: &str, hit_modifier: i32) -> Self {
+        Self {
+            name: name.to_owned(),
+            hit_modifier,
+            effect: None,
+        }
+    }
+
+    pub fn with_effect(mut self, effect: PartEffect) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub const fn hit_modifier(&self) -> i32 {
+        self.hit_modifier
+    }
+
+    pub const fn effect(&self) -> Option<PartEffect> {
+        self.effect
+    }
+}
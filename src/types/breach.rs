@@ -0,0 +1,69 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{Durability, Pathway};
+
+// A floor or ceiling that, once broken, opens a new path into the Room beyond it;
+// a dropped ladder leads back, otherwise the way through is a one-way fall
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Breach {
+    reply: String,
+    directions: Vec<String>,
+    target: String,
+    // how the new path is described once it opens
+    desc: String,
+    durability: Durability,
+    // the directions of a ladder leading back from the Room beyond
+    #[cfg_attr(feature = "serde", serde(default))]
+    ladder: Vec<String>,
+}
+
+impl Breach {
+    pub fn new(reply: &str, directions: &[&str], target: &str, desc: &str, hits: u32) -> Self {
+        Self {
+            reply: reply.to_owned(),
+            directions: directions.iter().map(|dir| (*dir).to_owned()).collect(),
+            target: target.to_owned(),
+            desc: desc.to_owned(),
+            durability: Durability::Durable(hits),
+            ladder: Vec::new(),
+        }
+    }
+
+    pub fn with_ladder(mut self, directions: &[&str]) -> Self {
+        self.ladder = directions.iter().map(|dir| (*dir).to_owned()).collect();
+        self
+    }
+
+    pub fn reply(&self) -> &str {
+        &self.reply
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rename_target(&mut self, rename: &impl Fn(&mut String)) {
+        rename(&mut self.target);
+    }
+
+    // true once the blow breaks it
+    pub fn strike(&mut self, damage: u32) -> bool {
+        self.durability.wear(damage)
+    }
+
+    pub fn path(&self) -> Pathway {
+        let directions: Vec<&str> = self.directions.iter().map(String::as_str).collect();
+        Pathway::new(&directions, &self.target, &self.desc, &self.desc)
+    }
+
+    // the way back up or down from the Room beyond, if a ladder was dropped
+    pub fn ladder(&self, from: &str) -> Option<Pathway> {
+        let direction = self.ladder.first()?;
+        let directions: Vec<&str> = self.ladder.iter().map(String::as_str).collect();
+        let desc = format!("A ladder leads {}.", direction);
+        Some(Pathway::new(&directions, from, &desc, &desc))
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+fn default_day_length() -> u32 {
+    24
+}
+
+// Something that happens in the story on a set day, like an eclipse that opens the crypt
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StoryEvent {
+    day: u32,
+    text: String,
+    // the Room and the path in it that swings open
+    #[cfg_attr(feature = "serde", serde(default))]
+    opens: Option<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    happened: bool,
+}
+
+impl StoryEvent {
+    pub fn new(day: u32, text: &str) -> Self {
+        Self {
+            day,
+            text: text.to_owned(),
+            opens: None,
+            happened: false,
+        }
+    }
+
+    pub fn with_opens(mut self, room: &str, direction: &str) -> Self {
+        self.opens = Some((room.to_owned(), direction.to_owned()));
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn opens(&self) -> Option<(&str, &str)> {
+        self.opens
+            .as_ref()
+            .map(|(room, direction)| (room.as_str(), direction.as_str()))
+    }
+}
+
+// Days counted off the World's clock, starting on day 1, with names for the days of the week,
+// festivals, and story events that happen on the day they fall on
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Calendar {
+    // how many turns make a day
+    #[cfg_attr(feature = "serde", serde(default = "default_day_length"))]
+    day_length: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    weekdays: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    festivals: Vec<(u32, String)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    events: Vec<StoryEvent>,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self {
+            day_length: default_day_length(),
+            weekdays: Vec::new(),
+            festivals: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+impl Calendar {
+    pub fn new(day_length: u32) -> Self {
+        Self {
+            day_length: day_length.max(1),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_weekdays(mut self, weekdays: &[&str]) -> Self {
+        self.weekdays = weekdays.iter().map(|&day| day.to_owned()).collect();
+        self
+    }
+
+    pub fn with_festival(mut self, day: u32, name: &str) -> Self {
+        self.festivals.push((day, name.to_owned()));
+        self
+    }
+
+    pub fn with_event(mut self, event: StoryEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    pub fn day(&self, clock: u32) -> u32 {
+        clock / self.day_length.max(1) + 1
+    }
+
+    // "It is Moonday, day 3. Today is the Feast of Lanterns."
+    pub fn date(&self, clock: u32) -> String {
+        let day = self.day(clock);
+        let mut date = match self.weekdays.len() {
+            0 => format!("It is day {}.", day),
+            len => format!(
+                "It is {}, day {}.",
+                self.weekdays[(day as usize - 1) % len],
+                day
+            ),
+        };
+        for (_, festival) in self.festivals.iter().filter(|(on, _)| *on == day) {
+            date.push_str(&format!(" Today is the {}.", festival));
+        }
+        date
+    }
+
+    // the story events whose day has come, each only the once
+    pub fn take_due(&mut self, clock: u32) -> Vec<StoryEvent> {
+        let day = self.day(clock);
+        let mut due = Vec::new();
+        for event in self.events.iter_mut() {
+            if !event.happened && event.day <= day {
+                event.happened = true;
+                due.push(event.clone());
+            }
+        }
+        due
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Ability, Deed, Karma, Memory};
+use crate::entity::{Ally, Item};
+
+// How the player tries to sway someone in conversation
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Approach {
+    Persuade,
+    Intimidate,
+    Deceive,
+}
+
+impl Approach {
+    // threats lean on strength, while charm and lies lean on charisma
+    pub const fn ability(self) -> Ability {
+        match self {
+            Self::Intimidate => Ability::Strength,
+            Self::Persuade | Self::Deceive => Ability::Charisma,
+        }
+    }
+}
+
+impl fmt::Display for Approach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Persuade => "Persuade",
+            Self::Intimidate => "Intimidate",
+            Self::Deceive => "Deceive",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// What comes of a check once the roll is made
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Consequence {
+    // the speaker hands something over, like gold, a key, or a discount token
+    Gift(Box<Item>),
+    // every Enemy in the Room stands down
+    Calm,
+    // every Enemy in the Room attacks
+    Provoke,
+    // the choice weighs on the player's karma, for good or ill
+    Karma(i32),
+    // someone turns up in the Room, like a travelling merchant
+    Arrive(Box<Ally>),
+    // the path in this direction from the Room swings open
+    Open(String),
+}
+
+// A dialogue option that can only be tried once, rolled against one of the player's abilities
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Check {
+    approach: Approach,
+    topic: String,
+    dc: i32,
+    success: String,
+    failure: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rewards: Vec<Consequence>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    penalties: Vec<Consequence>,
+    // the option is only offered to a player whose karma falls within this
+    #[cfg_attr(feature = "serde", serde(default))]
+    karma: Option<Karma>,
+    // the option is only offered by someone who remembers this of the player
+    #[cfg_attr(feature = "serde", serde(default))]
+    recalls: Option<Deed>,
+}
+
+impl Check {
+    pub fn new(approach: Approach, topic: &str, dc: i32, success: &str, failure: &str) -> Self {
+        Self {
+            approach,
+            topic: topic.to_owned(),
+            dc,
+            success: success.to_owned(),
+            failure: failure.to_owned(),
+            rewards: Vec::new(),
+            penalties: Vec::new(),
+            karma: None,
+            recalls: None,
+        }
+    }
+
+    pub fn with_reward(mut self, reward: Consequence) -> Self {
+        self.rewards.push(reward);
+        self
+    }
+
+    pub fn with_penalty(mut self, penalty: Consequence) -> Self {
+        self.penalties.push(penalty);
+        self
+    }
+
+    pub fn with_karma(mut self, karma: Karma) -> Self {
+        self.karma = Some(karma);
+        self
+    }
+
+    pub fn with_recalls(mut self, deed: Deed) -> Self {
+        self.recalls = Some(deed);
+        self
+    }
+
+    // whether the option is offered to a player with this karma by someone with this memory
+    pub fn is_offered(&self, karma: i32, memory: &Memory) -> bool {
+        self.karma.as_ref().is_none_or(|bound| bound.allows(karma))
+            && self.recalls.is_none_or(|deed| memory.recalls(deed))
+    }
+
+    pub const fn approach(&self) -> Approach {
+        self.approach
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    pub const fn dc(&self) -> i32 {
+        self.dc
+    }
+
+    // the visible tag listed beside the topic, like "[Persuade DC 12] the toll"
+    pub fn label(&self) -> String {
+        format!("[{} DC {}] {}", self.approach, self.dc, self.topic)
+    }
+
+    // the reply and what follows from it, given whether the roll beat the difficulty
+    pub fn outcome(&self, passed: bool) -> (&str, &[Consequence]) {
+        if passed {
+            (&self.success, &self.rewards)
+        } else {
+            (&self.failure, &self.penalties)
+        }
+    }
+}
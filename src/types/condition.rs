@@ -0,0 +1,27 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Deed;
+
+// What must hold in a Room for one of its hidden paths to appear
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Condition {
+    // no Enemies are left in the Room
+    Cleared,
+    // an Item with this name lies in the Room, like a gem set down on an altar
+    Item(String),
+    // the path with this name in the Room stands open
+    Open(String),
+    // the Ally or Enemy with this name here remembers this of the player
+    Remembers(String, Deed),
+}
+
+impl Condition {
+    #[cfg(feature = "serde")]
+    pub fn rename_item(&mut self, rename: &impl Fn(&mut String)) {
+        if let Self::Item(name) = self {
+            rename(name);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Action, Check, CmdResult, Prompt};
+use crate::input::{fold, same_name};
+
+// Where the next line of input goes; with no context on the stack it goes to the normal parser
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputContext {
+    Conversation(Conversation),
+    Question(Prompt),
+    // the next line answers the riddle of the named Element
+    Riddle(String),
+}
+
+// Talking with an Ally, where input picks a topic by name or number until the player leaves;
+// `with` is how the Ally is referred to, as in "the squire" or "Gandalf", and `speaker` is the
+// name it is found by in its Room
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Conversation {
+    with: String,
+    topics: Vec<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    speaker: String,
+    // skill-checked options, numbered after the topics
+    #[cfg_attr(feature = "serde", serde(default))]
+    checks: Vec<Check>,
+}
+
+impl Conversation {
+    pub fn new(with: &str, topics: &[(String, String)]) -> Self {
+        Self {
+            with: with.to_owned(),
+            topics: topics.to_vec(),
+            speaker: String::new(),
+            checks: Vec::new(),
+        }
+    }
+
+    pub fn with_checks(mut self, speaker: &str, checks: &[Check]) -> Self {
+        self.speaker = speaker.to_owned();
+        self.checks = checks.to_vec();
+        self
+    }
+
+    pub fn with(&self) -> &str {
+        &self.with
+    }
+
+    pub fn speaker(&self) -> &str {
+        &self.speaker
+    }
+
+    // list the topics that can be brought up, then the checks with their difficulty
+    pub fn topics(&self) -> String {
+        self.topics
+            .iter()
+            .map(|(topic, _)| topic.to_owned())
+            .chain(self.checks.iter().map(Check::label))
+            .enumerate()
+            .fold(
+                format!("You can ask {} about:", self.with),
+                |res, (i, topic)| format!("{}\n  {}. {}", res, i + 1, topic),
+            )
+            + "\n(bye to leave)"
+    }
+
+    // the check picked by its number or topic, which is used up once picked
+    pub fn take_check(&mut self, input: &str) -> Option<Check> {
+        let input = fold(input.trim());
+        let pos = if let Ok(num) = input.parse::<usize>() {
+            num.checked_sub(self.topics.len() + 1)
+                .filter(|pos| *pos < self.checks.len())
+        } else {
+            self.checks
+                .iter()
+                .position(|check| same_name(check.topic(), &input))
+        }?;
+        Some(self.checks.remove(pos))
+    }
+
+    pub fn reply(&self, input: &str) -> Option<&str> {
+        let input = fold(input.trim());
+        let topic = if let Ok(num) = input.parse::<usize>() {
+            num.checked_sub(1).and_then(|i| self.topics.get(i))
+        } else {
+            self.topics
+                .iter()
+                .find(|(topic, _)| same_name(topic, &input))
+        };
+        topic.map(|(_, reply)| reply.as_str())
+    }
+
+    // returns the result and whether the conversation is still going
+    pub fn respond(&self, input: &str) -> (CmdResult, bool) {
+        match fold(input.trim()).as_str() {
+            "bye" | "goodbye" | "leave" => (
+                CmdResult::new(
+                    Action::Passive,
+                    format!("You take your leave of {}.", self.with),
+                ),
+                false,
+            ),
+            _ => {
+                if let Some(reply) = self.reply(input) {
+                    (CmdResult::new(Action::Passive, reply.to_owned()), true)
+                } else {
+                    (CmdResult::new(Action::Failed, self.topics()), true)
+                }
+            }
+        }
+    }
+}
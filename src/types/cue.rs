@@ -0,0 +1,23 @@
+use super::Rumble;
+
+// An audio or haptic event for front-ends to play; the engine itself never makes a sound
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cue {
+    // a one-off effect, like a sword hitting or a door creaking
+    Sound(String),
+    // a theme that should play from now on, until the next Music cue
+    Music(String),
+    // the player has crossed into the named region, for changing the ambience
+    Region(String),
+    // a hint for controllers to rumble on big hits, deaths, and explosions
+    Rumble(Rumble),
+}
+
+impl Cue {
+    pub fn id(&self) -> &str {
+        match self {
+            Cue::Sound(id) | Cue::Music(id) | Cue::Region(id) => id,
+            Cue::Rumble(rumble) => rumble.name(),
+        }
+    }
+}
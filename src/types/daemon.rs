@@ -0,0 +1,96 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Something a carried Thing does on its own as turns pass
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Daemon {
+    // says the next of its lines every few turns, like a cursed ring whispering
+    Whisper {
+        every: u32,
+        lines: Vec<String>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        waited: u32,
+        #[cfg_attr(feature = "serde", serde(default))]
+        said: usize,
+    },
+    // counts down the turns it is carried, then goes off with its text, hurting the player
+    // for the damage and leaving nothing behind, like a lit bomb
+    Countdown {
+        turns: u32,
+        text: String,
+        #[cfg_attr(feature = "serde", serde(default))]
+        damage: u32,
+    },
+    // points the way toward a Room after every move, like a lodestone compass
+    Compass(String),
+}
+
+// What a Daemon does on a turn, for the game to tell of
+#[derive(Clone, Debug, PartialEq)]
+pub enum Stir {
+    Say(String),
+    // turns left on a countdown
+    Count(u32),
+    // the goal of a compass
+    Point(String),
+    // the text and damage of a countdown that has gone off
+    GoOff(String, u32),
+}
+
+impl Daemon {
+    pub fn whisper(every: u32, lines: &[&str]) -> Self {
+        Self::Whisper {
+            every: every.max(1),
+            lines: lines.iter().map(|line| (*line).to_owned()).collect(),
+            waited: 0,
+            said: 0,
+        }
+    }
+
+    pub fn countdown(turns: u32, text: &str, damage: u32) -> Self {
+        Self::Countdown {
+            turns,
+            text: text.to_owned(),
+            damage,
+        }
+    }
+
+    pub fn compass(room: &str) -> Self {
+        Self::Compass(room.to_owned())
+    }
+
+    // let turns pass, saying what it does, if anything
+    pub fn stir(&mut self, turns: u32) -> Option<Stir> {
+        match self {
+            Self::Whisper {
+                every,
+                lines,
+                waited,
+                said,
+            } => {
+                *waited += turns;
+                if *waited < *every || lines.is_empty() {
+                    return None;
+                }
+                *waited %= (*every).max(1);
+                let line = lines[*said % lines.len()].clone();
+                *said += 1;
+                Some(Stir::Say(line))
+            }
+            Self::Countdown {
+                turns: left,
+                text,
+                damage,
+            } => {
+                *left = left.saturating_sub(turns);
+                if *left == 0 {
+                    Some(Stir::GoOff(text.clone(), *damage))
+                } else {
+                    Some(Stir::Count(*left))
+                }
+            }
+            Self::Compass(room) => Some(Stir::Point(room.clone())),
+        }
+    }
+}
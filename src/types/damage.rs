@@ -0,0 +1,29 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum DamageType {
+    Bludgeoning,
+    Slashing,
+    Piercing,
+    Fire,
+    Ice,
+    Holy,
+}
+
+impl fmt::Display for DamageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Bludgeoning => "bludgeoning",
+            Self::Slashing => "slashing",
+            Self::Piercing => "piercing",
+            Self::Fire => "fire",
+            Self::Ice => "ice",
+            Self::Holy => "holy",
+        };
+        write!(f, "{}", s)
+    }
+}
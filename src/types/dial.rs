@@ -0,0 +1,75 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// A combination lock worked one number at a time or all at once, which opens a way once the
+// numbers entered match its combination; a wrong combination spins it back to the start
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Dial {
+    combination: Vec<u32>,
+    reply: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    opens: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    entered: Vec<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    open: bool,
+}
+
+impl Dial {
+    pub fn new(combination: &[u32], reply: &str) -> Self {
+        Self {
+            combination: combination.to_vec(),
+            reply: reply.to_owned(),
+            opens: None,
+            entered: Vec::new(),
+            open: false,
+        }
+    }
+
+    pub fn with_opens(mut self, direction: &str) -> Self {
+        self.opens = Some(direction.to_owned());
+        self
+    }
+
+    pub fn opens(&self) -> Option<&str> {
+        self.opens.as_deref()
+    }
+
+    pub const fn is_open(&self) -> bool {
+        self.open
+    }
+
+    // what the dial reads so far, like "7-2"
+    pub fn reading(&self) -> String {
+        self.entered
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    pub fn reset(&mut self) {
+        self.entered.clear();
+    }
+
+    pub fn reply(&self) -> &str {
+        &self.reply
+    }
+
+    // turn to each number in order, returning whether the lock opened once a whole combination
+    // has been entered, a wrong one spinning the dial back to the start
+    pub fn turn(&mut self, numbers: &[u32]) -> Option<bool> {
+        for number in numbers {
+            self.entered.push(*number);
+            if self.entered.len() == self.combination.len() {
+                self.open = self.entered == self.combination;
+                if !self.open {
+                    self.entered.clear();
+                }
+                return Some(self.open);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// The way a path leads. The compass points, up and down, and in and out are known by name and
+// abbreviation and each has an opposite; anything else, like "vestry door", is kept as written.
+// World files write each as a plain string like "north" or "n"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(from = "String", into = "String"))]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    Northeast,
+    Northwest,
+    Southeast,
+    Southwest,
+    Up,
+    Down,
+    In,
+    Out,
+    Custom(String),
+}
+
+impl Direction {
+    // every direction known by name, for looking up words and suggesting fixes for typos
+    pub const STANDARD: [Direction; 12] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+        Direction::Northeast,
+        Direction::Northwest,
+        Direction::Southeast,
+        Direction::Southwest,
+        Direction::Up,
+        Direction::Down,
+        Direction::In,
+        Direction::Out,
+    ];
+
+    // a direction from its name or abbreviation, like "north" or "n", in any case
+    pub fn parse(word: &str) -> Self {
+        let lower = word.trim().to_lowercase();
+        Self::STANDARD
+            .iter()
+            .find(|dir| dir.name() == lower || dir.abbreviation() == lower)
+            .cloned()
+            .unwrap_or_else(|| Self::Custom(word.to_owned()))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::North => "north",
+            Self::South => "south",
+            Self::East => "east",
+            Self::West => "west",
+            Self::Northeast => "northeast",
+            Self::Northwest => "northwest",
+            Self::Southeast => "southeast",
+            Self::Southwest => "southwest",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::In => "in",
+            Self::Out => "out",
+            Self::Custom(name) => name,
+        }
+    }
+
+    // the short form players type and map tools write, like "ne"
+    pub fn abbreviation(&self) -> &str {
+        match self {
+            Self::North => "n",
+            Self::South => "s",
+            Self::East => "e",
+            Self::West => "w",
+            Self::Northeast => "ne",
+            Self::Northwest => "nw",
+            Self::Southeast => "se",
+            Self::Southwest => "sw",
+            Self::Up => "u",
+            Self::Down => "d",
+            _ => self.name(),
+        }
+    }
+
+    pub fn opposite(&self) -> Option<Self> {
+        Some(match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+            Self::Northeast => Self::Southwest,
+            Self::Northwest => Self::Southeast,
+            Self::Southeast => Self::Northwest,
+            Self::Southwest => Self::Northeast,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::In => Self::Out,
+            Self::Out => Self::In,
+            Self::Custom(_) => return None,
+        })
+    }
+
+    // one of the eight points of the compass
+    pub fn is_compass(&self) -> bool {
+        !matches!(
+            self,
+            Self::Up | Self::Down | Self::In | Self::Out | Self::Custom(_)
+        )
+    }
+
+    // a way the player can go just by typing it, like "north" or "d"
+    pub fn is_bare(&self) -> bool {
+        self.is_compass() || matches!(self, Self::Up | Self::Down)
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+}
+
+impl From<String> for Direction {
+    fn from(word: String) -> Self {
+        Self::parse(&word)
+    }
+}
+
+impl From<Direction> for String {
+    fn from(direction: Direction) -> Self {
+        direction.name().to_owned()
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
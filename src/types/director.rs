@@ -0,0 +1,83 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// how many turns of HP the Director looks back over to tell whether the player is losing ground
+const HP_WINDOW: usize = 5;
+
+fn default_calm() -> u32 {
+    5
+}
+
+fn default_step() -> u32 {
+    2
+}
+
+// Keeps a run tense by nudging the chance of wandering and waylaying Enemies up the longer the
+// player goes without a fight, and down while their HP is falling, never past the bounds the
+// author set; a chance of 0 is left alone, so nothing turns up where the author ruled it out
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Director {
+    // the most percentage points taken off or added to any chance
+    #[cfg_attr(feature = "serde", serde(default))]
+    most_lowered: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    most_raised: u32,
+    // turns without a fight before chances start to rise, and the points added each turn after
+    #[cfg_attr(feature = "serde", serde(default = "default_calm"))]
+    calm: u32,
+    #[cfg_attr(feature = "serde", serde(default = "default_step"))]
+    step: u32,
+    // turns since the last fight
+    #[cfg_attr(feature = "serde", serde(default))]
+    quiet: u32,
+    // the player's HP as a percent of the most they can have, oldest first
+    #[cfg_attr(feature = "serde", serde(default))]
+    hp: Vec<i32>,
+}
+
+impl Director {
+    pub fn new(most_lowered: u32, most_raised: u32) -> Self {
+        Self {
+            most_lowered,
+            most_raised,
+            calm: default_calm(),
+            step: default_step(),
+            quiet: 0,
+            hp: Vec::new(),
+        }
+    }
+
+    pub fn with_calm(mut self, turns: u32, step: u32) -> Self {
+        self.calm = turns;
+        self.step = step;
+        self
+    }
+
+    // note how a turn went: whether there was a fight, and the HP the player was left with
+    pub fn pace(&mut self, fought: bool, hp: i32, hp_cap: u32) {
+        self.quiet = if fought { 0 } else { self.quiet + 1 };
+        if self.hp.len() > HP_WINDOW {
+            self.hp.remove(0);
+        }
+        self.hp.push(hp.max(0) * 100 / hp_cap.max(1) as i32);
+    }
+
+    // the percentage points to add to every chance now, which are negative to take them off
+    pub fn nudge(&self) -> i32 {
+        let rise = self.quiet.saturating_sub(self.calm) * self.step;
+        let fall = match (self.hp.first(), self.hp.last()) {
+            (Some(first), Some(last)) => (first - last).max(0),
+            _ => 0,
+        };
+        (rise.min(self.most_raised) as i32 - fall).max(-(self.most_lowered as i32))
+    }
+
+    // a percent chance set by the author, nudged for the pace of the run
+    pub fn adjust(&self, chance: u32) -> u32 {
+        if chance == 0 {
+            return 0;
+        }
+        (chance as i32 + self.nudge()).clamp(0, 100) as u32
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::entity::Enemy;
+
+// An Enemy that may waylay the player each turn of a long journey along a path
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Encounter {
+    // the percent chance on each turn of the way
+    chance: u32,
+    enemy: Enemy,
+}
+
+impl Encounter {
+    pub fn new(chance: u32, enemy: Enemy) -> Self {
+        Self { chance, enemy }
+    }
+
+    pub const fn chance(&self) -> u32 {
+        self.chance
+    }
+
+    pub fn enemy(&self) -> &Enemy {
+        &self.enemy
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+// A problem found by linting a world, printed as tab-separated severity, code, and message
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Finding {
+    severity: Severity,
+    code: &'static str,
+    message: String,
+}
+
+impl Finding {
+    pub fn error(code: &'static str, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            code,
+            message,
+        }
+    }
+
+    pub fn warning(code: &'static str, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            code,
+            message,
+        }
+    }
+
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub const fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}\t{}\t{}", severity, self.code, self.message)
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// How the player can occupy an Element: on a chair or bed, or in a cage or wardrobe
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Furniture {
+    On,
+    In,
+}
+
+impl Furniture {
+    pub const fn prep(self) -> &'static str {
+        match self {
+            Furniture::On => "on",
+            Furniture::In => "in",
+        }
+    }
+
+    pub const fn leave(self) -> &'static str {
+        match self {
+            Furniture::On => "off",
+            Furniture::In => "out of",
+        }
+    }
+}
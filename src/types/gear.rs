@@ -0,0 +1,12 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// What a Thing is good for when making camp
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Gear {
+    // a shelter to sleep in, packed up again on leaving
+    Tent,
+    // burned for a campfire to cook over
+    Firewood,
+}
@@ -0,0 +1,47 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Sentries posted on a path who let through only members of a faction, or anyone who can pass
+// for one in a disguise
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Guard {
+    faction: String,
+    // the Charisma check a disguise must pass to fool them
+    dc: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    refusal: Option<String>,
+}
+
+impl Guard {
+    pub fn new(faction: &str, dc: i32) -> Self {
+        Self {
+            faction: faction.to_owned(),
+            dc,
+            refusal: None,
+        }
+    }
+
+    // what the guards say as they turn away someone they do not let through
+    pub fn with_refusal(mut self, refusal: &str) -> Self {
+        self.refusal = Some(refusal.to_owned());
+        self
+    }
+
+    pub fn faction(&self) -> &str {
+        &self.faction
+    }
+
+    pub const fn dc(&self) -> i32 {
+        self.dc
+    }
+
+    pub fn refusal(&self) -> String {
+        self.refusal.clone().unwrap_or_else(|| {
+            format!(
+                "The guards bar your way. Only the {} may pass.",
+                self.faction
+            )
+        })
+    }
+}
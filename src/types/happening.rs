@@ -0,0 +1,124 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Condition, Consequence};
+use crate::{dice_roll, weighted_pick};
+
+fn default_weight() -> u32 {
+    1
+}
+
+// Something that may happen while the player is out in a region, like a travelling merchant
+// turning up or an earthquake opening a passage, with what follows from it
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Happening {
+    text: String,
+    #[cfg_attr(feature = "serde", serde(default = "default_weight"))]
+    weight: u32,
+    // the regions it can happen in, or any region when empty
+    #[cfg_attr(feature = "serde", serde(default))]
+    regions: Vec<String>,
+    // what must hold in the current Room for it to happen
+    #[cfg_attr(feature = "serde", serde(default))]
+    condition: Option<Condition>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    effects: Vec<Consequence>,
+    // it happens at most once a game
+    #[cfg_attr(feature = "serde", serde(default))]
+    once: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    happened: bool,
+}
+
+impl Happening {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_owned(),
+            weight: default_weight(),
+            regions: Vec::new(),
+            condition: None,
+            effects: Vec::new(),
+            once: false,
+            happened: false,
+        }
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_region(mut self, region: &str) -> Self {
+        self.regions.push(region.to_owned());
+        self
+    }
+
+    pub fn with_condition(mut self, condition: Condition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    pub fn with_effect(mut self, effect: Consequence) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    pub fn with_once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn effects(&self) -> &[Consequence] {
+        &self.effects
+    }
+
+    fn can_happen(&self, region: &str, holds: &impl Fn(&Condition) -> bool) -> bool {
+        !(self.once && self.happened)
+            && (self.regions.is_empty() || self.regions.iter().any(|known| known == region))
+            && self.condition.as_ref().is_none_or(holds)
+    }
+}
+
+// Happenings drawn now and then while the player is out in a region, for variety in long games
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Deck {
+    // the percent chance each turn that something happens
+    #[cfg_attr(feature = "serde", serde(default))]
+    chance: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    happenings: Vec<Happening>,
+}
+
+impl Deck {
+    pub fn new(chance: u32) -> Self {
+        Self {
+            chance,
+            happenings: Vec::new(),
+        }
+    }
+
+    pub fn with_happening(mut self, happening: Happening) -> Self {
+        self.happenings.push(happening);
+        self
+    }
+
+    // whatever happens this turn in the region, if anything does, spending it if it happens once
+    pub fn draw(&mut self, region: &str, holds: impl Fn(&Condition) -> bool) -> Option<Happening> {
+        if dice_roll(1, 100) > self.chance {
+            return None;
+        }
+        let open: Vec<usize> = (0..self.happenings.len())
+            .filter(|&i| self.happenings[i].can_happen(region, &holds))
+            .collect();
+        let pick = open[weighted_pick(open.iter().map(|&i| self.happenings[i].weight))?];
+        let happening = &mut self.happenings[pick];
+        happening.happened = true;
+        Some(happening.clone())
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Items;
+use crate::entity::Item;
+
+fn one() -> u32 {
+    1
+}
+
+// Materials to be gathered from something in a Room, like berries from a bush or ore from a
+// vein, which grow back a number of turns after being taken
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Harvest {
+    yields: Item,
+    #[cfg_attr(feature = "serde", serde(default = "one"))]
+    amount: u32,
+    // the Item needed to gather, like a fishing rod or a pickaxe
+    #[cfg_attr(feature = "serde", serde(default))]
+    tool: Option<String>,
+    // how many turns it takes to grow back; none means it never runs out
+    #[cfg_attr(feature = "serde", serde(default))]
+    regrowth: u32,
+    // the turn it is ready to be gathered again
+    #[cfg_attr(feature = "serde", serde(default))]
+    ready_at: u32,
+}
+
+impl Harvest {
+    pub fn new(yields: Item) -> Self {
+        Self {
+            yields,
+            amount: one(),
+            tool: None,
+            regrowth: 0,
+            ready_at: 0,
+        }
+    }
+
+    pub fn with_amount(mut self, amount: u32) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    pub fn with_tool(mut self, tool: &str) -> Self {
+        self.tool = Some(tool.to_owned());
+        self
+    }
+
+    pub fn with_regrowth(mut self, turns: u32) -> Self {
+        self.regrowth = turns;
+        self
+    }
+
+    pub fn tool(&self) -> Option<&str> {
+        self.tool.as_deref()
+    }
+
+    // the Items gathered on the given turn, or how many turns until there is more
+    pub fn gather(&mut self, now: u32) -> Result<Items, u32> {
+        if now < self.ready_at {
+            return Err(self.ready_at - now);
+        }
+        self.ready_at = now + self.regrowth;
+        Ok((0..self.amount)
+            .map(|_| Box::new(self.yields.clone()))
+            .collect())
+    }
+}
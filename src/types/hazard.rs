@@ -0,0 +1,42 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::SavingThrow;
+
+// Environmental danger in a Room that hurts anyone who ends up in it
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Hazard {
+    desc: String,
+    damage: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    save: Option<SavingThrow>,
+}
+
+impl Hazard {
+    pub fn new(desc: &str, damage: u32) -> Self {
+        Self {
+            desc: desc.to_owned(),
+            damage,
+            save: None,
+        }
+    }
+
+    // a deadly hazard must be saved against instead of dealing damage
+    pub fn with_save(mut self, save: SavingThrow) -> Self {
+        self.save = Some(save);
+        self
+    }
+
+    pub const fn save(&self) -> Option<&SavingThrow> {
+        self.save.as_ref()
+    }
+
+    pub fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    pub const fn damage(&self) -> u32 {
+        self.damage
+    }
+}
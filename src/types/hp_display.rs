@@ -0,0 +1,73 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// How the player's vitals are shown; some worlds hide exact numbers for immersion
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HpDisplay {
+    #[default]
+    Numeric,
+    Descriptive,
+    Bar,
+}
+
+impl HpDisplay {
+    const BAR_WIDTH: i32 = 10;
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            HpDisplay::Numeric => "numeric",
+            HpDisplay::Descriptive => "descriptive",
+            HpDisplay::Bar => "bar",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "numeric" | "numbers" => Some(HpDisplay::Numeric),
+            "descriptive" | "words" => Some(HpDisplay::Descriptive),
+            "bar" => Some(HpDisplay::Bar),
+            _ => None,
+        }
+    }
+
+    // full rendering of vitals for the character sheet
+    pub fn render(self, hp: i32, hp_cap: u32) -> String {
+        match self {
+            HpDisplay::Numeric => format!("({} / {})", hp, hp_cap),
+            HpDisplay::Descriptive => HpDisplay::describe(hp, hp_cap).to_owned(),
+            HpDisplay::Bar => HpDisplay::bar(hp, hp_cap),
+        }
+    }
+
+    // a short sentence reporting vitals after taking damage or healing
+    pub fn report(self, hp: i32, hp_cap: u32) -> String {
+        match self {
+            HpDisplay::Numeric => format!("You have {} HP left.", hp),
+            HpDisplay::Descriptive => format!("You are {}.", HpDisplay::describe(hp, hp_cap)),
+            HpDisplay::Bar => format!("HP {}", HpDisplay::bar(hp, hp_cap)),
+        }
+    }
+
+    fn describe(hp: i32, hp_cap: u32) -> &'static str {
+        let percent = hp.max(0) * 100 / hp_cap.max(1) as i32;
+        match percent {
+            100..=i32::MAX => "unhurt",
+            75..=99 => "lightly wounded",
+            50..=74 => "wounded",
+            25..=49 => "badly wounded",
+            1..=24 => "near death",
+            _ => "mortally wounded",
+        }
+    }
+
+    fn bar(hp: i32, hp_cap: u32) -> String {
+        let filled =
+            (hp.max(0) * HpDisplay::BAR_WIDTH / hp_cap.max(1) as i32).min(HpDisplay::BAR_WIDTH);
+        format!(
+            "[{}{}]",
+            "█".repeat(filled as usize),
+            "░".repeat((HpDisplay::BAR_WIDTH - filled) as usize)
+        )
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// The groups a categorized inventory is listed under, in listing order
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Weapons,
+    Armor,
+    Consumables,
+    Quest,
+    Misc,
+}
+
+impl Category {
+    pub const ALL: [Category; 5] = [
+        Category::Weapons,
+        Category::Armor,
+        Category::Consumables,
+        Category::Quest,
+        Category::Misc,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Category::Weapons => "Weapons",
+            Category::Armor => "Armor",
+            Category::Consumables => "Consumables",
+            Category::Quest => "Quest items",
+            Category::Misc => "Misc",
+        }
+    }
+}
+
+// How the inventory is listed: in one list or under categories, in the order items were
+// picked up or alphabetically
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InventoryFormat {
+    categorized: bool,
+    alphabetical: bool,
+}
+
+impl InventoryFormat {
+    pub const fn with_categories(mut self, categorized: bool) -> Self {
+        self.categorized = categorized;
+        self
+    }
+
+    pub const fn with_alphabetical(mut self, alphabetical: bool) -> Self {
+        self.alphabetical = alphabetical;
+        self
+    }
+
+    pub const fn is_categorized(self) -> bool {
+        self.categorized
+    }
+
+    pub const fn is_alphabetical(self) -> bool {
+        self.alphabetical
+    }
+}
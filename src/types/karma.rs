@@ -0,0 +1,57 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// A bound on the player's karma, for dialogue options, paths, and endings open only to the good
+// or only to the wicked
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Karma {
+    at_least: Option<i32>,
+    at_most: Option<i32>,
+    // said when a path will not let the player through
+    refusal: Option<String>,
+}
+
+impl Karma {
+    pub fn at_least(score: i32) -> Self {
+        Self {
+            at_least: Some(score),
+            ..Self::default()
+        }
+    }
+
+    pub fn at_most(score: i32) -> Self {
+        Self {
+            at_most: Some(score),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_refusal(mut self, refusal: &str) -> Self {
+        self.refusal = Some(refusal.to_owned());
+        self
+    }
+
+    pub fn allows(&self, score: i32) -> bool {
+        self.at_least.is_none_or(|least| score >= least)
+            && self.at_most.is_none_or(|most| score <= most)
+    }
+
+    pub fn refusal(&self) -> String {
+        self.refusal
+            .clone()
+            .unwrap_or_else(|| String::from("You cannot bring yourself to go that way."))
+    }
+
+    // how a karma score is spoken of
+    pub fn describe(score: i32) -> &'static str {
+        match score {
+            i32::MIN..=-50 => "villainous",
+            -49..=-10 => "wicked",
+            -9..=9 => "neutral",
+            10..=49 => "good",
+            _ => "saintly",
+        }
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::fold;
+
+fn default_range() -> u32 {
+    2
+}
+
+// Someone or something in a Room that answers when a word is shouted close enough to hear,
+// like a gatekeeper who opens the gate at the password
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Listener {
+    word: String,
+    reply: String,
+    // how many rooms away a shout can still be heard
+    #[cfg_attr(feature = "serde", serde(default = "default_range"))]
+    range: u32,
+    // the path in the Listener's Room that swings open when it hears the word
+    #[cfg_attr(feature = "serde", serde(default))]
+    opens: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    heard: bool,
+}
+
+impl Listener {
+    pub fn new(word: &str, reply: &str) -> Self {
+        Self {
+            word: fold(word),
+            reply: reply.to_owned(),
+            range: default_range(),
+            opens: None,
+            heard: false,
+        }
+    }
+
+    pub fn with_range(mut self, range: u32) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn with_opens(mut self, direction: &str) -> Self {
+        self.opens = Some(direction.to_owned());
+        self
+    }
+
+    pub fn opens(&self) -> Option<&str> {
+        self.opens.as_deref()
+    }
+
+    // answers only the first time every word it listens for is shouted within range
+    pub fn hear(&mut self, shout: &str, distance: u32) -> Option<&str> {
+        let shout = fold(shout);
+        let shouted: Vec<&str> = shout
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        if !self.heard
+            && distance <= self.range
+            && self
+                .word
+                .split_whitespace()
+                .all(|word| shouted.contains(&word))
+        {
+            self.heard = true;
+            Some(&self.reply)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::{error, fmt, io};
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
+
+use super::Metadata;
+
+/// Why a world or a save could not be loaded
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// The file could not be read
+    Read { path: String, source: io::Error },
+    /// The text is not a valid world, or one of the files it includes is not
+    #[cfg(feature = "serde")]
+    World(ron::Error),
+    /// A Room of a world loaded lazily is not a valid Room
+    #[cfg(feature = "serde")]
+    Room { name: String, source: ron::Error },
+    /// The text is not a valid save
+    #[cfg(feature = "serde")]
+    Save(ron::Error),
+    /// The packed archive cannot be opened, or holds no world
+    Archive(String),
+    /// The Trizbort map cannot be read
+    Map(String),
+    /// The save store has no such save, or could not read it
+    Store(String),
+    /// The save is from another world or release than the game loading it
+    Incompatible {
+        save: Box<Metadata>,
+        game: Box<Metadata>,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read { path, source } => write!(f, "Unable to read {}: {}", path, source),
+            #[cfg(feature = "serde")]
+            Self::World(err) => write!(f, "Error creating world: {}", err),
+            #[cfg(feature = "serde")]
+            Self::Room { name, source } => write!(f, "Unable to read room {}: {}", name, source),
+            #[cfg(feature = "serde")]
+            Self::Save(err) => write!(f, "Unable to read save: {}", err),
+            Self::Archive(msg) | Self::Map(msg) | Self::Store(msg) => f.write_str(msg),
+            Self::Incompatible { save, game } => write!(
+                f,
+                "This save is from {} release {}, not {} release {}.",
+                save.title(),
+                save.release(),
+                game.title(),
+                game.release()
+            ),
+        }
+    }
+}
+
+impl error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Read { source, .. } => Some(source),
+            #[cfg(feature = "serde")]
+            Self::World(source) | Self::Room { source, .. } | Self::Save(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// the whole of a file, or which file could not be read and why
+#[cfg(feature = "serde")]
+pub(crate) fn read_text<P: AsRef<Path>>(path: P) -> Result<String, LoadError> {
+    fs::read_to_string(&path).map_err(|source| LoadError::Read {
+        path: path.as_ref().display().to_string(),
+        source,
+    })
+}
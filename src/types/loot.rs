@@ -0,0 +1,114 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dice_roll,
+    entity::{item::Gold, Item},
+    weighted_pick,
+};
+
+use super::Items;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LootEntry {
+    weight: u32,
+    item: Item,
+}
+
+// A set of weighted drops that can be shared by any number of Enemies
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LootTable {
+    #[cfg_attr(feature = "serde", serde(default))]
+    nothing: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    gold: Option<(u32, u32)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    common: Vec<LootEntry>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rare: Vec<LootEntry>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rare_chance: u32,
+}
+
+impl LootTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_nothing(mut self, weight: u32) -> Self {
+        self.nothing = weight;
+        self
+    }
+
+    pub fn with_gold(mut self, min: u32, max: u32) -> Self {
+        self.gold = Some((min, max));
+        self
+    }
+
+    pub fn with_common(mut self, weight: u32, item: Item) -> Self {
+        self.common.push(LootEntry { weight, item });
+        self
+    }
+
+    pub fn with_rare(mut self, weight: u32, item: Item) -> Self {
+        self.rare.push(LootEntry { weight, item });
+        self
+    }
+
+    pub fn with_rare_chance(mut self, percent: u32) -> Self {
+        self.rare_chance = percent;
+        self
+    }
+
+    // rewrite the names of the Items it drops, and what they refer to
+    #[cfg(feature = "serde")]
+    pub fn rename_items(
+        &mut self,
+        item: &impl Fn(&mut String),
+        code: &impl Fn(&mut String),
+        quest: &impl Fn(&mut String),
+    ) {
+        for entry in self.common.iter_mut().chain(self.rare.iter_mut()) {
+            entry.item.rename_references(item, code, quest);
+        }
+    }
+
+    fn pick(entries: &[LootEntry]) -> Option<Box<Item>> {
+        weighted_pick(entries.iter().map(|entry| entry.weight))
+            .map(|i| Box::new(entries[i].item.clone()))
+    }
+
+    // roll the table to produce a set of drops
+    pub fn roll(&self) -> Items {
+        let mut drops = Items::new();
+
+        if let Some((min, max)) = self.gold {
+            let amount = if max > min {
+                // the full range of a u32 has one more amount than a die can have sides
+                min + dice_roll(1, (max - min).saturating_add(1)) - 1
+            } else {
+                min
+            };
+            if amount > 0 {
+                drops.push(Box::new(Item::Gold(Gold::new(amount))));
+            }
+        }
+
+        let weights = std::iter::once(self.nothing).chain(self.common.iter().map(|e| e.weight));
+        if let Some(i) = weighted_pick(weights) {
+            if i > 0 {
+                drops.push(Box::new(self.common[i - 1].item.clone()));
+            }
+        }
+
+        if self.rare_chance > 0 && dice_roll(1, 100) <= self.rare_chance {
+            if let Some(item) = LootTable::pick(&self.rare) {
+                drops.push(item);
+            }
+        }
+
+        drops
+    }
+}
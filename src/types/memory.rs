@@ -0,0 +1,64 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Something the player did that an Ally or Enemy remembers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Deed {
+    // the player struck it
+    Attacked,
+    // the player handed it something
+    Gift,
+    // it saw the player take what was not theirs
+    Theft,
+}
+
+// What an Ally or Enemy remembers of the player, kept with it across saves. A deed it holds a
+// grudge over turns an Enemy on the player and an Ally silent, and a remark for a deed is the
+// first thing it says when talked to after
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Memory {
+    #[cfg_attr(feature = "serde", serde(default))]
+    deeds: Vec<Deed>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    grudges: Vec<Deed>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    remarks: Vec<(Deed, String)>,
+}
+
+impl Memory {
+    pub fn with_grudge(mut self, deed: Deed) -> Self {
+        self.grudges.push(deed);
+        self
+    }
+
+    pub fn with_remark(mut self, deed: Deed, remark: &str) -> Self {
+        self.remarks.push((deed, remark.to_owned()));
+        self
+    }
+
+    pub fn remember(&mut self, deed: Deed) {
+        if !self.recalls(deed) {
+            self.deeds.push(deed);
+        }
+    }
+
+    pub fn recalls(&self, deed: Deed) -> bool {
+        self.deeds.contains(&deed)
+    }
+
+    pub fn bears_grudge(&self) -> bool {
+        self.grudges.iter().any(|&deed| self.recalls(deed))
+    }
+
+    // the remark for the deed most recently remembered that has one
+    pub fn remark(&self) -> Option<&str> {
+        self.deeds.iter().rev().find_map(|deed| {
+            self.remarks
+                .iter()
+                .find(|(remarked, _)| remarked == deed)
+                .map(|(_, remark)| remark.as_str())
+        })
+    }
+}
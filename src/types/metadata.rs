@@ -0,0 +1,139 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Information about a world, shown on a new game and by "about", and kept in saves
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Metadata {
+    title: String,
+    author: String,
+    release: String,
+    blurb: String,
+    intro: String,
+    max_score: Option<u32>,
+    content_warnings: Vec<String>,
+    min_age: Option<u32>,
+}
+
+impl Metadata {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_author(mut self, author: &str) -> Self {
+        self.author = author.to_owned();
+        self
+    }
+
+    pub fn with_release(mut self, release: &str) -> Self {
+        self.release = release.to_owned();
+        self
+    }
+
+    pub fn with_blurb(mut self, blurb: &str) -> Self {
+        self.blurb = blurb.to_owned();
+        self
+    }
+
+    // the message shown before the first room
+    pub fn with_intro(mut self, intro: &str) -> Self {
+        self.intro = intro.to_owned();
+        self
+    }
+
+    pub fn with_max_score(mut self, max_score: u32) -> Self {
+        self.max_score = Some(max_score);
+        self
+    }
+
+    // something players may want to know before playing, such as "violence"
+    pub fn with_content_warning(mut self, warning: &str) -> Self {
+        self.content_warnings.push(warning.to_owned());
+        self
+    }
+
+    pub fn with_min_age(mut self, min_age: u32) -> Self {
+        self.min_age = Some(min_age);
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn release(&self) -> &str {
+        &self.release
+    }
+
+    pub fn intro(&self) -> &str {
+        &self.intro
+    }
+
+    pub const fn max_score(&self) -> Option<u32> {
+        self.max_score
+    }
+
+    pub fn content_warnings(&self) -> &[String] {
+        &self.content_warnings
+    }
+
+    pub const fn min_age(&self) -> Option<u32> {
+        self.min_age
+    }
+
+    // the content warnings and age rating, if the world declares either
+    pub fn content_notice(&self) -> Option<String> {
+        let mut notice = Vec::new();
+        if !self.content_warnings.is_empty() {
+            notice.push(format!(
+                "Content warning: {}.",
+                self.content_warnings.join(", ")
+            ));
+        }
+        if let Some(min_age) = self.min_age {
+            notice.push(format!("Intended for ages {} and up.", min_age));
+        }
+        if notice.is_empty() {
+            None
+        } else {
+            Some(notice.join("\n"))
+        }
+    }
+
+    // a save only fits the world it was made from
+    pub fn is_compatible(&self, save: &Metadata) -> bool {
+        self.title == save.title && self.release == save.release
+    }
+
+    pub fn about(&self) -> String {
+        let mut about = if self.title.is_empty() {
+            String::from("An untitled world")
+        } else {
+            self.title.to_owned()
+        };
+        if !self.author.is_empty() {
+            about.push_str(&format!(" by {}", self.author));
+        }
+        if !self.release.is_empty() {
+            about.push_str(&format!("\nRelease {}", self.release));
+        }
+        if !self.blurb.is_empty() {
+            about.push_str(&format!("\n{}", self.blurb));
+        }
+        if let Some(max_score) = self.max_score {
+            about.push_str(&format!("\nMaximum score: {}", max_score));
+        }
+        if let Some(notice) = self.content_notice() {
+            about.push_str(&format!("\n{}", notice));
+        }
+        about.push_str(&format!(
+            "\nKingslayer engine {}",
+            env!("CARGO_PKG_VERSION")
+        ));
+        about
+    }
+}
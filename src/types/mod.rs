@@ -1,15 +1,124 @@
 mod aliases;
+mod arena;
 mod attack;
+mod bestiary;
+mod body_part;
+mod breach;
+mod calendar;
+mod check;
 mod class;
+mod condition;
+mod context;
+mod cue;
+mod daemon;
+mod damage;
+mod dial;
+mod direction;
+mod director;
+mod encounter;
+mod finding;
+mod furniture;
+mod gear;
+mod guard;
+mod happening;
+mod harvest;
+mod hazard;
+mod hp_display;
+mod inventory_format;
+mod karma;
+mod listener;
+mod load_error;
+mod loot;
+mod memory;
+mod metadata;
+mod order;
+mod pause;
+mod plate;
+mod prompt;
+mod pronouns;
+mod quest;
 mod race;
+mod recipe;
+mod region;
+mod requirements;
+mod responses;
 mod results;
+mod riddle;
+mod role;
+mod rumble;
+mod saving_throw;
+mod sequence;
+mod slot_map;
 mod stats;
 mod status;
+mod status_line;
+mod tie;
+mod tool_use;
+mod tune;
+mod variations;
+mod world_format;
+mod world_stats;
 
-pub use aliases::{Allies, Elements, Enemies, Items, Paths, Rooms};
+pub use aliases::{
+    Allies, AllyId, Elements, Enemies, EnemyId, ItemId, Items, LootTables, Paths, PetId, Pets,
+    Quests, Recipes, Regions, RoomId, Rooms,
+};
 pub use attack::Attack;
+pub use bestiary::{Bestiary, Codex};
+pub use body_part::{BodyPart, PartEffect};
+pub use breach::Breach;
+pub use calendar::{Calendar, StoryEvent};
+pub use check::{Approach, Check, Consequence};
 pub use class::Class;
+pub use condition::Condition;
+pub use context::{Conversation, InputContext};
+pub use cue::Cue;
+pub use daemon::{Daemon, Stir};
+pub use damage::DamageType;
+pub use dial::Dial;
+pub use direction::Direction;
+pub use director::Director;
+pub use encounter::Encounter;
+pub use finding::{Finding, Severity};
+pub use furniture::Furniture;
+pub use gear::Gear;
+pub use guard::Guard;
+pub use happening::{Deck, Happening};
+pub use harvest::Harvest;
+pub use hazard::Hazard;
+pub use hp_display::HpDisplay;
+pub use inventory_format::{Category, InventoryFormat};
+pub use karma::Karma;
+pub use listener::Listener;
+#[cfg(feature = "serde")]
+pub(crate) use load_error::read_text;
+pub use load_error::LoadError;
+pub use loot::LootTable;
+pub use memory::{Deed, Memory};
+pub use metadata::Metadata;
+pub use order::Order;
+pub use pause::Pause;
+pub use plate::Plate;
+pub use prompt::{Answer, Prompt};
+pub use pronouns::Pronouns;
+pub use quest::{Quest, QuestGraph};
 pub use race::Race;
+pub use recipe::Recipe;
+pub use region::Region;
+pub use requirements::Requirements;
+pub use responses::{Reply, Responses};
 pub use results::{Action, CmdResult};
-pub use stats::Stats;
+pub use riddle::Riddle;
+pub use role::Role;
+pub use rumble::Rumble;
+pub use saving_throw::SavingThrow;
+pub use sequence::{Sequence, Step};
+pub use stats::{Ability, Stats};
 pub use status::{CombatStatus, EnemyStatus};
+pub use status_line::StatusLine;
+pub use tie::Tie;
+pub use tool_use::ToolUse;
+pub use tune::Tune;
+pub use variations::Variations;
+pub use world_format::WorldFormat;
+pub use world_stats::WorldStats;
@@ -0,0 +1,65 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Something a companion Ally is told to do with "tell squire to wait here", carried out on the
+// Ally's own turn
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Order {
+    // stay in this Room while the player goes on, or come along again
+    Wait,
+    Follow,
+    // fight the named Enemy until it falls
+    Attack(String),
+    // pick the lock on the named path, or on any locked path here
+    Pick(Option<String>),
+    // pick up the named Item to carry along, or put down everything carried
+    Carry(String),
+    Drop,
+}
+
+impl Order {
+    // read an order like "attack troll", "pick lock", or "carry chest"
+    pub fn parse(verb: &str, obj: &str) -> Option<Self> {
+        let obj = obj.trim();
+        match verb {
+            "wait" | "stay" | "halt" => Some(Order::Wait),
+            "follow" | "come" | "heel" => Some(Order::Follow),
+            "attack" | "fight" | "kill" | "hit" | "slay" if !obj.is_empty() => {
+                Some(Order::Attack(obj.to_owned()))
+            }
+            "pick" | "unlock" => match obj.strip_suffix("lock").unwrap_or(obj).trim() {
+                "" => Some(Order::Pick(None)),
+                path => Some(Order::Pick(Some(path.to_owned()))),
+            },
+            "carry" | "take" | "get" | "lift" | "hold" if !obj.is_empty() => {
+                Some(Order::Carry(obj.to_owned()))
+            }
+            "drop" | "unload" => Some(Order::Drop),
+            _ => None,
+        }
+    }
+
+    // the name an Ally lists among what it can be told to do
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Order::Wait => "wait",
+            Order::Follow => "follow",
+            Order::Attack(_) => "attack",
+            Order::Pick(_) => "pick",
+            Order::Carry(_) | Order::Drop => "carry",
+        }
+    }
+
+    // how the order is put when listing what an Ally can be told to do
+    pub fn describe(name: &str) -> &str {
+        match name {
+            "wait" => "wait here",
+            "follow" => "follow you",
+            "attack" => "attack someone",
+            "pick" => "pick a lock",
+            "carry" => "carry something",
+            name => name,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// A beat of real time a front-end may wait before showing the result of a command
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Pause {
+    Rest,
+    Dramatic,
+}
+
+impl Pause {
+    pub const fn duration(self) -> Duration {
+        match self {
+            Pause::Rest => Duration::from_millis(1500),
+            Pause::Dramatic => Duration::from_millis(1000),
+        }
+    }
+}
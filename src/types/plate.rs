@@ -0,0 +1,42 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// A pressure plate or a hole that a heavy object pushed into its Room settles onto,
+// which can open a way, like a gate worked by the plate or a path across the filled hole
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Plate {
+    reply: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    opens: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pressed: bool,
+}
+
+impl Plate {
+    pub fn new(reply: &str) -> Self {
+        Self {
+            reply: reply.to_owned(),
+            opens: None,
+            pressed: false,
+        }
+    }
+
+    pub fn with_opens(mut self, direction: &str) -> Self {
+        self.opens = Some(direction.to_owned());
+        self
+    }
+
+    pub fn opens(&self) -> Option<&str> {
+        self.opens.as_deref()
+    }
+
+    pub const fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    pub fn press(&mut self) -> &str {
+        self.pressed = true;
+        &self.reply
+    }
+}
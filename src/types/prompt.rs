@@ -0,0 +1,69 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::{fold, CmdTokens};
+
+// what happens when the player picks one of a Prompt's choices
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Answer {
+    Reply(String),
+    Command(CmdTokens),
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Choice {
+    answers: Vec<String>,
+    then: Answer,
+}
+
+// A question left pending on a CmdResult; the next line of input answers it instead of being parsed
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Prompt {
+    question: String,
+    choices: Vec<Choice>,
+}
+
+impl Prompt {
+    pub fn new(question: &str) -> Self {
+        Self {
+            question: question.to_owned(),
+            choices: Vec::new(),
+        }
+    }
+
+    pub fn yes_no(question: &str, yes: Answer, no: Answer) -> Self {
+        Self::new(question)
+            .with_choice(&["yes", "y"], yes)
+            .with_choice(&["no", "n"], no)
+    }
+
+    // a choice can also be picked by its number, counting from 1
+    pub fn with_choice(mut self, answers: &[&str], then: Answer) -> Self {
+        self.choices.push(Choice {
+            answers: answers.iter().map(|answer| fold(answer)).collect(),
+            then,
+        });
+        self
+    }
+
+    pub fn question(&self) -> &str {
+        &self.question
+    }
+
+    pub fn answer(&self, input: &str) -> Option<&Answer> {
+        let input = fold(input.trim());
+        if let Ok(num) = input.parse::<usize>() {
+            return num
+                .checked_sub(1)
+                .and_then(|i| self.choices.get(i))
+                .map(|choice| &choice.then);
+        }
+        self.choices
+            .iter()
+            .find(|choice| choice.answers.contains(&input))
+            .map(|choice| &choice.then)
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prose;
+
+// The words that stand in for a character's name once it has been mentioned
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Pronouns {
+    subject: String,
+    object: String,
+    possessive: String,
+    reflexive: String,
+    // "they attack" rather than "she attacks"
+    #[cfg_attr(feature = "serde", serde(default))]
+    plural: bool,
+}
+
+impl Default for Pronouns {
+    fn default() -> Self {
+        Self::it()
+    }
+}
+
+impl Pronouns {
+    // a custom set such as xe/xem/xyr/xemself, which takes verbs like "xe attacks"
+    pub fn new(subject: &str, object: &str, possessive: &str, reflexive: &str) -> Self {
+        Self {
+            subject: subject.to_lowercase(),
+            object: object.to_lowercase(),
+            possessive: possessive.to_lowercase(),
+            reflexive: reflexive.to_lowercase(),
+            plural: false,
+        }
+    }
+
+    pub fn he() -> Self {
+        Self::new("he", "him", "his", "himself")
+    }
+
+    pub fn she() -> Self {
+        Self::new("she", "her", "her", "herself")
+    }
+
+    pub fn they() -> Self {
+        Self::new("they", "them", "their", "themself").with_plural_verbs()
+    }
+
+    pub fn it() -> Self {
+        Self::new("it", "it", "its", "itself")
+    }
+
+    pub fn with_plural_verbs(mut self) -> Self {
+        self.plural = true;
+        self
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn object(&self) -> &str {
+        &self.object
+    }
+
+    pub fn possessive(&self) -> &str {
+        &self.possessive
+    }
+
+    pub fn reflexive(&self) -> &str {
+        &self.reflexive
+    }
+
+    // the subject starting a sentence with its verb in agreement: "She nods." or "They nod."
+    pub fn act(&self, singular: &str, plural: &str) -> String {
+        format!(
+            "{} {}",
+            prose::capitalize(&self.subject),
+            if self.plural { plural } else { singular }
+        )
+    }
+
+    // a number from the prompt, or a custom set typed out like "xe/xem/xyr/xemself"
+    pub fn select_pronouns(input: &str) -> Self {
+        match input.trim() {
+            "1" => Self::he(),
+            "2" => Self::she(),
+            "4" => Self::it(),
+            custom => match custom.split('/').map(str::trim).collect::<Vec<&str>>()[..] {
+                [subject, object, possessive, reflexive] => {
+                    Self::new(subject, object, possessive, reflexive)
+                }
+                _ => Self::they(),
+            },
+        }
+    }
+
+    pub fn pronouns_prompt() -> String {
+        "Choose pronouns:\n  \
+            1) he/him\n  \
+            2) she/her\n  \
+            3) they/them\n  \
+            4) it/its\n  \
+            or type your own, like xe/xem/xyr/xemself\n\n"
+            .to_owned()
+    }
+}
+
+impl fmt::Display for Pronouns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // "it/its" reads better than "it/it"
+        if self.subject == self.object {
+            write!(f, "{}/{}", self.subject, self.possessive)
+        } else {
+            write!(f, "{}/{}", self.subject, self.object)
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// A quest named in the world file, with the quests that must be done before it
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Quest {
+    #[cfg_attr(feature = "serde", serde(default))]
+    requires: Vec<String>,
+}
+
+impl Quest {
+    pub fn with_requires(mut self, quest: &str) -> Self {
+        self.requires.push(quest.to_owned());
+        self
+    }
+
+    pub fn requires(&self) -> &[String] {
+        &self.requires
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rename_requires(&mut self, rename: &impl Fn(&mut String)) {
+        for quest in self.requires.iter_mut() {
+            rename(quest);
+        }
+    }
+}
+
+// How the quests of a World hang together, for authors to draw: which quests unlock which,
+// where each is completed, and who holds the items each needs
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuestGraph {
+    // each node's kind, like "quest", "room", "item", or "npc", and its name
+    nodes: Vec<(String, String)>,
+    // from one node to another, by index, and how they are linked
+    edges: Vec<(usize, usize, String)>,
+}
+
+impl QuestGraph {
+    // the index of a node, added if it is new
+    pub(crate) fn node(&mut self, kind: &str, name: &str) -> usize {
+        match self
+            .nodes
+            .iter()
+            .position(|(known_kind, known)| known_kind == kind && known == name)
+        {
+            Some(node) => node,
+            None => {
+                self.nodes.push((kind.to_owned(), name.to_owned()));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    pub(crate) fn edge(&mut self, from: usize, to: usize, label: &str) {
+        if !self.edges.iter().any(|(known_from, known_to, known)| {
+            (*known_from, *known_to) == (from, to) && known == label
+        }) {
+            self.edges.push((from, to, label.to_owned()));
+        }
+    }
+
+    pub fn nodes(&self) -> &[(String, String)] {
+        &self.nodes
+    }
+
+    // every edge as the name of each end and its label
+    pub fn edges(&self) -> Vec<(&str, &str, &str)> {
+        self.edges
+            .iter()
+            .map(|(from, to, label)| {
+                (
+                    self.nodes[*from].1.as_str(),
+                    self.nodes[*to].1.as_str(),
+                    label.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    // each circle of quests that unlock one another, as the quests around it in order
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let is_quest = |node: usize| self.nodes[node].0 == "quest";
+        let unlocks = |node: usize| {
+            self.edges
+                .iter()
+                .filter(move |(from, to, _)| *from == node && is_quest(*to))
+                .map(|(_, to, _)| *to)
+        };
+        let mut cycles: Vec<Vec<String>> = Vec::new();
+        for start in (0..self.nodes.len()).filter(|&node| is_quest(node)) {
+            // walk depth first from each quest, only through quests after it, so that each
+            // circle is found once, from its first quest
+            let mut stack = vec![(start, vec![start])];
+            while let Some((node, path)) = stack.pop() {
+                for next in unlocks(node) {
+                    if next == start {
+                        cycles.push(path.iter().map(|&n| self.nodes[n].1.clone()).collect());
+                    } else if next > start && !path.contains(&next) {
+                        let mut path = path.clone();
+                        path.push(next);
+                        stack.push((next, path));
+                    }
+                }
+            }
+        }
+        cycles
+    }
+
+    // the graph in Graphviz's DOT language
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph quests {\n");
+        for (i, (kind, name)) in self.nodes.iter().enumerate() {
+            let shape = match kind.as_str() {
+                "quest" => "box",
+                "room" => "house",
+                "item" => "ellipse",
+                _ => "diamond",
+            };
+            let _ = writeln!(
+                dot,
+                "    n{} [label=\"{}\", shape={}];",
+                i,
+                escape(name),
+                shape
+            );
+        }
+        for (from, to, label) in &self.edges {
+            let _ = writeln!(
+                dot,
+                "    n{} -> n{} [label=\"{}\"];",
+                from,
+                to,
+                escape(label)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    // the graph as JSON, with nodes listed by kind and name and edges naming their ends
+    pub fn to_json(&self) -> String {
+        let nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|(kind, name)| {
+                format!(
+                    "{{\"kind\":\"{}\",\"name\":\"{}\"}}",
+                    escape(kind),
+                    escape(name)
+                )
+            })
+            .collect();
+        let edges: Vec<String> = self
+            .edges
+            .iter()
+            .map(|(from, to, label)| {
+                format!(
+                    "{{\"from\":{},\"to\":{},\"label\":\"{}\"}}",
+                    from,
+                    to,
+                    escape(label)
+                )
+            })
+            .collect();
+        format!(
+            "{{\"nodes\":[{}],\"edges\":[{}]}}",
+            nodes.join(","),
+            edges.join(",")
+        )
+    }
+}
+
+// quotes, backslashes, and line breaks made safe inside a quoted string in DOT or JSON
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+        escaped
+    })
+}
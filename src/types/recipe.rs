@@ -0,0 +1,90 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Ability;
+use crate::entity::Item;
+
+// Something brewed or cooked at a station such as a cauldron or campfire, which turns out well
+// or badly depending on an ability check against its difficulty
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Recipe {
+    station: String,
+    ingredients: Vec<String>,
+    makes: Item,
+    // made instead when the check beats the difficulty by 10 or more
+    #[cfg_attr(feature = "serde", serde(default))]
+    fine: Option<Item>,
+    ability: Ability,
+    dc: i32,
+}
+
+impl Recipe {
+    pub fn new(station: &str, makes: Item, ability: Ability, dc: i32) -> Self {
+        Self {
+            station: station.to_owned(),
+            ingredients: Vec::new(),
+            makes,
+            fine: None,
+            ability,
+            dc,
+        }
+    }
+
+    pub fn with_ingredient(mut self, ingredient: &str) -> Self {
+        self.ingredients.push(ingredient.to_owned());
+        self
+    }
+
+    pub fn with_fine(mut self, fine: Item) -> Self {
+        self.fine = Some(fine);
+        self
+    }
+
+    pub fn station(&self) -> &str {
+        &self.station
+    }
+
+    pub fn ingredients(&self) -> &[String] {
+        &self.ingredients
+    }
+
+    // rewrite the names of the Items it makes, and what they refer to
+    #[cfg(feature = "serde")]
+    pub fn rename_made(
+        &mut self,
+        item: &impl Fn(&mut String),
+        code: &impl Fn(&mut String),
+        quest: &impl Fn(&mut String),
+    ) {
+        for made in std::iter::once(&mut self.makes).chain(self.fine.as_mut()) {
+            made.rename_references(item, code, quest);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rename_ingredients(&mut self, rename: &impl Fn(&mut String)) {
+        for ingredient in self.ingredients.iter_mut() {
+            rename(ingredient);
+        }
+    }
+
+    pub const fn ability(&self) -> Ability {
+        self.ability
+    }
+
+    pub const fn dc(&self) -> i32 {
+        self.dc
+    }
+
+    // what comes of a check, or nothing if the ingredients were spoiled
+    pub fn outcome(&self, check: i32) -> Option<&Item> {
+        if check < self.dc {
+            None
+        } else if check >= self.dc + 10 {
+            Some(self.fine.as_ref().unwrap_or(&self.makes))
+        } else {
+            Some(&self.makes)
+        }
+    }
+}
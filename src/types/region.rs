@@ -0,0 +1,92 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{dice_roll, entity::Enemy, weighted_pick};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Wanderer {
+    weight: u32,
+    enemy: Enemy,
+}
+
+// Wandering Enemies shared by every Room of an overland area, rolled each turn of a long
+// journey and whenever the player idles there, and what is said on crossing into it
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Region {
+    // the percent chance each turn that something wanders near
+    #[cfg_attr(feature = "serde", serde(default))]
+    chance: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    wanderers: Vec<Wanderer>,
+    // a paragraph for crossing in from each neighbouring region, like leaving the forest for
+    // the city, written once for the border rather than in every Room along it
+    #[cfg_attr(feature = "serde", serde(default))]
+    borders: Vec<(String, String)>,
+    // said on arriving from anywhere without a border paragraph of its own
+    #[cfg_attr(feature = "serde", serde(default))]
+    arrival: Option<String>,
+}
+
+impl Region {
+    pub fn new(chance: u32) -> Self {
+        Self {
+            chance,
+            wanderers: Vec::new(),
+            borders: Vec::new(),
+            arrival: None,
+        }
+    }
+
+    pub fn with_wanderer(mut self, weight: u32, enemy: Enemy) -> Self {
+        self.wanderers.push(Wanderer { weight, enemy });
+        self
+    }
+
+    pub fn with_border(mut self, from: &str, paragraph: &str) -> Self {
+        self.borders.push((from.to_owned(), paragraph.to_owned()));
+        self
+    }
+
+    pub fn with_arrival(mut self, paragraph: &str) -> Self {
+        self.arrival = Some(paragraph.to_owned());
+        self
+    }
+
+    // what is said on crossing in from the named region, or from outside any region
+    pub fn transition(&self, from: Option<&str>) -> Option<&str> {
+        from.and_then(|from| {
+            self.borders
+                .iter()
+                .find(|(region, _)| region == from)
+                .map(|(_, paragraph)| paragraph.as_str())
+        })
+        .or(self.arrival.as_deref())
+    }
+
+    // rewrite the name of every region this one borders
+    #[cfg(feature = "serde")]
+    pub fn rename_regions(&mut self, rename: &impl Fn(&mut String)) {
+        for (region, _) in self.borders.iter_mut() {
+            rename(region);
+        }
+    }
+
+    // rewrite the loot table of every Enemy that may wander here
+    #[cfg(feature = "serde")]
+    pub fn rename_loot_tables(&mut self, rename: &impl Fn(&mut String)) {
+        for wanderer in self.wanderers.iter_mut() {
+            wanderer.enemy.rename_loot_table(rename);
+        }
+    }
+
+    // whatever wanders near this turn, if anything does, with the chance of it adjusted first
+    pub fn roll(&self, adjust: impl FnOnce(u32) -> u32) -> Option<Enemy> {
+        if dice_roll(1, 100) > adjust(self.chance) {
+            return None;
+        }
+        weighted_pick(self.wanderers.iter().map(|wanderer| wanderer.weight))
+            .map(|i| self.wanderers[i].enemy.clone())
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Ability, Action, Class, CmdResult, Stats};
+use crate::prose;
+
+// What a player must have to wield or wear an Item
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Requirements {
+    abilities: Vec<(Ability, i32)>,
+    classes: Vec<Class>,
+}
+
+impl Requirements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // a minimum ability score
+    pub fn with_ability(mut self, ability: Ability, score: i32) -> Self {
+        self.abilities.push((ability, score));
+        self
+    }
+
+    // only the listed classes may use the Item
+    pub fn with_class(mut self, class: Class) -> Self {
+        self.classes.push(class);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.abilities.is_empty() && self.classes.is_empty()
+    }
+
+    // "Mage or Cleric", or "a Mage or a Cleric" with articles
+    fn class_list(&self, articles: bool) -> String {
+        let classes: Vec<String> = self
+            .classes
+            .iter()
+            .map(|class| {
+                if articles {
+                    prose::a(&class.to_string())
+                } else {
+                    class.to_string()
+                }
+            })
+            .collect();
+        prose::list_with(&classes, "or")
+    }
+
+    // explain the first requirement the player falls short of
+    #[allow(clippy::result_large_err)]
+    pub fn check(&self, item_name: &str, class: &Class, stats: &Stats) -> Result<(), CmdResult> {
+        if !self.classes.is_empty() && !self.classes.contains(class) {
+            return Err(CmdResult::new(
+                Action::Failed,
+                format!("Only {} can use the {}.", self.class_list(true), item_name),
+            ));
+        }
+        for (ability, score) in &self.abilities {
+            let yours = stats.score(*ability);
+            if yours < *score {
+                return Err(CmdResult::new(
+                    Action::Failed,
+                    format!(
+                        "You need {} {} to use the {}, but you have {}.",
+                        score, ability, item_name, yours
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn describe(&self) -> String {
+        let mut parts: Vec<String> = self
+            .abilities
+            .iter()
+            .map(|(ability, score)| format!("{} {}", ability, score))
+            .collect();
+        if !self.classes.is_empty() {
+            parts.push(self.class_list(false));
+        }
+        format!("Requires: {}.", parts.join(", "))
+    }
+}
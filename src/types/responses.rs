@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::CmdResult;
+use crate::input::named;
+
+// Refusals for verbs that do nothing in the game, like jumping or swearing, so the player hears
+// something in keeping with the game rather than that the word is unknown. A World or a Room
+// can say something else for any of them, filed under the verb's name such as "jump"
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Responses(HashMap<String, String>);
+
+impl Responses {
+    pub fn with(mut self, verb: &str, response: &str) -> Self {
+        self.set(verb, response);
+        self
+    }
+
+    pub fn set(&mut self, verb: &str, response: &str) {
+        self.0.insert(verb.to_lowercase(), response.to_owned());
+    }
+
+    pub fn get(&self, verb: &str) -> Option<&str> {
+        self.0.get(verb).map(String::as_str)
+    }
+
+    // the name a response is filed under, for the first six letters of any verb meaning it
+    pub fn verb(short_verb: &str) -> Option<&'static str> {
+        match short_verb {
+            "jump" | "leap" | "hop" | "skip" => Some("jump"),
+            "sing" | "hum" | "whistl" | "chant" => Some("sing"),
+            "dance" | "twirl" | "jig" => Some("dance"),
+            "pray" | "kneel" => Some("pray"),
+            "xyzzy" | "plugh" | "plover" | "abraca" => Some("xyzzy"),
+            "swear" | "curse" | "damn" | "cuss" | "fuck" | "shit" | "bother" => Some("swear"),
+            _ => None,
+        }
+    }
+
+    // what is said for a verb when neither the Room nor the World says otherwise
+    pub fn stock(verb: &str) -> &'static str {
+        match verb {
+            "jump" => "You jump on the spot. Nothing happens, and you feel a little silly.",
+            "sing" => "You sing a few bars. Only the echo joins in.",
+            "dance" => "You dance a short jig. Nobody is impressed.",
+            "pray" => "There is no shrine here.",
+            "xyzzy" => "A hollow voice says \"Fool.\"",
+            "swear" => "Such language! It does not help.",
+            _ => "Nothing happens.",
+        }
+    }
+}
+
+// What is said for a verb done to one thing, in place of what the engine would say. A refusal
+// stops the verb before anything is done, like taking a sword set in stone; a success or a
+// failure only rewords how it went, like opening a coffin
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Reply {
+    // the verbs it answers, like "take" and "get"
+    verbs: Vec<String>,
+    object: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    refusal: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    success: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    failure: Option<String>,
+}
+
+impl Reply {
+    pub fn new(verbs: &[&str], object: &str) -> Self {
+        Self {
+            verbs: verbs.iter().map(|verb| verb.to_lowercase()).collect(),
+            object: object.to_owned(),
+            refusal: None,
+            success: None,
+            failure: None,
+        }
+    }
+
+    pub fn with_refusal(mut self, refusal: &str) -> Self {
+        self.refusal = Some(refusal.to_owned());
+        self
+    }
+
+    pub fn with_success(mut self, success: &str) -> Self {
+        self.success = Some(success.to_owned());
+        self
+    }
+
+    pub fn with_failure(mut self, failure: &str) -> Self {
+        self.failure = Some(failure.to_owned());
+        self
+    }
+
+    // whether it is for the verb, by its first six letters as the parser reads it, done to
+    // the thing named
+    pub fn answers(&self, short_verb: &str, obj: &str) -> bool {
+        self.verbs
+            .iter()
+            .any(|verb| verb.get(..6).unwrap_or(verb) == short_verb)
+            && named(&self.object, obj)
+    }
+
+    pub fn refusal(&self) -> Option<&str> {
+        self.refusal.as_deref()
+    }
+
+    // say the success in place of what the engine said when the verb took a turn, and the
+    // failure when it did not
+    pub fn reword(&self, res: CmdResult) -> CmdResult {
+        let reworded = if res.is_active() {
+            &self.success
+        } else {
+            &self.failure
+        };
+        match reworded {
+            Some(output) => res.reworded(output),
+            None => res,
+        }
+    }
+}
@@ -1,19 +1,29 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use super::{Conversation, InputContext, Pause, Prompt};
 use crate::input::CmdTokens;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Action {
     Active,
     Passive,
     Failed,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CmdResult {
     action: Action,
     output: String,
     request_input: Option<CmdTokens>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pause: Option<Pause>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    misunderstood: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    context: Option<InputContext>,
 }
 
 impl Default for CmdResult {
@@ -22,6 +32,9 @@ impl Default for CmdResult {
             action: Action::Passive,
             output: String::new(),
             request_input: None,
+            pause: None,
+            misunderstood: false,
+            context: None,
         }
     }
 }
@@ -35,14 +48,65 @@ impl CmdResult {
             action,
             output: output.into(),
             request_input: None,
+            pause: None,
+            misunderstood: false,
+            context: None,
         }
     }
 
+    // put a line ahead of the output, like what happens on the way before a Room is described
+    pub fn with_preface(mut self, preface: &str) -> Self {
+        self.output = format!("{}\n{}", preface, self.output);
+        self
+    }
+
+    // say something else in place of the output, keeping whether it took a turn
+    pub fn reworded(mut self, output: &str) -> Self {
+        self.output = output.to_owned();
+        self
+    }
+
+    // put a line after the output, like who saw it happen
+    pub fn with_postscript(mut self, postscript: &str) -> Self {
+        self.output = format!("{}\n{}", self.output, postscript);
+        self
+    }
+
     pub fn with_request_input(mut self, cmd: CmdTokens) -> Self {
         self.request_input = Some(cmd);
         self
     }
 
+    pub fn with_pause(mut self, pause: Pause) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
+    // leave a question pending so the next input answers it
+    pub fn with_prompt(mut self, prompt: Prompt) -> Self {
+        self.context = Some(InputContext::Question(prompt));
+        self
+    }
+
+    pub fn with_conversation(mut self, conversation: Conversation) -> Self {
+        self.context = Some(InputContext::Conversation(conversation));
+        self
+    }
+
+    // an input context for Cli to push so it handles the next input
+    pub fn with_riddle(mut self, element: &str) -> Self {
+        self.context = Some(InputContext::Riddle(element.to_owned()));
+        self
+    }
+
+    pub fn take_context(&mut self) -> Option<InputContext> {
+        self.context.take()
+    }
+
+    pub const fn pause(&self) -> Option<Pause> {
+        self.pause
+    }
+
     pub fn has_request(&self) -> bool {
         self.request_input.is_some()
     }
@@ -55,6 +119,15 @@ impl CmdResult {
         self.action == Action::Active
     }
 
+    // whether the parser could not make sense of the input at all
+    pub const fn is_misunderstood(&self) -> bool {
+        self.misunderstood
+    }
+
+    pub const fn action(&self) -> &Action {
+        &self.action
+    }
+
     pub fn succeeded(&self) -> bool {
         self.action != Action::Failed
     }
@@ -104,13 +177,30 @@ impl CmdResult {
     }
 
     pub fn no_comprendo() -> CmdResult {
-        CmdResult::new(Action::Failed, "I do not understand that phrase.")
+        CmdResult {
+            misunderstood: true,
+            ..CmdResult::new(Action::Failed, "I do not understand that phrase.")
+        }
+    }
+
+    pub fn unknown_word(word: &str) -> CmdResult {
+        CmdResult {
+            misunderstood: true,
+            ..CmdResult::new(
+                Action::Failed,
+                format!("I do not know the word \"{}\".", word),
+            )
+        }
     }
 
     pub fn no_item_here(name: &str) -> CmdResult {
         CmdResult::new(Action::Failed, format!("There is no \"{}\" here.", name))
     }
 
+    pub fn nowhere() -> CmdResult {
+        CmdResult::new(Action::Failed, "The way leads nowhere.")
+    }
+
     pub fn not_container(name: &str) -> CmdResult {
         CmdResult::new(Action::Failed, format!("The {} is not a container.", name))
     }
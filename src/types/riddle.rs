@@ -0,0 +1,143 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use regex::RegexBuilder;
+
+use crate::input::fold;
+
+// A question posed by a sphinx, a talking door, or the like, which takes the next line of input
+// as its answer; wrong guesses earn a hint apiece and may hurt, and a right one can open a way
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Riddle {
+    question: String,
+    answers: Vec<String>,
+    // regular expressions the whole answer must match, like ".*echo.*", tried against it after
+    // it is lowercased and stripped of punctuation and articles
+    #[cfg_attr(feature = "serde", serde(default))]
+    patterns: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hints: Vec<String>,
+    reply: String,
+    // damage dealt for each wrong guess
+    #[cfg_attr(feature = "serde", serde(default))]
+    penalty: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    opens: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    guesses: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    solved: bool,
+}
+
+impl Riddle {
+    pub fn new(question: &str, answer: &str, reply: &str) -> Self {
+        Self {
+            question: question.to_owned(),
+            answers: vec![answer.to_owned()],
+            patterns: Vec::new(),
+            hints: Vec::new(),
+            reply: reply.to_owned(),
+            penalty: 0,
+            opens: None,
+            guesses: 0,
+            solved: false,
+        }
+    }
+
+    pub fn with_answer(mut self, answer: &str) -> Self {
+        self.answers.push(answer.to_owned());
+        self
+    }
+
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.to_owned());
+        self
+    }
+
+    pub fn with_hint(mut self, hint: &str) -> Self {
+        self.hints.push(hint.to_owned());
+        self
+    }
+
+    pub fn with_penalty(mut self, damage: u32) -> Self {
+        self.penalty = damage;
+        self
+    }
+
+    pub fn with_opens(mut self, direction: &str) -> Self {
+        self.opens = Some(direction.to_owned());
+        self
+    }
+
+    pub fn question(&self) -> &str {
+        &self.question
+    }
+
+    pub fn opens(&self) -> Option<&str> {
+        self.opens.as_deref()
+    }
+
+    pub const fn penalty(&self) -> u32 {
+        self.penalty
+    }
+
+    pub const fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    fn accepts(&self, guess: &str) -> bool {
+        let guess = normalize(guess);
+        self.answers.iter().any(|answer| normalize(answer) == guess)
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| matches(pattern, &guess).unwrap_or(false))
+    }
+
+    // the patterns that are not regular expressions, which never match anything
+    pub fn bad_patterns(&self) -> impl Iterator<Item = &str> {
+        self.patterns
+            .iter()
+            .filter(|pattern| matches(pattern, "").is_none())
+            .map(String::as_str)
+    }
+
+    // the reply to a guess and whether it was right; each wrong guess gives the next hint
+    pub fn guess(&mut self, guess: &str) -> (String, bool) {
+        if self.accepts(guess) {
+            self.solved = true;
+            (self.reply.to_owned(), true)
+        } else {
+            let hint = self.hints.get(self.guesses).or_else(|| self.hints.last());
+            let res = match hint {
+                Some(hint) => format!("That is not the answer. {}", hint),
+                None => String::from("That is not the answer."),
+            };
+            self.guesses += 1;
+            (res, false)
+        }
+    }
+}
+
+// answers are compared in lowercase without punctuation, articles, or extra spaces
+fn normalize(text: &str) -> String {
+    fold(text)
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .filter(|word| !["a", "an", "the"].contains(word))
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+// whether the whole text matches a pattern, or None if it is not a regular expression; the
+// regex crate runs in time linear in the text, so no answer can stall the game
+fn matches(pattern: &str, text: &str) -> Option<bool> {
+    RegexBuilder::new(&format!("^(?:{})$", pattern))
+        .case_insensitive(true)
+        .build()
+        .ok()
+        .map(|regex| regex.is_match(text))
+}
@@ -0,0 +1,20 @@
+// Who is playing a hosted game, which decides whether debug commands are available
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Role {
+    #[default]
+    Player,
+    // may move about freely, spawn enemies, and set quest flags to test a world
+    Author,
+    // may also reload the world
+    Admin,
+}
+
+impl Role {
+    pub const fn can_debug(self) -> bool {
+        matches!(self, Role::Author | Role::Admin)
+    }
+
+    pub const fn is_admin(self) -> bool {
+        matches!(self, Role::Admin)
+    }
+}
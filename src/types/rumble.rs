@@ -0,0 +1,38 @@
+// How hard a controller should rumble for a haptic hint
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rumble {
+    Light,
+    Medium,
+    Heavy,
+}
+
+impl Rumble {
+    // scaled from 0 to 1 for backends that take a strength
+    pub fn strength(self) -> f32 {
+        match self {
+            Rumble::Light => 0.25,
+            Rumble::Medium => 0.5,
+            Rumble::Heavy => 1.0,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Rumble::Light => "light",
+            Rumble::Medium => "medium",
+            Rumble::Heavy => "heavy",
+        }
+    }
+
+    // a hit that takes a quarter of the player's health is felt, and half is felt hard
+    pub fn for_damage(damage: i32, hp_cap: u32) -> Option<Self> {
+        let hp_cap = hp_cap.max(1) as i32;
+        if damage * 2 >= hp_cap {
+            Some(Rumble::Heavy)
+        } else if damage * 4 >= hp_cap {
+            Some(Rumble::Medium)
+        } else {
+            None
+        }
+    }
+}
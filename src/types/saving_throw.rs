@@ -0,0 +1,41 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Ability;
+
+// A roll the player must make or die outright, such as a basilisk's gaze or a poisoned needle
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct SavingThrow {
+    ability: Ability,
+    dc: i32,
+    desc: String,
+    death: String,
+}
+
+impl SavingThrow {
+    pub fn new(ability: Ability, dc: i32, desc: &str, death: &str) -> Self {
+        Self {
+            ability,
+            dc,
+            desc: desc.to_owned(),
+            death: death.to_owned(),
+        }
+    }
+
+    pub const fn ability(&self) -> Ability {
+        self.ability
+    }
+
+    pub const fn dc(&self) -> i32 {
+        self.dc
+    }
+
+    pub fn desc(&self) -> &str {
+        &self.desc
+    }
+
+    pub fn death(&self) -> &str {
+        &self.death
+    }
+}
@@ -0,0 +1,117 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::{fold, named};
+
+fn default_wrong() -> String {
+    String::from("Nothing seems to come of it.")
+}
+
+// One step of a Sequence, like "ring bell", and what is said when it is done in its turn
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Step {
+    verbs: Vec<String>,
+    obj: String,
+    feedback: String,
+}
+
+impl Step {
+    pub fn new(verbs: &[&str], obj: &str, feedback: &str) -> Self {
+        Self {
+            verbs: verbs.iter().map(|verb| fold(verb)).collect(),
+            obj: obj.to_owned(),
+            feedback: feedback.to_owned(),
+        }
+    }
+
+    fn is(&self, verb: &str, obj: &str) -> bool {
+        self.verbs.iter().any(|known| *known == fold(verb)) && named(&self.obj, obj)
+    }
+}
+
+// Steps in a Room that must be done in order, like lighting the incense, ringing the bell, then
+// reading the book; a step done out of turn starts it over. Once every step is done it may
+// complete a quest and swing a path open
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Sequence {
+    steps: Vec<Step>,
+    // said when a step is done out of turn
+    #[cfg_attr(feature = "serde", serde(default = "default_wrong"))]
+    wrong: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    completes: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    opens: Option<String>,
+    // how many steps have been done in order so far
+    #[cfg_attr(feature = "serde", serde(default))]
+    progress: usize,
+}
+
+impl Sequence {
+    pub fn new(steps: Vec<Step>) -> Self {
+        Self {
+            steps,
+            wrong: default_wrong(),
+            completes: None,
+            opens: None,
+            progress: 0,
+        }
+    }
+
+    pub fn with_wrong(mut self, wrong: &str) -> Self {
+        self.wrong = wrong.to_owned();
+        self
+    }
+
+    pub fn with_completes(mut self, quest: &str) -> Self {
+        self.completes = Some(quest.to_owned());
+        self
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn rename_quest(&mut self, rename: &impl Fn(&mut String)) {
+        if let Some(quest) = &mut self.completes {
+            rename(quest);
+        }
+    }
+
+    pub fn with_opens(mut self, direction: &str) -> Self {
+        self.opens = Some(direction.to_owned());
+        self
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.progress >= self.steps.len()
+    }
+
+    pub fn completes(&self) -> Option<&str> {
+        self.completes.as_deref()
+    }
+
+    pub fn opens(&self) -> Option<&str> {
+        self.opens.as_deref()
+    }
+
+    // whether the command is one of the steps still to be done
+    pub fn is_step(&self, verb: &str, obj: &str) -> bool {
+        !self.is_solved() && self.steps.iter().any(|step| step.is(verb, obj))
+    }
+
+    // do one of the steps, saying how it went; out of turn, it starts over, counting the step
+    // if it is the first
+    pub fn follow(&mut self, verb: &str, obj: &str) -> String {
+        if self.steps[self.progress].is(verb, obj) {
+            self.progress += 1;
+            return self.steps[self.progress - 1].feedback.to_owned();
+        }
+        self.progress = 0;
+        if self.steps[0].is(verb, obj) {
+            self.progress = 1;
+            format!("{}\n{}", self.wrong, self.steps[0].feedback)
+        } else {
+            self.wrong.to_owned()
+        }
+    }
+}
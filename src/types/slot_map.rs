@@ -0,0 +1,343 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::OnceLock,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::entity::Entity;
+
+// A typed handle to an entry of a SlotMap, which stays valid as other entries come and go and
+// never finds an entry that took the place of its own once it is removed
+pub struct Key<T> {
+    index: u32,
+    generation: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Key<T> {
+    const fn new(index: u32, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Key<T> {}
+
+impl<T> PartialEq for Key<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Key<T> {}
+
+impl<T> Hash for Key<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Key<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Key({}v{})", self.index, self.generation)
+    }
+}
+
+// where the entry a key was handed out for sits, while it is there
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    generation: u32,
+    pos: Option<usize>,
+}
+
+// Storage for the Items, Enemies, and NPCs of a Room or an inventory, which keep the order they
+// were added in and are addressed by typed Keys, with an index from the name each was defined
+// under to its Keys for the parser.
+// Entries sit together in order, so reading them is reading a slice, and handles stay good
+// however the entries around them move; a slot freed by a removal is reused with a new
+// generation. The name index is built the first time it is asked for after any change.
+pub struct SlotMap<T> {
+    entries: Vec<Box<T>>,
+    keys: Vec<Key<T>>,
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    index: OnceLock<HashMap<String, Vec<Key<T>>>>,
+}
+
+impl<T> SlotMap<T> {
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            keys: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+            index: OnceLock::new(),
+        }
+    }
+
+    // add an entry after the others, giving its Key
+    pub fn push(&mut self, entry: Box<T>) -> Key<T> {
+        let pos = self.entries.len();
+        let key = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.generation += 1;
+                slot.pos = Some(pos);
+                Key::new(index, slot.generation)
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    pos: Some(pos),
+                });
+                Key::new((self.slots.len() - 1) as u32, 0)
+            }
+        };
+        self.entries.push(entry);
+        self.keys.push(key);
+        self.index.take();
+        key
+    }
+
+    // take out the entry at a position, the ones after it moving up
+    pub fn remove(&mut self, pos: usize) -> Box<T> {
+        let key = self.keys.remove(pos);
+        let entry = self.entries.remove(pos);
+        self.slots[key.index as usize].pos = None;
+        self.free.push(key.index);
+        for key in &self.keys[pos..] {
+            if let Some(pos) = &mut self.slots[key.index as usize].pos {
+                *pos -= 1;
+            }
+        }
+        self.index.take();
+        entry
+    }
+
+    // take out the entry a Key was handed out for, if it is still here
+    pub fn remove_key(&mut self, key: Key<T>) -> Option<Box<T>> {
+        let pos = self.position(key)?;
+        Some(self.remove(pos))
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&Box<T>) -> bool) {
+        self.retain_mut(|entry| keep(entry));
+    }
+
+    pub fn retain_mut(&mut self, mut keep: impl FnMut(&mut Box<T>) -> bool) {
+        let mut pos = 0;
+        while pos < self.entries.len() {
+            if keep(&mut self.entries[pos]) {
+                pos += 1;
+            } else {
+                self.remove(pos);
+            }
+        }
+        self.index.take();
+    }
+
+    // take out every entry, leaving it empty
+    pub fn take(&mut self) -> Self {
+        std::mem::take(self)
+    }
+
+    // take out the entries that match, in order, the rest keeping their Keys
+    pub fn extract(&mut self, mut take: impl FnMut(&T) -> bool) -> Self {
+        let mut taken = Self::new();
+        let mut pos = 0;
+        while pos < self.entries.len() {
+            if take(&self.entries[pos]) {
+                taken.push(self.remove(pos));
+            } else {
+                pos += 1;
+            }
+        }
+        taken
+    }
+
+    // where the entry a Key was handed out for sits, if it is still here
+    pub fn position(&self, key: Key<T>) -> Option<usize> {
+        self.slots
+            .get(key.index as usize)
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.pos)
+    }
+
+    // the Key of the entry at a position
+    pub fn key(&self, pos: usize) -> Option<Key<T>> {
+        self.keys.get(pos).copied()
+    }
+
+    // the Key of every entry, in order
+    pub fn keys(&self) -> impl Iterator<Item = Key<T>> + '_ {
+        self.keys.iter().copied()
+    }
+
+    pub fn get_key(&self, key: Key<T>) -> Option<&T> {
+        let pos = self.position(key)?;
+        Some(&self.entries[pos])
+    }
+
+    pub fn get_key_mut(&mut self, key: Key<T>) -> Option<&mut T> {
+        let pos = self.position(key)?;
+        self.index.take();
+        Some(&mut self.entries[pos])
+    }
+}
+
+impl<T: Entity> SlotMap<T> {
+    // every entry by the name it was defined under, built once and kept until a change
+    fn index(&self) -> &HashMap<String, Vec<Key<T>>> {
+        self.index.get_or_init(|| {
+            let mut index: HashMap<String, Vec<Key<T>>> = HashMap::new();
+            for (entry, key) in self.entries.iter().zip(&self.keys) {
+                index.entry(entry.id().to_owned()).or_default().push(*key);
+            }
+            index
+        })
+    }
+
+    // the first entry defined under a name, such as "dragons:key"
+    pub fn key_of(&self, id: &str) -> Option<Key<T>> {
+        self.index().get(id)?.first().copied()
+    }
+
+    // how many entries were defined under a name
+    pub fn count_of(&self, id: &str) -> usize {
+        self.index().get(id).map_or(0, Vec::len)
+    }
+
+    // where the first entry defined under a name sits
+    pub fn position_of(&self, id: &str) -> Option<usize> {
+        self.position(self.key_of(id)?)
+    }
+}
+
+impl<T> Default for SlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// a copy keeps the same Keys, so a Key from a Room also finds the entry in a snapshot of it
+impl<T: Clone> Clone for SlotMap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            keys: self.keys.clone(),
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+            index: OnceLock::new(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SlotMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.entries).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SlotMap<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<T> Deref for SlotMap<T> {
+    type Target = [Box<T>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.entries
+    }
+}
+
+// entries changed in place may change their names, so the index is built again
+impl<T> DerefMut for SlotMap<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.index.take();
+        &mut self.entries
+    }
+}
+
+impl<T> Extend<Box<T>> for SlotMap<T> {
+    fn extend<I: IntoIterator<Item = Box<T>>>(&mut self, entries: I) {
+        for entry in entries {
+            self.push(entry);
+        }
+    }
+}
+
+impl<T> FromIterator<Box<T>> for SlotMap<T> {
+    fn from_iter<I: IntoIterator<Item = Box<T>>>(entries: I) -> Self {
+        let mut map = Self::new();
+        map.extend(entries);
+        map
+    }
+}
+
+impl<T> From<Vec<Box<T>>> for SlotMap<T> {
+    fn from(entries: Vec<Box<T>>) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+impl<T> IntoIterator for SlotMap<T> {
+    type Item = Box<T>;
+    type IntoIter = std::vec::IntoIter<Box<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SlotMap<T> {
+    type Item = &'a Box<T>;
+    type IntoIter = std::slice::Iter<'a, Box<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SlotMap<T> {
+    type Item = &'a mut Box<T>;
+    type IntoIter = std::slice::IterMut<'a, Box<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.index.take();
+        self.entries.iter_mut()
+    }
+}
+
+// written as a list, like the Vec it stands in for, and given fresh Keys when read
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for SlotMap<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.entries.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SlotMap<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Box<T>>::deserialize(deserializer).map(Self::from)
+    }
+}
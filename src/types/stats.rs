@@ -1,8 +1,37 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use super::{Action, CmdResult};
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Ability {
+    Strength,
+    Dexterity,
+    Constitution,
+    Intellect,
+    Wisdom,
+    Charisma,
+}
+
+impl fmt::Display for Ability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Strength => "Strength",
+            Self::Dexterity => "Dexterity",
+            Self::Constitution => "Constitution",
+            Self::Intellect => "Intellect",
+            Self::Wisdom => "Wisdom",
+            Self::Charisma => "Charisma",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Stats {
     pub pts: u32,
     pub strngth: i32,
@@ -29,7 +58,6 @@ impl Stats {
     pub fn print_stats(&self) -> String {
         format!(
             "Stat points: {}\
-                
              \n  Strength:     {} ({})\
              \n  Dexterity:    {} ({})\
              \n  Constitution: {} ({})\
@@ -54,10 +82,10 @@ impl Stats {
 
     pub fn increase_ability_score(&mut self, ability_score: &str) -> CmdResult {
         if self.pts > 0 {
-            match &ability_score[0..3] {
+            match ability_score.get(0..3).unwrap_or_default() {
                 "str" | "dex" | "con" | "int" | "wis" | "cha" => {
                     self.pts -= 1;
-                    match &ability_score[0..3] {
+                    match ability_score.get(0..3).unwrap_or_default() {
                         "str" => self.strngth += 1,
                         "dex" => self.dex += 1,
                         "con" => self.con += 1,
@@ -78,6 +106,28 @@ impl Stats {
         }
     }
 
+    pub const fn score(&self, ability: Ability) -> i32 {
+        match ability {
+            Ability::Strength => self.strngth,
+            Ability::Dexterity => self.dex,
+            Ability::Constitution => self.con,
+            Ability::Intellect => self.int,
+            Ability::Wisdom => self.wis,
+            Ability::Charisma => self.cha,
+        }
+    }
+
+    pub fn modifier(&self, ability: Ability) -> i32 {
+        match ability {
+            Ability::Strength => self.strngth_mod(),
+            Ability::Dexterity => self.dex_mod(),
+            Ability::Constitution => self.con_mod(),
+            Ability::Intellect => self.int_mod(),
+            Ability::Wisdom => self.wis_mod(),
+            Ability::Charisma => self.cha_mod(),
+        }
+    }
+
     pub fn strngth_mod(&self) -> i32 {
         (f64::from(self.strngth - 10) / 2.0).floor() as i32
     }
@@ -0,0 +1,42 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// An Infocom-style header with the room name on the left and score and turns on the right
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatusLine {
+    room: String,
+    score: u32,
+    turns: u32,
+}
+
+impl StatusLine {
+    pub fn new(room: &str, score: u32, turns: u32) -> Self {
+        Self {
+            room: room.to_owned(),
+            score,
+            turns,
+        }
+    }
+
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    pub const fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub const fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    // pad between the two halves so the line fills the given width
+    pub fn render(&self, width: usize) -> String {
+        let right = format!("Score: {}  Turns: {}", self.score, self.turns);
+        let gap = width
+            .saturating_sub(self.room.chars().count() + right.chars().count())
+            .max(1);
+        format!("{}{}{}", self.room, " ".repeat(gap), right)
+    }
+}
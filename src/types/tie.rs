@@ -0,0 +1,59 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{Entity, Item},
+    prose,
+};
+
+// A rope tied to an anchor in a Room, or to an Item lowered through one of the Room's paths
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Tie {
+    rope: Box<Item>,
+    anchor: String,
+    // the path the tied Item was lowered through
+    #[cfg_attr(feature = "serde", serde(default))]
+    lowered: Option<String>,
+}
+
+impl Tie {
+    pub fn new(rope: Box<Item>, anchor: &str) -> Self {
+        Self {
+            rope,
+            anchor: anchor.to_owned(),
+            lowered: None,
+        }
+    }
+
+    pub fn with_lowered(mut self, direction: &str) -> Self {
+        self.lowered = Some(direction.to_owned());
+        self
+    }
+
+    pub fn rope(&self) -> &str {
+        self.rope.name()
+    }
+
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+
+    pub fn lowered(&self) -> Option<&str> {
+        self.lowered.as_deref()
+    }
+
+    pub fn into_rope(self) -> Box<Item> {
+        self.rope
+    }
+
+    pub fn desc(&self) -> String {
+        let rope = prose::capitalize(&prose::a(self.rope()));
+        match &self.lowered {
+            Some(direction) => {
+                format!("{} hangs {}, tied to the {}.", rope, direction, self.anchor)
+            }
+            None => format!("{} is tied to the {}.", rope, self.anchor),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Items;
+use crate::{
+    entity::Item,
+    input::{fold, named},
+};
+
+// Something done to an Element with a carried tool, like cutting a rope with a knife, prying
+// open a crate with a crowbar, or digging a hole with a spoon; any one of the tools will do
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ToolUse {
+    verbs: Vec<String>,
+    tools: Vec<String>,
+    success: String,
+    // Items turned up by it, left lying in the Room
+    #[cfg_attr(feature = "serde", serde(default))]
+    yields: Items,
+    // the Element is gone afterwards, like a rope cut down or a crate smashed open
+    #[cfg_attr(feature = "serde", serde(default))]
+    consumes: bool,
+}
+
+impl ToolUse {
+    pub fn new(verbs: &[&str], tools: &[&str], success: &str) -> Self {
+        Self {
+            verbs: verbs.iter().map(|verb| fold(verb)).collect(),
+            tools: tools.iter().map(|&tool| tool.to_owned()).collect(),
+            success: success.to_owned(),
+            yields: Items::new(),
+            consumes: false,
+        }
+    }
+
+    pub fn with_yield(mut self, item: Item) -> Self {
+        self.yields.push(Box::new(item));
+        self
+    }
+
+    pub fn with_consumes(mut self) -> Self {
+        self.consumes = true;
+        self
+    }
+
+    pub fn is_for(&self, verb: &str) -> bool {
+        self.verbs.iter().any(|known| *known == fold(verb))
+    }
+
+    pub fn tools(&self) -> &[String] {
+        &self.tools
+    }
+
+    // whether the carried Item with this name is one of the tools
+    pub fn accepts(&self, item: &str) -> bool {
+        self.tools.iter().any(|tool| named(item, tool))
+    }
+
+    pub fn success(&self) -> &str {
+        &self.success
+    }
+
+    pub const fn consumes(&self) -> bool {
+        self.consumes
+    }
+
+    pub fn into_yields(self) -> Items {
+        self.yields
+    }
+}
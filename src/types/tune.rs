@@ -0,0 +1,55 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::same_name;
+
+// A fixture that stirs when a particular song is played near it, like a singing stone that opens
+// a way; it answers only once
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Tune {
+    song: String,
+    reply: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    opens: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    answered: bool,
+}
+
+impl Tune {
+    pub fn new(song: &str, reply: &str) -> Self {
+        Self {
+            song: song.to_owned(),
+            reply: reply.to_owned(),
+            opens: None,
+            answered: false,
+        }
+    }
+
+    pub fn with_opens(mut self, direction: &str) -> Self {
+        self.opens = Some(direction.to_owned());
+        self
+    }
+
+    pub fn song(&self) -> &str {
+        &self.song
+    }
+
+    pub fn opens(&self) -> Option<&str> {
+        self.opens.as_deref()
+    }
+
+    pub const fn is_answered(&self) -> bool {
+        self.answered
+    }
+
+    // the reply to a song played nearby, if it is the right one and has not been answered yet
+    pub fn hear(&mut self, song: &str) -> Option<&str> {
+        if self.answered || !same_name(&self.song, song) {
+            None
+        } else {
+            self.answered = true;
+            Some(&self.reply)
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::weighted_pick;
+
+// Pools of interchangeable wordings for the engine's most repeated responses,
+// chosen with the seeded RNG so that golden tests stay reproducible
+#[derive(Clone, Debug)]
+pub struct Variations {
+    pools: Vec<(String, Vec<(u32, String)>)>,
+    // how many recent choices from a pool are skipped before they can come up again
+    window: usize,
+    recent: Vec<(String, Vec<usize>)>,
+}
+
+impl Default for Variations {
+    fn default() -> Self {
+        let mut variations = Self {
+            pools: Vec::new(),
+            window: 1,
+            recent: Vec::new(),
+        };
+        variations.set_pool(
+            "Taken.",
+            &[(3, "Taken."), (1, "Got it."), (1, "You pick it up.")],
+        );
+        variations.set_pool(
+            "Dropped.",
+            &[
+                (3, "Dropped."),
+                (1, "You set it down."),
+                (1, "You let it go."),
+            ],
+        );
+        variations.set_pool(
+            "Opened.",
+            &[(3, "Opened."), (1, "It opens."), (1, "You open it.")],
+        );
+        variations.set_pool(
+            "Closed.",
+            &[(3, "Closed."), (1, "It closes."), (1, "You close it.")],
+        );
+        variations.set_pool(
+            "You cannot go that way.",
+            &[
+                (3, "You cannot go that way."),
+                (1, "There is no way through there."),
+                (1, "You can't go that way."),
+            ],
+        );
+        variations
+    }
+}
+
+impl Variations {
+    // replace the wordings of a message, or remove its pool when given none
+    pub fn set_pool(&mut self, message: &str, pool: &[(u32, &str)]) {
+        self.pools.retain(|(m, _)| m != message);
+        self.recent.retain(|(m, _)| m != message);
+        if !pool.is_empty() {
+            self.pools.push((
+                message.to_owned(),
+                pool.iter()
+                    .map(|(weight, text)| (*weight, (*text).to_owned()))
+                    .collect(),
+            ));
+        }
+    }
+
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window;
+    }
+
+    fn pick(&mut self, message: &str) -> Option<String> {
+        let (_, pool) = self.pools.iter().find(|(m, _)| m == message)?;
+        let recent = match self.recent.iter().position(|(m, _)| m == message) {
+            Some(i) => i,
+            None => {
+                self.recent.push((message.to_owned(), Vec::new()));
+                self.recent.len() - 1
+            }
+        };
+        let skipped = &self.recent[recent].1;
+        let fresh = |i: usize| !skipped.contains(&i);
+        let choice = weighted_pick(
+            pool.iter()
+                .enumerate()
+                .map(|(i, (weight, _))| if fresh(i) { *weight } else { 0 }),
+        )
+        .or_else(|| weighted_pick(pool.iter().map(|(weight, _)| *weight)))?;
+
+        let skipped = &mut self.recent[recent].1;
+        skipped.push(choice);
+        if skipped.len() > self.window {
+            skipped.remove(0);
+        }
+        Some(pool[choice].1.clone())
+    }
+
+    // swap each whole-sentence occurrence of a pooled message for one of its wordings
+    pub fn vary(&mut self, output: &str) -> String {
+        let messages: Vec<String> = self.pools.iter().map(|(m, _)| m.clone()).collect();
+        let mut varied = output.to_owned();
+        for message in messages {
+            let mut from = 0;
+            while let Some(found) = varied[from..].find(&message) {
+                let start = from + found;
+                let end = start + message.len();
+                let bounded = !matches!(varied[..start].chars().last(), Some(c) if !c.is_whitespace())
+                    && !matches!(varied[end..].chars().next(), Some(c) if !c.is_whitespace());
+                if !bounded {
+                    from = end;
+                    continue;
+                }
+                let replacement = self.pick(&message).unwrap_or_else(|| message.clone());
+                varied.replace_range(start..end, &replacement);
+                from = start + replacement.len();
+            }
+        }
+        varied
+    }
+}
@@ -0,0 +1,7 @@
+// How a World is written out by `to_file`; pretty RON is meant for hand-editing
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WorldFormat {
+    Ron,
+    #[default]
+    PrettyRon,
+}
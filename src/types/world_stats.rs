@@ -0,0 +1,115 @@
+use std::fmt;
+
+// enemies are ranked by the experience they give, from the weakest tier up
+const DIFFICULTIES: [(&str, u32); 4] = [
+    ("trivial", 0),
+    ("easy", 25),
+    ("moderate", 50),
+    ("hard", 100),
+];
+
+// words read per minute, and minutes spent per Room and per fight, for estimating playtime
+const READING_SPEED: usize = 200;
+const MINUTES_PER_ROOM: usize = 1;
+const MINUTES_PER_ENEMY: usize = 2;
+
+// A summary of a world's size and balance for authors
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorldStats {
+    rooms: usize,
+    exits: usize,
+    items: usize,
+    enemies: Vec<(&'static str, usize)>,
+    text_len: usize,
+    words: usize,
+}
+
+impl WorldStats {
+    pub fn new() -> Self {
+        Self {
+            enemies: DIFFICULTIES.iter().map(|(tier, _)| (*tier, 0)).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn add_room(&mut self, exits: usize) {
+        self.rooms += 1;
+        self.exits += exits;
+    }
+
+    pub fn add_item(&mut self) {
+        self.items += 1;
+    }
+
+    pub fn add_enemy(&mut self, xp: u32) {
+        let tier = DIFFICULTIES
+            .iter()
+            .rposition(|(_, min_xp)| xp >= *min_xp)
+            .unwrap_or(0);
+        self.enemies[tier].1 += 1;
+    }
+
+    pub fn add_text(&mut self, text: &str) {
+        self.text_len += text.len();
+        self.words += text.split_whitespace().count();
+    }
+
+    pub const fn rooms(&self) -> usize {
+        self.rooms
+    }
+
+    pub const fn items(&self) -> usize {
+        self.items
+    }
+
+    pub fn enemies(&self) -> usize {
+        self.enemies.iter().map(|(_, count)| count).sum()
+    }
+
+    // how many enemies fall into a difficulty tier: trivial, easy, moderate, or hard
+    pub fn enemies_by_difficulty(&self, tier: &str) -> usize {
+        self.enemies
+            .iter()
+            .find(|(name, _)| *name == tier)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn average_exits(&self) -> f64 {
+        if self.rooms == 0 {
+            0.0
+        } else {
+            self.exits as f64 / self.rooms as f64
+        }
+    }
+
+    // characters of descriptive text across every Room, Item, and Enemy
+    pub const fn text_len(&self) -> usize {
+        self.text_len
+    }
+
+    // a rough estimate in minutes: reading every description, visiting every Room,
+    // and fighting every Enemy
+    pub fn playtime(&self) -> usize {
+        (self.words as f64 / READING_SPEED as f64).ceil() as usize
+            + self.rooms * MINUTES_PER_ROOM
+            + self.enemies() * MINUTES_PER_ENEMY
+    }
+}
+
+impl fmt::Display for WorldStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Rooms: {}", self.rooms)?;
+        writeln!(f, "Average exits per room: {:.1}", self.average_exits())?;
+        writeln!(f, "Items: {}", self.items)?;
+        write!(f, "Enemies: {} (", self.enemies())?;
+        for (i, (tier, count)) in self.enemies.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, tier)?;
+        }
+        writeln!(f, ")")?;
+        writeln!(f, "Text: {} characters", self.text_len)?;
+        write!(f, "Estimated playtime: {} minutes", self.playtime())
+    }
+}
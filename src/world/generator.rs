@@ -0,0 +1,94 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::World;
+use crate::{
+    entity::{Pathway, Room},
+    prose,
+    types::Rooms,
+    RandomSource, SplitMix64,
+};
+
+const ADJECTIVES: [&str; 6] = ["Damp", "Narrow", "Vaulted", "Crumbling", "Silent", "Frozen"];
+const PLACES: [&str; 6] = ["Cavern", "Passage", "Crypt", "Hall", "Grotto", "Cell"];
+const DETAILS: [&str; 6] = [
+    "Water drips steadily from the ceiling.",
+    "Old bones are scattered across the floor.",
+    "The air is thick with dust.",
+    "Faded carvings cover the walls.",
+    "A cold draft blows through the room.",
+    "Roots have broken through the stone above.",
+];
+
+fn room_key(i: usize) -> String {
+    format!("Dungeon {}", i)
+}
+
+// rooms are laid out as a binary tree, so each one only needs its own index to be built,
+// and each has its own RNG so the result doesn't depend on which thread built it
+fn generate_room(i: usize, num_rooms: usize, seed: u64) -> Room {
+    let mut rng = SplitMix64::new(seed ^ (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    let mut choose =
+        |options: &[&'static str]| options[(rng.next_u64() % options.len() as u64) as usize];
+    let adjective = choose(&ADJECTIVES);
+    let place = choose(&PLACES);
+    let detail = choose(&DETAILS);
+
+    let mut room = Room::new(
+        &format!("{} {}", adjective, place),
+        &format!(
+            "You are in {}. {}",
+            prose::a(&format!("{} {}", adjective, place).to_lowercase()),
+            detail
+        ),
+    );
+    if i > 0 {
+        room.add_path(Pathway::new(
+            &["up", "back"],
+            &room_key((i - 1) / 2),
+            "A passage leads back up.",
+            "It is the way you came.",
+        ));
+    }
+    for (child, direction) in [(2 * i + 1, "west"), (2 * i + 2, "east")].iter() {
+        if *child < num_rooms {
+            room.add_path(Pathway::new(
+                &[direction, "down"],
+                &room_key(*child),
+                &format!("A passage to the {} leads deeper.", direction),
+                "It slopes downward into darkness.",
+            ));
+        }
+    }
+    room
+}
+
+impl World {
+    // build a dungeon of the given size that is the same every time for the same seed
+    pub fn generate(num_rooms: usize, seed: u64) -> Self {
+        let num_rooms = num_rooms.max(1);
+
+        #[cfg(feature = "parallel")]
+        let generated: Vec<Room> = (0..num_rooms)
+            .into_par_iter()
+            .map(|i| generate_room(i, num_rooms, seed))
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let generated: Vec<Room> = (0..num_rooms)
+            .map(|i| generate_room(i, num_rooms, seed))
+            .collect();
+
+        let mut rooms = Rooms::default();
+        for (i, room) in generated.into_iter().enumerate() {
+            rooms.insert(&room_key(i), room);
+        }
+
+        Self {
+            curr_room: rooms
+                .id(&room_key(0))
+                .expect("The first room is always generated"),
+            rooms,
+            ..Self::default()
+        }
+    }
+}
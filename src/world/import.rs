@@ -0,0 +1,167 @@
+use super::World;
+use crate::{
+    entity::{Pathway, Room},
+    types::{Direction, Rooms},
+};
+
+const TODO: &str = "TODO";
+
+// Trizbort names the eight compass points by abbreviation, and up, down, in, and out in full
+fn direction(port: &str) -> Option<Direction> {
+    Some(Direction::parse(port)).filter(|dir| !dir.is_custom())
+}
+
+// An opening or self-closing XML tag with its attributes; closing tags have names starting with /
+struct Tag<'a> {
+    name: &'a str,
+    attrs: Vec<(&'a str, String)>,
+}
+
+impl Tag<'_> {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// just enough XML to read the tags of a map export, skipping text, comments, and declarations
+fn tags(xml: &str) -> Result<Vec<Tag<'_>>, String> {
+    let mut tags = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if rest.starts_with("!--") {
+            let end = rest.find("-->").ok_or("An XML comment is never closed.")?;
+            rest = &rest[end + 3..];
+            continue;
+        }
+        let end = rest.find('>').ok_or("An XML tag is never closed.")?;
+        let body = rest[..end].trim_end_matches('/');
+        rest = &rest[end + 1..];
+        if body.starts_with('?') || body.starts_with('!') {
+            continue;
+        }
+
+        let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+        let mut attrs = Vec::new();
+        let mut attr_text = &body[name_end..];
+        while let Some(eq) = attr_text.find('=') {
+            let key = attr_text[..eq].trim();
+            let value = attr_text[eq + 1..].trim_start();
+            let quote = value
+                .chars()
+                .next()
+                .filter(|c| matches!(c, '"' | '\''))
+                .ok_or_else(|| format!("The attribute {} is not quoted.", key))?;
+            let close = value[1..]
+                .find(quote)
+                .ok_or_else(|| format!("The attribute {} is never closed.", key))?;
+            attrs.push((key, unescape(&value[1..=close])));
+            attr_text = &value[close + 2..];
+        }
+        tags.push(Tag {
+            name: &body[..name_end],
+            attrs,
+        });
+    }
+    Ok(tags)
+}
+
+impl World {
+    // build a skeleton World from a Trizbort map export, with a Room for each room on the map
+    // and a Pathway for each end of a line; descriptions are left for the author to write
+    pub fn from_trizbort(xml: &str) -> Result<Self, String> {
+        let tags = tags(xml)?;
+
+        // map ids to unique Room keys, since a map may reuse a name for several rooms
+        let mut keys: Vec<(String, String)> = Vec::new();
+        let mut rooms: Vec<(String, Room)> = Vec::new();
+        let mut start = None;
+        for tag in tags.iter().filter(|tag| tag.name == "room") {
+            let id = tag.attr("id").ok_or("A room has no id.")?;
+            let name = tag.attr("name").unwrap_or("Unnamed Room");
+            let mut key = name.to_owned();
+            let mut copies = 1;
+            while rooms.iter().any(|(k, _)| *k == key) {
+                copies += 1;
+                key = format!("{} {}", name, copies);
+            }
+            if tag.attr("isStartRoom") == Some("yes") {
+                start = Some(key.clone());
+            }
+            keys.push((id.to_owned(), key.clone()));
+            rooms.push((
+                key,
+                Room::new(name, &format!("{}: describe {}.", TODO, name)),
+            ));
+        }
+        if rooms.is_empty() {
+            return Err(String::from("The map has no rooms."));
+        }
+        let key = |id: &str| {
+            keys.iter()
+                .find(|(k, _)| k == id)
+                .map(|(_, key)| key.clone())
+                .ok_or_else(|| format!("A line connects to {}, which is not a room.", id))
+        };
+
+        // each line is followed by its docks, the first at its start and the second at its end
+        let mut i = 0;
+        while i < tags.len() {
+            if tags[i].name != "line" {
+                i += 1;
+                continue;
+            }
+            let one_way = tags[i].attr("flow") == Some("oneWay");
+            let mut docks = Vec::new();
+            i += 1;
+            while i < tags.len() && tags[i].name == "dock" {
+                let id = tags[i].attr("id").ok_or("A line is docked to no room.")?;
+                let port = tags[i].attr("port").unwrap_or_default();
+                docks.push((key(id)?, direction(port)));
+                i += 1;
+            }
+            if let [(from, from_dir), (to, to_dir)] = docks.as_slice() {
+                let mut connect = |from: &str, dir: Option<&Direction>, to: &str| {
+                    let dir = dir.map_or_else(|| to.to_lowercase(), |dir| dir.name().to_owned());
+                    if let Some((_, room)) = rooms.iter_mut().find(|(k, _)| k == from) {
+                        room.add_path(Pathway::new(
+                            &[&dir],
+                            to,
+                            &format!("{}: describe the way {}.", TODO, dir),
+                            &format!("{}: describe what lies toward {}.", TODO, to),
+                        ));
+                    }
+                };
+                connect(from, from_dir.as_ref(), to);
+                if !one_way {
+                    connect(to, to_dir.as_ref(), from);
+                }
+            }
+        }
+
+        let start = start.unwrap_or_else(|| rooms[0].0.clone());
+        let mut arena = Rooms::default();
+        for (key, room) in rooms {
+            arena.insert(&key, room);
+        }
+        Ok(Self {
+            curr_room: arena
+                .id(&start)
+                .expect("The start room was added to the map"),
+            rooms: arena,
+            ..Self::default()
+        })
+    }
+}
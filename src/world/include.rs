@@ -0,0 +1,245 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use super::{namespace, WorldFile};
+use crate::types::{LootTables, Quests, Recipes, Regions, Reply, Rooms};
+
+// reads an included file by its path, relative to the file that included it
+pub(crate) type ReadInclude = dyn Fn(&Path) -> Result<String, String>;
+
+// part of a World kept in a file of its own, which may include more files in turn; a content
+// pack names itself so everything it defines is kept apart from the World it joins, and the
+// files it includes belong to the same pack unless they name their own. Only what can be merged
+// with the rest of the World is allowed here: settings such as the starting Room or the clock
+// belong to the main file alone
+#[derive(Deserialize)]
+#[serde(rename = "World", deny_unknown_fields)]
+struct Part {
+    #[serde(default)]
+    pack: Option<String>,
+    #[serde(default)]
+    rooms: Rooms,
+    #[serde(default)]
+    loot_tables: LootTables,
+    #[serde(default)]
+    regions: Regions,
+    #[serde(default)]
+    recipes: Recipes,
+    #[serde(default)]
+    quests: Quests,
+    #[serde(default)]
+    replies: Vec<Reply>,
+    #[serde(default)]
+    include: Vec<String>,
+}
+
+pub(crate) fn read_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| err.to_string())
+}
+
+// merge every file the world read from `main` includes into it, reading each with `read`
+// relative to the file that names it, and refusing anything defined twice
+pub(super) fn resolve(
+    world: &mut WorldFile,
+    main: &Path,
+    read: &ReadInclude,
+) -> Result<(), String> {
+    if world.include.is_empty() {
+        return Ok(());
+    }
+    let names = std::mem::take(&mut world.include);
+    include(world, &names, None, read, &mut vec![main.to_owned()])?;
+    resolve_pack_references(world);
+    Ok(())
+}
+
+// `stack` holds the file that names these includes, after every file that led to it
+fn include(
+    world: &mut WorldFile,
+    names: &[String],
+    pack: Option<&str>,
+    read: &ReadInclude,
+    stack: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    let dir = stack
+        .last()
+        .and_then(|from| from.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    for name in names {
+        let path = dir.join(name);
+        let shown = path.display().to_string();
+        if stack.contains(&path) {
+            return Err(format!("{} includes itself", shown));
+        }
+        let text = read(&path).map_err(|err| format!("Unable to include {}: {}", shown, err))?;
+        let part: Part =
+            ron::de::from_str(&text).map_err(|err| format!("Error in {}: {}", shown, err))?;
+        if let Some(name) = part.pack.as_deref() {
+            if !namespace::is_pack_name(name) {
+                return Err(format!(
+                    "{}: {} is not a valid pack name; it cannot be empty or hold spaces or colons",
+                    shown, name
+                ));
+            }
+        }
+        let pack = part.pack.as_deref().or(pack);
+
+        for (room_name, room) in part.rooms.iter_named() {
+            let room_name = qualify(pack, room_name);
+            if world.rooms.id(&room_name).is_some() {
+                return Err(duplicate("room", &room_name, &shown));
+            }
+            world.rooms.insert(&room_name, room.clone());
+        }
+        merge(
+            &mut world.loot_tables,
+            part.loot_tables,
+            pack,
+            "loot table",
+            &shown,
+        )?;
+        merge(&mut world.regions, part.regions, pack, "region", &shown)?;
+        merge(&mut world.recipes, part.recipes, pack, "recipe", &shown)?;
+        merge(&mut world.quests, part.quests, pack, "quest", &shown)?;
+        world.replies.extend(part.replies);
+
+        stack.push(path);
+        include(world, &part.include, pack, read, stack)?;
+        stack.pop();
+    }
+    Ok(())
+}
+
+fn merge<T>(
+    into: &mut HashMap<String, T>,
+    from: HashMap<String, T>,
+    pack: Option<&str>,
+    kind: &str,
+    file: &str,
+) -> Result<(), String> {
+    for (name, value) in from {
+        let name = qualify(pack, &name);
+        if into.contains_key(&name) {
+            return Err(duplicate(kind, &name, file));
+        }
+        into.insert(name, value);
+    }
+    Ok(())
+}
+
+fn qualify(pack: Option<&str>, name: &str) -> String {
+    match pack {
+        Some(pack) => namespace::qualify(pack, name),
+        None => name.to_owned(),
+    }
+}
+
+// the Items a pack's Rooms, loot tables, and recipes hold and the codes of its keys are the
+// pack's own, so they are given the pack's name; then, as a pack refers to what it defines by
+// the names it wrote, each plain name in a pack's Rooms, regions, recipes, and quests that the
+// pack itself defines is given the pack's name too; anything else it names, or names with
+// another pack's name, is left to be found in the rest of the World
+fn resolve_pack_references(world: &mut WorldFile) {
+    let rooms: HashSet<String> = world
+        .rooms
+        .iter_named()
+        .map(|(name, _)| name.to_owned())
+        .collect();
+    let tables: HashSet<String> = world.loot_tables.keys().cloned().collect();
+    let regions: HashSet<String> = world.regions.keys().cloned().collect();
+    let quests: HashSet<String> = world.quests.keys().cloned().collect();
+
+    let items = RefCell::new(HashSet::new());
+    let codes = RefCell::new(HashSet::new());
+    for name in &rooms {
+        if let (Some(pack), Some(id)) = (namespace::pack_of(name), world.rooms.id(name)) {
+            world.rooms.get_mut(id).rename_items(
+                &to_pack(pack, &items),
+                &to_pack(pack, &codes),
+                &in_pack(pack, &quests),
+            );
+        }
+    }
+    for (name, table) in world.loot_tables.iter_mut() {
+        if let Some(pack) = namespace::pack_of(name) {
+            table.rename_items(
+                &to_pack(pack, &items),
+                &to_pack(pack, &codes),
+                &in_pack(pack, &quests),
+            );
+        }
+    }
+    for (name, recipe) in world.recipes.iter_mut() {
+        if let Some(pack) = namespace::pack_of(name) {
+            recipe.rename_made(
+                &to_pack(pack, &items),
+                &to_pack(pack, &codes),
+                &in_pack(pack, &quests),
+            );
+        }
+    }
+    let (items, codes) = (items.into_inner(), codes.into_inner());
+
+    for name in &rooms {
+        if let (Some(pack), Some(id)) = (namespace::pack_of(name), world.rooms.id(name)) {
+            world.rooms.get_mut(id).rename_references(
+                in_pack(pack, &rooms),
+                in_pack(pack, &tables),
+                in_pack(pack, &regions),
+                in_pack(pack, &items),
+                in_pack(pack, &codes),
+                in_pack(pack, &quests),
+            );
+        }
+    }
+    for (name, region) in world.regions.iter_mut() {
+        if let Some(pack) = namespace::pack_of(name) {
+            region.rename_loot_tables(&in_pack(pack, &tables));
+            region.rename_regions(&in_pack(pack, &regions));
+        }
+    }
+    for (name, recipe) in world.recipes.iter_mut() {
+        if let Some(pack) = namespace::pack_of(name) {
+            recipe.rename_ingredients(&in_pack(pack, &items));
+        }
+    }
+    for (name, quest) in world.quests.iter_mut() {
+        if let Some(pack) = namespace::pack_of(name) {
+            quest.rename_requires(&in_pack(pack, &quests));
+        }
+    }
+}
+
+// give a name the pack's name, noting it among what the pack defines
+fn to_pack<'a>(pack: &'a str, defined: &'a RefCell<HashSet<String>>) -> impl Fn(&mut String) + 'a {
+    move |name| {
+        *name = namespace::qualify(pack, name);
+        defined.borrow_mut().insert(name.clone());
+    }
+}
+
+// give a plain name the pack's name if the pack defines it
+fn in_pack<'a>(pack: &'a str, defined: &'a HashSet<String>) -> impl Fn(&mut String) + 'a {
+    move |name| {
+        if namespace::pack_of(name).is_none() {
+            let qualified = namespace::qualify(pack, name);
+            if defined.contains(&qualified) {
+                *name = qualified;
+            }
+        }
+    }
+}
+
+fn duplicate(kind: &str, name: &str, file: &str) -> String {
+    format!(
+        "{} defines the {} {}, which is already defined",
+        file, kind, name
+    )
+}
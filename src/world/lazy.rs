@@ -0,0 +1,138 @@
+// Splits the Rooms out of a world file without building them, so a huge world can start by
+// reading everything else and build each Room the first time it is needed, and finds where the
+// World starts, so it can be read apart from the rest of the game along with the files it
+// includes.
+// RON itself does the scanning: each value is skipped over with IgnoredAny, and how much text
+// the deserializer has left shows where the value ended.
+
+use ron::de::Deserializer;
+use serde::{de::IgnoredAny, Deserialize};
+
+// a Room with nothing in it, standing in for each Room until it is read
+const STUB: &str = "(name:\"\",desc:\"\",paths:[])";
+
+// a World of one such Room, standing in for a World read on its own
+const WORLD_STUB: &str = "(curr_room:\"\",rooms:{\"\":(name:\"\",desc:\"\",paths:[])})";
+
+// how much text after a position the deserializer is first given
+const WINDOW: usize = 256;
+
+// read from a position with a deserializer, giving what was read and where it stopped; asking
+// ron where it stopped costs as much as the text it was given, so it is given a window of the
+// text, ending just past a closing bracket so no value can be cut short and still read, and the
+// window grows until what is read fits inside it
+fn scan<T>(
+    src: &str,
+    pos: usize,
+    read: impl Fn(&mut Deserializer) -> Option<T>,
+) -> Option<(T, usize)> {
+    let mut len = WINDOW;
+    loop {
+        let mut end = (pos + len).min(src.len());
+        while !src.is_char_boundary(end) {
+            end += 1;
+        }
+        let end = src[end..]
+            .find([')', ']', '}'])
+            .map_or(src.len(), |i| end + i + 1);
+        let read = Deserializer::from_str(&src[pos..end])
+            .ok()
+            .and_then(|mut de| Some((read(&mut de)?, de.remainder().len())));
+        match read {
+            // running into the end of the window may mean it was too small
+            Some((value, rest)) if rest > 0 || end == src.len() => {
+                return Some((value, end - rest))
+            }
+            _ if end == src.len() => return None,
+            _ => len *= 2,
+        }
+    }
+}
+
+// where the rest of the text starts, past whitespace and comments
+fn skip_trivia(src: &str, pos: usize) -> Option<usize> {
+    scan(src, pos, |_| Some(())).map(|((), end)| end)
+}
+
+// where the value starting at a position ends
+fn value_end(src: &str, pos: usize) -> Option<usize> {
+    scan(src, pos, |de| IgnoredAny::deserialize(de).ok()).map(|(_, end)| end)
+}
+
+// the string at a position, and where it ends
+fn string(src: &str, pos: usize) -> Option<(String, usize)> {
+    scan(src, pos, |de| String::deserialize(de).ok())
+}
+
+// the field name at a position, and where it ends
+fn ident(src: &str, pos: usize) -> Option<(&str, usize)> {
+    let rest = &src[pos..];
+    let len = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    (len > 0).then(|| (&rest[..len], pos + len))
+}
+
+// just past the punctuation that comes next, if it is what is expected
+fn punct(src: &str, pos: usize, expected: char) -> Option<usize> {
+    let pos = skip_trivia(src, pos)?;
+    src[pos..].starts_with(expected).then(|| pos + 1)
+}
+
+// where the value of a field starts, in the struct starting at a position, named or not
+fn field(src: &str, pos: usize, name: &str) -> Option<usize> {
+    let start = skip_trivia(src, pos)?;
+    let start = ident(src, start).map_or(start, |(_, after)| after);
+    let mut pos = punct(src, start, '(')?;
+    loop {
+        let (key, after) = ident(src, skip_trivia(src, pos)?)?;
+        let value = skip_trivia(src, punct(src, after, ':')?)?;
+        if key == name {
+            return Some(value);
+        }
+        pos = punct(src, value_end(src, value)?, ',')?;
+    }
+}
+
+// where the World starts, under the "world" key
+pub(crate) fn world_start(src: &str) -> Option<usize> {
+    field(src, 0, "world")
+}
+
+// the file with an empty stand-in for the World that starts and ends at these positions
+pub(crate) fn stub_world(src: &str, start: usize, end: usize) -> String {
+    format!("{}{}{}", &src[..start], WORLD_STUB, &src[end..])
+}
+
+// where the map of Rooms opens, under the "rooms" key of the World under the "world" key
+fn rooms_open(src: &str) -> Option<usize> {
+    let world = world_start(src)?;
+    let rooms = field(src, world, "rooms")?;
+    src[rooms..].starts_with('{').then_some(rooms)
+}
+
+// the world file with an empty stand-in for each Room, and the text of each Room by name;
+// None if the file is not laid out as expected, which a full parse will explain
+pub(crate) fn split_rooms(src: &str) -> Option<(String, Vec<(String, String)>)> {
+    let open = rooms_open(src)?;
+    let mut stubbed = String::with_capacity(src.len() / 8);
+    stubbed.push_str(&src[..=open]);
+    let mut rooms = Vec::new();
+    let mut pos = open + 1;
+    loop {
+        let start = skip_trivia(src, pos)?;
+        if src[start..].starts_with('}') {
+            stubbed.push_str(&src[start..]);
+            return Some((stubbed, rooms));
+        }
+        let (name, after) = string(src, start)?;
+        let body = skip_trivia(src, punct(src, after, ':')?)?;
+        let end = value_end(src, body)?;
+        stubbed.push_str(&src[start..after]);
+        stubbed.push(':');
+        stubbed.push_str(STUB);
+        stubbed.push(',');
+        rooms.push((name, src[body..end].to_owned()));
+        pos = punct(src, end, ',').unwrap_or(end);
+    }
+}
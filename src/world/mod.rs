@@ -1,50 +1,744 @@
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::{collections::VecDeque, convert::TryFrom, path::Path};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "serde")]
+use ron::de::Deserializer;
+#[cfg(feature = "serde")]
+use serde::{de::Error as _, ser::SerializeStruct, Deserialize, Serialize, Serializer};
 
 use crate::{
-    entity::{Closeable, Element, Enemy, Entity, Item, Lockable, Room},
-    types::{Action, Attack, CmdResult, Items, Rooms},
+    dice_roll,
+    entity::{Ally, Closeable, Element, Enemy, Entity, Item, Lockable, Pathway, Pet, Room},
+    input::{named, same_name},
+    opposed_roll, prose,
+    types::{
+        Action, Attack, Bestiary, Calendar, CmdResult, Cue, Deck, Deed, Direction, Director,
+        EnemyId, Finding, Happening, Hazard, Items, Listener, LootTable, LootTables, Order, Quest,
+        QuestGraph, Quests, Recipe, Recipes, Region, Regions, Reply, Responses, RoomId, Rooms,
+        Rumble, SavingThrow, Sequence, Tie, WorldStats,
+    },
 };
 
+#[cfg(feature = "serde")]
+use crate::types::{LoadError, WorldFormat};
+#[cfg(feature = "serde")]
+use include::ReadInclude;
+
+mod generator;
+mod import;
+#[cfg(feature = "serde")]
+pub(crate) mod include;
+#[cfg(feature = "serde")]
+pub(crate) mod lazy;
+pub(crate) mod namespace;
+
 // Represents a world for the player to explore that consists of a grid of Rooms.
 // A World is a graph data structure that encapsulates a collection of Room nodes.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+// Rooms are referred to by name in world files and by RoomId once loaded
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "WorldFile"))]
 pub struct World {
+    curr_room: RoomId,
+    rooms: Rooms,
+    loot_tables: LootTables,
+    regions: Regions,
+    recipes: Recipes,
+    shrine: Option<RoomId>,
+    // audio cues raised since the last time they were taken
+    cues: Vec<Cue>,
+    // a debug cheat that lets the player walk through closed and locked paths
+    noclip: bool,
+    // a long journey cut short: where it set out from, the way, and the turns still to go
+    journey: Option<(RoomId, String, u32)>,
+    // turns spent travelling beyond the one each command takes
+    elapsed: u32,
+    // turns passed in the World, for things that grow back
+    clock: u32,
+    // nudges the chance of wandering and waylaying Enemies to the pace of the run
+    director: Option<Director>,
+    // whether the player can see their karma with the karma command
+    shows_karma: bool,
+    // saving is refused while enemies are upon the player and in dangerous Rooms
+    restrict_saves: bool,
+    // what is said for verbs that do nothing, wherever a Room does not say otherwise
+    responses: Responses,
+    // what is said for verbs done to particular things, wherever a Room does not say otherwise
+    replies: Vec<Reply>,
+    // days and festivals counted off the clock, and story events set on them
+    calendar: Option<Calendar>,
+    // happenings drawn now and then while the player is out in a region
+    deck: Option<Deck>,
+    // the quests of the World and the quests each needs done first
+    quests: Quests,
+    // Rooms read from a lazily loaded world file, least recently entered first
+    #[cfg(feature = "serde")]
+    hydrated: VecDeque<RoomId>,
+    // how many of those Rooms to keep read before putting the oldest back into text, or 0 to
+    // keep every Room once read
+    #[cfg(feature = "serde")]
+    hydrate_limit: usize,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "World")]
+struct WorldFile {
     curr_room: String,
     rooms: Rooms,
+    #[serde(default)]
+    loot_tables: LootTables,
+    #[serde(default)]
+    regions: Regions,
+    #[serde(default)]
+    recipes: Recipes,
+    #[serde(default)]
+    clock: u32,
+    #[serde(default)]
+    shrine: Option<String>,
+    #[serde(default)]
+    director: Option<Director>,
+    #[serde(default)]
+    shows_karma: bool,
+    #[serde(default)]
+    restrict_saves: bool,
+    #[serde(default)]
+    responses: Responses,
+    #[serde(default)]
+    replies: Vec<Reply>,
+    #[serde(default)]
+    calendar: Option<Calendar>,
+    #[serde(default)]
+    deck: Option<Deck>,
+    #[serde(default)]
+    quests: Quests,
+    // other files holding more of the World, relative to this one
+    #[serde(default)]
+    include: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<WorldFile> for World {
+    type Error = String;
+
+    // a World read on its own, rather than by `World::read_at`, includes files relative to the
+    // working directory
+    fn try_from(mut file: WorldFile) -> Result<Self, Self::Error> {
+        include::resolve(&mut file, Path::new(""), &include::read_file)?;
+        let room_id = |name: &str| {
+            file.rooms
+                .id(name)
+                .ok_or_else(|| format!("{} is not a valid room", name))
+        };
+        Ok(Self {
+            curr_room: room_id(&file.curr_room)?,
+            shrine: file.shrine.as_deref().map(room_id).transpose()?,
+            rooms: file.rooms,
+            loot_tables: file.loot_tables,
+            regions: file.regions,
+            recipes: file.recipes,
+            clock: file.clock,
+            director: file.director,
+            shows_karma: file.shows_karma,
+            restrict_saves: file.restrict_saves,
+            responses: file.responses,
+            replies: file.replies,
+            calendar: file.calendar,
+            deck: file.deck,
+            quests: file.quests,
+            cues: Vec::new(),
+            noclip: false,
+            journey: None,
+            elapsed: 0,
+            hydrated: VecDeque::new(),
+            hydrate_limit: 0,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl World {
+    // read the World whose text starts at a position in a file of a whole game, reading the
+    // files it includes with `read`, relative to `main`; gives where its text ends
+    pub(crate) fn read_at(
+        src: &str,
+        start: usize,
+        main: &Path,
+        read: &ReadInclude,
+    ) -> Result<(Self, usize), ron::Error> {
+        // spaces stand in for the text before it, so errors give the line and column in the file
+        let mut text: String = src[..start]
+            .chars()
+            .map(|c| if c == '\n' { c } else { ' ' })
+            .collect();
+        text.push_str(&src[start..]);
+        let mut de = Deserializer::from_str(&text)?;
+        let mut file = WorldFile::deserialize(&mut de)?;
+        let end = src.len() - de.remainder().len();
+        include::resolve(&mut file, main, read).map_err(ron::Error::custom)?;
+        let world = Self::try_from(file).map_err(ron::Error::custom)?;
+        Ok((world, end))
+    }
+}
+
+// everything in a World but its Rooms, for stores that keep each Room on its own
+#[cfg(feature = "sqlite")]
+#[derive(Deserialize, Serialize)]
+pub(crate) struct WorldHeader {
+    curr_room: String,
+    loot_tables: LootTables,
+    regions: Regions,
+    recipes: Recipes,
+    clock: u32,
+    shrine: Option<String>,
+    director: Option<Director>,
+    shows_karma: bool,
+    restrict_saves: bool,
+    responses: Responses,
+    replies: Vec<Reply>,
+    calendar: Option<Calendar>,
+    deck: Option<Deck>,
+    quests: Quests,
+}
+
+#[cfg(feature = "sqlite")]
+impl World {
+    pub(crate) fn header(&self) -> WorldHeader {
+        WorldHeader {
+            curr_room: self.rooms.name(self.curr_room).to_owned(),
+            loot_tables: self.loot_tables.clone(),
+            regions: self.regions.clone(),
+            recipes: self.recipes.clone(),
+            clock: self.clock,
+            shrine: self.shrine.map(|id| self.rooms.name(id).to_owned()),
+            director: self.director.clone(),
+            shows_karma: self.shows_karma,
+            restrict_saves: self.restrict_saves,
+            responses: self.responses.clone(),
+            replies: self.replies.clone(),
+            calendar: self.calendar.clone(),
+            deck: self.deck.clone(),
+            quests: self.quests.clone(),
+        }
+    }
+
+    // the Rooms added or changed since an earlier copy of this World was taken, or every Room
+    // without one
+    pub(crate) fn rooms_changed_since<'a>(
+        &'a self,
+        earlier: Option<&'a World>,
+    ) -> Vec<(&'a str, &'a Room)> {
+        match earlier {
+            Some(earlier) => self.rooms.changed_since(&earlier.rooms).collect(),
+            None => self.rooms.iter_named().collect(),
+        }
+    }
+
+    // put a World back together from its header and its Rooms
+    pub(crate) fn from_parts(
+        header: WorldHeader,
+        rooms: Vec<(String, Room)>,
+    ) -> Result<Self, String> {
+        let mut arena = Rooms::default();
+        for (name, room) in rooms {
+            arena.insert(&name, room);
+        }
+        Self::try_from(WorldFile {
+            curr_room: header.curr_room,
+            rooms: arena,
+            loot_tables: header.loot_tables,
+            regions: header.regions,
+            recipes: header.recipes,
+            clock: header.clock,
+            shrine: header.shrine,
+            director: header.director,
+            shows_karma: header.shows_karma,
+            restrict_saves: header.restrict_saves,
+            responses: header.responses,
+            replies: header.replies,
+            calendar: header.calendar,
+            deck: header.deck,
+            quests: header.quests,
+            include: Vec::new(),
+        })
+    }
+}
+
+// a Room kept as text by a lazily loaded World, read the first time it is needed
+#[cfg(feature = "serde")]
+fn read_room(source: &str) -> ron::Result<Room> {
+    ron::de::from_str(source)
+}
+
+#[cfg(feature = "serde")]
+impl World {
+    // keep Rooms as their text until they are needed, holding at most `limit` entered Rooms
+    // read at once, or every Room read once needed if it is 0; only the Room the player starts
+    // in is read here, so any other Room that is broken is reported when it is first read
+    pub(crate) fn read_lazily(
+        &mut self,
+        rooms: Vec<(String, String)>,
+        limit: usize,
+    ) -> Result<(), LoadError> {
+        self.rooms.read_with(read_room);
+        for (name, source) in rooms {
+            if let Some(id) = self.rooms.id(&name) {
+                self.rooms.set_source(id, &source);
+            }
+        }
+        self.hydrate_limit = limit;
+        self.keep_hydrated(self.curr_room);
+        self.get_curr_room();
+        self.broken_room().map_or(Ok(()), Err)
+    }
+
+    // whether Rooms are kept as text until they are needed
+    pub(crate) fn reads_lazily(&self) -> bool {
+        self.rooms.reads_lazily()
+    }
+
+    // the error for the Room the player is in, if its text could not be read
+    pub(crate) fn broken_room(&self) -> Option<LoadError> {
+        self.rooms
+            .error(self.curr_room)
+            .map(|source| LoadError::Room {
+                name: self.rooms.name(self.curr_room).to_owned(),
+                source: source.clone(),
+            })
+    }
+
+    // mark a Room as the most recently entered, putting the least recently entered Rooms
+    // back into text once there are too many read
+    fn keep_hydrated(&mut self, id: RoomId) {
+        if self.hydrate_limit == 0 {
+            return;
+        }
+        self.hydrated.retain(|room| *room != id);
+        self.hydrated.push_back(id);
+        while self.hydrated.len() > self.hydrate_limit {
+            if let Some(oldest) = self.hydrated.pop_front() {
+                self.rooms
+                    .unread(oldest, |room| ron::ser::to_string(room).ok());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for World {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut world = serializer.serialize_struct("World", 15)?;
+        world.serialize_field("curr_room", self.rooms.name(self.curr_room))?;
+        world.serialize_field("rooms", &self.rooms)?;
+        world.serialize_field("loot_tables", &self.loot_tables)?;
+        world.serialize_field("regions", &self.regions)?;
+        world.serialize_field("recipes", &self.recipes)?;
+        world.serialize_field("clock", &self.clock)?;
+        world.serialize_field("shrine", &self.shrine.map(|id| self.rooms.name(id)))?;
+        world.serialize_field("director", &self.director)?;
+        world.serialize_field("shows_karma", &self.shows_karma)?;
+        world.serialize_field("restrict_saves", &self.restrict_saves)?;
+        world.serialize_field("responses", &self.responses)?;
+        world.serialize_field("replies", &self.replies)?;
+        world.serialize_field("calendar", &self.calendar)?;
+        world.serialize_field("deck", &self.deck)?;
+        world.serialize_field("quests", &self.quests)?;
+        world.end()
+    }
+}
+
+// world files hold a World under the same key that a Cli reads it from
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct ExportFile<'a> {
+    world: &'a World,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        let mut rooms = Rooms::default();
+        let curr_room = rooms.insert("", Room::default());
+        Self {
+            curr_room,
+            rooms,
+            loot_tables: LootTables::new(),
+            regions: Regions::new(),
+            recipes: Recipes::new(),
+            shrine: None,
+            cues: Vec::new(),
+            noclip: false,
+            journey: None,
+            elapsed: 0,
+            clock: 0,
+            director: None,
+            shows_karma: false,
+            restrict_saves: false,
+            responses: Responses::default(),
+            replies: Vec::new(),
+            calendar: None,
+            deck: None,
+            quests: Quests::new(),
+            #[cfg(feature = "serde")]
+            hydrated: VecDeque::new(),
+            #[cfg(feature = "serde")]
+            hydrate_limit: 0,
+        }
+    }
 }
 
 impl World {
+    // a World of one Room, where the player starts
+    pub fn new(start: &str, desc: &str) -> Self {
+        let mut rooms = Rooms::default();
+        let curr_room = rooms.insert(start, Room::new(start, desc));
+        Self {
+            curr_room,
+            rooms,
+            ..Self::default()
+        }
+    }
+
     pub fn get_curr_room(&self) -> &Room {
-        if let Some(room) = self.rooms.get(&self.curr_room) {
-            room
-        } else {
-            panic!(
-                "ERROR: {} is not a valid room (The world should be fixed).",
-                self.curr_room
-            )
+        self.rooms.get(self.curr_room)
+    }
+
+    // every distinct kind of Enemy in the World
+    pub fn enemy_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for enemy in self.rooms.iter().flat_map(|room| room.enemies()) {
+            if !names.iter().any(|name| name == enemy.name()) {
+                names.push(enemy.name().to_owned());
+            }
         }
+        names
+    }
+
+    // every distinct kind of Item lying in the World
+    pub fn item_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for item in self.rooms.iter().flat_map(|room| room.items()) {
+            if !names.iter().any(|name| name == item.name()) {
+                names.push(item.name().to_owned());
+            }
+        }
+        names
     }
 
     pub fn get_curr_room_mut(&mut self) -> &mut Room {
-        if let Some(room) = self.rooms.get_mut(&self.curr_room) {
-            room
-        } else {
-            panic!(
-                "ERROR: {} is not a valid room (The world should be fixed).",
-                self.curr_room
-            )
+        self.rooms.get_mut(self.curr_room)
+    }
+
+    // a broken world can name Rooms that do not exist, which `validate` reports instead
+    fn room_id(&self, room_name: &str) -> Option<RoomId> {
+        let id = self.rooms.id(room_name);
+        if id.is_none() {
+            debug!("{} is not a valid room", room_name);
         }
+        id
     }
 
-    fn get_room_mut(&mut self, room_name: &str) -> &mut Room {
-        if let Some(room) = self.rooms.get_mut(room_name) {
-            room
-        } else {
-            panic!(
-                "ERROR: {} is not a valid room (The world should be fixed).",
-                room_name
-            )
+    fn get_room_mut(&mut self, room_name: &str) -> Option<&mut Room> {
+        let id = self.room_id(room_name)?;
+        Some(self.rooms.get_mut(id))
+    }
+
+    // find every problem in the world, checking Rooms in parallel for large worlds
+    pub fn validate(&self) -> Vec<String> {
+        let is_room = |name: &str| self.rooms.id(name).is_some();
+        let is_loot_table = |name: &str| self.loot_tables.contains_key(name);
+
+        #[cfg(feature = "parallel")]
+        let mut problems: Vec<String> = self
+            .rooms
+            .par_iter()
+            .flat_map_iter(|room| room.validate(is_room, is_loot_table))
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let mut problems: Vec<String> = self
+            .rooms
+            .iter()
+            .flat_map(|room| room.validate(is_room, is_loot_table))
+            .collect();
+        #[cfg(feature = "serde")]
+        problems.extend(self.rooms.errors().map(|(name, source)| {
+            LoadError::Room {
+                name: name.to_owned(),
+                source: source.clone(),
+            }
+            .to_string()
+        }));
+
+        problems.sort();
+        problems
+    }
+
+    // problems and loose ends for world authors: broken references with suggested fixes,
+    // rooms the player can never reach, and content nothing uses
+    pub fn lint(&self) -> Vec<Finding> {
+        let room_names: Vec<&str> = self.rooms.iter_named().map(|(name, _)| name).collect();
+        let table_names: Vec<&str> = self.loot_tables.keys().map(String::as_str).collect();
+        let region_names: Vec<&str> = self.regions.keys().map(String::as_str).collect();
+        let mut findings = Vec::new();
+
+        for (name, room) in self.rooms.iter_named() {
+            for target in room.neighbors() {
+                if self.rooms.id(target).is_none() {
+                    findings.push(Finding::error(
+                        "unknown-room",
+                        format!(
+                            "{}: a path leads to {}, which is not a room{}",
+                            name,
+                            target,
+                            did_you_mean(target, &room_names)
+                        ),
+                    ));
+                }
+            }
+            for direction in room.paths().iter().flat_map(|path| path.directions()) {
+                if let Some(meant) = misspelled(direction) {
+                    findings.push(Finding::warning(
+                        "misspelled-direction",
+                        format!(
+                            "{}: a path leads {}, which is not a direction (did you mean {}?)",
+                            name, direction, meant
+                        ),
+                    ));
+                }
+            }
+            for view in room.views() {
+                if self.rooms.id(view).is_none() {
+                    findings.push(Finding::error(
+                        "unknown-room",
+                        format!(
+                            "{}: a window looks into {}, which is not a room{}",
+                            name,
+                            view,
+                            did_you_mean(view, &room_names)
+                        ),
+                    ));
+                }
+            }
+            for breach in room.breaches() {
+                if self.rooms.id(breach).is_none() {
+                    findings.push(Finding::error(
+                        "unknown-room",
+                        format!(
+                            "{}: a breakable floor or ceiling opens into {}, which is not a room{}",
+                            name,
+                            breach,
+                            did_you_mean(breach, &room_names)
+                        ),
+                    ));
+                }
+            }
+            if let Some(region) = room.region() {
+                if !self.regions.contains_key(region) {
+                    findings.push(Finding::error(
+                        "unknown-region",
+                        format!(
+                            "{}: the room lies in the region {}, which does not exist{}",
+                            name,
+                            region,
+                            did_you_mean(region, &region_names)
+                        ),
+                    ));
+                }
+            }
+            for (poser, riddle) in room.riddles() {
+                for pattern in riddle.bad_patterns() {
+                    findings.push(Finding::error(
+                        "bad-pattern",
+                        format!(
+                            "{}: the {} accepts answers like {}, which is not a regular expression",
+                            name, poser, pattern
+                        ),
+                    ));
+                }
+            }
+            for enemy in room.enemies() {
+                if let Some(table) = enemy.loot_table() {
+                    if !self.loot_tables.contains_key(table) {
+                        findings.push(Finding::error(
+                            "unknown-loot-table",
+                            format!(
+                                "{}: the {} uses the loot table {}, which does not exist{}",
+                                name,
+                                enemy.name(),
+                                table,
+                                did_you_mean(table, &table_names)
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // walk every path from the starting Room
+        let reached: Vec<RoomId> = self.distances().into_iter().map(|(id, _)| id).collect();
+        for (name, room) in self.rooms.iter_named() {
+            if room.is_camp() || matches!(self.rooms.id(name), Some(id) if reached.contains(&id)) {
+                continue;
+            }
+            findings.push(Finding::warning(
+                "unreachable-room",
+                format!("{}: no path leads here from the start", name),
+            ));
+            for item in room.items() {
+                findings.push(Finding::warning(
+                    "unused-item",
+                    format!(
+                        "{}: the {} lies in a room that cannot be reached",
+                        name,
+                        item.name()
+                    ),
+                ));
+            }
+        }
+
+        let quest_names: Vec<&str> = self.quests.keys().map(String::as_str).collect();
+        for (name, quest) in &self.quests {
+            for required in quest.requires() {
+                if !self.quests.contains_key(required) {
+                    findings.push(Finding::error(
+                        "unknown-quest",
+                        format!(
+                            "the quest {} requires the quest {}, which does not exist{}",
+                            name,
+                            required,
+                            did_you_mean(required, &quest_names)
+                        ),
+                    ));
+                }
+            }
         }
+        for cycle in self.quest_graph().cycles() {
+            findings.push(Finding::error(
+                "quest-cycle",
+                format!(
+                    "the quests {} each require the one before, so none can be done",
+                    prose::list(&cycle)
+                ),
+            ));
+        }
+
+        for table in &table_names {
+            let used = self
+                .rooms
+                .iter()
+                .flat_map(|room| room.enemies())
+                .any(|enemy| enemy.loot_table() == Some(*table));
+            if !used {
+                findings.push(Finding::warning(
+                    "unused-loot-table",
+                    format!("the loot table {} is never used", table),
+                ));
+            }
+        }
+        // every Room has been read by now, so any broken one in a lazily loaded world is known
+        #[cfg(feature = "serde")]
+        for (name, err) in self.rooms.errors() {
+            findings.push(Finding::error(
+                "broken-room",
+                format!("{}: the room cannot be read: {}", name, err),
+            ));
+        }
+
+        findings.sort();
+        findings
+    }
+
+    // which quests unlock which, the Rooms where sequences complete them, and who holds the
+    // items each needs
+    pub fn quest_graph(&self) -> QuestGraph {
+        let mut graph = QuestGraph::default();
+        let mut quests: Vec<(&String, &Quest)> = self.quests.iter().collect();
+        quests.sort_by_key(|(name, _)| *name);
+        for (name, quest) in quests {
+            let to = graph.node("quest", name);
+            for required in quest.requires() {
+                let from = graph.node("quest", required);
+                graph.edge(from, to, "unlocks");
+            }
+        }
+        let mut rooms: Vec<(&str, &Room)> = self.rooms.iter_named().collect();
+        rooms.sort_by_key(|(name, _)| *name);
+        for (name, room) in rooms {
+            for quest in room.sequences().iter().filter_map(|seq| seq.completes()) {
+                let from = graph.node("room", name);
+                let to = graph.node("quest", quest);
+                graph.edge(from, to, "completes");
+            }
+            // who holds each quest item here, by kind and name, and how
+            let mut holders: Vec<(&str, String, &str, &Item)> = room
+                .items()
+                .iter()
+                .map(|item| ("room", name.to_owned(), "holds", item.as_ref()))
+                .collect();
+            for enemy in room.enemies() {
+                let enemy_name = format!("{} ({})", enemy.name(), name);
+                holders.extend(
+                    enemy
+                        .loot()
+                        .iter()
+                        .map(|item| ("npc", enemy_name.clone(), "drops", item.as_ref())),
+                );
+            }
+            for ally in room.allies() {
+                let ally_name = format!("{} ({})", ally.name(), name);
+                holders.extend(
+                    ally.carrying()
+                        .iter()
+                        .map(|item| ("npc", ally_name.clone(), "carries", item.as_ref())),
+                );
+            }
+            for (kind, holder, how, item) in holders {
+                if let Some(quest) = item.quest() {
+                    let holder = graph.node(kind, &holder);
+                    let item_node = graph.node("item", item.name());
+                    graph.edge(holder, item_node, how);
+                    let quest = graph.node("quest", quest);
+                    graph.edge(item_node, quest, "needed for");
+                }
+            }
+        }
+        graph
+    }
+
+    // write the World as a world file that a Cli can load again, however it was built
+    #[cfg(feature = "serde")]
+    pub fn to_file(&self, path: &str, format: WorldFormat) -> Result<(), String> {
+        let file = ExportFile { world: self };
+        let data = match format {
+            WorldFormat::Ron => ron::ser::to_string(&file),
+            WorldFormat::PrettyRon => {
+                ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::new())
+            }
+        }
+        .map_err(|e| e.to_string())?;
+        std::fs::write(path, data).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    // counts and distributions for gauging a world's scope and balance
+    pub fn stats(&self) -> WorldStats {
+        let mut stats = WorldStats::new();
+        for room in self.rooms.iter() {
+            stats.add_room(room.neighbors().len());
+            stats.add_text(room.desc());
+            for item in room.items() {
+                stats.add_item();
+                stats.add_text(item.desc());
+                stats.add_text(item.inspect());
+            }
+            for enemy in room.enemies() {
+                stats.add_enemy(enemy.xp());
+                stats.add_text(enemy.desc());
+                stats.add_text(enemy.inspect());
+            }
+        }
+        stats
     }
 
     // displays description of the current Room
@@ -52,8 +746,187 @@ impl World {
         CmdResult::new(Action::Active, self.get_curr_room().long_desc())
     }
 
-    pub fn inspect(&self, name: &str) -> Option<CmdResult> {
-        self.get_curr_room().inspect(name)
+    // how many rooms away each Room is by any path, closed or not, as sound carries
+    pub fn distances(&self) -> Vec<(RoomId, u32)> {
+        let mut found = vec![(self.curr_room, 0)];
+        let mut next = 0;
+        while next < found.len() {
+            let (id, distance) = found[next];
+            for target in self.rooms.get(id).neighbors() {
+                if let Some(target) = self.rooms.id(target) {
+                    if !found.iter().any(|(seen, _)| *seen == target) {
+                        found.push((target, distance + 1));
+                    }
+                }
+            }
+            next += 1;
+        }
+        found
+    }
+
+    // the way out of this Room that starts the shortest walk to another, for compasses; None
+    // when the Room is here or cannot be reached
+    pub fn way_toward(&self, room: &str) -> Option<Direction> {
+        let goal = self.rooms.id(room).filter(|goal| *goal != self.curr_room)?;
+        let mut found: Vec<(RoomId, Direction)> = Vec::new();
+        for path in self.get_curr_room().paths() {
+            if let (Some(target), Some(direction)) =
+                (self.rooms.id(path.name()), path.directions().first())
+            {
+                if target == goal {
+                    return Some(direction.clone());
+                }
+                if target != self.curr_room && !found.iter().any(|(seen, _)| *seen == target) {
+                    found.push((target, direction.clone()));
+                }
+            }
+        }
+        let mut next = 0;
+        while next < found.len() {
+            let (id, direction) = found[next].clone();
+            for target in self.rooms.get(id).neighbors() {
+                if let Some(target) = self.rooms.id(target) {
+                    if target == goal {
+                        return Some(direction);
+                    }
+                    if target != self.curr_room && !found.iter().any(|(seen, _)| *seen == target) {
+                        found.push((target, direction.clone()));
+                    }
+                }
+            }
+            next += 1;
+        }
+        None
+    }
+
+    // the answers of Listeners in this and nearby Rooms that hear a shout
+    pub fn hear(&mut self, words: &str) -> Vec<String> {
+        let mut replies = Vec::new();
+        for (id, distance) in self.distances() {
+            replies.extend(self.rooms.get_mut(id).hear(words, distance));
+        }
+        replies
+    }
+
+    pub fn shout(&mut self, words: &str) -> CmdResult {
+        let replies = self.hear(words);
+        CmdResult::new(
+            Action::Active,
+            replies
+                .iter()
+                .fold(format!("You shout, \"{}\"", words), |res, reply| {
+                    format!("{}\n{}", res, reply)
+                }),
+        )
+    }
+
+    pub fn add_sequence(&mut self, room: &str, sequence: Sequence) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.add_sequence(sequence);
+        }
+    }
+
+    pub fn follow_sequence(
+        &mut self,
+        verb: &str,
+        obj: &str,
+    ) -> Option<(CmdResult, Option<String>)> {
+        let (said, quest) = self.get_curr_room_mut().follow_sequence(verb, obj)?;
+        Some((CmdResult::new(Action::Active, said), quest))
+    }
+
+    pub fn add_listener(&mut self, room: &str, listener: Listener) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.add_listener(listener);
+        }
+    }
+
+    pub fn knock(&mut self, name: &str) -> CmdResult {
+        match self.get_curr_room_mut().find_path_mut(name) {
+            Some(path) if path.is_door() => path.knock(name),
+            Some(_) => CmdResult::new(Action::Passive, "There is no door there to knock on."),
+            None => CmdResult::no_item_here(name),
+        }
+    }
+
+    pub fn listen(&self, name: &str) -> CmdResult {
+        match self.get_curr_room().find_path(name) {
+            Some(path) if path.is_door() => path.listen(name),
+            Some(_) => CmdResult::new(Action::Passive, "You hear nothing unusual."),
+            None => CmdResult::no_item_here(name),
+        }
+    }
+
+    pub fn look_under(&self, name: &str) -> CmdResult {
+        match self.get_curr_room().find_path(name) {
+            Some(path) if path.is_door() => path.look_under(name),
+            Some(_) => CmdResult::new(Action::Passive, "There is nothing to look under."),
+            None => CmdResult::no_item_here(name),
+        }
+    }
+
+    // see into another Room through a window or keyhole without entering it
+    pub fn look_through(&self, name: &str) -> Option<CmdResult> {
+        let (element, view) = self.get_curr_room().find_view(name)?;
+        let room = self.rooms.get(self.room_id(view)?);
+        Some(CmdResult::new(
+            Action::Passive,
+            format!(
+                "Through the {} you see the {}.\n{}",
+                element,
+                room.name(),
+                room.glimpse()
+            ),
+        ))
+    }
+
+    // peer along an exit without moving, seeing one more room for each step of light radius
+    pub fn look_toward(&self, direction: &str, radius: u32) -> CmdResult {
+        let toward = match Direction::parse(direction) {
+            Direction::Up => String::from("above"),
+            Direction::Down => String::from("below"),
+            compass if compass.is_compass() => format!("to the {}", compass),
+            _ => format!("through the {}", direction),
+        };
+        if radius == 0 {
+            return CmdResult::new(
+                Action::Passive,
+                format!("It is too dark to make out what lies {}.", toward),
+            );
+        }
+
+        let mut room = self.get_curr_room();
+        let mut seen = Vec::new();
+        for _ in 0..radius {
+            match room.find_path(direction) {
+                Some(path) if !path.is_closed() => match self.room_id(path.name()) {
+                    Some(id) => {
+                        room = self.rooms.get(id);
+                        seen.push(room.name().to_owned());
+                    }
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        match seen.split_first() {
+            Some((first, beyond)) => CmdResult::new(
+                Action::Passive,
+                beyond.iter().fold(
+                    format!(
+                        "{} you can make out the {}.",
+                        prose::capitalize(&toward),
+                        first
+                    ),
+                    |res, room| format!("{} Beyond it lies the {}.", res, room),
+                ),
+            ),
+            None => CmdResult::new(Action::Passive, "The way is shut."),
+        }
+    }
+
+    pub fn inspect(&self, name: &str, bestiary: &Bestiary) -> Option<CmdResult> {
+        self.get_curr_room().inspect(name, bestiary)
     }
 
     pub fn any_angry_enemies(&self) -> bool {
@@ -68,18 +941,364 @@ impl World {
     // changes the current Room to the target of the current Room's chosen path
     pub fn move_room(&mut self, direction: &str) -> CmdResult {
         if let Some(path) = self.get_curr_room().find_path(direction) {
-            if path.is_closed() {
+            if path.is_closed() && !self.noclip {
                 CmdResult::new(Action::Active, "The way is shut.")
-            } else if path.is_locked() {
+            } else if path.is_locked() && !self.noclip {
                 CmdResult::is_locked(direction)
+            } else if path.needs_rope()
+                && !self.get_curr_room().is_roped()
+                && !self
+                    .room_id(path.name())
+                    .is_some_and(|id| self.rooms.get(id).is_roped())
+            {
+                CmdResult::new(
+                    Action::Passive,
+                    format!("It is too sheer to climb {} without a rope.", direction),
+                )
             } else if self.any_angry_enemies() {
                 CmdResult::new(Action::Passive, "Enemies bar your way.")
+            } else if let Some(res) = self.get_curr_room().stuck_on_furniture() {
+                res
+            } else if path.turns() > 1 {
+                let turns = path.turns();
+                self.travel(direction, turns)
             } else {
-                self.curr_room = path.name().to_owned();
-                self.look()
+                let target = match self.room_id(self.get_curr_room().destination(path)) {
+                    Some(target) => target,
+                    None => return CmdResult::nowhere(),
+                };
+                let message = path.message().map(str::to_owned);
+                let crossing = self.enter(target);
+                let arrival = self.arrival(crossing);
+                match message {
+                    Some(message) => {
+                        CmdResult::new(Action::Active, format!("{}\n{}", message, arrival))
+                    }
+                    None => CmdResult::new(Action::Active, arrival),
+                }
             }
         } else {
-            CmdResult::new(Action::Passive, "You cannot go that way.")
+            self.cannot_go(direction)
+        }
+    }
+
+    // what is said when there is no way in this direction
+    fn cannot_go(&self, direction: &str) -> CmdResult {
+        CmdResult::new(
+            Action::Passive,
+            self.get_curr_room()
+                .dead_end(direction)
+                .unwrap_or("You cannot go that way."),
+        )
+    }
+
+    // make a journey of several turns along a path, which an encounter on the way can cut short
+    fn travel(&mut self, direction: &str, turns: u32) -> CmdResult {
+        let path = match self.get_curr_room().find_path(direction) {
+            Some(path) => path,
+            None => return CmdResult::new(Action::Passive, "You cannot go that way."),
+        };
+        let target = match self.room_id(self.get_curr_room().destination(path)) {
+            Some(target) => target,
+            None => return CmdResult::nowhere(),
+        };
+        let message = path.message().map(str::to_owned);
+        let encounter = path.encounter().cloned();
+
+        // the path's own encounter comes first, then whatever wanders the region
+        for turn in 1..turns {
+            let enemy = match &encounter {
+                Some(encounter) if dice_roll(1, 100) <= self.adjust(encounter.chance()) => {
+                    Some(encounter.enemy().clone())
+                }
+                _ => self.wanderer(),
+            };
+            if let Some(enemy) = enemy {
+                self.elapsed += turn - 1;
+                self.journey = Some((self.curr_room, direction.to_owned(), turns - turn));
+                let res = format!(
+                    "You set out {}, but {} waylays you on the way!\n{}",
+                    direction,
+                    enemy.the(),
+                    enemy.desc()
+                );
+                self.get_curr_room_mut().spawn_enemy(enemy);
+                return CmdResult::new(Action::Active, res);
+            }
+        }
+
+        self.elapsed += turns - 1;
+        let crossing = self.enter(target);
+        let message = message.unwrap_or_else(|| {
+            format!(
+                "You travel {} for {}.",
+                direction,
+                prose::count(turns as usize, "turn")
+            )
+        });
+        CmdResult::new(
+            Action::Active,
+            format!("{}\n{}", message, self.arrival(crossing)),
+        )
+    }
+
+    // the Room's description, after anything said on crossing into its region
+    fn arrival(&self, crossing: Option<String>) -> String {
+        match crossing {
+            Some(crossing) => format!("{}\n{}", crossing, self.look().output()),
+            None => self.look().output().to_owned(),
+        }
+    }
+
+    // press on with a journey that an encounter cut short
+    pub fn resume(&mut self) -> CmdResult {
+        match self.journey.take() {
+            Some(journey) if journey.0 == self.curr_room && self.any_angry_enemies() => {
+                self.journey = Some(journey);
+                CmdResult::new(Action::Passive, "Enemies bar your way.")
+            }
+            Some((origin, direction, turns)) if origin == self.curr_room => {
+                self.travel(&direction, turns)
+            }
+            _ => CmdResult::new(Action::Passive, "You are not on a journey."),
+        }
+    }
+
+    // turns spent travelling since the last time they were taken
+    pub fn take_elapsed(&mut self) -> u32 {
+        std::mem::take(&mut self.elapsed)
+    }
+
+    // let turns go by, telling of any story events whose day has come
+    pub fn pass_turns(&mut self, turns: u32) -> String {
+        self.clock += turns;
+        let due = match &mut self.calendar {
+            Some(calendar) => calendar.take_due(self.clock),
+            None => return String::new(),
+        };
+        let mut events = String::new();
+        for event in due {
+            if let Some((room, direction)) = event.opens() {
+                if let Some(path) = self
+                    .get_room_mut(room)
+                    .and_then(|room| room.find_path_mut(direction))
+                {
+                    path.swing_open();
+                }
+            }
+            events.push_str(&format!("\n{}", event.text()));
+        }
+        events
+    }
+
+    pub fn set_calendar(&mut self, calendar: Calendar) {
+        self.calendar = Some(calendar);
+    }
+
+    pub fn set_deck(&mut self, deck: Deck) {
+        self.deck = Some(deck);
+    }
+
+    // something from the deck that happens this turn out in a region, if anything does
+    pub fn happening(&mut self) -> Option<Happening> {
+        let room = self.rooms.get(self.curr_room);
+        let region = room.region()?;
+        self.deck
+            .as_mut()?
+            .draw(region, |condition| room.holds(condition))
+    }
+
+    // someone turns up in the current Room
+    pub fn arrive(&mut self, ally: Ally) {
+        self.get_curr_room_mut().summon(ally);
+    }
+
+    // swing open the path in this direction from the current Room
+    pub fn swing_open(&mut self, direction: &str) {
+        if let Some(path) = self.get_curr_room_mut().find_path_mut(direction) {
+            path.swing_open();
+        }
+    }
+
+    pub fn date(&self) -> CmdResult {
+        match &self.calendar {
+            Some(calendar) => CmdResult::new(Action::Passive, calendar.date(self.clock)),
+            None => CmdResult::new(Action::Passive, "You have lost track of the days."),
+        }
+    }
+
+    // do the verb to an Element here with a carried tool, leaving what it turns up in the Room
+    pub fn use_tool(&mut self, name: &str, verb: &str) -> CmdResult {
+        match self.get_curr_room_mut().use_tool(name, verb) {
+            Some(tool_use) => {
+                let res = CmdResult::new(Action::Active, tool_use.success());
+                for item in tool_use.into_yields() {
+                    self.get_curr_room_mut().add_item(*item);
+                }
+                res
+            }
+            None => CmdResult::no_item_here(name),
+        }
+    }
+
+    // gather materials from a bush, fishing spot, or vein here
+    pub fn gather(&mut self, name: &str) -> (CmdResult, Items) {
+        let el_name = match self.get_curr_room().find_harvest(name) {
+            Some((el_name, _)) => el_name.to_owned(),
+            None => return (CmdResult::no_item_here(name), Items::new()),
+        };
+        let clock = self.clock;
+        match self.get_curr_room_mut().gather(name, clock) {
+            Some(Ok(items)) => {
+                let mut names: Vec<(&str, usize)> = Vec::new();
+                for item in items.iter() {
+                    match names.iter_mut().find(|(name, _)| *name == item.name()) {
+                        Some(entry) => entry.1 += 1,
+                        None => names.push((item.name(), 1)),
+                    }
+                }
+                let names: Vec<String> = names
+                    .into_iter()
+                    .map(|(name, count)| {
+                        if count > 1 {
+                            prose::count(count, name)
+                        } else {
+                            prose::a(name)
+                        }
+                    })
+                    .collect();
+                let res = format!("You gather {} from the {}.", prose::list(&names), el_name);
+                (CmdResult::new(Action::Active, res), items)
+            }
+            Some(Err(turns)) => (
+                CmdResult::new(
+                    Action::Passive,
+                    format!(
+                        "The {} has nothing more to give. It will be ready again in {}.",
+                        el_name,
+                        prose::count(turns as usize, "turn")
+                    ),
+                ),
+                Items::new(),
+            ),
+            None => (CmdResult::no_item_here(name), Items::new()),
+        }
+    }
+
+    // why a camp cannot be made here, if it cannot
+    pub fn camp_blocked(&self) -> Option<CmdResult> {
+        let room = self.get_curr_room();
+        if room.is_camp() {
+            Some(CmdResult::new(
+                Action::Passive,
+                "You have already made camp.",
+            ))
+        } else if room.region().is_none() {
+            Some(CmdResult::new(
+                Action::Passive,
+                "You can only make camp out in the wilds.",
+            ))
+        } else if self.any_angry_enemies() {
+            Some(CmdResult::new(
+                Action::Passive,
+                "You cannot make camp with enemies about.",
+            ))
+        } else {
+            None
+        }
+    }
+
+    // make camp beside the current Room and settle into it
+    pub fn make_camp(&mut self, tent: bool, fire: bool) -> CmdResult {
+        let origin = self.rooms.name(self.curr_room).to_owned();
+        let camp = self.rooms.insert(
+            &format!("{} (camp)", origin),
+            Room::camp(&origin, tent, fire),
+        );
+        self.enter(camp);
+        let res = match (tent, fire) {
+            (true, true) => "You pitch your tent and light a campfire.",
+            (true, false) => "You pitch your tent.",
+            _ => "You light a campfire.",
+        };
+        CmdResult::new(Action::Active, format!("{}\n{}", res, self.look().output()))
+    }
+
+    fn set_curr_room(&mut self, id: RoomId) {
+        self.curr_room = id;
+        #[cfg(feature = "serde")]
+        self.keep_hydrated(id);
+    }
+
+    // move the player into another Room, with any summoned Allies and tame Pets following;
+    // leaving cuts off any journey in progress, and a camp is packed up behind them. Crossing
+    // into another region raises its cue and gives back what is said on the way in; a camp
+    // lies in no region, but pitching or leaving one crosses no border
+    fn enter(&mut self, target: RoomId) -> Option<String> {
+        self.journey = None;
+        let left = self.get_curr_room().region().map(str::to_owned);
+        let camping = self.get_curr_room().is_camp() || self.rooms.get(target).is_camp();
+        let summoned = self.get_curr_room_mut().take_followers();
+        let pets = self.get_curr_room_mut().take_tame();
+        if self.get_curr_room().is_camp() {
+            self.get_curr_room_mut().pack_up();
+        }
+        self.set_curr_room(target);
+        for ally in summoned {
+            self.get_curr_room_mut().summon(*ally);
+        }
+        for pet in pets {
+            self.get_curr_room_mut().add_pet(*pet);
+        }
+
+        let region = self.get_curr_room().region()?.to_owned();
+        if camping || left.as_deref() == Some(region.as_str()) {
+            return None;
+        }
+        self.cue(Cue::Region(region.clone()));
+        self.regions
+            .get(&region)?
+            .transition(left.as_deref())
+            .map(str::to_owned)
+    }
+
+    pub fn set_noclip(&mut self, noclip: bool) {
+        self.noclip = noclip;
+    }
+
+    // copies of every Item lying anywhere in the World
+    pub fn all_items(&self) -> Items {
+        self.rooms
+            .iter()
+            .flat_map(|room| room.items().iter().cloned())
+            .collect()
+    }
+
+    pub fn cue(&mut self, cue: Cue) {
+        self.cues.push(cue);
+    }
+
+    pub fn take_cues(&mut self) -> Vec<Cue> {
+        std::mem::take(&mut self.cues)
+    }
+
+    pub fn set_music(&mut self, room: &str, cue: &str) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_music(cue);
+        }
+    }
+
+    // a door's sound plays only when it actually opens or closes
+    fn path_sound(&mut self, name: &str, res: &CmdResult) {
+        if res.is_active() {
+            let sound = self
+                .get_curr_room()
+                .find_path(name)
+                .and_then(|path| path.sound())
+                .map(str::to_owned);
+            if let Some(sound) = sound {
+                self.cue(Cue::Sound(sound));
+            }
         }
     }
 
@@ -88,11 +1307,431 @@ impl World {
     }
 
     pub fn open(&mut self, name: &str) -> CmdResult {
-        self.get_curr_room_mut().open(name)
+        let res = self.get_curr_room_mut().open(name);
+        self.path_sound(name, &res);
+        res
+    }
+
+    pub fn take_trap(&mut self, name: &str) -> Option<SavingThrow> {
+        let trap = self.get_curr_room_mut().take_trap(name);
+        if trap.is_some() {
+            self.cue(Cue::Rumble(Rumble::Medium));
+        }
+        trap
     }
 
     pub fn close(&mut self, name: &str) -> CmdResult {
-        self.get_curr_room_mut().close(name)
+        let res = self.get_curr_room_mut().close(name);
+        self.path_sound(name, &res);
+        res
+    }
+
+    // badly hurt or leaderless Enemies may flee to an adjacent Room or surrender
+    pub fn check_morale(&mut self) -> String {
+        let routes = self.get_curr_room().escape_routes();
+        let allies_fell = self
+            .get_curr_room()
+            .enemies()
+            .iter()
+            .any(|enemy| !enemy.is_alive());
+
+        let enemies = self.get_curr_room().enemies();
+        let breaking: Vec<EnemyId> = enemies
+            .iter()
+            .zip(enemies.keys())
+            .filter(|(enemy, _)| {
+                enemy.is_alive() && enemy.is_angry() && enemy.morale_breaks(allies_fell)
+            })
+            .map(|(_, key)| key)
+            .collect();
+
+        let mut events = String::new();
+        for key in breaking.into_iter().rev() {
+            let can_flee = self
+                .get_curr_room()
+                .enemies()
+                .get_key(key)
+                .is_some_and(Enemy::can_flee);
+            if routes.is_empty() || !can_flee || dice_roll(1, 2) == 1 {
+                events.push_str(&self.get_curr_room_mut().surrender(key));
+            } else if let Some(enemy) = self.get_curr_room_mut().enemies_mut().remove_key(key) {
+                let target = &routes[dice_roll(1, routes.len() as u32) as usize - 1];
+                events.push_str(&format!("\n{} flees!", prose::capitalize(&enemy.the())));
+                let target = target.to_owned();
+                self.force_enemy(&target, enemy);
+            }
+        }
+        events
+    }
+
+    pub fn summon(&mut self, ally: Ally) {
+        self.get_curr_room_mut().summon(ally);
+    }
+
+    // Allies in the current Room take their turn doing as they were told and fighting alongside
+    // the player
+    pub fn ally_turn(&mut self) -> String {
+        let room = self.rooms.get_mut(self.curr_room);
+        format!(
+            "{}{}{}",
+            room.ally_orders(),
+            room.ally_attacks(&self.loot_tables),
+            room.tick_allies()
+        )
+    }
+
+    // summoned Allies in the current Room run out of time without fighting
+    pub fn fade_allies(&mut self) -> String {
+        self.get_curr_room_mut().tick_allies()
+    }
+
+    pub fn grapple(&mut self, enemy_name: &str, strength_mod: i32) -> CmdResult {
+        self.get_curr_room_mut().grapple(enemy_name, strength_mod)
+    }
+
+    // push or pull a heavy Element through a path, following it into the next Room,
+    // e.g. "boulder north"; nothing happens unless such an Element is named
+    pub fn push_object(&mut self, verb: &str, obj: &str) -> Option<CmdResult> {
+        let words: Vec<&str> = obj.split_whitespace().collect();
+
+        for split in 1..words.len() {
+            let name = words[..split].join(" ");
+            let direction = words[split..]
+                .iter()
+                .skip_while(|word| ["off", "into", "over"].contains(word))
+                .copied()
+                .collect::<Vec<&str>>()
+                .join(" ");
+
+            let path = match self.get_curr_room().find_path(&direction) {
+                Some(path) => path,
+                None => continue,
+            };
+            let target = match self.room_id(path.name()) {
+                Some(target) => target,
+                None => continue,
+            };
+            let shut = (path.is_closed() || path.is_locked()) && !self.noclip;
+            let el = match self.get_curr_room_mut().remove_heavy(&name) {
+                Some(el) => el,
+                None => continue,
+            };
+            if shut || self.any_angry_enemies() {
+                let res = if shut {
+                    "The way is shut."
+                } else {
+                    "Enemies bar your way."
+                };
+                self.get_curr_room_mut().add_element(*el);
+                return Some(CmdResult::new(Action::Passive, res));
+            }
+
+            let mut res = format!("You {} the {} {}.\n", verb, el.name(), direction);
+            let crossing = self.enter(target);
+            let settled = self.get_curr_room_mut().settle(el);
+            res.push_str(&self.arrival(crossing));
+            if let Some(reply) = settled {
+                res.push('\n');
+                res.push_str(&reply);
+            }
+            return Some(CmdResult::new(Action::Active, res));
+        }
+        None
+    }
+
+    // strike a breakable floor or ceiling; once it gives way a new path opens through it,
+    // and without a ladder to climb back the player falls through
+    pub fn smash(&mut self, name: &str, damage: u32) -> Option<CmdResult> {
+        let (el_name, breach) = self.get_curr_room_mut().strike(name, damage)?;
+        let breach = match breach {
+            Some(breach) => breach,
+            None => {
+                return Some(CmdResult::new(
+                    Action::Active,
+                    format!("You strike the {}, but it holds.", el_name),
+                ))
+            }
+        };
+
+        let here = self.get_curr_room().name().to_owned();
+        let target = match self.room_id(breach.target()) {
+            Some(target) => target,
+            None => {
+                return Some(CmdResult::new(
+                    Action::Active,
+                    format!("You strike the {}, but it holds.", el_name),
+                ))
+            }
+        };
+        self.get_curr_room_mut().add_path(breach.path());
+        let mut res = breach.reply().to_owned();
+        if let Some(ladder) = breach.ladder(&here) {
+            self.rooms.get_mut(target).add_path(ladder);
+        } else {
+            let crossing = self.enter(target);
+            res.push_str(&format!(
+                "\nYou fall through the broken {}!\n{}",
+                el_name,
+                self.arrival(crossing)
+            ));
+        }
+        Some(CmdResult::new(Action::Active, res))
+    }
+
+    // tie a rope to an anchor here so the climbs from this Room can be made
+    pub fn tie(&mut self, rope: Box<Item>, anchor: &str) -> CmdResult {
+        let res = format!("You tie the {} to the {}.", rope.name(), anchor);
+        self.get_curr_room_mut().add_tie(Tie::new(rope, anchor));
+        CmdResult::new(Action::Active, res)
+    }
+
+    // let an Item down through a path on a rope, keeping hold of the rope's end here;
+    // both are handed back if the way is shut
+    pub fn lower(
+        &mut self,
+        rope: Box<Item>,
+        item: Box<Item>,
+        direction: &str,
+    ) -> (CmdResult, Items) {
+        let target = match self.get_curr_room().find_path(direction) {
+            Some(path) if !path.is_closed() && !path.is_locked() => path.name().to_owned(),
+            Some(_) => {
+                return (
+                    CmdResult::new(Action::Passive, "The way is shut."),
+                    vec![rope, item].into(),
+                )
+            }
+            None => return (self.cannot_go(direction), vec![rope, item].into()),
+        };
+        if self.room_id(&target).is_none() {
+            return (CmdResult::nowhere(), vec![rope, item].into());
+        }
+        let res = format!(
+            "You tie the {} to the {} and lower it {}.",
+            rope.name(),
+            item.name(),
+            direction
+        );
+        let tie = Tie::new(rope, item.name()).with_lowered(direction);
+        if let Some(room) = self.get_room_mut(&target) {
+            room.add_item(*item);
+        }
+        self.get_curr_room_mut().add_tie(tie);
+        (CmdResult::new(Action::Active, res), Items::new())
+    }
+
+    // haul up a lowered rope, along with whatever is still tied to it in the Room beyond
+    pub fn haul(&mut self, name: &str) -> Option<(CmdResult, Items)> {
+        let name = name.strip_prefix("up ").unwrap_or(name);
+        let tie = self.get_curr_room_mut().remove_tie(name)?;
+        let direction = match tie.lowered() {
+            Some(direction) => direction.to_owned(),
+            None => {
+                self.get_curr_room_mut().add_tie(tie);
+                return None;
+            }
+        };
+        let anchor = tie.anchor().to_owned();
+        let rope = tie.into_rope();
+        let target = self
+            .get_curr_room()
+            .find_path(&direction)
+            .map(|path| path.name().to_owned());
+        let load = target.and_then(|target| self.get_room_mut(&target)?.remove_item(&anchor));
+
+        let res = match &load {
+            Some(item) => format!(
+                "You haul up the {}, and the {} with it.",
+                rope.name(),
+                item.name()
+            ),
+            None => format!(
+                "You haul up the {}, but nothing is tied to it anymore.",
+                rope.name()
+            ),
+        };
+        let mut items = Items::new();
+        items.push(rope);
+        items.extend(load);
+        Some((CmdResult::new(Action::Active, res), items))
+    }
+
+    // untie a rope from an anchor here
+    pub fn untie(&mut self, name: &str) -> Option<Box<Item>> {
+        self.get_curr_room_mut()
+            .remove_tie(name)
+            .map(Tie::into_rope)
+    }
+
+    // shove an Enemy through a path into the next Room, e.g. "ogre off bridge"
+    pub fn push_enemy(&mut self, obj: &str, strength_mod: i32) -> CmdResult {
+        let words: Vec<&str> = obj.split_whitespace().collect();
+
+        for split in 1..words.len() {
+            let enemy_name = words[..split].join(" ");
+            let direction = words[split..]
+                .iter()
+                .skip_while(|word| ["off", "into", "over"].contains(word))
+                .copied()
+                .collect::<Vec<&str>>()
+                .join(" ");
+
+            if let (Some(path), Some(enemy)) = (
+                self.get_curr_room().find_path(&direction),
+                self.get_curr_room().find_enemy(&enemy_name),
+            ) {
+                return if path.is_closed() || path.is_locked() {
+                    CmdResult::new(Action::Passive, "The way is shut.")
+                } else if opposed_roll(
+                    strength_mod + if enemy.is_grappled() { 2 } else { 0 },
+                    enemy.strength_mod(),
+                ) {
+                    let target = path.name().to_owned();
+                    if let Some(mut enemy) = self.get_curr_room_mut().remove_enemy(&enemy_name) {
+                        enemy.release();
+                        let res = format!("You shove {} {}!", enemy.the(), direction);
+                        CmdResult::new(
+                            Action::Active,
+                            format!("{}{}", res, self.force_enemy(&target, enemy)),
+                        )
+                    } else {
+                        CmdResult::no_item_here(&enemy_name)
+                    }
+                } else {
+                    let res = format!(
+                        "{} holds {} ground.",
+                        prose::capitalize(&enemy.the()),
+                        enemy.possessive()
+                    );
+                    self.get_curr_room_mut().anger_enemy(&enemy_name);
+                    CmdResult::new(Action::Active, res)
+                };
+            }
+        }
+
+        CmdResult::new(
+            Action::Failed,
+            format!("You cannot push \"{}\" anywhere.", obj),
+        )
+    }
+
+    // move an Enemy into a Room regardless of what it wants
+    pub fn force_enemy(&mut self, room: &str, enemy: Box<Enemy>) -> String {
+        match self.get_room_mut(room) {
+            Some(room) => room.receive_enemy(enemy),
+            None => String::new(),
+        }
+    }
+
+    // hurl the player through a random open exit, returning any hazard waiting there
+    pub fn force_player(&mut self) -> Option<(String, Option<Hazard>)> {
+        let routes = self.get_curr_room().escape_routes();
+        if routes.is_empty() {
+            None
+        } else {
+            let target = routes[dice_roll(1, routes.len() as u32) as usize - 1].to_owned();
+            self.set_curr_room(self.room_id(&target)?);
+            Some((
+                format!(
+                    "\nYou are thrown from your feet!\n\n{}",
+                    self.look().output()
+                ),
+                self.get_curr_room().hazard().cloned(),
+            ))
+        }
+    }
+
+    pub fn add_hazard(&mut self, room: &str, hazard: Hazard) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_hazard(hazard);
+        }
+    }
+
+    pub fn set_floor_capacity(&mut self, room: &str, capacity: usize) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_floor_capacity(capacity);
+        }
+    }
+
+    pub fn allow_pvp(&mut self, room: &str) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_pvp();
+        }
+    }
+
+    pub fn add_shrine(&mut self, room: &str) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_shrine();
+        }
+    }
+
+    // bind the player to the shrine in the current Room so they return there on death
+    pub fn pray(&mut self) -> CmdResult {
+        if self.get_curr_room().has_shrine() {
+            self.shrine = Some(self.curr_room);
+            CmdResult::new(
+                Action::Active,
+                "You kneel at the shrine. A quiet warmth settles over you.",
+            )
+        } else {
+            self.respond("pray")
+                .unwrap_or_else(|| CmdResult::new(Action::Passive, Responses::stock("pray")))
+        }
+    }
+
+    // a refusal for a verb that does nothing, as this Room or else the World says it
+    pub fn respond(&self, short_verb: &str) -> Option<CmdResult> {
+        let verb = Responses::verb(short_verb)?;
+        let response = self
+            .get_curr_room()
+            .responses()
+            .get(verb)
+            .or_else(|| self.responses.get(verb))
+            .unwrap_or_else(|| Responses::stock(verb));
+        Some(CmdResult::new(Action::Passive, response))
+    }
+
+    // say something else for a verb that does nothing, in the named Room or else everywhere
+    pub fn set_response(&mut self, room: Option<&str>, verb: &str, response: &str) {
+        match room {
+            Some(room) => {
+                if let Some(room) = self.get_room_mut(room) {
+                    room.set_response(verb, response);
+                }
+            }
+            None => self.responses.set(verb, response),
+        }
+    }
+
+    // what this Room or else the World says for the verb done to the thing named
+    pub fn reply(&self, short_verb: &str, obj: &str) -> Option<&Reply> {
+        let answers = |reply: &&Reply| reply.answers(short_verb, obj);
+        self.get_curr_room()
+            .replies()
+            .iter()
+            .find(answers)
+            .or_else(|| self.replies.iter().find(answers))
+    }
+
+    // say something else for a verb done to a thing, in the named Room or else everywhere
+    pub fn add_reply(&mut self, room: Option<&str>, reply: Reply) {
+        match room {
+            Some(room) => {
+                if let Some(room) = self.get_room_mut(room) {
+                    room.add_reply(reply);
+                }
+            }
+            None => self.replies.push(reply),
+        }
+    }
+
+    // carry a dead player back to the shrine they last prayed at
+    pub fn return_to_shrine(&mut self) -> Option<String> {
+        self.set_curr_room(self.shrine?);
+        Some(format!(
+            "\n\nThe shrine calls you back from death.\n\n{}",
+            self.look().output()
+        ))
     }
 
     pub fn clear_dead_enemies(&mut self) {
@@ -103,7 +1742,19 @@ impl World {
 
     // have an Enemy in the current Room take damage
     pub fn harm_enemy(&mut self, enemy_name: &str, attack: Attack) -> CmdResult {
-        self.get_curr_room_mut().harm_enemy(enemy_name, attack)
+        let sound = attack.sound().map(str::to_owned);
+        let explosion = attack.is_area() && attack.damage().is_some();
+        let res =
+            self.rooms
+                .get_mut(self.curr_room)
+                .harm_enemy(enemy_name, attack, &self.loot_tables);
+        if let (true, Some(sound)) = (res.is_active(), sound) {
+            self.cue(Cue::Sound(sound));
+        }
+        if explosion && res.is_active() {
+            self.cue(Cue::Rumble(Rumble::Heavy));
+        }
+        res
     }
 
     // move an Item out of the current Room
@@ -112,6 +1763,7 @@ impl World {
     }
 
     // take an Item from a container Item in the current Room
+    #[allow(clippy::result_large_err)]
     pub fn give_from(
         &mut self,
         item_name: &str,
@@ -121,12 +1773,52 @@ impl World {
             .give_from(item_name, container_name)
     }
 
+    // reveal a disguised Enemy in the current Room
+    pub fn reveal(&mut self, name: &str) -> Option<CmdResult> {
+        self.get_curr_room_mut().reveal(name)
+    }
+
+    pub fn reveal_all(&mut self) -> Option<CmdResult> {
+        self.get_curr_room_mut().reveal_all()
+    }
+
+    pub fn get_on(&mut self, name: &str) -> CmdResult {
+        self.get_curr_room_mut().get_on(name)
+    }
+
+    pub fn get_off(&mut self) -> CmdResult {
+        self.get_curr_room_mut().get_off()
+    }
+
+    pub fn out_of_reach(&self, name: &str) -> Option<CmdResult> {
+        self.get_curr_room().out_of_reach(name)
+    }
+
+    pub fn set_reach(&mut self, room: &str, name: &str, furniture: &str) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_reach(name, furniture);
+        }
+    }
+
+    #[allow(clippy::borrowed_box)]
+    pub fn find_item(&self, name: &str) -> Option<&Box<Item>> {
+        self.get_curr_room().find_item(name)
+    }
+
+    pub fn scenery(&self, name: &str) -> Option<CmdResult> {
+        self.get_curr_room().scenery(name)
+    }
+
     pub fn give_all(&mut self) -> Items {
         self.get_curr_room_mut().drain_all()
     }
 
     // insert an Item into the current Room
-    pub fn insert(&mut self, name: &str, item: Option<Box<Item>>) -> CmdResult {
+    pub fn insert(
+        &mut self,
+        name: &str,
+        item: Option<Box<Item>>,
+    ) -> (CmdResult, Option<Box<Item>>) {
         self.get_curr_room_mut().take_item(name, item)
     }
 
@@ -141,20 +1833,529 @@ impl World {
             .insert_into(item_name, container_name, item)
     }
 
+    // hand a carried Item to someone in the current Room, or get it back if they are not here
+    #[allow(clippy::result_large_err)]
+    pub fn give_to(&mut self, name: &str, item: Box<Item>) -> Result<CmdResult, Box<Item>> {
+        self.get_curr_room_mut().give_to(name, item)
+    }
+
+    // whoever in the current Room sees the player take what belongs to someone remembers it
+    pub fn theft(&mut self) -> Option<String> {
+        if !self.get_curr_room().is_owned() {
+            return None;
+        }
+        let witnesses = self.get_curr_room_mut().witness(Deed::Theft);
+        if witnesses.is_empty() {
+            None
+        } else {
+            Some(format!(
+                "{} saw you take it.",
+                prose::capitalize(&prose::list(&witnesses))
+            ))
+        }
+    }
+
+    // join two Rooms both ways, the way back leading in the opposite direction
+    pub fn connect(&mut self, from: &str, direction: &str, to: &str) -> Result<(), String> {
+        let direction = Direction::parse(direction);
+        let back = direction.opposite().ok_or_else(|| {
+            format!(
+                "The way {} has no opposite, so give each room its own path.",
+                direction
+            )
+        })?;
+        for room in [from, to].iter() {
+            if self.room_id(room).is_none() {
+                return Err(format!("There is no room called {}.", room));
+            }
+        }
+        for (room, direction, target) in [(from, direction, to), (to, back, from)].iter() {
+            if let Some(room) = self.get_room_mut(room) {
+                room.add_path(Pathway::new(
+                    &[direction.name()],
+                    target,
+                    &format!("A way leads {}.", direction),
+                    "Nothing about the way stands out.",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_owned(&mut self, room: &str, owned: bool) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_owned(owned);
+        }
+    }
+
     // interact with an Ally
     pub fn hail(&self, ally_name: &str) -> CmdResult {
         self.get_curr_room().hail(ally_name)
     }
 
+    pub fn talk(&self, ally_name: &str, karma: i32) -> CmdResult {
+        self.get_curr_room().talk(ally_name, karma)
+    }
+
+    pub fn turn_dial(&mut self, name: &str, numbers: &[u32]) -> CmdResult {
+        self.get_curr_room_mut()
+            .turn_dial(name, numbers)
+            .unwrap_or_else(|| CmdResult::no_item_here(name))
+    }
+
+    pub fn reset_dial(&mut self, name: &str) -> CmdResult {
+        self.get_curr_room_mut()
+            .reset_dial(name)
+            .unwrap_or_else(|| CmdResult::no_item_here(name))
+    }
+
+    // play a learned song, which may stir something here
+    pub fn play_song(&mut self, song: &str, instrument: &str) -> CmdResult {
+        let played = format!("You play the {} on the {}", song, instrument);
+        match self.get_curr_room_mut().play_song(song) {
+            Some(res) => res.with_preface(&format!("{}.", played)),
+            None => CmdResult::new(Action::Active, format!("{}, but nothing stirs.", played)),
+        }
+    }
+
+    pub fn teacher(&self, name: &str) -> Option<(String, Vec<String>)> {
+        self.get_curr_room()
+            .teacher(name)
+            .map(|(teacher, songs)| (teacher, songs.to_vec()))
+    }
+
+    pub fn pose_riddle(&self, name: &str) -> CmdResult {
+        self.get_curr_room()
+            .pose_riddle(name)
+            .unwrap_or_else(|| CmdResult::no_item_here(name))
+    }
+
+    // the result of a guess at a riddle here, whether no more guesses are wanted, and the
+    // damage for a wrong one
+    pub fn answer_riddle(&mut self, name: &str, guess: &str) -> (CmdResult, bool, u32) {
+        match self.get_curr_room_mut().answer_riddle(name, guess) {
+            Some((reply, true, _)) => (CmdResult::new(Action::Active, reply), true, 0),
+            Some((reply, false, penalty)) => {
+                (CmdResult::new(Action::Failed, reply), false, penalty)
+            }
+            None => (CmdResult::no_item_here(name), true, 0),
+        }
+    }
+
+    pub fn spend_check(&mut self, speaker: &str, topic: &str) {
+        self.get_curr_room_mut().spend_check(speaker, topic);
+    }
+
+    pub fn calm_enemies(&mut self) {
+        self.get_curr_room_mut().calm_enemies();
+    }
+
+    pub fn provoke_enemies(&mut self) {
+        self.get_curr_room_mut().provoke_enemies();
+    }
+
+    pub fn dismiss(&mut self, ally_name: &str) -> CmdResult {
+        self.get_curr_room_mut().dismiss(ally_name)
+    }
+
+    // a treat has been given to a Pet, winning it over a little more
+    pub fn feed_pet(&mut self, pet_name: &str) -> CmdResult {
+        match self.get_curr_room_mut().find_pet_mut(pet_name) {
+            Some(pet) => {
+                let tamed = !pet.is_tame();
+                pet.feed();
+                let res = if tamed {
+                    format!(
+                        "{} gobbles up the {} and will follow you now.",
+                        prose::capitalize(&pet.the()),
+                        pet.treat()
+                    )
+                } else {
+                    format!(
+                        "{} gobbles up the {} happily.",
+                        prose::capitalize(&pet.the()),
+                        pet.treat()
+                    )
+                };
+                CmdResult::new(Action::Active, res)
+            }
+            None => CmdResult::no_item_here(pet_name),
+        }
+    }
+
+    pub fn name_pet(&mut self, pet_name: &str, given_name: &str) -> CmdResult {
+        match self.get_curr_room_mut().find_pet_mut(pet_name) {
+            Some(pet) if pet.is_tame() => {
+                pet.set_given_name(given_name);
+                CmdResult::new(
+                    Action::Active,
+                    format!("The {} will answer to {} now.", pet.name(), pet.the()),
+                )
+            }
+            Some(pet) => CmdResult::new(
+                Action::Failed,
+                format!("{} does not trust you yet.", prose::capitalize(&pet.the())),
+            ),
+            None => CmdResult::no_item_here(pet_name),
+        }
+    }
+
+    // a loyal Pet sniffs out anything hidden in the current Room
+    pub fn command_ally(&mut self, ally_name: &str, order: Order) -> CmdResult {
+        self.get_curr_room_mut().command_ally(ally_name, order)
+    }
+
+    pub fn pet_search(&mut self, pet_name: &str) -> CmdResult {
+        let pet = match self.get_curr_room().find_pet(pet_name) {
+            Some(pet) if pet.loyalty() < Pet::OBEDIENT => return pet_ignores(pet),
+            Some(pet) => prose::capitalize(&pet.the()),
+            None => return CmdResult::no_item_here(pet_name),
+        };
+        let found = self.get_curr_room_mut().unearth();
+        if found.is_empty() {
+            CmdResult::new(
+                Action::Active,
+                format!("{} sniffs about but finds nothing.", pet),
+            )
+        } else {
+            let names: Vec<String> = found.iter().map(|item| prose::a(item.name())).collect();
+            CmdResult::new(
+                Action::Active,
+                format!("{} sniffs about and paws up {}.", pet, prose::list(&names)),
+            )
+        }
+    }
+
+    // have a tame Pet carry an Item, handing it back if the Pet cannot
+    pub fn pet_carry(&mut self, pet_name: &str, item: Box<Item>) -> (CmdResult, Option<Box<Item>>) {
+        match self.get_curr_room_mut().find_pet_mut(pet_name) {
+            Some(pet) if !pet.is_tame() => (pet_ignores(pet), Some(item)),
+            Some(pet) if pet.is_full() => (
+                CmdResult::new(
+                    Action::Failed,
+                    format!("{} cannot carry any more.", prose::capitalize(&pet.the())),
+                ),
+                Some(item),
+            ),
+            Some(pet) => {
+                let res = format!(
+                    "{} takes the {} to carry.",
+                    prose::capitalize(&pet.the()),
+                    item.name()
+                );
+                pet.carry(item);
+                (CmdResult::new(Action::Active, res), None)
+            }
+            None => (CmdResult::no_item_here(pet_name), Some(item)),
+        }
+    }
+
+    // a Pet drops everything it carries at the player's feet
+    pub fn pet_drop(&mut self, pet_name: &str) -> CmdResult {
+        let room = self.get_curr_room_mut();
+        let (pet, pack) = match room.find_pet_mut(pet_name) {
+            Some(pet) if !pet.is_tame() => return pet_ignores(pet),
+            Some(pet) => (prose::capitalize(&pet.the()), pet.unload()),
+            None => return CmdResult::no_item_here(pet_name),
+        };
+        if pack.is_empty() {
+            return CmdResult::new(
+                Action::Passive,
+                format!("{} is not carrying anything.", pet),
+            );
+        }
+        let names: Vec<String> = pack.iter().map(|item| prose::a(item.name())).collect();
+        for item in pack {
+            room.add_item(*item);
+        }
+        CmdResult::new(
+            Action::Active,
+            format!("{} drops {} at your feet.", pet, prose::list(&names)),
+        )
+    }
+
+    pub fn add_pet(&mut self, room: &str, pet: Pet) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.add_pet(pet);
+        }
+    }
+
+    pub fn add_ally(&mut self, room: &str, ally: Ally) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.summon(ally);
+        }
+    }
+
+    pub fn add_room(&mut self, name: &str, desc: &str) {
+        if self.rooms.id(name).is_none() {
+            self.rooms.insert(name, Room::new(name, desc));
+        }
+    }
+
+    pub fn add_path(&mut self, room: &str, path: Pathway) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.add_path(path);
+        }
+    }
+
     pub fn add_element(&mut self, room: &str, el: Element) {
-        self.get_room_mut(room).add_element(el);
+        if let Some(room) = self.get_room_mut(room) {
+            room.add_element(el);
+        }
     }
 
     pub fn add_item(&mut self, room: &str, item: Item) {
-        self.get_room_mut(room).add_item(item);
+        if let Some(room) = self.get_room_mut(room) {
+            room.add_item(item);
+        }
+    }
+
+    // how many Items lying in a Room have exactly this name
+    pub fn count_items(&self, room: &str, name: &str) -> usize {
+        self.room_id(room)
+            .map_or(0, |id| self.rooms.get(id).items().count_of(name))
+    }
+
+    // take an Item with exactly this name out of a Room
+    pub fn remove_item(&mut self, room: &str, name: &str) -> Option<Box<Item>> {
+        self.get_room_mut(room)?.remove_exact_item(name)
+    }
+
+    // the name the current Room is kept under, which paths and hosts use to find it
+    pub fn curr_room_key(&self) -> &str {
+        self.rooms.name(self.curr_room)
+    }
+
+    // move the player straight to a Room, ignoring exits
+    pub fn teleport(&mut self, room_name: &str) -> CmdResult {
+        let id = self
+            .rooms
+            .iter_named()
+            .find(|(name, room)| same_name(name, room_name) || same_name(room.name(), room_name))
+            .and_then(|(name, _)| self.rooms.id(name));
+        if let Some(id) = id {
+            self.set_curr_room(id);
+            self.look()
+        } else {
+            CmdResult::new(
+                Action::Failed,
+                format!("There is no room called \"{}\".", room_name),
+            )
+        }
+    }
+
+    // copy an Enemy found anywhere in the World into the current Room
+    pub fn spawn_copy(&mut self, enemy_name: &str) -> CmdResult {
+        let enemy = self
+            .rooms
+            .iter()
+            .flat_map(|room| room.enemies())
+            .find(|enemy| same_name(enemy.name(), enemy_name))
+            .map(|enemy| (**enemy).clone());
+        if let Some(enemy) = enemy {
+            self.get_curr_room_mut().spawn_enemy(enemy);
+            CmdResult::new(
+                Action::Passive,
+                format!("{} appears.", prose::capitalize(&prose::a(enemy_name))),
+            )
+        } else {
+            CmdResult::new(
+                Action::Failed,
+                format!("There is no enemy called \"{}\" in this world.", enemy_name),
+            )
+        }
     }
 
     pub fn spawn_enemy(&mut self, room: &str, enemy: Enemy) {
-        self.get_room_mut(room).spawn_enemy(enemy);
+        if let Some(room) = self.get_room_mut(room) {
+            room.spawn_enemy(enemy);
+        }
+    }
+
+    pub fn add_loot_table(&mut self, name: &str, table: LootTable) {
+        self.loot_tables.insert(name.to_owned(), table);
+    }
+
+    pub fn add_recipe(&mut self, name: &str, recipe: Recipe) {
+        self.recipes.insert(name.to_owned(), recipe);
+    }
+
+    // the Recipe called by the given name, with its full name as the player sees it
+    pub fn recipe(&self, name: &str) -> Option<(&str, &Recipe)> {
+        self.recipes
+            .iter()
+            .find(|(recipe, _)| named(namespace::local(recipe), name))
+            .map(|(name, recipe)| (namespace::local(name), recipe))
+    }
+
+    // what can be made at the stations in the current Room, and from what
+    pub fn recipes_here(&self) -> CmdResult {
+        let room = self.get_curr_room();
+        let mut recipes: Vec<String> = self
+            .recipes
+            .iter()
+            .filter(|(_, recipe)| room.has_station(recipe.station()))
+            .map(|(name, recipe)| {
+                format!(
+                    "  {} ({}, difficulty {})",
+                    namespace::local(name),
+                    prose::list(
+                        &recipe
+                            .ingredients()
+                            .iter()
+                            .map(|ingredient| namespace::local(ingredient).to_owned())
+                            .collect::<Vec<_>>()
+                    ),
+                    recipe.dc()
+                )
+            })
+            .collect();
+        if recipes.is_empty() {
+            return CmdResult::new(
+                Action::Passive,
+                "There is nothing to make anything at here.",
+            );
+        }
+        recipes.sort();
+        CmdResult::new(
+            Action::Passive,
+            format!("You can make:\n{}", recipes.join("\n")),
+        )
+    }
+
+    pub fn add_quest(&mut self, name: &str, quest: Quest) {
+        self.quests.insert(name.to_owned(), quest);
+    }
+
+    pub fn add_region(&mut self, name: &str, region: Region) {
+        self.regions.insert(name.to_owned(), region);
+    }
+
+    pub fn add_dead_end(&mut self, room: &str, direction: &str, message: &str) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.add_dead_end(direction, message);
+        }
+    }
+
+    pub fn set_region(&mut self, room: &str, region: &str) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_region(region);
+        }
+    }
+
+    // something from the current Room's region that wanders near this turn
+    fn wanderer(&self) -> Option<Enemy> {
+        let region = self.get_curr_room().region()?;
+        self.regions.get(region)?.roll(|chance| self.adjust(chance))
+    }
+
+    pub const fn shows_karma(&self) -> bool {
+        self.shows_karma
+    }
+
+    pub fn set_shows_karma(&mut self, shows_karma: bool) {
+        self.shows_karma = shows_karma;
+    }
+
+    pub fn set_restrict_saves(&mut self, restrict_saves: bool) {
+        self.restrict_saves = restrict_saves;
+    }
+
+    // why the player cannot save now, if saves are restricted and this is no safe place
+    pub fn save_refusal(&self) -> Option<&'static str> {
+        if !self.restrict_saves {
+            None
+        } else if self.any_angry_enemies() {
+            Some("You cannot save with enemies upon you.")
+        } else if self.get_curr_room().is_dangerous() {
+            Some("This place is too dangerous to save in.")
+        } else {
+            None
+        }
+    }
+
+    pub fn set_dangerous(&mut self, room: &str, dangerous: bool) {
+        if let Some(room) = self.get_room_mut(room) {
+            room.set_dangerous(dangerous);
+        }
+    }
+
+    pub fn set_director(&mut self, director: Director) {
+        self.director = Some(director);
+    }
+
+    // a percent chance of meeting an Enemy, nudged by the Director if there is one
+    fn adjust(&self, chance: u32) -> u32 {
+        match &self.director {
+            Some(director) => director.adjust(chance),
+            None => chance,
+        }
+    }
+
+    // let the Director know how the last turn went
+    pub fn pace(&mut self, hp: i32, hp_cap: u32) {
+        let fought = self.any_angry_enemies();
+        if let Some(director) = self.director.as_mut() {
+            director.pace(fought, hp, hp_cap);
+        }
+    }
+
+    // while the player idles, something from the region may wander near
+    pub fn wander(&mut self) -> Option<CmdResult> {
+        let enemy = self.wanderer()?;
+        let res = format!("Something wanders near.\n{}", enemy.desc());
+        self.get_curr_room_mut().spawn_enemy(enemy);
+        Some(CmdResult::new(Action::Active, res))
+    }
+}
+
+// a Pet that is not loyal enough to do as it is told
+fn pet_ignores(pet: &Pet) -> CmdResult {
+    CmdResult::new(
+        Action::Failed,
+        format!(
+            "{} ignores you and looks hopefully for a treat.",
+            prose::capitalize(&pet.the())
+        ),
+    )
+}
+
+// the direction a custom one is a letter away from, like "north" for "nort"; typos keep the
+// first letter, which spares words like "mast" that are only a letter from "east"
+fn misspelled(direction: &Direction) -> Option<String> {
+    let name = direction.name().to_lowercase();
+    if !direction.is_custom() || name.chars().count() < 4 {
+        return None;
+    }
+    Direction::STANDARD
+        .iter()
+        .map(Direction::name)
+        .find(|known| known.get(..1) == name.get(..1) && edit_distance(&name, known) == 1)
+        .map(str::to_owned)
+}
+
+// suggest the closest known name for a misspelled reference
+fn did_you_mean(name: &str, known: &[&str]) -> String {
+    known
+        .iter()
+        .map(|known| (edit_distance(name, known), known))
+        .filter(|(distance, _)| *distance <= 2.max(name.len() / 3))
+        .min()
+        .map(|(_, known)| format!(" (did you mean {}?)", known))
+        .unwrap_or_default()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.to_lowercase().chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
     }
+    row[b.len()]
 }
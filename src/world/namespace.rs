@@ -0,0 +1,33 @@
+// Content packs merged into a World keep their names apart by prefixing them with the name of
+// the pack, as in "dragons:Lair", so two packs can both define a "Cellar"
+
+const SEPARATOR: char = ':';
+
+// the pack a name was defined in, if any
+pub(crate) fn pack_of(name: &str) -> Option<&str> {
+    name.split_once(SEPARATOR)
+        .map(|(pack, _)| pack)
+        .filter(|pack| is_pack_name(pack))
+}
+
+// the name as the pack that defined it wrote it, for showing to players
+pub(crate) fn local(name: &str) -> &str {
+    match pack_of(name) {
+        Some(pack) => &name[pack.len() + SEPARATOR.len_utf8()..],
+        None => name,
+    }
+}
+
+pub(crate) fn is_pack_name(pack: &str) -> bool {
+    !pack.is_empty() && !pack.contains(|c: char| c == SEPARATOR || c.is_whitespace())
+}
+
+// a name defined by a pack, left as it is if it already names its pack
+#[cfg(feature = "serde")]
+pub(crate) fn qualify(pack: &str, name: &str) -> String {
+    if pack_of(name).is_some() {
+        name.to_owned()
+    } else {
+        format!("{}{}{}", pack, SEPARATOR, name)
+    }
+}
@@ -1,10 +1,13 @@
+#![cfg(feature = "serde")]
+
 #[cfg(test)]
 mod tests {
-    use kingslayer::Cli;
+    use kingslayer::{Cli, Item, Thing};
 
     #[test]
+    #[allow(clippy::never_loop)]
     fn readme_example() {
-        let cli = Cli::from_file("worlds/world.ron");
+        let cli = Cli::from_file("worlds/world.ron").unwrap();
 
         println!("{}", cli.ask("l"));
         loop {
@@ -19,7 +22,35 @@ mod tests {
 
     #[test]
     fn from_file() {
-        let _cli = Cli::from_file("worlds/test_world.ron");
-        let _cli = Cli::from_file("worlds/world.ron");
+        let _cli = Cli::from_file("worlds/test_world.ron").unwrap();
+        let _cli = Cli::from_file("worlds/world.ron").unwrap();
+    }
+
+    #[test]
+    fn worlds_can_be_built_in_code() {
+        let cli = Cli::new("Cell", "A damp cell.");
+        cli.add_room("Yard", "An empty yard.");
+        cli.add_room("Cell", "This does not replace the cell.");
+        cli.add_path(
+            "Cell",
+            &["north", "door"],
+            "Yard",
+            "A door leads north.",
+            "It is ajar.",
+        );
+        cli.add_path(
+            "Yard",
+            &["south"],
+            "Cell",
+            "The cell is south.",
+            "It is dark.",
+        );
+        cli.add_item("Yard", Item::Thing(Thing::new("stone", "It is smooth.")));
+        assert!(cli.validate().is_empty(), "{:?}", cli.validate());
+
+        assert!(cli.ask("l").contains("A damp cell."));
+        assert!(cli.ask("x door").contains("It is ajar."));
+        assert!(cli.ask("n").contains("stone"));
+        assert!(cli.ask("s").contains("A damp cell."));
     }
 }
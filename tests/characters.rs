@@ -0,0 +1,957 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use kingslayer::{
+        Ally, Approach, Check, Cli, Consequence, Container, DamageType, Deed, Enemy, EnemyStatus,
+        HpDisplay, Item, Karma, Memory, Opening, Pet, Pronouns, Reply, Role, SessionManager,
+        Speech, Thing, Weapon,
+    };
+
+    use crate::common::{test_world, Cursed, Loaded};
+
+    fn cli_with_hermit() -> Cli {
+        let cli = test_world();
+        cli.add_ally(
+            "Central Room",
+            Ally::new("hermit", "It is wrapped in moss.")
+                .with_topic("cave", "The cave to the north is home to many rats.")
+                .with_topic("king", "The king has not been seen in years."),
+        );
+        cli
+    }
+
+    #[test]
+    fn talk_about_topics() {
+        let cli = cli_with_hermit();
+
+        let res = cli.ask("talk to hermit");
+        assert!(res.contains("You can ask the hermit about:"));
+        assert!(res.contains("1. cave"));
+        assert!(res.contains("2. king"));
+        assert_eq!(cli.ask("king"), "The king has not been seen in years.");
+        assert_eq!(cli.ask("1"), "The cave to the north is home to many rats.");
+    }
+
+    #[test]
+    fn moves_are_topics_until_bye() {
+        let cli = cli_with_hermit();
+
+        cli.ask("talk with hermit");
+        assert!(cli.ask("north").contains("You can ask the hermit about:"));
+        assert_eq!(cli.ask("bye"), "You take your leave of the hermit.");
+        assert!(cli.ask("north").contains("Small Cave"));
+    }
+
+    #[test]
+    fn question_inside_conversation() {
+        let cli = cli_with_hermit();
+
+        cli.ask("talk to hermit");
+        cli.ask("leave");
+        cli.ask("hail hermit");
+        assert_eq!(cli.ask("yes"), "The hermit nods.");
+        assert!(cli.ask("talk to hermit").contains("1. cave"));
+        assert_eq!(cli.ask("leave"), "You take your leave of the hermit.");
+    }
+
+    #[test]
+    fn nothing_to_say() {
+        let cli = test_world();
+        cli.add_ally(
+            "Central Room",
+            Ally::new("squire", "It carries a spare lantern."),
+        );
+
+        assert_eq!(cli.ask("talk to squire"), "The squire has nothing to say.");
+        assert!(cli
+            .ask("talk to ghost")
+            .contains("There is no \"ghost\" here."));
+        assert!(cli.ask("n").contains("Small Cave"));
+    }
+
+    #[test]
+    fn restart_waits_until_bye() {
+        let cli = cli_with_hermit();
+
+        cli.ask("talk to hermit");
+        assert!(cli.ask("restart").contains("You can ask the hermit about:"));
+        cli.ask("bye");
+        assert!(cli.ask("restart").contains("Restarted."));
+    }
+
+    #[derive(Default)]
+    struct Spoken(Vec<Vec<String>>);
+
+    impl Speech for Spoken {
+        fn speak(&mut self, sentences: &[String]) {
+            self.0.push(sentences.to_vec());
+        }
+    }
+
+    fn listen(cli: &Cli) -> Arc<Mutex<Spoken>> {
+        let spoken = Arc::new(Mutex::new(Spoken::default()));
+        cli.set_speech(spoken.clone());
+        spoken
+    }
+
+    #[test]
+    fn sentences() {
+        let cli = test_world();
+        let spoken = listen(&cli);
+        cli.show_status_line(true);
+
+        assert!(cli.ask("l").contains("Turns:"));
+        let said = spoken.lock().unwrap().0[0].clone();
+        assert_eq!(said[0], "Central Room.");
+        assert!(said.iter().all(|sentence| !sentence.contains("Turns")));
+        assert!(said
+            .iter()
+            .all(|sentence| sentence.ends_with(['.', '!', '?'])));
+    }
+
+    #[test]
+    fn abbreviations_and_bars() {
+        let cli = test_world();
+        let spoken = listen(&cli);
+        cli.set_hp_display(HpDisplay::Bar);
+
+        assert!(cli.ask("c").contains('█'));
+        let said = spoken.lock().unwrap().0[0].clone();
+        assert!(said.contains(&String::from("hit points.")));
+        assert!(said
+            .iter()
+            .any(|sentence| sentence.starts_with("armor class: ")));
+        assert!(said.iter().all(|sentence| !sentence.contains('█')));
+    }
+
+    #[test]
+    fn no_engine() {
+        let cli = test_world();
+        assert!(cli.ask("l").starts_with("Central Room"));
+    }
+
+    fn cli_with_dog() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Yard",rooms:{
+                "Yard":(name:"Yard",desc:"Weeds choke the flagstones.",paths:[
+                    (directions:["north"],target:"Garden",opening:None,lock:None)
+                ],items:[
+                    Thing((name:"bone",desc:"There is a bone here.",inspect:"It is meaty.")),
+                    Thing((name:"bone",desc:"There is a bone here.",inspect:"It is meaty."))
+                ]),
+                "Garden":(name:"Garden",desc:"The soil is freshly turned.",paths:[
+                    (directions:["south"],target:"Yard",opening:None,lock:None)
+                ],buried:[
+                    Thing((name:"silver ring",desc:"There is a silver ring here.",inspect:"It is tarnished."))
+                ])
+            }))"#,
+        ).unwrap();
+        cli.add_pet("Yard", Pet::new("dog", "Its coat is matted.", "bone"));
+        cli
+    }
+
+    #[test]
+    fn treats_tame_animals_to_follow() {
+        let cli = cli_with_dog();
+
+        assert_eq!(cli.ask("feed dog"), "You have no bone to feed the dog.");
+        assert_eq!(cli.ask("name dog rex"), "The dog does not trust you yet.");
+        cli.ask("take all");
+        assert_eq!(
+            cli.ask("feed dog"),
+            "The dog gobbles up the bone and will follow you now."
+        );
+        assert_eq!(cli.ask("name dog rex"), "The dog will answer to Rex now.");
+        assert!(cli.ask("n").contains("Rex the dog is here."));
+        assert!(cli.ask("x rex").contains("It seems to trust you."));
+    }
+
+    #[test]
+    fn loyal_pets_sniff_out_hidden_items() {
+        let cli = cli_with_dog();
+        cli.ask("take all");
+        cli.ask("feed dog");
+        cli.ask("n");
+
+        assert_eq!(
+            cli.ask("command dog to search"),
+            "The dog ignores you and looks hopefully for a treat."
+        );
+        cli.ask("feed dog");
+        assert_eq!(
+            cli.ask("command dog to search"),
+            "The dog sniffs about and paws up a silver ring."
+        );
+        assert_eq!(
+            cli.ask("command dog to search"),
+            "The dog sniffs about but finds nothing."
+        );
+        cli.ask("take ring");
+        assert!(cli.ask("i").contains("silver ring"));
+    }
+
+    #[test]
+    fn pets_carry_small_loads() {
+        let cli = cli_with_dog();
+        cli.ask("take all");
+        cli.ask("feed dog");
+        for name in ["stick", "ball", "rag"] {
+            cli.receive_item(Item::Thing(Thing::new(name, "It is chewed.")));
+        }
+
+        assert_eq!(
+            cli.ask("command dog to carry stick"),
+            "The dog takes the stick to carry."
+        );
+        cli.ask("command dog to carry ball");
+        assert_eq!(
+            cli.ask("command dog to carry rag"),
+            "The dog cannot carry any more."
+        );
+        assert!(cli.ask("i").contains("rag"));
+        assert!(cli
+            .ask("x dog")
+            .contains("It is carrying a stick and a ball."));
+        cli.ask("n");
+        assert_eq!(
+            cli.ask("command dog to drop"),
+            "The dog drops a stick and a ball at your feet."
+        );
+    }
+
+    #[test]
+    fn wild_animals_stay_put() {
+        let cli = cli_with_dog();
+
+        assert!(!cli.ask("n").contains("dog"));
+        assert_eq!(
+            cli.ask("command dog to search"),
+            "There is no animal here to command."
+        );
+    }
+
+    #[test]
+    fn ally_pronouns() {
+        let cli = test_world();
+        cli.add_ally(
+            "Central Room",
+            Ally::new("squire", "She carries a spare lantern.").with_pronouns(Pronouns::she()),
+        );
+        cli.add_ally(
+            "Central Room",
+            Ally::new("twin", "They look alike.").with_pronouns(Pronouns::they()),
+        );
+
+        cli.ask("hail squire");
+        assert_eq!(cli.ask("yes"), "She nods.");
+        cli.ask("hail twin");
+        assert_eq!(cli.ask("yes"), "They nod.");
+    }
+
+    #[test]
+    fn enemy_pronouns() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("Grendel", "He is huge.", EnemyStatus::Asleep)
+                .with_proper()
+                .with_pronouns(Pronouns::he()),
+        );
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("pair of ghouls", "They reek.", EnemyStatus::Asleep)
+                .with_pronouns(Pronouns::they()),
+        );
+
+        let res = cli.ask("n");
+        assert!(res.contains("Grendel is here. He is asleep."));
+        assert!(res.contains("There is a pair of ghouls here. They are asleep."));
+    }
+
+    #[test]
+    fn player_pronouns() {
+        let cli = test_world();
+        assert!(cli
+            .ask("c")
+            .starts_with("Level 1 Human Warrior (they/them)"));
+
+        cli.set_pronouns(Pronouns::she());
+        assert!(cli.ask("c").starts_with("Level 1 Human Warrior (she/her)"));
+
+        cli.set_pronouns(Pronouns::select_pronouns("xe/xem/xyr/xemself"));
+        assert_eq!(cli.pronouns().possessive(), "xyr");
+        assert!(cli.ask("c").starts_with("Level 1 Human Warrior (xe/xem)"));
+    }
+
+    #[test]
+    fn proper_nouns_have_no_article() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("Grendel", "He is huge.", EnemyStatus::Asleep)
+                .with_proper()
+                .with_strength(100),
+        );
+
+        let res = cli.ask("n");
+        assert!(res.contains("Grendel is here."));
+        assert!(!res.contains("a Grendel"));
+
+        let res = cli.ask("grapple grendel");
+        assert!(res.starts_with("Grendel twists out of your grasp."));
+        assert!(!res.contains("the Grendel"));
+    }
+
+    #[test]
+    fn possessives() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("Grendel", "He is huge.", EnemyStatus::Asleep)
+                .with_proper()
+                .with_strength(100),
+        );
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("Boris", "He is stubborn.", EnemyStatus::Asleep)
+                .with_proper()
+                .with_possessive("his")
+                .with_strength(100),
+        );
+        cli.ask("n");
+
+        assert!(cli
+            .ask("push grendel south")
+            .starts_with("Grendel holds Grendel's ground."));
+        assert!(cli
+            .ask("push boris south")
+            .starts_with("Boris holds his ground."));
+    }
+
+    #[test]
+    fn proper_allies() {
+        let cli = test_world();
+        cli.add_ally(
+            "Small Cave",
+            Ally::new("Gandalf", "He leans on his staff.")
+                .with_proper()
+                .with_topic("rings", "Keep it secret."),
+        );
+
+        assert!(cli.ask("n").contains("Gandalf is here."));
+        assert!(cli
+            .ask("talk to gandalf")
+            .starts_with("You can ask Gandalf about:"));
+        assert_eq!(cli.ask("bye"), "You take your leave of Gandalf.");
+    }
+
+    #[test]
+    fn players_cannot_debug() {
+        let cli = test_world();
+
+        assert_eq!(cli.role(), Role::Player);
+        assert!(cli.ask("tp bedroom").contains("tp"));
+        assert!(cli.ask("l").contains("Central Room"));
+    }
+
+    #[test]
+    fn author_commands() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("goblin", "It is short and green.", EnemyStatus::Asleep),
+        );
+        cli.add_item(
+            "Central Room",
+            Item::Thing(Thing::new("crown", "It is gold.").with_quest("regicide")),
+        );
+        cli.set_role(Role::Author);
+
+        assert!(cli.ask("tp bedroom").starts_with("Bedroom"));
+        assert_eq!(cli.ask("tp attic"), "There is no room called \"attic\".");
+        assert_eq!(cli.ask("spawn goblin"), "A goblin appears.");
+        assert!(cli.ask("l").contains("goblin"));
+
+        cli.ask("tp central room");
+        cli.ask("take crown");
+        assert_eq!(cli.ask("drop crown"), "You feel you will need this.");
+        assert_eq!(cli.ask("setflag regicide"), "Quest \"regicide\" complete.");
+        assert_eq!(cli.ask("drop crown"), "Dropped.");
+
+        assert_eq!(cli.ask("reload"), "Only an admin can reload the world.");
+    }
+
+    #[test]
+    fn admin_reload() {
+        let cli = test_world();
+        cli.set_role(Role::Admin);
+
+        cli.ask("take leaf");
+        cli.ask("n");
+        assert!(cli
+            .ask("reload")
+            .starts_with("World reloaded.\n\nCentral Room"));
+        assert!(cli.ask("l").contains("leaf"));
+        assert!(cli.ask("i").contains("leaf"));
+    }
+
+    #[test]
+    fn roles_in_sessions() {
+        let mut sessions = SessionManager::new(|_| test_world());
+
+        assert!(!sessions.set_role("alice", Role::Author));
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        assert!(sessions.set_role("alice", Role::Author));
+        assert!(sessions
+            .ask("alice", "tp small cave")
+            .starts_with("Small Cave"));
+        assert!(!sessions
+            .ask("bob", "tp small cave")
+            .starts_with("Small Cave"));
+    }
+
+    fn cli_in_village() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Square",shows_karma:true,rooms:{
+                "Square":(name:"Square",desc:"Stalls crowd the square.",paths:[
+                    (directions:["north"],target:"Temple",opening:None,lock:None,
+                        karma:Some((at_least:Some(10),refusal:Some("The temple doors will not open for you.")))),
+                    (directions:["down"],target:"Den",opening:None,lock:None,
+                        karma:Some((at_most:Some(-10))))
+                ]),
+                "Temple":(name:"Temple",desc:"Candles burn before the altar.",paths:[]),
+                "Den":(name:"Den",desc:"Thieves count their take.",paths:[])
+            }))"#,
+        ).unwrap();
+        cli.set_random_source(Loaded);
+        cli.add_ally(
+            "Square",
+            Ally::new("beggar", "She holds out a bowl.")
+                .with_check(
+                    Check::new(
+                        Approach::Persuade,
+                        "give alms",
+                        0,
+                        "\"Bless you.\"",
+                        "\"Bless you anyway.\"",
+                    )
+                    .with_reward(Consequence::Karma(15)),
+                )
+                .with_check(
+                    Check::new(
+                        Approach::Persuade,
+                        "ask for a blessing",
+                        0,
+                        "She blesses you.",
+                        "She turns away.",
+                    )
+                    .with_karma(Karma::at_least(10)),
+                ),
+        );
+        cli
+    }
+
+    #[test]
+    fn good_deeds_open_the_way() {
+        let cli = cli_in_village();
+
+        assert_eq!(cli.ask("karma"), "Karma: 0 (neutral)");
+        assert_eq!(cli.ask("n"), "The temple doors will not open for you.");
+        assert!(!cli.ask("talk to beggar").contains("blessing"));
+        cli.ask("give alms");
+        cli.ask("bye");
+        assert_eq!(cli.karma(), 15);
+        assert_eq!(cli.ask("karma"), "Karma: 15 (good)");
+
+        assert!(cli.ask("talk to beggar").contains("ask for a blessing"));
+        cli.ask("bye");
+        assert!(cli
+            .ask("d")
+            .contains("You cannot bring yourself to go that way."));
+        assert!(cli.ask("n").contains("Candles burn"));
+
+        // a save keeps the player's karma
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert_eq!(cli.karma(), 15);
+        assert_eq!(cli.ask("karma"), "Karma: 15 (good)");
+    }
+
+    #[test]
+    fn killing_civilians_weighs_on_karma() {
+        let cli = cli_in_village();
+        cli.spawn_enemy(
+            "Square",
+            Enemy::new("merchant", "He haggles loudly.", EnemyStatus::Distracted).with_karma(-20),
+        );
+
+        cli.add_item(
+            "Square",
+            Item::Weapon(Weapon::new("dagger", "It is sharp.", 10)),
+        );
+        cli.ask("take dagger");
+        cli.ask("kill merchant with dagger");
+        assert_eq!(cli.kills("merchant"), 1);
+        assert_eq!(cli.ask("karma"), "Karma: -20 (wicked)");
+        assert!(cli.ask("d").contains("Thieves count"));
+    }
+
+    #[test]
+    fn hosts_can_weigh_quest_choices() {
+        let cli = test_world();
+        cli.adjust_karma(-60);
+        assert_eq!(cli.karma(), -60);
+        assert!(!cli.ask("karma").contains("Karma"));
+
+        cli.set_shows_karma(true);
+        assert_eq!(cli.ask("karma"), "Karma: -60 (villainous)");
+    }
+
+    fn cli_with_gatekeeper() -> Cli {
+        let cli = test_world();
+        cli.add_ally(
+            "Central Room",
+            Ally::new("gatekeeper", "He jingles a ring of keys.")
+                .with_topic("gate", "The gate stays shut after dark.")
+                .with_check(
+                    Check::new(
+                        Approach::Persuade,
+                        "the key",
+                        10,
+                        "\"Very well, take it.\"",
+                        "\"Not a chance.\"",
+                    )
+                    .with_reward(Consequence::Gift(Box::new(Item::Thing(
+                        Thing::new("brass key", "It is worn smooth."),
+                    )))),
+                ),
+        );
+        cli
+    }
+
+    #[test]
+    fn checks_are_listed_with_their_difficulty() {
+        let cli = cli_with_gatekeeper();
+
+        let res = cli.ask("talk to gatekeeper");
+        assert!(res.contains("1. gate"));
+        assert!(res.contains("2. [Persuade DC 10] the key"));
+    }
+
+    #[test]
+    fn passing_a_check_earns_its_reward_once() {
+        let cli = cli_with_gatekeeper();
+        cli.set_random_source(Loaded);
+
+        cli.ask("talk to gatekeeper");
+        assert_eq!(
+            cli.ask("2"),
+            "Success! \"Very well, take it.\"\nYou receive a brass key."
+        );
+        assert!(!cli.ask("the key").contains("You receive"));
+        cli.ask("bye");
+        assert!(cli.ask("i").contains("brass key"));
+        assert!(!cli.ask("talk to gatekeeper").contains("the key"));
+    }
+
+    #[test]
+    fn failing_a_check_can_start_a_fight() {
+        let cli = test_world();
+        cli.set_random_source(Cursed);
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("bandit", "He fingers his dagger.", EnemyStatus::Distracted).with_check(
+                Check::new(
+                    Approach::Intimidate,
+                    "the toll",
+                    12,
+                    "The bandit backs off.",
+                    "The bandit laughs and draws his dagger.",
+                )
+                .with_reward(Consequence::Calm)
+                .with_penalty(Consequence::Provoke),
+            ),
+        );
+
+        assert!(cli
+            .ask("talk to bandit")
+            .contains("1. [Intimidate DC 12] the toll"));
+        assert!(cli
+            .ask("the toll")
+            .starts_with("Failure. The bandit laughs and draws his dagger.\nThe bandit hit you"));
+        assert_eq!(cli.ask("n"), "Enemies bar your way.");
+    }
+
+    #[test]
+    fn nonsense_verbs_get_stock_refusals() {
+        let cli = test_world();
+
+        assert_eq!(
+            cli.ask("jump"),
+            "You jump on the spot. Nothing happens, and you feel a little silly."
+        );
+        assert_eq!(
+            cli.ask("whistle"),
+            "You sing a few bars. Only the echo joins in."
+        );
+        assert_eq!(cli.ask("xyzzy"), "A hollow voice says \"Fool.\"");
+        assert_eq!(cli.ask("damn"), "Such language! It does not help.");
+        assert_eq!(cli.ask("pray"), "There is no shrine here.");
+        assert!(cli.ask("frobnicate").contains("I do not know the word"));
+    }
+
+    #[test]
+    fn rooms_and_worlds_override_refusals() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Ledge",responses:{"jump":"Not from this height."},rooms:{
+                "Ledge":(name:"Ledge",desc:"Wind tugs at you.",paths:[
+                    (directions:["door"],target:"Chapel",opening:None,lock:None)
+                ]),
+                "Chapel":(name:"Chapel",desc:"The pews are empty.",paths:[
+                    (directions:["out"],target:"Ledge",opening:None,lock:None)
+                ],responses:{"pray":"The empty pews seem to listen."})
+            }))"#,
+        )
+        .unwrap();
+
+        assert_eq!(cli.ask("jump"), "Not from this height.");
+        assert_eq!(cli.ask("pray"), "There is no shrine here.");
+        cli.ask("enter door");
+        assert_eq!(cli.ask("pray"), "The empty pews seem to listen.");
+
+        cli.set_response(Some("Chapel"), "jump", "You would rather not, in here.");
+        cli.set_response(None, "sing", "Your voice carries far.");
+        assert_eq!(cli.ask("leap"), "You would rather not, in here.");
+        assert_eq!(cli.ask("sing"), "Your voice carries far.");
+
+        // a save keeps what the World and its Rooms say
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert_eq!(cli.ask("pray"), "The empty pews seem to listen.");
+        assert_eq!(cli.ask("sing"), "Your voice carries far.");
+    }
+
+    #[test]
+    fn replies_stand_in_for_verbs_done_to_things() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Crypt",replies:[
+                (verbs:["eat","devour"],object:"apple",failure:Some("You are not hungry enough."))
+            ],rooms:{
+                "Crypt":(name:"Crypt",desc:"Dust lies thick.",paths:[],replies:[
+                    (verbs:["take","get"],object:"sword",
+                        refusal:Some("The sword will not budge from the stone.")),
+                    (verbs:["open"],object:"coffin",
+                        success:Some("The lid grinds aside. Something inside sighs."))
+                ])
+            }))"#,
+        )
+        .unwrap();
+        cli.add_item("Crypt", Item::Thing(Thing::new("sword", "It gleams.")));
+        cli.add_item(
+            "Crypt",
+            Item::Container(Container::new("coffin", "It is carved.", Opening::Closed)),
+        );
+
+        assert_eq!(
+            cli.ask("get the sword"),
+            "The sword will not budge from the stone."
+        );
+        assert!(!cli.ask("i").contains("sword"));
+        assert_eq!(
+            cli.ask("open coffin"),
+            "The lid grinds aside. Something inside sighs."
+        );
+        // the engine still says how it went when there is nothing to stand in for it
+        assert_eq!(cli.ask("open coffin"), "The coffin is already opened.");
+        assert_eq!(cli.ask("devour apple"), "You are not hungry enough.");
+
+        cli.add_reply(
+            None,
+            Reply::new(&["x", "examine"], "sword").with_refusal("It is too bright to look at."),
+        );
+        assert_eq!(cli.ask("x sword"), "It is too bright to look at.");
+
+        // a save keeps what the World and its Rooms say
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert_eq!(
+            cli.ask("take sword"),
+            "The sword will not budge from the stone."
+        );
+        assert_eq!(cli.ask("examine sword"), "It is too bright to look at.");
+    }
+
+    fn cli_with_squire() -> Cli {
+        let cli = test_world();
+        cli.add_ally(
+            "Central Room",
+            Ally::new("squire", "It carries a spare lantern."),
+        );
+        cli
+    }
+
+    #[test]
+    fn answer_yes() {
+        let cli = cli_with_squire();
+
+        assert!(cli
+            .ask("hail squire")
+            .contains("Shall the squire stay by your side? (yes/no)"));
+        assert_eq!(cli.ask("yes"), "The squire nods.");
+        assert!(cli.ask("l").contains("There is a squire here."));
+    }
+
+    #[test]
+    fn answer_no() {
+        let cli = cli_with_squire();
+
+        cli.ask("hail squire");
+        assert_eq!(cli.ask("n"), "The squire departs.");
+        assert!(!cli.ask("l").contains("squire"));
+        assert!(cli.ask("n").contains("Small Cave"));
+    }
+
+    #[test]
+    fn gibberish_asks_again() {
+        let cli = cli_with_squire();
+
+        cli.ask("hail squire");
+        assert_eq!(
+            cli.ask("florp"),
+            "Shall the squire stay by your side? (yes/no)"
+        );
+        assert_eq!(cli.ask("1"), "The squire nods.");
+    }
+
+    #[test]
+    fn commands_set_the_question_aside() {
+        let cli = cli_with_squire();
+
+        cli.ask("hail squire");
+        assert!(cli.ask("north").contains("Small Cave"));
+        assert_eq!(cli.ask("yes"), "I do not know the word \"yes\".");
+
+        cli.ask("s");
+        cli.ask("hail squire");
+        assert!(cli.ask("i").contains("empty"));
+        assert!(!cli.ask("l").contains("Shall the squire"));
+    }
+
+    #[test]
+    fn hail_stranger() {
+        let cli = cli_with_squire();
+
+        assert_eq!(cli.ask("hail ghost"), "Hail, friend.");
+        assert!(cli.ask("n").contains("Small Cave"));
+    }
+
+    fn goblin() -> Enemy {
+        Enemy::new("goblin", "It is short and green.", EnemyStatus::Asleep)
+            .with_hp(8)
+            .with_damage(4)
+            .with_vulnerability(DamageType::Fire)
+    }
+
+    fn kill_goblin(cli: &Cli) {
+        let kills = cli.kills("goblin");
+        cli.spawn_enemy("Central Room", goblin().with_hp(1));
+        while cli.kills("goblin") == kills {
+            cli.ask("kill goblin with leaf");
+        }
+    }
+
+    #[test]
+    fn intel_grows_with_kills() {
+        let cli = test_world();
+        cli.ask("take leaf");
+        cli.spawn_enemy("Small Cave", goblin());
+        let inspect = |cli: &Cli| {
+            cli.ask("n");
+            let res = cli.ask("x goblin");
+            cli.ask("s");
+            res
+        };
+
+        assert_eq!(inspect(&cli), "It is short and green.");
+
+        kill_goblin(&cli);
+        assert_eq!(cli.kills("goblin"), 1);
+        assert_eq!(inspect(&cli), "It is short and green.\nHP: 6-11");
+
+        kill_goblin(&cli);
+        assert_eq!(
+            inspect(&cli),
+            "It is short and green.\nHP: 6-11\nDefenses: vulnerable to fire"
+        );
+
+        kill_goblin(&cli);
+        assert!(inspect(&cli).ends_with("\nAttacks: 1d4 damage"));
+    }
+
+    fn cli_with_goblin() -> Cli {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("goblin", "It is short and green.", EnemyStatus::Asleep),
+        );
+        cli
+    }
+
+    #[test]
+    fn bestiary() {
+        let cli = cli_with_goblin();
+
+        assert_eq!(cli.ask("bestiary"), "Bestiary:\n  ???");
+        cli.ask("n");
+        assert_eq!(
+            cli.ask("bestiary"),
+            "Bestiary:\n  goblin: It is short and green."
+        );
+    }
+
+    #[test]
+    fn codex() {
+        let cli = cli_with_goblin();
+
+        cli.ask("xyzzy");
+        let res = cli.ask("codex");
+        assert!(res.starts_with("Codex:\n  leaf: "));
+        assert!(!res.contains("iron sword"));
+        assert_eq!(res.matches("???").count(), 3);
+
+        cli.ask("n");
+        let res = cli.ask("codex");
+        assert!(res.contains("iron sword: The iron sword is of crude workmanship"));
+        assert_eq!(res.matches("???").count(), 1);
+    }
+
+    #[test]
+    fn kept_in_saves() {
+        let cli = cli_with_goblin();
+        cli.ask("n");
+        let save = cli.to_ron();
+
+        let loaded = cli_with_goblin().load_save(&save).unwrap();
+        assert!(loaded.ask("bestiary").contains("goblin: "));
+        assert!(loaded.ask("codex").contains("leather armor: "));
+    }
+
+    fn cli_at_market() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Market",rooms:{
+                "Market":(name:"Market",desc:"Stalls crowd the square.",paths:[
+                    (directions:["north"],target:"Gate",opening:None,lock:None),
+                    (directions:["cellar"],target:"Cellar",opening:None,lock:None,
+                        condition:Some(Remembers("squire",Gift)))
+                ]),
+                "Gate":(name:"Gate",desc:"The portcullis is raised.",paths:[]),
+                "Cellar":(name:"Cellar",desc:"Barrels line the walls.",paths:[])
+            }))"#,
+        )
+        .unwrap();
+        cli.set_random_source(Loaded);
+        cli.add_item("Market", Item::Thing(Thing::new("apple", "It is red.")));
+        cli.add_ally(
+            "Market",
+            Ally::new("squire", "He polishes a helmet.")
+                .with_topic("the road", "\"Bandits, mostly.\"")
+                .with_check(
+                    Check::new(
+                        Approach::Persuade,
+                        "the cellar",
+                        0,
+                        "\"There is a way down, for a friend.\"",
+                        "\"What cellar?\"",
+                    )
+                    .with_recalls(Deed::Gift),
+                )
+                .with_memory(
+                    Memory::default()
+                        .with_grudge(Deed::Theft)
+                        .with_remark(Deed::Gift, "\"Thanks again for the apple.\"")
+                        .with_remark(Deed::Theft, "\"Thief!\""),
+                ),
+        );
+        cli
+    }
+
+    #[test]
+    fn gifts_open_up_conversation() {
+        let cli = cli_at_market();
+
+        assert!(!cli.ask("talk to squire").contains("the cellar"));
+        cli.ask("bye");
+        cli.ask("take apple");
+        assert_eq!(
+            cli.ask("give apple to squire"),
+            "You give the apple to the squire."
+        );
+        let res = cli.ask("talk to squire");
+        assert!(res.starts_with("\"Thanks again for the apple.\""));
+        assert!(res.contains("[Persuade DC 0] the cellar"));
+    }
+
+    #[test]
+    fn gifts_need_someone_to_take_them() {
+        let cli = cli_at_market();
+
+        cli.ask("take apple");
+        assert_eq!(cli.ask("give apple to ogre"), "There is no \"ogre\" here.");
+        assert!(cli.ask("i").contains("apple"));
+        assert_eq!(
+            cli.ask("give pear to squire"),
+            "You do not have the \"pear\"."
+        );
+    }
+
+    #[test]
+    fn theft_is_seen_and_held_against_the_player() {
+        let cli = cli_at_market();
+        cli.set_owned("Market", true);
+
+        assert_eq!(cli.ask("take apple"), "Taken.\nThe squire saw you take it.");
+        assert_eq!(
+            cli.ask("talk to squire"),
+            "\"Thief!\"\nThe squire will not speak to you."
+        );
+    }
+
+    #[test]
+    fn taking_what_is_free_goes_unremarked() {
+        let cli = cli_at_market();
+
+        assert_eq!(cli.ask("take apple"), "Taken.");
+        assert!(!cli.ask("talk to squire").contains("Thief!"));
+    }
+
+    #[test]
+    fn grudges_turn_enemies_hostile() {
+        let cli = cli_at_market();
+        cli.set_owned("Market", true);
+        cli.spawn_enemy(
+            "Market",
+            Enemy::new("guard", "He leans on his halberd.", EnemyStatus::Distracted)
+                .with_hp(50)
+                .with_memory(Memory::default().with_grudge(Deed::Theft)),
+        );
+
+        assert!(cli
+            .ask("take apple")
+            .contains("The squire and the guard saw you take it."));
+        assert_eq!(cli.ask("n"), "Enemies bar your way.");
+    }
+
+    #[test]
+    fn memories_are_saved_and_open_hidden_ways() {
+        let cli = cli_at_market();
+
+        assert!(!cli.ask("go cellar").starts_with("Cellar"));
+        cli.ask("take apple");
+        cli.ask("give apple to squire");
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(cli.ask("go cellar").starts_with("Cellar"));
+    }
+}
@@ -0,0 +1,841 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use kingslayer::{
+        Ability, Ally, BodyPart, Cli, Container, DamageType, Element, Enemy, EnemyStatus, Gold,
+        Hazard, HpDisplay, Item, LootTable, Mimic, Opening, PartEffect, Plate, Revival,
+        SavingThrow, Summon, Thing, Weapon,
+    };
+
+    use crate::common::{test_world, Loaded};
+
+    fn cli_with_troll(hp: i32, damage: u32) -> Cli {
+        let cli = test_world();
+        cli.set_random_source(Loaded);
+        cli.receive_item(Item::Weapon(Weapon::new("club", "It is knotted.", 8)));
+        cli.ask("equip club");
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("troll", "It is covered in warts.", EnemyStatus::Angry)
+                .with_hp(hp)
+                .with_damage(damage),
+        );
+        cli
+    }
+
+    #[test]
+    fn fights_until_the_enemy_falls() {
+        let cli = cli_with_troll(20, 0);
+
+        let res = cli.ask("fight troll");
+        assert!(res.matches("You hit the troll").count() > 1);
+        assert!(res.contains("It is dead."));
+        assert!(!cli.ask("l").contains("troll"));
+    }
+
+    #[test]
+    fn breaks_off_when_hp_runs_low() {
+        let cli = cli_with_troll(1000, 4);
+        cli.ask("set fight threshold 50");
+
+        let res = cli.ask("fight troll");
+        assert!(res.ends_with("HP left."));
+        assert!(res.contains("You break off the fight with "));
+        assert!(cli.ask("c").contains("HP: "));
+    }
+
+    #[test]
+    fn stops_when_interrupted() {
+        let cli = cli_with_troll(1000, 0);
+        cli.interrupt_handle().store(true, Ordering::SeqCst);
+
+        let res = cli.ask("fight troll");
+        assert_eq!(res.matches("You hit the troll").count(), 1);
+        assert!(res.ends_with("You hold off and take stock."));
+        assert!(!cli.interrupt_handle().load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn needs_someone_to_fight() {
+        let cli = test_world();
+
+        assert_eq!(cli.ask("fight"), "What do you want to fight?");
+        let cli = test_world();
+        assert_eq!(
+            cli.ask("fight troll"),
+            "What do you want to attack the troll with?"
+        );
+    }
+
+    fn armed_cli() -> Cli {
+        let cli = test_world();
+        cli.set_random_source(Loaded);
+        cli.receive_item(Item::Weapon(Weapon::new("club", "It is knotted.", 1)));
+        cli.ask("equip club");
+        cli
+    }
+
+    fn rat(name: &str) -> Enemy {
+        Enemy::new(name, "It twitches.", EnemyStatus::Angry).with_hp(100)
+    }
+
+    #[test]
+    fn bare_attacks_find_the_only_enemy() {
+        assert_eq!(armed_cli().ask("attack"), "What do you want to attack?");
+
+        let cli = armed_cli();
+        cli.spawn_enemy("Central Room", rat("rat"));
+        assert!(cli.ask("attack").starts_with("You hit the rat"));
+        assert!(cli.ask("kill").starts_with("You hit the rat"));
+    }
+
+    #[test]
+    fn bare_attacks_remember_the_target() {
+        let cli = armed_cli();
+        cli.spawn_enemy("Central Room", rat("brown rat"));
+        cli.spawn_enemy("Central Room", rat("black rat"));
+
+        assert!(cli
+            .ask("attack black rat")
+            .starts_with("You hit the black rat"));
+        assert!(cli.ask("attack").starts_with("You hit the black rat"));
+
+        let cli = armed_cli();
+        cli.spawn_enemy("Central Room", rat("brown rat"));
+        cli.spawn_enemy("Central Room", rat("black rat"));
+        assert_eq!(cli.ask("attack"), "What do you want to attack?");
+    }
+
+    fn ogre_cli() -> Cli {
+        let cli = test_world();
+        cli.seed(684);
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("ogre", "It is enormous.", EnemyStatus::Asleep)
+                .with_hp(100)
+                .with_strength(1),
+        );
+        cli
+    }
+
+    #[test]
+    fn push_enemy() {
+        let cli = ogre_cli();
+
+        assert!(cli.ask("push ogre west").contains("cannot push"));
+        assert!((0..20).any(|_| cli
+            .ask("push ogre north")
+            .contains("You shove the ogre north!")));
+        assert!(!cli.ask("l").contains("ogre"));
+        assert!(cli.ask("n").contains("There is an ogre here."));
+    }
+
+    #[test]
+    fn grapple_enemy() {
+        let cli = ogre_cli();
+
+        assert!((0..20).any(|_| cli
+            .ask("grapple ogre")
+            .contains("You wrestle the ogre into your grip.")));
+        assert!(cli
+            .ask("grab ogre")
+            .contains("You already have hold of the ogre."));
+    }
+
+    #[test]
+    fn morale_breaks() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("goblin", "It is short and green.", EnemyStatus::Asleep).with_hp(1),
+        );
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("kobold", "It yips nervously.", EnemyStatus::Angry)
+                .with_hp(50)
+                .with_morale(0)
+                .with_item(Item::Gold(Gold::new(7))),
+        );
+
+        cli.ask("take leaf");
+        let res = cli.ask("kill goblin with leaf");
+        assert!(
+            res.contains("The kobold flees!") || res.contains("The kobold throws down its arms")
+        );
+        assert!(!cli.ask("l").contains("There is a kobold here.\n"));
+
+        if res.contains("surrenders") {
+            assert!(cli.ask("l").contains("It has surrendered."));
+            cli.ask("take gold");
+            assert!(cli.ask("i").contains("Gold: 7"));
+        }
+    }
+
+    #[test]
+    fn fearless() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("goblin", "It is short and green.", EnemyStatus::Asleep).with_hp(1),
+        );
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("kobold", "It yips nervously.", EnemyStatus::Angry).with_hp(50),
+        );
+
+        cli.ask("take leaf");
+        let res = cli.ask("kill goblin with leaf");
+        assert!(!res.contains("flees") && !res.contains("surrenders"));
+    }
+
+    #[test]
+    fn target_body_part() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("troll", "It is huge and warty.", EnemyStatus::Asleep)
+                .with_hp(100)
+                .with_body_part(BodyPart::new("head", 0).with_effect(PartEffect::Stun))
+                .with_body_part(BodyPart::new("leg", 0).with_effect(PartEffect::Slow)),
+        );
+
+        cli.ask("take leaf");
+        assert!(cli
+            .ask("attack troll's head with leaf")
+            .contains("You hit the troll in the head with your leaf"));
+        assert!(cli
+            .ask("attack troll's leg with leaf")
+            .contains("The troll staggers on its wounded leg."));
+        assert!(cli
+            .ask("attack troll's tail with leaf")
+            .contains("The troll has no tail."));
+    }
+
+    #[test]
+    fn resistances() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("skeleton", "Its bones rattle.", EnemyStatus::Asleep)
+                .with_hp(100)
+                .with_immunity(DamageType::Piercing)
+                .with_vulnerability(DamageType::Bludgeoning),
+        );
+        cli.add_item(
+            "Central Room",
+            Item::Weapon(
+                Weapon::new("arrow", "It is fletched.", 4).with_damage_type(DamageType::Piercing),
+            ),
+        );
+        cli.add_item(
+            "Central Room",
+            Item::Weapon(
+                Weapon::new("mace", "It is heavy.", 4).with_damage_type(DamageType::Bludgeoning),
+            ),
+        );
+
+        cli.ask("take arrow");
+        cli.ask("take mace");
+        assert!(cli
+            .ask("kill skeleton with arrow")
+            .contains("The skeleton shrugs off the arrow."));
+        assert!(cli
+            .ask("kill skeleton with mace")
+            .contains("The mace tears into the skeleton!"));
+    }
+
+    #[test]
+    fn gaze_attack() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            // it never bites, so only its gaze can kill
+            Enemy::new("basilisk", "Its eyes glow.", EnemyStatus::Angry)
+                .with_damage(0)
+                .with_deadly(SavingThrow::new(
+                    Ability::Constitution,
+                    100,
+                    "the basilisk's gaze",
+                    "Your body hardens into cold stone.",
+                )),
+        );
+
+        let res = (0..50)
+            .map(|_| cli.ask("wait"))
+            .find(|res| res.contains("You fail to escape the basilisk's gaze."))
+            .unwrap();
+        assert!(res.contains("Your body hardens into cold stone.\n\nYou died. Farewell."));
+    }
+
+    #[test]
+    fn poison_needle_trap() {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Container(
+                Container::new("chest", "It is made of oak.", Opening::Closed)
+                    .with_item(Item::Thing(Thing::new("ruby", "It sparkles.")))
+                    .with_trap(SavingThrow::new(
+                        Ability::Dexterity,
+                        100,
+                        "the poisoned needle",
+                        "The poison stops your heart.",
+                    )),
+            ),
+        );
+
+        let res = cli.ask("open chest");
+        assert!(res.contains("You fail to escape the poisoned needle."));
+        assert!(res.contains("The poison stops your heart."));
+        assert!(res.contains("You died. Farewell."));
+    }
+
+    #[test]
+    fn survive_fall() {
+        let cli = test_world();
+        let fall = Hazard::new("a chasm", 0).with_save(SavingThrow::new(
+            Ability::Dexterity,
+            -100,
+            "the fall",
+            "You plummet into the darkness.",
+        ));
+        cli.add_hazard("Small Cave", fall.clone());
+        cli.add_hazard("Bedroom", fall);
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("giant", "It is enormous.", EnemyStatus::Angry).with_knockback(100),
+        );
+
+        let res = cli.ask("l");
+        assert!(res.contains("You narrowly escape the fall."));
+        assert!(!res.contains("You died."));
+    }
+
+    fn goblin_cli() -> Cli {
+        let cli = test_world();
+        for _ in 0..3 {
+            cli.spawn_enemy(
+                "Central Room",
+                Enemy::new("goblin", "It is short and green.", EnemyStatus::Asleep).with_hp(1),
+            );
+        }
+        cli
+    }
+
+    #[test]
+    fn grouped_desc() {
+        let cli = goblin_cli();
+
+        assert!(cli
+            .ask("l")
+            .contains("There are 3 goblins here. They are asleep."));
+        cli.ask("take leaf");
+        assert!(cli.ask("kill goblin 2 with leaf").contains("It is dead."));
+        assert!(cli.ask("l").contains("There are 2 goblins here."));
+    }
+
+    #[test]
+    fn area_attack() {
+        let cli = goblin_cli();
+        cli.add_item(
+            "Central Room",
+            Item::Weapon(Weapon::new("firebomb", "It smells of oil.", 4).with_area()),
+        );
+
+        cli.ask("take firebomb");
+        assert_eq!(
+            cli.ask("kill goblin with firebomb")
+                .matches("It is dead.")
+                .count(),
+            3
+        );
+        assert!(!cli.ask("l").contains("goblin"));
+    }
+
+    #[test]
+    fn push_into_hazard() {
+        let cli = test_world();
+        cli.seed(685);
+        cli.add_hazard("Small Cave", Hazard::new("a pool of lava", 100));
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("ogre", "It is enormous.", EnemyStatus::Asleep)
+                .with_hp(20)
+                .with_strength(1),
+        );
+
+        let res = (0..20)
+            .map(|_| cli.ask("push ogre north"))
+            .find(|res| res.contains("You shove"))
+            .unwrap();
+        assert!(res.contains("The ogre is caught in a pool of lava. It is dead."));
+        assert!(!cli.ask("n").contains("ogre"));
+    }
+
+    #[test]
+    fn knockback() {
+        let cli = test_world();
+        cli.add_hazard("Small Cave", Hazard::new("a pool of lava", 1));
+        cli.add_hazard("Bedroom", Hazard::new("a pile of broken glass", 1));
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("giant", "It is enormous.", EnemyStatus::Angry).with_knockback(100),
+        );
+
+        let res = cli.ask("l");
+        assert!(res.contains("You are thrown from your feet!"));
+        assert!(res.contains("You are caught in"));
+        assert!(!cli.ask("l").contains("Central Room"));
+    }
+
+    #[test]
+    fn enemies_read_without_a_maximum_keep_their_first_hp() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Ledge",rooms:{
+                "Ledge":(name:"Ledge",desc:"A ledge over a fiery pit.",paths:[
+                    (directions:["down"],target:"Pit",opening:None,lock:None)
+                ],enemies:[(name:"ogre",desc:"An ogre dozes here.",inspect:"It is enormous.",
+                    hp:20,ac:1,xp:1,damage:1,status:Asleep,strength:1)]),
+                "Pit":(name:"Pit",desc:"Lava bubbles all around.",paths:[])
+            }))"#,
+        )
+        .unwrap();
+        cli.seed(685);
+        cli.add_hazard("Pit", Hazard::new("a pool of lava", 12));
+
+        (0..20)
+            .map(|_| cli.ask("push ogre down"))
+            .find(|res| res.contains("The ogre is caught in a pool of lava."))
+            .unwrap();
+        assert!(cli.to_ron().contains("hp:8,max_hp:20,"));
+    }
+
+    fn cli() -> Cli {
+        let cli = test_world();
+        cli.add_element(
+            "Small Cave",
+            Element::new(
+                "boulder",
+                "A round boulder sits by the wall.",
+                "It is far too heavy to lift.",
+            )
+            .with_heavy(),
+        );
+        cli.add_element(
+            "Central Room",
+            Element::new(
+                "stone plate",
+                "A stone plate is set into the floor.",
+                "It gives a little underfoot.",
+            )
+            .with_plate(
+                Plate::new("The plate sinks with a grinding click.")
+                    .with_opens("small wooden door"),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn push_boulder_follows_it() {
+        let cli = cli();
+        cli.ask("n");
+
+        let res = cli.ask("push boulder south");
+        assert!(res.contains("You push the boulder south."));
+        assert!(res.contains("Central Room"));
+        assert!(res.contains("A round boulder sits by the wall."));
+    }
+
+    #[test]
+    fn boulder_presses_plate() {
+        let cli = cli();
+        cli.ask("n");
+
+        let res = cli.ask("pull boulder south");
+        assert!(res.contains("The plate sinks with a grinding click."));
+        assert!(cli.ask("enter small wooden door").contains("Closet"));
+    }
+
+    #[test]
+    fn plate_only_answers_once() {
+        let cli = cli();
+        cli.ask("n");
+        cli.ask("push boulder south");
+        cli.ask("push boulder north");
+
+        let res = cli.ask("push boulder south");
+        assert!(!res.contains("The plate sinks"));
+    }
+
+    #[test]
+    fn heavy_objects_stay_behind_shut_doors() {
+        let cli = test_world();
+        cli.add_element(
+            "Central Room",
+            Element::new("crate", "A heavy crate sits here.", "It is nailed shut.").with_heavy(),
+        );
+
+        assert_eq!(cli.ask("push crate small wooden door"), "The way is shut.");
+        assert!(cli.ask("l").contains("A heavy crate sits here."));
+    }
+
+    #[test]
+    fn light_objects_cannot_be_pushed_away() {
+        let cli = cli();
+
+        assert!(!cli.ask("push stone plate north").contains("You push"));
+    }
+
+    #[test]
+    fn numeric() {
+        let cli = test_world();
+        assert!(cli.ask("c").contains("HP: (13 / 13)"));
+    }
+
+    #[test]
+    fn descriptive() {
+        let cli = test_world();
+        cli.set_hp_display(HpDisplay::Descriptive);
+        let res = cli.ask("c");
+        assert!(res.contains("HP: unhurt"));
+        assert!(!res.contains("(13 / 13)"));
+    }
+
+    #[test]
+    fn bar() {
+        let cli = test_world();
+        cli.set_hp_display(HpDisplay::Bar);
+        assert!(cli.ask("c").contains("HP: [██████████]"));
+    }
+
+    #[test]
+    fn descriptive_damage() {
+        let cli = test_world();
+        cli.set_hp_display(HpDisplay::Descriptive);
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small.", EnemyStatus::Angry).with_damage(1),
+        );
+
+        let res = (0..50)
+            .map(|_| cli.ask("wait"))
+            .find(|res| res.contains("The rat hit you for"))
+            .unwrap();
+        assert!(res.contains("You are lightly wounded."));
+    }
+
+    fn mimic_cli() -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Mimic(Mimic::new(
+                "wooden chest",
+                "It is bound with iron bands.",
+                Enemy::new("mimic", "It is all teeth.", EnemyStatus::Asleep).with_hp(5),
+            )),
+        );
+        cli
+    }
+
+    #[test]
+    fn take_mimic() {
+        let cli = mimic_cli();
+
+        assert!(cli.ask("l").contains("There is a wooden chest here."));
+        assert!(cli.ask("take chest").contains("springs to life"));
+        assert!(!cli.ask("i").contains("chest"));
+        assert!(cli.ask("l").contains("There is a mimic here."));
+        assert!(cli.ask("s").contains("Enemies bar your way."));
+    }
+
+    #[test]
+    fn open_mimic() {
+        let cli = mimic_cli();
+
+        assert!(cli.ask("open chest").contains("springs to life"));
+        assert!(cli.ask("take all").contains("Taken."));
+        assert!(cli.ask("i").contains("leaf"));
+    }
+
+    #[test]
+    fn summon_ally() {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Summon(Summon::new(
+                "bone horn",
+                "It is carved with wolves.",
+                Ally::new("spirit wolf", "It glows faintly.").with_damage(1),
+                4,
+            )),
+        );
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("goblin", "It is short and green.", EnemyStatus::Angry).with_hp(1),
+        );
+
+        cli.ask("take horn");
+        assert!(cli
+            .ask("use horn")
+            .contains("A spirit wolf appears at your side."));
+        assert!(!cli.ask("i").contains("bone horn"));
+        assert!(cli.ask("l").contains("There is a spirit wolf here."));
+
+        let res = cli.ask("n");
+        assert!(res.contains("The spirit wolf hits the goblin for 1 damage. It is dead."));
+        assert!(res.contains("The spirit wolf fades away."));
+        assert!(!cli.ask("l").contains("spirit wolf"));
+    }
+
+    #[test]
+    fn use_nothing() {
+        let cli = test_world();
+
+        assert!(cli.ask("use horn").contains("You do not have"));
+        cli.ask("take leaf");
+        assert!(cli.ask("use leaf").contains("You cannot use the leaf."));
+    }
+
+    #[test]
+    fn loot_table() {
+        let cli = test_world();
+        cli.seed(1);
+
+        cli.add_loot_table(
+            "vermin",
+            LootTable::new().with_gold(5, 5).with_common(
+                1,
+                Item::Thing(Thing::new("rat tail", "It is long and bald.")),
+            ),
+        );
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small and mangy.", EnemyStatus::Asleep)
+                .with_loot_table("vermin"),
+        );
+
+        cli.ask("take leaf");
+        assert!(cli.ask("kill rat with leaf").contains("rat tail"));
+        cli.ask("take all");
+        assert!(cli.ask("i").contains("rat tail"));
+        assert!(cli.ask("i").contains("Gold: 5"));
+    }
+
+    #[test]
+    fn loot_table_gold_over_the_whole_range() {
+        let table = LootTable::new().with_gold(0, u32::MAX);
+        for _ in 0..10 {
+            assert!(table.roll().len() <= 1);
+        }
+    }
+
+    #[test]
+    fn loot_table_nothing() {
+        let cli = test_world();
+
+        cli.add_loot_table(
+            "empty",
+            LootTable::new().with_nothing(1).with_common(
+                0,
+                Item::Thing(Thing::new("rat tail", "It is long and bald.")),
+            ),
+        );
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small and mangy.", EnemyStatus::Asleep)
+                .with_loot_table("empty"),
+        );
+
+        cli.ask("take leaf");
+        assert!(!cli.ask("kill rat with leaf").contains("dropped"));
+    }
+
+    fn cli_with_rats(seed: u64) -> Cli {
+        let cli = test_world();
+        cli.seed(seed);
+        cli.add_loot_table("vermin", LootTable::new().with_gold(1, 100));
+        for _ in 0..5 {
+            cli.spawn_enemy(
+                "Central Room",
+                Enemy::new("rat", "It is small and mangy.", EnemyStatus::Asleep)
+                    .with_loot_table("vermin"),
+            );
+        }
+        cli.ask("take leaf");
+        cli
+    }
+
+    #[test]
+    fn games_roll_their_own_dice() {
+        let alone = cli_with_rats(7);
+        let expected: Vec<String> = (0..5).map(|_| alone.ask("kill rat with leaf")).collect();
+
+        // another game rolling on the same thread in between does not change the sequence
+        let (game, other) = (cli_with_rats(7), cli_with_rats(8));
+        for expected in expected {
+            other.ask("kill rat with leaf");
+            assert_eq!(game.ask("kill rat with leaf"), expected);
+        }
+    }
+
+    #[test]
+    fn phoenix_feather() {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Revival(Revival::new(
+                "phoenix feather",
+                "It is warm to the touch.",
+                50,
+            )),
+        );
+        cli.ask("take feather");
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("dragon", "It is huge.", EnemyStatus::Angry).with_damage(1000),
+        );
+
+        let res = (0..50)
+            .map(|_| cli.ask("wait"))
+            .find(|res| res.contains("crumbles to ash"))
+            .unwrap();
+        assert!(res.contains("The phoenix feather crumbles to ash"));
+        assert!(!res.contains("You died. Farewell."));
+        assert!(!cli.ask("i").contains("phoenix feather"));
+    }
+
+    #[test]
+    fn shrine() {
+        let cli = test_world();
+        cli.add_shrine("Central Room");
+        assert!(cli.ask("pray").contains("You kneel at the shrine."));
+        cli.ask("n");
+        assert!(cli.ask("pray").contains("There is no shrine here."));
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("dragon", "It is huge.", EnemyStatus::Angry).with_damage(1000),
+        );
+
+        let res = (0..50)
+            .map(|_| cli.ask("wait"))
+            .find(|res| res.contains("The shrine calls you back from death."))
+            .unwrap();
+        assert!(res.contains("Central Room"));
+        assert!(!res.contains("You died. Farewell."));
+    }
+
+    #[test]
+    fn no_revival() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("dragon", "It is huge.", EnemyStatus::Angry).with_damage(1000),
+        );
+
+        assert!((0..50).any(|_| cli.ask("wait").contains("You died. Farewell.")));
+    }
+
+    fn cli_at_the_gate() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Yard",rooms:{
+                "Yard":(name:"Yard",desc:"Weeds choke the yard.",paths:[
+                    (directions:["iron gate"],target:"Vault",opening:Some(Closed),lock:Some(Locked("gate key"))),
+                    (directions:["north"],target:"Hall",opening:None,lock:None)
+                ],items:[
+                    Thing((name:"chest",desc:"A chest sits in the weeds.",inspect:"It is heavy."))
+                ]),
+                "Vault":(name:"Vault",desc:"Gold glitters.",paths:[]),
+                "Hall":(name:"Hall",desc:"Banners hang from the rafters.",paths:[
+                    (directions:["south"],target:"Yard",opening:None,lock:None)
+                ])
+            }))"#,
+        ).unwrap();
+        cli.set_random_source(Loaded);
+        cli.add_ally(
+            "Yard",
+            Ally::new("thief", "She twirls a lockpick.")
+                .with_order("pick")
+                .with_order("carry")
+                .with_refusal("\"Fight? Not me.\""),
+        );
+        cli.add_ally(
+            "Yard",
+            Ally::new("squire", "He grips his sword.")
+                .with_damage(6)
+                .with_order("attack"),
+        );
+        cli
+    }
+
+    #[test]
+    fn companions_do_what_they_can_on_their_turn() {
+        let cli = cli_at_the_gate();
+
+        assert_eq!(
+            cli.ask("tell thief to pick lock on gate"),
+            "The thief nods.\nThe thief picks the lock on the iron gate."
+        );
+        assert_eq!(
+            cli.ask("tell thief to carry chest"),
+            "The thief nods.\nThe thief picks up the chest."
+        );
+        assert!(!cli.ask("l").contains("A chest sits"));
+        assert_eq!(
+            cli.ask("tell thief to drop"),
+            "The thief nods.\nThe thief puts down the chest."
+        );
+        assert!(cli.ask("l").contains("A chest sits"));
+    }
+
+    #[test]
+    fn companions_refuse_what_they_cannot_do() {
+        let cli = cli_at_the_gate();
+
+        assert_eq!(cli.ask("tell thief to attack troll"), "\"Fight? Not me.\"");
+        assert_eq!(
+            cli.ask("tell squire to carry chest"),
+            "The squire will not do that."
+        );
+        assert_eq!(
+            cli.ask("tell squire to juggle"),
+            "The squire can be told to wait here, follow you, or attack someone."
+        );
+    }
+
+    #[test]
+    fn companions_attack_whom_they_are_told() {
+        let cli = cli_at_the_gate();
+        cli.spawn_enemy(
+            "Yard",
+            Enemy::new("troll", "It is huge.", EnemyStatus::Asleep).with_hp(50),
+        );
+
+        assert!(cli
+            .ask("tell squire to attack troll")
+            .starts_with("The squire nods.\nThe squire hits the troll for"));
+    }
+
+    #[test]
+    fn companions_wait_or_follow_as_told() {
+        let cli = cli_at_the_gate();
+
+        cli.ask("tell thief to follow");
+        cli.ask("tell squire to wait");
+        cli.ask("n");
+        let hall = cli.ask("l");
+        assert!(hall.contains("thief"));
+        assert!(!hall.contains("squire"));
+
+        // a save keeps who follows
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        cli.ask("s");
+        cli.ask("n");
+        assert!(cli.ask("l").contains("thief"));
+    }
+}
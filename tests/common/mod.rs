@@ -0,0 +1,48 @@
+// Fixtures shared by the integration tests; each test file uses only some of them
+#![cfg(feature = "serde")]
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use kingslayer::{Cli, RandomSource, SaveStore};
+
+// the small world most tests play in
+pub fn test_world() -> Cli {
+    Cli::from_file("worlds/test_world.ron").unwrap()
+}
+
+// always rolls the highest number on every die
+pub struct Loaded;
+
+impl RandomSource for Loaded {
+    fn next_u64(&mut self) -> u64 {
+        u64::MAX
+    }
+}
+
+// always rolls a 1
+pub struct Cursed;
+
+impl RandomSource for Cursed {
+    fn next_u64(&mut self) -> u64 {
+        0
+    }
+}
+
+// keeps saves in memory, so the tests leave no files behind
+#[derive(Default)]
+pub struct MemoryStore(pub HashMap<String, String>);
+
+impl SaveStore for MemoryStore {
+    fn write(&mut self, name: &str, save: &str) -> Result<String, String> {
+        self.0.insert(name.to_owned(), save.to_owned());
+        Ok(format!("memory:{}", name))
+    }
+
+    fn read(&mut self, name: &str) -> Result<String, String> {
+        self.0
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("no save called {}", name))
+    }
+}
@@ -1,10 +1,12 @@
+#![cfg(feature = "serde")]
+
 #[cfg(test)]
 mod tests {
     use kingslayer::Cli;
 
     #[test]
     fn player_equip() {
-        let cli = Cli::from_file("worlds/test_world.ron");
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
 
         cli.ask("n");
         cli.ask("take iron sword");
@@ -24,7 +26,7 @@ mod tests {
 
     #[test]
     fn player_don() {
-        let cli = Cli::from_file("worlds/test_world.ron");
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
 
         cli.ask("n");
         cli.ask("take leather armor");
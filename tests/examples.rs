@@ -0,0 +1,64 @@
+#![cfg(any(feature = "example-dungeon", feature = "example-house"))]
+
+#[cfg(test)]
+mod tests {
+    use kingslayer::Cli;
+
+    #[cfg(feature = "example-dungeon")]
+    #[test]
+    fn tiny_dungeon_plays_through() {
+        let cli = Cli::tiny_dungeon();
+        cli.seed(3);
+        assert!(cli.validate().is_empty());
+        assert!(cli.lint().iter().all(|finding| !finding.is_error()));
+        assert!(cli.ask("about").contains("The Tiny Dungeon"));
+
+        assert!(cli.ask("take torch").contains("Taken"));
+        assert!(cli.ask("n").contains("goblin"));
+        cli.ask("take sword");
+        cli.ask("e");
+        cli.ask("take leather jerkin");
+        cli.ask("open chest");
+        cli.ask("take healing potion from chest");
+        let inventory = cli.ask("i");
+        for item in ["torch", "short sword", "leather jerkin", "healing potion"] {
+            assert!(inventory.contains(item), "{}", inventory);
+        }
+        cli.ask("w");
+        assert!(cli.ask("n").contains("skeleton king"));
+    }
+
+    #[cfg(feature = "example-house")]
+    #[test]
+    fn tutorial_house_plays_through() {
+        let cli = Cli::tutorial_house();
+        assert!(cli.validate().is_empty());
+        assert!(cli.lint().iter().all(|finding| !finding.is_error()));
+        assert!(cli.ask("l").contains("take note"));
+
+        cli.ask("take note");
+        cli.ask("s");
+        cli.ask("e");
+        assert!(cli.ask("recipes").contains("toast"));
+        cli.ask("open cupboard");
+        cli.ask("take bread from cupboard");
+        assert!(cli.ask("make toast").contains("toast"));
+        assert!(cli.ask("i").contains("toast"));
+        cli.ask("w");
+        assert!(cli.ask("w").contains("cat"));
+        cli.ask("feed cat");
+        assert!(!cli.ask("i").contains("toast"));
+    }
+
+    #[test]
+    fn example_is_built_in() {
+        let cli = Cli::example();
+        assert!(cli.validate().is_empty());
+        let about = cli.ask("about");
+        assert!(
+            about.contains("The Tiny Dungeon") || about.contains("The Tutorial House"),
+            "{}",
+            about
+        );
+    }
+}
@@ -0,0 +1,1010 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        env, fs,
+        panic::{self, AssertUnwindSafe},
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use kingslayer::{
+        testing::Golden, Ability, Audio, Class, Cli, Container, Cue, Enemy, EnemyStatus, Haptics,
+        Item, Metrics, NoDelay, Opening, Pause, RandomSource, Requirements, Role, Rumble,
+        SavingThrow, SplitMix64, Thing, Timing, Weapon,
+    };
+
+    use crate::common::{test_world, Loaded};
+
+    #[derive(Default)]
+    struct Cues(Vec<Cue>);
+
+    impl Audio for Cues {
+        fn cue(&mut self, cue: &Cue) {
+            self.0.push(cue.clone());
+        }
+    }
+
+    fn listen_for_cues(cli: &Cli) -> Arc<Mutex<Cues>> {
+        let cues = Arc::new(Mutex::new(Cues::default()));
+        cli.set_audio(cues.clone());
+        cues
+    }
+
+    #[test]
+    fn door_and_music() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"It is long.",paths:[(directions:["door","north"],target:"Crypt",opening:Some(Closed),lock:None,sound:Some("door_creak"))]),
+                "Crypt":(name:"Crypt",desc:"It is cold.",paths:[(directions:["south"],target:"Hall",opening:None,lock:None)])
+            }))"#,
+        ).unwrap();
+        cli.set_music("Crypt", "dungeon_theme");
+        let cues = listen_for_cues(&cli);
+
+        cli.ask("open door");
+        cli.ask("open door");
+        cli.ask("n");
+        cli.ask("l");
+        assert_eq!(
+            cues.lock().unwrap().0,
+            vec![
+                Cue::Sound(String::from("door_creak")),
+                Cue::Music(String::from("dungeon_theme"))
+            ]
+        );
+        assert_eq!(cues.lock().unwrap().0[1].id(), "dungeon_theme");
+    }
+
+    #[test]
+    fn combat_sounds() {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Weapon(Weapon::new("axe", "It is sharp.", 1).with_sound("axe_hit")),
+        );
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("goblin", "It is short and green.", EnemyStatus::Angry)
+                .with_hp(100)
+                .with_sound("goblin_snarl"),
+        );
+        let cues = listen_for_cues(&cli);
+
+        cli.ask("take axe");
+        cli.ask("hit goblin with axe");
+        let cues = cues.lock().unwrap();
+        assert!(cues.0.contains(&Cue::Sound(String::from("axe_hit"))));
+        assert!(cues.0.contains(&Cue::Sound(String::from("goblin_snarl"))));
+    }
+
+    #[test]
+    fn silent_without_backend() {
+        let cli = test_world();
+        cli.set_music("Small Cave", "cave_theme");
+        assert!(cli.ask("n").starts_with("Small Cave"));
+    }
+
+    #[test]
+    fn region_changes() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Glade",rooms:{
+                "Glade":(name:"Glade",desc:"Birds sing.",paths:[(directions:["north"],target:"Bog",opening:None,lock:None)]),
+                "Bog":(name:"Bog",desc:"It squelches.",paths:[(directions:["south"],target:"Glade",opening:None,lock:None)])
+            },regions:{"Woods":(),"Marsh":()}))"#,
+        ).unwrap();
+        cli.set_region("Glade", "Woods");
+        cli.set_region("Bog", "Marsh");
+        let cues = listen_for_cues(&cli);
+
+        cli.ask("n");
+        cli.ask("l");
+        cli.ask("s");
+        assert_eq!(
+            cues.lock().unwrap().0,
+            vec![
+                Cue::Region(String::from("Marsh")),
+                Cue::Region(String::from("Woods"))
+            ]
+        );
+    }
+
+    #[derive(Default)]
+    struct Rumbles(Vec<Rumble>);
+
+    impl Haptics for Rumbles {
+        fn rumble(&mut self, rumble: Rumble) {
+            self.0.push(rumble);
+        }
+    }
+
+    fn listen_for_rumbles(cli: &Cli) -> Arc<Mutex<Rumbles>> {
+        let rumbles = Arc::new(Mutex::new(Rumbles::default()));
+        cli.set_haptics(rumbles.clone());
+        rumbles
+    }
+
+    #[test]
+    fn quiet_turns() {
+        let cli = test_world();
+        let rumbles = listen_for_rumbles(&cli);
+        cli.ask("l");
+        cli.ask("n");
+        assert!(rumbles.lock().unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn big_hits_and_kills() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("ogre", "It is huge.", EnemyStatus::Angry)
+                .with_hp(100)
+                .with_damage(1000),
+        );
+        cli.set_random_source(Loaded);
+        let rumbles = listen_for_rumbles(&cli);
+
+        cli.ask("l");
+        assert_eq!(rumbles.lock().unwrap().0, vec![Rumble::Heavy]);
+        assert_eq!(Rumble::Heavy.strength(), 1.0);
+    }
+
+    #[test]
+    fn explosions_and_traps() {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Weapon(Weapon::new("bomb", "It ticks.", 1).with_area()),
+        );
+        cli.add_item(
+            "Central Room",
+            Item::Container(
+                Container::new("chest", "It is made of oak.", Opening::Closed).with_trap(
+                    SavingThrow::new(Ability::Dexterity, -100, "the needle", "It stings."),
+                ),
+            ),
+        );
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small.", EnemyStatus::Asleep).with_hp(100),
+        );
+        let rumbles = listen_for_rumbles(&cli);
+
+        cli.ask("take bomb");
+        cli.ask("hit rat with bomb");
+        assert_eq!(rumbles.lock().unwrap().0, vec![Rumble::Heavy]);
+        cli.ask("open chest");
+        assert_eq!(rumbles.lock().unwrap().0[1], Rumble::Medium);
+    }
+
+    #[test]
+    fn thresholds() {
+        assert_eq!(Rumble::for_damage(1, 20), None);
+        assert_eq!(Rumble::for_damage(5, 20), Some(Rumble::Medium));
+        assert_eq!(Rumble::for_damage(10, 20), Some(Rumble::Heavy));
+        assert_eq!(Rumble::for_damage(-10, 20), None);
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        verbs: Vec<String>,
+        failures: u32,
+        deaths: Vec<String>,
+        games: Vec<u32>,
+    }
+
+    impl Metrics for Counter {
+        fn command_parsed(&mut self, verb: &str) {
+            self.verbs.push(verb.to_owned());
+        }
+
+        fn parse_failed(&mut self) {
+            self.failures += 1;
+        }
+
+        fn player_died(&mut self, room: &str) {
+            self.deaths.push(room.to_owned());
+        }
+
+        fn game_over(&mut self, turns: u32) {
+            self.games.push(turns);
+        }
+    }
+
+    #[test]
+    fn counts_commands_and_failures() {
+        let cli = test_world();
+        let counter = Arc::new(Mutex::new(Counter::default()));
+        cli.set_metrics(counter.clone());
+
+        cli.ask("n");
+        cli.ask("frobnicate");
+        cli.ask("the");
+
+        let counter = counter.lock().unwrap();
+        assert_eq!(counter.verbs, vec!["north"]);
+        assert_eq!(counter.failures, 2);
+    }
+
+    #[test]
+    fn one_collector_for_many_games() {
+        let counter = Arc::new(Mutex::new(Counter::default()));
+        for _ in 0..3 {
+            let cli = test_world();
+            cli.set_metrics(counter.clone());
+            cli.ask("n");
+            cli.ask("quit");
+        }
+
+        assert_eq!(counter.lock().unwrap().games, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn reports_room_of_death() {
+        let cli = test_world();
+        let counter = Arc::new(Mutex::new(Counter::default()));
+        cli.set_metrics(counter.clone());
+        cli.ask("n");
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("dragon", "It is huge.", EnemyStatus::Angry).with_damage(1000),
+        );
+
+        for _ in 0..50 {
+            cli.ask("wait");
+        }
+        assert!(cli.is_over());
+        let counter = counter.lock().unwrap();
+        assert_eq!(counter.deaths, vec!["Small Cave"]);
+        assert_eq!(counter.games.len(), 1);
+    }
+
+    #[test]
+    fn silent_by_default() {
+        let cli = test_world();
+        assert!(cli.ask("frobnicate").contains("I do not know the word"));
+    }
+
+    #[test]
+    fn profile_reports_each_phase_of_recent_turns() {
+        let cli = test_world();
+        assert!(!cli.ask("profile").starts_with("Last "));
+
+        let cli = test_world();
+        cli.set_role(Role::Author);
+        assert_eq!(cli.ask("profile"), "No turns have been timed yet.");
+
+        for _ in 0..3 {
+            cli.ask("l");
+        }
+        let res = cli.ask("profile 2");
+        assert!(res.starts_with("Last 2 turns:\n"));
+        for phase in ["parse", "resolve", "events", "combat", "render", "total"] {
+            assert!(res.contains(&format!("\n  {}", phase)), "{}", res);
+        }
+        assert!(res.contains(" average, "));
+        assert!(res.contains("\nSlowest: turn "));
+
+        // the profile commands are turns like any other
+        assert!(cli.ask("profile").starts_with("Last 5 turns:\n"));
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        pauses: RefCell<Vec<Pause>>,
+    }
+
+    impl Timing for Recorder {
+        fn pause(&self, pause: Pause) {
+            self.pauses.borrow_mut().push(pause);
+        }
+    }
+
+    #[test]
+    fn rest_pause() {
+        let cli = test_world();
+        assert_eq!(cli.pause(), None);
+        assert_eq!(cli.ask("rest"), "You already have full health.");
+        assert_eq!(cli.pause(), None);
+
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small.", EnemyStatus::Angry)
+                .with_hp(1)
+                .with_damage(1),
+        );
+        cli.ask("take leaf");
+        (0..50)
+            .map(|_| cli.ask("wait"))
+            .find(|res| res.contains("The rat hit you for"))
+            .unwrap();
+        (0..50)
+            .map(|_| cli.ask("kill rat with leaf"))
+            .find(|res| res.contains("dead"))
+            .unwrap();
+
+        cli.ask("rest");
+        assert_eq!(cli.pause(), Some(Pause::Rest));
+
+        let recorder = Recorder::default();
+        recorder.pause(cli.pause().unwrap());
+        NoDelay.pause(cli.pause().unwrap());
+        assert_eq!(*recorder.pauses.borrow(), vec![Pause::Rest]);
+        assert!(Pause::Rest.duration() > Duration::from_millis(0));
+    }
+
+    #[test]
+    fn death_pause() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("dragon", "It is huge.", EnemyStatus::Angry).with_damage(1000),
+        );
+
+        (0..50)
+            .map(|_| cli.ask("wait"))
+            .find(|res| res.contains("You died."))
+            .unwrap();
+        assert_eq!(cli.pause(), Some(Pause::Dramatic));
+    }
+
+    #[test]
+    fn paginate() {
+        let cli = test_world();
+        let help = cli.ask("help");
+        assert_eq!(cli.pages(&help), vec![help.clone()]);
+
+        cli.set_page_height(10);
+        let pages = cli.pages(&help);
+        assert_eq!(pages.len(), help.lines().count().div_ceil(10));
+        assert!(pages.iter().all(|page| page.lines().count() <= 10));
+        assert_eq!(
+            pages.join("\n"),
+            help.lines().collect::<Vec<&str>>().join("\n")
+        );
+    }
+
+    #[test]
+    fn list_settings() {
+        let cli = test_world();
+        cli.set_page_height(24);
+
+        let res = cli.ask("settings");
+        assert!(res.contains("status line: off"));
+        assert!(res.contains("paging: 24 lines"));
+        assert!(res.contains("undo: on"));
+        assert!(res.contains("hp display: numeric"));
+    }
+
+    #[test]
+    fn change_settings() {
+        let cli = test_world();
+
+        assert!(cli
+            .ask("set status line on")
+            .ends_with("status line set to on."));
+        assert!(cli.ask("l").starts_with("Central Room"));
+        assert!(cli.ask("set hp bar").ends_with("hp set to bar."));
+        assert!(cli.ask("c").contains("HP: ["));
+        assert!(cli.ask("set status off").ends_with("status set to off."));
+        assert_eq!(cli.ask("set paging off"), "paging set to off.");
+        assert_eq!(cli.ask("set undo off"), "undo set to off.");
+        cli.ask("n");
+        assert_eq!(cli.ask("undo"), "There is nothing to undo.");
+        assert!(cli.ask("options").contains("undo: off"));
+    }
+
+    #[test]
+    fn bad_settings() {
+        let cli = test_world();
+
+        assert_eq!(
+            cli.ask("set color off"),
+            "There is no setting called \"color\"."
+        );
+        assert_eq!(
+            cli.ask("set undo maybe"),
+            "\"maybe\" is not a value for undo."
+        );
+        assert!(cli.ask("set").contains("What do you want to set?"));
+    }
+
+    #[test]
+    fn settings_are_saved() {
+        let cli = test_world();
+        cli.ask("set undo off");
+        cli.ask("set hp descriptive");
+
+        let loaded = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        let res = loaded.ask("settings");
+        assert!(res.contains("undo: off"));
+        assert!(res.contains("hp display: descriptive"));
+    }
+
+    #[test]
+    fn status() {
+        let cli = test_world();
+        cli.ask("l");
+        cli.ask("n");
+
+        let status = cli.status();
+        assert_eq!(status.room(), "Small Cave");
+        assert_eq!(status.score(), 0);
+        assert_eq!(status.turns(), 2);
+    }
+
+    #[test]
+    fn status_line() {
+        let cli = test_world();
+        assert!(!cli.ask("l").contains("Turns:"));
+
+        cli.show_status_line(true);
+        let res = cli.ask("l");
+        let header = res.lines().next().unwrap();
+        assert!(header.starts_with("Central Room"));
+        assert!(header.ends_with("Score: 0  Turns: 2"));
+        assert_eq!(header.chars().count(), 80);
+    }
+
+    #[test]
+    fn score_counts_experience_and_quests() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small.", EnemyStatus::Asleep)
+                .with_hp(1)
+                .with_xp(10),
+        );
+
+        cli.ask("take leaf");
+        assert!(cli.ask("kill rat with leaf").contains("You gained 10 XP."));
+        assert_eq!(cli.status().score(), 10);
+        cli.complete_quest("rid the house of rats");
+        cli.complete_quest("rid the house of rats");
+        assert_eq!(cli.status().score(), 35);
+    }
+
+    fn author() -> Cli {
+        let cli = test_world();
+        cli.set_role(Role::Author);
+        cli.record_transcript();
+        cli
+    }
+
+    #[test]
+    fn players_cannot_cheat() {
+        let cli = test_world();
+        assert!(!cli.ask("god").starts_with("God mode"));
+        assert!(!cli.is_cheating("god"));
+    }
+
+    #[test]
+    fn god() {
+        let cli = author();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("ogre", "It is huge.", EnemyStatus::Angry)
+                .with_hp(1000)
+                .with_damage(1000),
+        );
+
+        assert_eq!(
+            cli.ask("god"),
+            "God mode on. Transcripts mark turns played with cheats."
+        );
+        for _ in 0..10 {
+            cli.ask("wait");
+        }
+        assert!(cli.ask("c").contains("HP: (13 / 13)"));
+        assert!(!cli.is_over());
+        assert!(cli.ask("god").starts_with("God mode off."));
+        assert!(!cli.is_cheating("god"));
+    }
+
+    #[test]
+    fn noclip() {
+        let cli = author();
+
+        assert_eq!(cli.ask("enter door"), "The way is shut.");
+        cli.ask("noclip");
+        assert!(cli.is_cheating("noclip"));
+        assert!(cli.ask("enter door").starts_with("Closet"));
+    }
+
+    #[test]
+    fn giveall() {
+        let cli = author();
+
+        assert!(cli
+            .ask("giveall")
+            .starts_with("You now carry a copy of every item"));
+        assert!(cli.ask("i").contains("leaf"));
+        assert!(cli.ask("l").contains("leaf"));
+        assert!(cli.is_cheating("giveall"));
+    }
+
+    #[test]
+    fn kept_in_saves() {
+        let cli = author();
+        cli.ask("giveall");
+        cli.ask("noclip");
+
+        let loaded = cli.load_save(&cli.to_ron()).unwrap();
+        assert!(loaded.is_cheating("giveall"));
+        assert!(loaded.is_cheating("noclip"));
+        assert!(loaded.ask("enter door").starts_with("Closet"));
+    }
+
+    #[test]
+    fn marked_in_transcripts() {
+        let cli = author();
+        cli.ask("l");
+        cli.ask("noclip");
+        cli.ask("l");
+        cli.ask("noclip");
+        cli.ask("l");
+
+        let transcript = cli.transcript().unwrap();
+        let markdown = transcript.to_markdown();
+        assert_eq!(markdown.matches("*[cheats: noclip]*").count(), 2);
+        assert!(markdown.contains("> **l**\n"));
+        assert!(transcript.to_html().contains(
+            "<p class=\"command cheat\">&gt; l <span class=\"cheats\">[cheats: noclip]</span></p>"
+        ));
+    }
+
+    fn cli_with(item: &str) -> Cli {
+        let cli = test_world();
+        cli.add_item("Central Room", Item::Thing(Thing::new(item, "It is old.")));
+        cli
+    }
+
+    #[test]
+    fn names_match_without_case() {
+        let cli = cli_with("Silver Crown");
+        assert_eq!(cli.ask("TAKE silver CROWN"), "Taken.");
+        assert_eq!(cli.ask("x Crown"), "It is old.");
+
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("Grey Wolf", "It snarls.", EnemyStatus::Asleep),
+        );
+        assert!(cli.ask("examine GREY wolf").contains("It snarls."));
+    }
+
+    #[test]
+    fn accents_match_however_they_were_typed() {
+        // the name is written with a composed é and typed with e and a combining accent
+        let cli = cli_with("caf\u{e9} sign");
+        assert_eq!(cli.ask("take cafe\u{301} sign"), "Taken.");
+
+        let cli = cli_with("cafe\u{301} sign");
+        assert_eq!(cli.ask("take CAF\u{c9} sign"), "Taken.");
+    }
+
+    #[test]
+    fn accented_verbs_get_an_answer() {
+        let cli = test_world();
+        // typed with a composed é and with e and a combining accent
+        for verb in ["\u{e9}teindre", "e\u{301}teindre"] {
+            assert_eq!(cli.ask(verb), "I do not know the word \"\u{e9}teindre\".");
+        }
+        assert_eq!(
+            cli.ask("a\u{e9}\u{e9}\u{e9}\u{e9}"),
+            "I do not know the word \"a\u{e9}\u{e9}\u{e9}\u{e9}\"."
+        );
+    }
+
+    #[test]
+    fn phone_punctuation_is_straightened() {
+        let cli = cli_with("jester's hat");
+        assert_eq!(cli.ask("take jester\u{2019}s hat"), "Taken.");
+
+        let cli = cli_with("well-worn map");
+        assert_eq!(cli.ask("take well\u{2014}worn map"), "Taken.");
+    }
+
+    const VERBS: &[&str] = &[
+        "",
+        "answer",
+        "assign",
+        "attack",
+        "bake",
+        "break",
+        "brew",
+        "c",
+        "camp",
+        "cast",
+        "clear",
+        "climb",
+        "close",
+        "codex",
+        "command",
+        "compare",
+        "continue",
+        "cook",
+        "craft",
+        "d",
+        "dial",
+        "dismiss",
+        "doff",
+        "don",
+        "douse",
+        "drag",
+        "draw",
+        "drop",
+        "e",
+        "enter",
+        "equip",
+        "examine",
+        "feed",
+        "fight",
+        "fish",
+        "gather",
+        "get",
+        "go",
+        "grapple",
+        "hail",
+        "heal",
+        "help",
+        "hit",
+        "i",
+        "increase",
+        "insert",
+        "inspect",
+        "kill",
+        "knock",
+        "l",
+        "learn",
+        "leave",
+        "light",
+        "listen",
+        "lock",
+        "look",
+        "lower",
+        "make",
+        "n",
+        "name",
+        "ne",
+        "nw",
+        "open",
+        "peek",
+        "play",
+        "pray",
+        "pull",
+        "push",
+        "put",
+        "r",
+        "read",
+        "recipe",
+        "remove",
+        "reset",
+        "rest",
+        "s",
+        "search",
+        "se",
+        "set",
+        "settings",
+        "shout",
+        "sit",
+        "sleep",
+        "smash",
+        "songs",
+        "stand",
+        "stats",
+        "summon",
+        "sw",
+        "take",
+        "talk",
+        "tame",
+        "tie",
+        "turn",
+        "u",
+        "unassign",
+        "undo",
+        "unlock",
+        "untie",
+        "use",
+        "w",
+        "wait",
+        "wear",
+        "x",
+        "yes",
+        "no",
+        "bye",
+        "1",
+        "2",
+        "0",
+        "aéééé",
+        "ñandú",
+        "日本語です",
+        "éteindre",
+        "ouvrir✨",
+    ];
+
+    const WORDS: &[&str] = &[
+        "",
+        "all",
+        "the",
+        "to",
+        "with",
+        "from",
+        "in",
+        "on",
+        "until",
+        "iron",
+        "sword",
+        "leaf",
+        "leather",
+        "armor",
+        "red",
+        "block",
+        "large",
+        "capsule",
+        "door",
+        "small",
+        "wooden",
+        "north",
+        "south",
+        "east",
+        "up",
+        "down",
+        "me",
+        "myself",
+        "goblin",
+        "goblin's",
+        "'s",
+        "head",
+        "1",
+        "0",
+        "-1",
+        "99999999999",
+        "4294967296",
+        "7-2-9",
+        ",",
+        "-",
+        "*",
+        "?",
+        "ñ",
+        "日本",
+        "threshold",
+        "fight",
+        "status",
+        "paging",
+        "undo",
+        "hp",
+        "inventory",
+        "sort",
+        "best",
+        "turn",
+        "enemy",
+        "something",
+    ];
+
+    fn pick<'a>(rng: &mut SplitMix64, words: &[&'a str]) -> &'a str {
+        words[(rng.next_u64() % words.len() as u64) as usize]
+    }
+
+    fn command(rng: &mut SplitMix64) -> String {
+        let mut words = vec![pick(rng, VERBS)];
+        for _ in 0..rng.next_u64() % 5 {
+            words.push(pick(rng, WORDS));
+        }
+        words.join(" ")
+    }
+
+    fn play(cli: &Cli, seed: u64, turns: usize) {
+        cli.seed(seed);
+        let mut rng = SplitMix64::new(seed);
+        let mut history = Vec::new();
+        for _ in 0..turns {
+            let input = command(&mut rng);
+            history.push(input.clone());
+            let played = panic::catch_unwind(AssertUnwindSafe(|| cli.ask(&input)));
+            assert!(played.is_ok(), "panicked after {:?}", history);
+            if cli.is_over() {
+                return;
+            }
+        }
+    }
+
+    #[test]
+    fn random_commands_never_panic() {
+        for seed in 0..8 {
+            play(&test_world(), seed, 500);
+            play(&Cli::from_file("worlds/world.ron").unwrap(), seed, 500);
+            play(&Cli::generate(30, seed), seed, 500);
+        }
+    }
+
+    #[test]
+    fn broken_worlds_never_panic() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"Doors lead nowhere.",paths:[
+                    (directions:["north"],target:"Nowhere",opening:None,lock:None),
+                    (directions:["south"],target:"Hall",opening:None,lock:None,turns:0)
+                ])
+            }))"#,
+        )
+        .unwrap();
+        for seed in 0..8 {
+            play(&cli, seed, 500);
+        }
+        assert!(Cli::from_ron_str("(world:(curr_room:\"Nowhere\",rooms:{}))").is_err());
+        assert!(Cli::from_ron_str("not a world").is_err());
+        assert!(Cli::from_file("worlds/missing.ron").is_err());
+    }
+
+    #[test]
+    fn not_recorded_by_default() {
+        let cli = test_world();
+        cli.ask("l");
+        assert!(cli.transcript().is_none());
+
+        cli.record_transcript();
+        assert!(cli.transcript().unwrap().is_empty());
+        cli.ask("l");
+        assert_eq!(cli.transcript().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn markdown() {
+        let cli = test_world();
+        cli.record_transcript();
+        cli.ask("take leaf");
+        cli.ask("i");
+        cli.ask("n");
+        cli.ask("l");
+
+        let markdown = cli.transcript().unwrap().to_markdown();
+        assert!(markdown.starts_with("## Central Room\n\n*Exits: "));
+        assert!(markdown.contains("north to Small Cave"));
+        assert!(markdown.contains("> **take leaf**\n\n```text\nTaken.\n```\n\n> **i**"));
+        assert_eq!(markdown.matches("## Central Room").count(), 1);
+        assert!(markdown.contains("> **n**\n\n```text\nSmall Cave"));
+        assert!(markdown.contains("## Small Cave\n\n"));
+    }
+
+    #[test]
+    fn html() {
+        let cli = test_world();
+        cli.record_transcript();
+        cli.ask("take <leaf>");
+
+        let html = cli.transcript().unwrap().to_html();
+        assert!(html.starts_with(
+            "<article class=\"transcript\">\n<h2 class=\"room\">Central Room</h2>\n<p class=\"exits\">Exits: "
+        ));
+        assert!(html
+            .contains("<p class=\"command\">&gt; take &lt;leaf&gt;</p>\n<pre class=\"output\">"));
+        assert!(html.ends_with("</pre>\n</article>\n"));
+    }
+
+    #[test]
+    fn test_world_matches_its_golden_transcript() {
+        Golden::new(
+            "worlds/test_world.ron",
+            "tests/golden/test_world.script",
+            "tests/golden/test_world.txt",
+        )
+        .with_seed(7)
+        .assert();
+    }
+
+    #[test]
+    fn record() {
+        let cli = test_world();
+        let transcript = kingslayer::testing::record(&cli, "# comment\n\ntake leaf\n");
+        assert_eq!(transcript, "> take leaf\nTaken.\n\n");
+    }
+
+    #[test]
+    fn bless_and_compare() {
+        let golden = env::temp_dir().join("kingslayer_golden.txt");
+        let golden = golden.to_str().unwrap();
+        let test = Golden::new(
+            "worlds/test_world.ron",
+            "tests/golden/test_world.script",
+            golden,
+        );
+
+        test.clone().with_bless(true).run().unwrap();
+        assert!(test.clone().with_bless(false).run().is_ok());
+
+        let changed = fs::read_to_string(golden)
+            .unwrap()
+            .replacen("Taken.", "Took it.", 1);
+        fs::write(golden, changed).unwrap();
+        let err = test.with_bless(false).run().unwrap_err();
+        fs::remove_file(golden).unwrap();
+        assert!(err.contains("expected: Took it."));
+        assert!(err.contains("actual:   Taken."));
+    }
+
+    #[test]
+    fn articles() {
+        let cli = test_world();
+        cli.add_item(
+            "Small Cave",
+            Item::Weapon(Weapon::new("axe", "It is sharp.", 6)),
+        );
+        cli.add_item(
+            "Small Cave",
+            Item::Thing(Thing::new("unicorn horn", "It spirals.")),
+        );
+        cli.add_item(
+            "Small Cave",
+            Item::Thing(Thing::new("hourglass", "It is slow.")),
+        );
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("owl", "It hoots.", EnemyStatus::Asleep),
+        );
+
+        let res = cli.ask("n");
+        assert!(res.contains("an axe"));
+        assert!(res.contains("a unicorn horn"));
+        assert!(res.contains("an hourglass"));
+        assert!(res.contains("There is an owl here."));
+    }
+
+    #[test]
+    fn plurals() {
+        let cli = test_world();
+        for _ in 0..2 {
+            cli.add_item(
+                "Small Cave",
+                Item::Thing(Thing::new("knife", "It is dull.")),
+            );
+            cli.add_item("Small Cave", Item::Thing(Thing::new("ruby", "It is red.")));
+            cli.add_item("Small Cave", Item::Thing(Thing::new("key", "It is iron.")));
+            cli.spawn_enemy(
+                "Small Cave",
+                Enemy::new("wolf", "It growls.", EnemyStatus::Asleep),
+            );
+            cli.spawn_enemy(
+                "Small Cave",
+                Enemy::new("swarm of bats", "They flap.", EnemyStatus::Asleep),
+            );
+        }
+
+        let res = cli.ask("n");
+        assert!(res.contains("2 knives, 2 rubies, and 2 keys"));
+        assert!(res.contains("There are 2 wolves here."));
+        assert!(res.contains("There are 2 swarms of bats here."));
+    }
+
+    #[test]
+    fn lists_and_capitals() {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Weapon(
+                Weapon::new("staff", "It is oak.", 4).with_requirements(
+                    Requirements::default()
+                        .with_class(Class::Mage)
+                        .with_class(Class::Assassin),
+                ),
+            ),
+        );
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("imp", "It cackles.", EnemyStatus::Asleep),
+        );
+
+        cli.ask("take staff");
+        assert_eq!(
+            cli.ask("equip staff"),
+            "Only a Mage or an Assassin can use the staff."
+        );
+        cli.set_role(kingslayer::Role::Author);
+        assert_eq!(cli.ask("spawn imp"), "An imp appears.");
+    }
+}
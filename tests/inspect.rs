@@ -1,10 +1,12 @@
+#![cfg(feature = "serde")]
+
 #[cfg(test)]
 mod tests {
     use kingslayer::Cli;
 
     #[test]
     fn player_inspect() {
-        let cli = Cli::from_file("worlds/test_world.ron");
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
 
         assert_eq!(cli.ask("inspect leaf"), "It's small, brown, and dry.");
         cli.ask("take leaf");
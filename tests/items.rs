@@ -0,0 +1,1076 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use kingslayer::{
+        Ability, Ally, Behavior, Cli, Container, Daemon, DamageType, Element, Harvest,
+        InventoryFormat, Item, Key, Light, Opening, Recipe, Revival, Summon, Thing, ToolUse, Tune,
+        Weapon,
+    };
+
+    use crate::common::{test_world, Cursed, Loaded};
+
+    fn cli_with_weapons() -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Small Cave",
+            Item::Weapon(Weapon::new("dagger", "A short, chipped blade.", 4)),
+        );
+        cli.add_item(
+            "Small Cave",
+            Item::Weapon(Weapon::new("battle axe", "A heavy two-handed axe.", 10)),
+        );
+        cli.ask("n");
+        cli
+    }
+
+    #[test]
+    fn equip_best() {
+        let cli = cli_with_weapons();
+
+        assert_eq!(cli.ask("equip best"), "You have no weapon to equip.");
+        cli.ask("take all");
+        assert_eq!(cli.ask("equip best"), "You equip the battle axe.");
+        assert!(cli.ask("i").contains("Main hand: battle axe"));
+        assert_eq!(
+            cli.ask("equip best"),
+            "The battle axe is already your best weapon."
+        );
+
+        cli.ask("equip dagger");
+        assert_eq!(cli.ask("equip best"), "You equip the battle axe.");
+        assert!(cli.ask("i").contains("dagger"));
+    }
+
+    #[test]
+    fn auto_equip_on_pickup() {
+        let cli = cli_with_weapons();
+
+        assert_eq!(cli.ask("set auto equip on"), "auto equip set to on.");
+        assert!(cli.ask("settings").contains("auto equip: on"));
+        assert_eq!(cli.ask("take dagger"), "Taken. You equip the dagger.");
+        assert_eq!(cli.ask("take axe"), "Taken. You equip the battle axe.");
+        assert_eq!(cli.ask("take sword"), "Taken.");
+        assert!(cli.ask("i").contains("Main hand: battle axe"));
+    }
+
+    #[test]
+    fn auto_equip_off_by_default() {
+        let cli = cli_with_weapons();
+
+        assert_eq!(cli.ask("take axe"), "Taken.");
+        assert!(!cli.ask("i").contains("Main hand"));
+    }
+
+    fn cli_with_axe() -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Small Cave",
+            Item::Weapon(
+                Weapon::new("battle axe", "A heavy two-handed axe.", 10)
+                    .with_damage_type(DamageType::Slashing)
+                    .with_area(),
+            ),
+        );
+        cli.ask("n");
+        cli
+    }
+
+    #[test]
+    fn side_by_side() {
+        let cli = cli_with_axe();
+        cli.ask("take sword");
+
+        let res = cli.ask("compare sword with axe");
+        assert!(res.contains("iron sword") && res.contains("battle axe"));
+        assert!(res.contains("Damage") && res.contains("1d6") && res.contains("1d10"));
+        assert!(res.contains("slashing, area"));
+        assert_eq!(cli.ask("compare sword and axe"), res);
+    }
+
+    #[test]
+    fn weapon_with_armor() {
+        let cli = cli_with_axe();
+
+        let res = cli.ask("compare axe with armor");
+        assert!(res.contains("Armor class") && res.contains("11"));
+        assert!(res.contains("weapons") && res.contains("armor"));
+    }
+
+    #[test]
+    fn unseen_item() {
+        let cli = cli_with_axe();
+
+        assert!(cli.ask("compare sword with dagger").contains("dagger"));
+        cli.ask("s");
+        assert!(cli.ask("compare sword with axe").contains("sword"));
+    }
+
+    fn cli_with_lamp(radius: u32) -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Light(Light::new("lamp", "It is full of oil.", radius)),
+        );
+        cli.ask("take lamp");
+        cli
+    }
+
+    #[test]
+    fn dark_without_a_lit_lamp() {
+        let cli = cli_with_lamp(1);
+
+        assert_eq!(
+            cli.ask("look north"),
+            "It is too dark to make out what lies to the north."
+        );
+        assert_eq!(cli.ask("douse lamp"), "The lamp is not lit.");
+    }
+
+    #[test]
+    fn lamp_shows_the_next_room() {
+        let cli = cli_with_lamp(1);
+
+        assert_eq!(cli.ask("light lamp"), "You light the lamp.");
+        assert_eq!(cli.ask("light lamp"), "The lamp is already lit.");
+        assert_eq!(
+            cli.ask("look north"),
+            "To the north you can make out the Small Cave."
+        );
+        assert_eq!(cli.ask("look small wooden door"), "The way is shut.");
+        assert!(cli.ask("l").contains("You are in the central room."));
+
+        cli.ask("douse lamp");
+        assert!(cli.ask("look north").starts_with("It is too dark"));
+    }
+
+    #[test]
+    fn radius_reaches_further_rooms() {
+        let cli = cli_with_lamp(3);
+        cli.ask("light lamp");
+        cli.ask("n");
+
+        assert_eq!(
+            cli.ask("look south"),
+            "To the south you can make out the Central Room. Beyond it lies the Bedroom."
+        );
+        assert!(cli.ask("look lamp").contains("It is full of oil."));
+    }
+
+    fn horn() -> Item {
+        Item::Summon(Summon::new(
+            "bone horn",
+            "It is carved with wolves.",
+            Ally::new("spirit wolf", "It glows faintly."),
+            4,
+        ))
+    }
+
+    #[test]
+    fn slots_use_items_by_number() {
+        let cli = test_world();
+        cli.receive_item(horn());
+
+        assert_eq!(
+            cli.ask("assign horn to 1"),
+            "The bone horn is now in quick slot 1."
+        );
+        assert!(cli.ask("i").ends_with("Quick slots:\n  1. bone horn"));
+        assert_eq!(
+            cli.ask("use 1"),
+            "You use the bone horn. A spirit wolf appears at your side."
+        );
+        assert!(cli.ask("use 1").contains("You do not have"));
+        assert_eq!(cli.ask("use 2"), "Quick slot 2 is empty.");
+    }
+
+    #[test]
+    fn slots_need_items_carried() {
+        let cli = test_world();
+        cli.receive_item(Item::Thing(Thing::new("pebble", "It is smooth.")));
+
+        assert!(cli.ask("assign horn to 1").contains("You do not have"));
+        assert_eq!(
+            cli.ask("assign pebble to 10"),
+            "Quick slots are numbered 1 to 9."
+        );
+        cli.ask("assign pebble to 3");
+        assert_eq!(
+            cli.ask("unassign 3"),
+            "The pebble is no longer in quick slot 3."
+        );
+        assert!(!cli.ask("i").contains("Quick slots"));
+    }
+
+    #[test]
+    fn slots_are_kept_in_saves() {
+        let cli = test_world();
+        cli.receive_item(horn());
+        cli.ask("assign horn to 2");
+        let save = cli.to_ron();
+
+        let loaded = Cli::from_file("worlds/test_world.ron")
+            .unwrap()
+            .load_save(&save)
+            .unwrap();
+        assert!(loaded.ask("i").contains("Quick slots:\n  2. bone horn"));
+    }
+
+    fn thing(name: &str) -> Item {
+        Item::Thing(Thing::new(name, "It is ordinary."))
+    }
+
+    #[test]
+    fn group_plain_items() {
+        let cli = test_world();
+        cli.add_item("Central Room", thing("lamp"));
+        cli.add_item("Central Room", thing("coin"));
+        cli.add_item("Central Room", thing("apple"));
+        cli.add_item("Central Room", thing("coin"));
+
+        let res = cli.ask("l");
+        assert!(res.contains("A leaf lies on the ground."));
+        assert!(res.contains("There is a lamp, 2 coins, and an apple here."));
+    }
+
+    #[test]
+    fn plural_first() {
+        let cli = test_world();
+        cli.add_item("Central Room", thing("box"));
+        cli.add_item("Central Room", thing("box"));
+        cli.add_item("Central Room", thing("lamp"));
+
+        assert!(cli.ask("l").contains("There are 2 boxes and a lamp here."));
+    }
+
+    #[test]
+    fn floor_capacity() {
+        let cli = test_world();
+        cli.add_item("Small Cave", thing("lamp"));
+        cli.set_floor_capacity("Small Cave", 3);
+
+        cli.ask("take leaf");
+        cli.ask("n");
+        assert_eq!(
+            cli.ask("drop leaf"),
+            "There is no room to drop the leaf here."
+        );
+        assert!(cli.ask("i").contains("leaf"));
+        cli.ask("take lamp");
+        assert_eq!(cli.ask("drop leaf"), "Dropped.");
+        assert!(!cli.ask("i").contains("leaf"));
+    }
+
+    fn cli_with_gear() -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Small Cave",
+            Item::Weapon(Weapon::new("dagger", "It is sharp.", 4)),
+        );
+        cli.add_item(
+            "Small Cave",
+            Item::Revival(Revival::new("phoenix feather", "It is warm.", 50)),
+        );
+        cli.ask("take leaf");
+        cli.ask("n");
+        cli.ask("take all");
+        cli
+    }
+
+    #[test]
+    fn categorized() {
+        let cli = cli_with_gear();
+        cli.ask("equip iron sword");
+        cli.ask("don leather armor");
+        cli.set_inventory_format(InventoryFormat::default().with_categories(true));
+
+        assert_eq!(
+            cli.ask("i"),
+            "Gold: 0\
+            \nWeapons:\
+            \n  iron sword (equipped)\
+            \n  dagger\
+            \nArmor:\
+            \n  leather armor (worn)\
+            \nConsumables:\
+            \n  phoenix feather\
+            \nMisc:\
+            \n  leaf"
+        );
+    }
+
+    #[test]
+    fn alphabetical() {
+        let cli = cli_with_gear();
+        cli.set_inventory_format(InventoryFormat::default().with_alphabetical(true));
+
+        assert!(cli.ask("i").ends_with(
+            "You are carrying:\
+            \n  dagger\
+            \n  iron sword\
+            \n  leaf\
+            \n  leather armor\
+            \n  phoenix feather"
+        ));
+    }
+
+    #[test]
+    fn from_settings() {
+        let cli = cli_with_gear();
+
+        assert_eq!(
+            cli.ask("set inventory categorized"),
+            "inventory set to categorized."
+        );
+        assert_eq!(
+            cli.ask("set sort alphabetical"),
+            "sort set to alphabetical."
+        );
+        let res = cli.ask("settings");
+        assert!(res.contains("inventory: categorized"));
+        assert!(res.contains("sort: alphabetical"));
+        assert!(cli
+            .ask("i")
+            .contains("Weapons:\n  dagger\n  iron sword\nArmor:"));
+    }
+
+    fn cli_with_crown() -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Thing(Thing::new("iron crown", "It is dented.").with_quest("return the crown")),
+        );
+        cli.ask("take crown");
+        cli
+    }
+
+    #[test]
+    fn cannot_drop_or_throw() {
+        let cli = cli_with_crown();
+
+        assert_eq!(cli.ask("drop crown"), "You feel you will need this.");
+        assert_eq!(cli.ask("throw crown"), "You feel you will need this.");
+        assert!(cli.ask("i").contains("iron crown"));
+    }
+
+    #[test]
+    fn cannot_put_in_room_container() {
+        let cli = cli_with_crown();
+        cli.ask("s");
+
+        assert_eq!(
+            cli.ask("put crown in capsule"),
+            "You feel you will need this."
+        );
+        assert!(cli.ask("i").contains("iron crown"));
+    }
+
+    #[test]
+    fn free_after_quest() {
+        let cli = cli_with_crown();
+        cli.complete_quest("return the crown");
+
+        assert_eq!(cli.ask("drop crown"), "Dropped.");
+        assert!(!cli.ask("i").contains("iron crown"));
+    }
+
+    #[test]
+    fn quest_category() {
+        let cli = cli_with_crown();
+        cli.add_item(
+            "Central Room",
+            Item::Key(Key::new("bone key", "It is carved.", "bone").with_quest("open the crypt")),
+        );
+        cli.ask("take key");
+        cli.ask("set inventory categorized");
+
+        assert!(cli
+            .ask("i")
+            .contains("Quest items:\n  iron crown\n  bone key"));
+        assert_eq!(cli.ask("drop key"), "You feel you will need this.");
+        assert!(cli.ask("drop leaf").contains("You do not have"));
+    }
+
+    #[test]
+    fn cannot_give_up_containers_holding_them() {
+        let cli = cli_with_crown();
+        cli.add_item(
+            "Central Room",
+            Item::Container(Container::new("sack", "It is patched.", Opening::Open)),
+        );
+        cli.ask("take sack");
+        cli.ask("put crown in sack");
+
+        assert_eq!(cli.ask("drop sack"), "You feel you will need this.");
+        assert!(cli.ask("i").contains("sack"));
+        cli.complete_quest("return the crown");
+        assert_eq!(cli.ask("drop sack"), "Dropped.");
+    }
+
+    fn cli_in_the_cellar() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Cellar",rooms:{
+                "Cellar":(name:"Cellar",desc:"Cobwebs hang from the beams.",paths:[],elements:[
+                    (name:"crate",desc:"A crate is nailed shut.",inspect:"The lid is nailed down.",
+                        uses:[(verbs:["open","pry"],tools:["crowbar"],success:"The lid screeches off.",
+                            yields:[Thing((name:"lantern",desc:"There is a lantern here.",inspect:"It is dented."))],
+                            consumes:true)])
+                ])
+            }))"#,
+        ).unwrap();
+        cli.add_element(
+            "Cellar",
+            Element::new(
+                "rope",
+                "A rope hangs from the beams.",
+                "It holds up a sack.",
+            )
+            .with_use(
+                ToolUse::new(
+                    &["cut", "slice"],
+                    &["knife", "dagger"],
+                    "The rope parts and the sack thumps down.",
+                )
+                .with_yield(Item::Thing(Thing::new("sack", "It is full of flour.")))
+                .with_consumes(),
+            ),
+        );
+        cli.add_element(
+            "Cellar",
+            Element::new(
+                "floor",
+                "The floor is packed dirt.",
+                "It is soft in one corner.",
+            )
+            .with_use(ToolUse::new(
+                &["dig"],
+                &["spoon", "shovel"],
+                "You scrape out a shallow hole.",
+            )),
+        );
+        cli
+    }
+
+    #[test]
+    fn tools_are_named_or_found_in_inventory() {
+        let cli = cli_in_the_cellar();
+
+        assert_eq!(
+            cli.ask("cut rope"),
+            "You need a knife or a dagger to cut the rope."
+        );
+        assert_eq!(
+            cli.ask("cut rope with knife"),
+            "You do not have the \"knife\"."
+        );
+        cli.receive_item(Item::Weapon(Weapon::new("rusty knife", "It is dull.", 2)));
+        cli.receive_item(Item::Thing(Thing::new("spoon", "It is bent.")));
+        assert_eq!(
+            cli.ask("slice rope with spoon"),
+            "You cannot slice the rope with the spoon."
+        );
+        assert_eq!(
+            cli.ask("cut rope using knife"),
+            "The rope parts and the sack thumps down."
+        );
+        assert!(cli.ask("l").contains("sack"));
+        assert!(!cli.ask("l").contains("A rope hangs"));
+
+        assert_eq!(cli.ask("dig floor"), "You scrape out a shallow hole.");
+    }
+
+    #[test]
+    fn prying_open_a_crate_leaves_its_contents() {
+        let cli = cli_in_the_cellar();
+
+        assert_eq!(
+            cli.ask("open crate"),
+            "You need a crowbar to open the crate."
+        );
+        cli.receive_item(Item::Thing(Thing::new("crowbar", "It is heavy.")));
+        assert_eq!(cli.ask("pry crate with crowbar"), "The lid screeches off.");
+        assert!(cli.ask("take lantern").contains("Taken."));
+        assert!(cli
+            .ask("open crate")
+            .contains("There is no \"crate\" here."));
+    }
+
+    fn cli_by_the_pond() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Pond",rooms:{
+                "Pond":(name:"Pond",desc:"Reeds ring the still water.",paths:[],elements:[
+                    (name:"berry bush",desc:"A berry bush grows by the bank.",inspect:"It is heavy with fruit.",
+                        harvest:Some((yields:Thing((name:"berry",desc:"There is a berry here.",inspect:"It is ripe.")),
+                            amount:3,regrowth:5)))
+                ])
+            }))"#,
+        ).unwrap();
+        cli.add_element(
+            "Pond",
+            Element::new(
+                "fishing spot",
+                "Fish dart beneath the surface.",
+                "The water is deep here.",
+            )
+            .with_harvest(
+                Harvest::new(Item::Thing(Thing::new("raw fish", "It is slimy.")))
+                    .with_tool("fishing rod"),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn gathering_stacks_materials() {
+        let cli = cli_by_the_pond();
+
+        assert_eq!(
+            cli.ask("gather bush"),
+            "You gather 3 berries from the berry bush."
+        );
+        assert_eq!(cli.ask("i").matches("berry").count(), 3);
+    }
+
+    #[test]
+    fn nodes_grow_back_after_a_while() {
+        let cli = cli_by_the_pond();
+        cli.ask("forage bush");
+
+        assert_eq!(
+            cli.ask("forage bush"),
+            "The berry bush has nothing more to give. It will be ready again in 4 turns."
+        );
+        cli.ask("wait");
+        cli.ask("wait");
+        cli.ask("wait");
+        cli.ask("wait");
+        assert_eq!(
+            cli.ask("forage bush"),
+            "You gather 3 berries from the berry bush."
+        );
+    }
+
+    #[test]
+    fn some_nodes_need_a_tool() {
+        let cli = cli_by_the_pond();
+
+        assert_eq!(cli.ask("fish spot"), "You need a fishing rod for that.");
+        cli.receive_item(Item::Thing(Thing::new("fishing rod", "It is bamboo.")));
+        assert_eq!(
+            cli.ask("fish spot"),
+            "You gather a raw fish from the fishing spot."
+        );
+        assert_eq!(
+            cli.ask("fish spot using fishing rod"),
+            "You gather a raw fish from the fishing spot."
+        );
+        assert_eq!(cli.ask("gather rock"), "There is no \"rock\" here.");
+    }
+
+    fn cli_in_hut() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Hut",rooms:{
+                "Hut":(name:"Hut",desc:"Herbs hang from the rafters.",paths:[],elements:[
+                    (name:"iron cauldron",desc:"An iron cauldron bubbles over the coals.",inspect:"It is black with soot.",
+                        station:Some("cauldron"))
+                ],items:[
+                    Thing((name:"herb",desc:"There is an herb here.",inspect:"It smells bitter.")),
+                    Thing((name:"herb",desc:"There is an herb here.",inspect:"It smells bitter.")),
+                    Thing((name:"water",desc:"There is some water here.",inspect:"It is clear."))
+                ])
+            },recipes:{
+                "healing potion":(station:"cauldron",ingredients:["herb","herb","water"],
+                    makes:Thing((name:"healing potion",desc:"There is a healing potion here.",inspect:"It glows red.")),
+                    fine:Some(Thing((name:"greater healing potion",desc:"There is a greater healing potion here.",inspect:"It glows bright red."))),
+                    ability:Intellect,dc:5)
+            }))"#,
+        ).unwrap()
+    }
+
+    #[test]
+    fn recipes_need_their_station_and_ingredients() {
+        let cli = cli_in_hut();
+        cli.add_recipe(
+            "stew",
+            Recipe::new(
+                "campfire",
+                Item::Thing(Thing::new("stew", "It is hearty.")),
+                Ability::Wisdom,
+                5,
+            )
+            .with_ingredient("herb"),
+        );
+
+        assert_eq!(
+            cli.ask("recipes"),
+            "You can make:\n  healing potion (herb, herb, and water, difficulty 5)"
+        );
+        assert_eq!(cli.ask("cook stew"), "You need a campfire to make a stew.");
+        assert_eq!(
+            cli.ask("brew potion"),
+            "You need 2 herbs and a water to make a healing potion."
+        );
+        assert_eq!(
+            cli.ask("brew elixir"),
+            "You do not know how to make an elixir."
+        );
+    }
+
+    #[test]
+    fn great_checks_make_fine_results() {
+        let cli = cli_in_hut();
+        cli.set_random_source(Loaded);
+        cli.ask("take all");
+
+        assert_eq!(
+            cli.ask("brew healing potion"),
+            "You make a greater healing potion."
+        );
+        let inventory = cli.ask("i");
+        assert!(inventory.contains("greater healing potion"));
+        assert!(!inventory.contains("herb"));
+    }
+
+    #[test]
+    fn failed_checks_waste_the_ingredients() {
+        let cli = cli_in_hut();
+        cli.set_random_source(Cursed);
+        cli.ask("take all");
+
+        assert_eq!(
+            cli.ask("brew potion"),
+            "You botch the healing potion, and the ingredients are wasted."
+        );
+        assert!(!cli.ask("i").contains("water"));
+    }
+
+    #[test]
+    fn quest_items_are_not_ingredients() {
+        let cli = cli_in_hut();
+        cli.add_item(
+            "Hut",
+            Item::Thing(Thing::new("holy water", "It shimmers.").with_quest("bless the font")),
+        );
+        cli.add_recipe(
+            "tea",
+            Recipe::new(
+                "cauldron",
+                Item::Thing(Thing::new("tea", "It steams.")),
+                Ability::Wisdom,
+                5,
+            )
+            .with_ingredient("herb")
+            .with_ingredient("holy water"),
+        );
+        cli.ask("take all");
+
+        assert_eq!(cli.ask("brew tea"), "You feel you will need this.");
+        let inventory = cli.ask("i");
+        assert!(inventory.contains("herb") && inventory.contains("holy water"));
+    }
+
+    fn cli_with_pit() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Ledge",rooms:{
+                "Ledge":(name:"Ledge",desc:"A pit yawns below.",paths:[
+                    (directions:["down","pit"],target:"Pit",opening:None,lock:None,climb:true)
+                ],elements:[
+                    (name:"iron post",desc:"An iron post is set in the rock.",inspect:"It is sturdy.",anchor:true),
+                    (name:"moss",desc:"Moss clings to the rock.",inspect:"It is damp.")
+                ],items:[
+                    Rope((name:"rope",desc:"A coil of rope lies here.",inspect:"It is long and strong.")),
+                    Thing((name:"lantern",desc:"There is a lantern here.",inspect:"It is brass."))
+                ]),
+                "Pit":(name:"Pit",desc:"It is dark down here.",paths:[
+                    (directions:["up"],target:"Ledge",opening:None,lock:None,climb:true)
+                ])
+            }))"#,
+        ).unwrap()
+    }
+
+    #[test]
+    fn climbing_needs_a_tied_rope() {
+        let cli = cli_with_pit();
+
+        assert_eq!(
+            cli.ask("d"),
+            "It is too sheer to climb down without a rope."
+        );
+        cli.ask("take rope");
+        assert_eq!(
+            cli.ask("tie rope to moss"),
+            "There is nothing here to tie the rope to."
+        );
+        assert_eq!(
+            cli.ask("tie rope to post"),
+            "You tie the rope to the iron post."
+        );
+        assert!(cli.ask("l").contains("A rope is tied to the iron post."));
+        assert!(cli.ask("climb down").contains("Pit"));
+        assert!(cli.ask("u").contains("Ledge"));
+    }
+
+    #[test]
+    fn untying_takes_the_rope_back() {
+        let cli = cli_with_pit();
+        cli.ask("take rope");
+        cli.ask("tie rope to post");
+
+        assert_eq!(cli.ask("untie rope"), "You untie the rope.");
+        assert!(cli.ask("i").contains("rope"));
+        assert_eq!(
+            cli.ask("d"),
+            "It is too sheer to climb down without a rope."
+        );
+    }
+
+    #[test]
+    fn lowering_and_hauling() {
+        let cli = cli_with_pit();
+        cli.ask("take rope");
+        cli.ask("take lantern");
+
+        assert_eq!(
+            cli.ask("lower lantern down"),
+            "You tie the rope to the lantern and lower it down."
+        );
+        assert!(cli
+            .ask("l")
+            .contains("A rope hangs down, tied to the lantern."));
+        assert!(!cli.ask("i").contains("lantern"));
+
+        assert_eq!(
+            cli.ask("haul rope"),
+            "You haul up the rope, and the lantern with it."
+        );
+        let inventory = cli.ask("i");
+        assert!(inventory.contains("rope"));
+        assert!(inventory.contains("lantern"));
+    }
+
+    #[test]
+    fn lowering_needs_a_rope() {
+        let cli = cli_with_pit();
+        cli.ask("take lantern");
+
+        assert_eq!(
+            cli.ask("lower lantern down"),
+            "You have no rope to lower it on."
+        );
+    }
+
+    #[test]
+    fn ties_are_saved() {
+        let cli = cli_with_pit();
+        cli.ask("take rope");
+        cli.ask("tie rope to post");
+
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(cli.ask("d").contains("Pit"));
+    }
+
+    fn cli_with(item: Thing) -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"It echoes.",paths:[
+                    (directions:["north"],target:"Gallery",opening:None,lock:None)
+                ]),
+                "Gallery":(name:"Gallery",desc:"Portraits line the walls.",paths:[
+                    (directions:["south"],target:"Hall",opening:None,lock:None),
+                    (directions:["up"],target:"Tower",opening:None,lock:None)
+                ]),
+                "Tower":(name:"Tower",desc:"Wind howls through it.",paths:[
+                    (directions:["down"],target:"Gallery",opening:None,lock:None)
+                ])
+            }))"#,
+        )
+        .unwrap();
+        cli.add_item("Hall", Item::Thing(item));
+        cli
+    }
+
+    #[test]
+    fn cursed_rings_whisper_every_few_turns() {
+        let cli = cli_with(Thing::new("ring", "It is cold to the touch.").with_daemon(
+            Daemon::whisper(2, &["The ring whispers your name.", "The ring hisses."]),
+        ));
+
+        assert_eq!(cli.ask("take ring"), "Taken.");
+        assert!(cli.ask("l").ends_with("\nThe ring whispers your name."));
+        assert_eq!(cli.ask("z"), "Time passes...");
+        assert_eq!(cli.ask("z"), "Time passes...\nThe ring hisses.");
+    }
+
+    #[test]
+    fn bombs_count_down_and_go_off() {
+        let cli = cli_with(
+            Thing::new("bomb", "Its fuse fizzes.").with_daemon(Daemon::countdown(
+                3,
+                "The bomb explodes!",
+                5,
+            )),
+        );
+
+        assert_eq!(
+            cli.ask("take bomb"),
+            "Taken.\nThe bomb ticks. 2 turns left."
+        );
+        assert_eq!(cli.ask("z"), "Time passes...\nThe bomb ticks. 1 turn left.");
+        assert!(cli
+            .ask("z")
+            .starts_with("Time passes...\nThe bomb explodes!\nYou take 5 damage."));
+        assert!(!cli.ask("i").contains("bomb"));
+    }
+
+    #[test]
+    fn compasses_point_the_way_after_each_move() {
+        let cli = cli_with(
+            Thing::new("compass", "Its needle trembles.").with_daemon(Daemon::compass("Tower")),
+        );
+
+        assert_eq!(cli.ask("take compass"), "Taken.");
+        assert!(cli.ask("n").ends_with("The compass points up."));
+        assert!(cli.ask("u").ends_with("The compass spins in place."));
+    }
+
+    #[test]
+    fn daemons_are_read_from_worlds() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"It echoes.",paths:[],items:[
+                    Thing((name:"bomb",desc:"There is a bomb here.",inspect:"Its fuse fizzes.",
+                        daemon:Some(Countdown(turns:5,text:"The bomb explodes!",damage:10))))
+                ])
+            }))"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cli.ask("take bomb"),
+            "Taken.\nThe bomb ticks. 4 turns left."
+        );
+    }
+
+    #[test]
+    fn daemons_are_saved_partway() {
+        let cli = cli_with(
+            Thing::new("bomb", "Its fuse fizzes.").with_daemon(Daemon::countdown(
+                3,
+                "The bomb explodes!",
+                0,
+            )),
+        );
+        cli.ask("take bomb");
+
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert_eq!(cli.ask("z"), "Time passes...\nThe bomb ticks. 1 turn left.");
+    }
+
+    struct Counter(u32);
+
+    impl Behavior for Counter {
+        fn turn(&mut self, item: &str, room: &str) -> Option<String> {
+            self.0 += 1;
+            Some(format!("The {} hums in the {} ({}).", item, room, self.0))
+        }
+    }
+
+    #[test]
+    fn hosts_give_items_behaviors() {
+        let cli = cli_with(Thing::new("orb", "It glows faintly."));
+        cli.add_behavior("orb", Arc::new(Mutex::new(Counter(0))));
+
+        assert_eq!(cli.ask("z"), "Time passes...");
+        assert_eq!(cli.ask("take orb"), "Taken.\nThe orb hums in the Hall (1).");
+        assert_eq!(
+            cli.ask("z"),
+            "Time passes...\nThe orb hums in the Hall (2)."
+        );
+    }
+
+    fn cli_at_gate() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Gate",rooms:{
+                "Gate":(name:"Gate",desc:"Spears glint atop the wall.",paths:[
+                    (directions:["north"],target:"Barracks",opening:None,lock:None,
+                        guard:Some((faction:"City Watch",dc:10)))
+                ]),
+                "Barracks":(name:"Barracks",desc:"Bunks line the walls.",paths:[
+                    (directions:["south"],target:"Gate",opening:None,lock:None)
+                ])
+            }))"#,
+        )
+        .unwrap();
+        cli.receive_item(Item::Thing(
+            Thing::new("watch tabard", "It bears the city's crest.").with_disguise("City Watch"),
+        ));
+        cli
+    }
+
+    #[test]
+    fn guards_turn_away_strangers() {
+        let cli = cli_at_gate();
+
+        assert_eq!(
+            cli.ask("n"),
+            "The guards bar your way. Only the City Watch may pass."
+        );
+        cli.join_faction("City Watch");
+        assert!(cli.ask("n").starts_with("Barracks"));
+    }
+
+    #[test]
+    fn disguises_pass_for_members() {
+        let cli = cli_at_gate();
+        cli.set_random_source(Loaded);
+
+        assert_eq!(
+            cli.ask("wear tabard"),
+            "You put on the watch tabard and pass for one of the City Watch."
+        );
+        assert!(cli
+            .ask("stats")
+            .contains("Disguised as one of the City Watch"));
+        assert!(cli
+            .ask("n")
+            .starts_with("The guards wave you through.\nBarracks"));
+        assert_eq!(cli.ask("doff"), "You take off the watch tabard.");
+        assert!(cli.ask("i").contains("watch tabard"));
+    }
+
+    #[test]
+    fn failed_checks_break_the_disguise() {
+        let cli = cli_at_gate();
+        cli.set_random_source(Cursed);
+        cli.ask("wear tabard");
+
+        assert_eq!(
+            cli.ask("n"),
+            "The guards see through your disguise and tear it off you. \
+             The guards bar your way. Only the City Watch may pass."
+        );
+        assert!(!cli.ask("stats").contains("Disguised"));
+        cli.set_random_source(Loaded);
+        cli.ask("wear tabard");
+        assert_eq!(
+            cli.ask("n"),
+            "The guards know your face. The guards bar your way. Only the City Watch may pass."
+        );
+    }
+
+    fn cli_with_stone() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Glade",rooms:{
+                "Glade":(name:"Glade",desc:"Moss covers everything.",paths:[
+                    (directions:["north","door"],target:"Barrow",opening:Some(Closed),lock:Some(Locked("")))
+                ]),
+                "Barrow":(name:"Barrow",desc:"Bones line the niches.",paths:[
+                    (directions:["south"],target:"Glade",opening:None,lock:None)
+                ])
+            }))"#,
+        ).unwrap();
+        cli.add_element(
+            "Glade",
+            Element::new(
+                "stone",
+                "A standing stone hums beside the barrow door.",
+                "Notes are carved around its base.",
+            )
+            .with_tune(
+                Tune::new(
+                    "song of opening",
+                    "The stone hums back, and the barrow door grinds open.",
+                )
+                .with_opens("north"),
+            ),
+        );
+        cli.receive_item(Item::Thing(
+            Thing::new("lute", "Its strings are freshly tuned.").with_instrument(),
+        ));
+        cli
+    }
+
+    #[test]
+    fn songs_are_learned_from_scrolls() {
+        let cli = cli_with_stone();
+        cli.receive_item(Item::Thing(
+            Thing::new("scroll", "Notes are inked across it.").with_song("song of opening"),
+        ));
+
+        assert_eq!(cli.ask("songs"), "You don't know any songs.");
+        assert_eq!(
+            cli.ask("read scroll"),
+            "Notes are inked across it.\nYou learn the song of opening."
+        );
+        assert_eq!(cli.ask("songs"), "Songs you know:\n  song of opening");
+        assert_eq!(
+            cli.ask("learn from scroll"),
+            "You already know the song of opening."
+        );
+    }
+
+    #[test]
+    fn songs_are_learned_from_teachers() {
+        let cli = cli_with_stone();
+        cli.add_ally(
+            "Glade",
+            Ally::new("bard", "She hums to herself.").with_song("song of opening"),
+        );
+
+        assert_eq!(
+            cli.ask("learn song of storms from bard"),
+            "There is no such song to learn from the bard."
+        );
+        assert_eq!(
+            cli.ask("learn song of opening from bard"),
+            "You learn the song of opening."
+        );
+    }
+
+    #[test]
+    fn playing_the_right_song_opens_the_way() {
+        let cli = cli_with_stone();
+
+        assert_eq!(cli.ask("play lute"), "You play a simple tune on the lute.");
+        assert_eq!(
+            cli.ask("play song of opening on lute"),
+            "You don't know that song."
+        );
+        assert_eq!(cli.ask("n"), "The way is shut.");
+
+        cli.add_ally(
+            "Glade",
+            Ally::new("bard", "She hums to herself.").with_song("song of opening"),
+        );
+        cli.ask("learn from bard");
+        assert_eq!(
+            cli.ask("play song of opening on lute"),
+            "You play the song of opening on the lute.\n\
+             The stone hums back, and the barrow door grinds open."
+        );
+        assert_eq!(
+            cli.ask("play song of opening"),
+            "You play the song of opening on the lute, but nothing stirs."
+        );
+        assert!(cli.ask("n").starts_with("Barrow"));
+    }
+
+    #[test]
+    fn songs_need_an_instrument() {
+        let cli = test_world();
+        cli.receive_item(Item::Thing(
+            Thing::new("scroll", "Notes are inked across it.").with_song("song of opening"),
+        ));
+        cli.ask("read scroll");
+
+        assert_eq!(
+            cli.ask("play song of opening"),
+            "You have nothing to play it on."
+        );
+        assert_eq!(cli.ask("play scroll"), "You can't play the scroll.");
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "log")]
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use kingslayer::Cli;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+
+    // keeps every debug message from this crate
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Log for Recorder {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= Level::Debug && metadata.target().starts_with("kingslayer")
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+    #[test]
+    fn turns_are_logged() {
+        log::set_logger(&RECORDER).unwrap();
+        log::set_max_level(LevelFilter::Debug);
+
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
+        cli.ask("n");
+        let save = cli.to_ron();
+        cli.load_save(&save).unwrap();
+
+        let logged = RECORDER.0.lock().unwrap();
+        assert!(logged
+            .iter()
+            .any(|line| line.starts_with("loading world from worlds/test_world.ron")));
+        assert!(logged
+            .iter()
+            .any(|line| line.starts_with("\"n\" parsed as") && line.ends_with("Active")));
+        assert!(logged
+            .iter()
+            .any(|line| line == "moved from Central Room to Small Cave"));
+        assert!(logged.iter().any(|line| line.starts_with("loaded save")));
+    }
+}
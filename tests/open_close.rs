@@ -1,10 +1,12 @@
+#![cfg(feature = "serde")]
+
 #[cfg(test)]
 mod tests {
     use kingslayer::Cli;
 
     #[test]
     fn open_close_path() {
-        let cli = Cli::from_file("worlds/test_world.ron");
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
 
         assert!(cli.ask("l").contains("way is shut"));
         assert_eq!(cli.ask("enter door"), "The way is shut.");
@@ -26,7 +28,7 @@ mod tests {
 
     #[test]
     fn open_close_item() {
-        let cli = Cli::from_file("worlds/test_world.ron");
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
 
         cli.ask("s");
 
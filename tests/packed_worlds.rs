@@ -0,0 +1,75 @@
+#![cfg(feature = "pack")]
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use kingslayer::{Archive, Cli};
+
+    const KEY: &[u8] = b"a key built into the game";
+
+    fn packed_test_world() -> Vec<u8> {
+        let mut archive = Archive::new();
+        archive.add(
+            "test_world.ron",
+            &fs::read_to_string("worlds/test_world.ron").unwrap(),
+        );
+        archive.add("notes.txt", "The leaf is in the central room.");
+        archive.pack(KEY)
+    }
+
+    #[test]
+    fn packed_worlds_play_like_their_files() {
+        let packed = packed_test_world();
+        assert!(!String::from_utf8_lossy(&packed).contains("Central Room"));
+
+        let cli = Cli::from_packed(&packed, KEY).unwrap();
+        let file = Cli::from_file("worlds/test_world.ron").unwrap();
+        for cmd in ["l", "n", "take all", "s", "i"] {
+            assert_eq!(cli.ask(cmd), file.ask(cmd));
+        }
+
+        let archive = Archive::unpack(&packed, KEY).unwrap();
+        assert_eq!(
+            archive.get("notes.txt"),
+            Some("The leaf is in the central room.")
+        );
+        assert_eq!(archive.files().count(), 2);
+    }
+
+    #[test]
+    fn packed_worlds_need_their_key() {
+        let packed = packed_test_world();
+        assert_eq!(
+            Cli::from_packed(&packed, b"a guess")
+                .unwrap_err()
+                .to_string(),
+            "The archive cannot be read with this key."
+        );
+        assert_eq!(
+            Archive::unpack(b"(world:())", KEY).unwrap_err(),
+            "This is not a packed world."
+        );
+        assert!(Archive::unpack(&packed[..packed.len() / 2], KEY).is_err());
+        assert_eq!(
+            Cli::from_packed(&Archive::new().pack(KEY), KEY)
+                .unwrap_err()
+                .to_string(),
+            "The archive holds no world."
+        );
+    }
+
+    #[test]
+    fn packed_worlds_include_files_from_the_archive() {
+        let mut archive = Archive::new();
+        for name in ["world.ron", "rooms.ron", "npcs/npcs.ron", "npcs/items.ron"] {
+            let path = format!("tests/includes/{}", name);
+            archive.add(name, &fs::read_to_string(path).unwrap());
+        }
+
+        let cli = Cli::from_packed(&archive.pack(KEY), KEY).unwrap();
+        cli.ask("n");
+        cli.ask("n");
+        assert!(cli.ask("e").contains("helmet"));
+    }
+}
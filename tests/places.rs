@@ -0,0 +1,1216 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use kingslayer::{
+        Ability, Armor, Breach, Class, Cli, Dial, Direction, DoorLock, Element, Encounter, Enemy,
+        EnemyStatus, Furniture, Guard, Item, Opening, Pathway, Region, Requirements, Revival,
+        Riddle, Thing, Weapon,
+    };
+
+    use crate::common::test_world;
+
+    fn cli_at_crossroads() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Crossroads",rooms:{
+                "Crossroads":(name:"Crossroads",desc:"Roads run off every way.",paths:[
+                    (directions:["ne"],target:"Orchard",opening:None,lock:None),
+                    (directions:["gate"],target:"Inn",opening:None,lock:None)
+                ]),
+                "Orchard":(name:"Orchard",desc:"Apples hang heavy.",paths:[
+                    (directions:["southwest"],target:"Crossroads",opening:None,lock:None)
+                ]),
+                "Inn":(name:"Inn",desc:"A fire crackles in the hearth.",paths:[])
+            }))"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn directions_know_their_names_and_opposites() {
+        assert_eq!(Direction::parse("NE"), Direction::Northeast);
+        assert_eq!(Direction::parse("down"), Direction::Down);
+        assert_eq!(
+            Direction::parse("vestry door"),
+            Direction::Custom(String::from("vestry door"))
+        );
+        assert_eq!(Direction::Northeast.opposite(), Some(Direction::Southwest));
+        assert_eq!(Direction::In.opposite(), Some(Direction::Out));
+        assert_eq!(Direction::parse("gate").opposite(), None);
+        assert!(Direction::West.is_compass());
+        assert!(!Direction::Up.is_compass());
+    }
+
+    #[test]
+    fn diagonals_can_be_walked_by_name_or_abbreviation() {
+        let cli = cli_at_crossroads();
+
+        assert!(cli.ask("northeast").starts_with("Orchard"));
+        assert!(cli.ask("sw").starts_with("Crossroads"));
+        assert!(cli.ask("go ne").starts_with("Orchard"));
+    }
+
+    #[test]
+    fn rooms_join_both_ways() {
+        let cli = cli_at_crossroads();
+
+        assert!(cli.connect("Inn", "up", "Orchard").is_ok());
+        cli.ask("go gate");
+        assert!(cli.ask("u").starts_with("Orchard"));
+        assert!(cli.ask("d").starts_with("Inn"));
+
+        assert!(cli.connect("Inn", "cellar", "Orchard").is_err());
+        assert!(cli.connect("Inn", "north", "Stable").is_err());
+        assert_eq!(cli.ask("n"), "You cannot go that way.");
+    }
+
+    #[test]
+    fn compass_lists_named_ways_out() {
+        let cli = cli_at_crossroads();
+
+        assert_eq!(
+            cli.compass(),
+            vec![(Direction::Northeast, String::from("Orchard"))]
+        );
+    }
+
+    #[test]
+    fn worlds_write_directions_in_full() {
+        let cli = Cli::from_ron_str(&cli_at_crossroads().to_ron()).unwrap();
+
+        let ron = cli.to_ron();
+        assert!(ron.contains("\"northeast\""));
+        assert!(ron.contains("\"gate\""));
+        assert!(cli.ask("ne").starts_with("Orchard"));
+    }
+
+    #[test]
+    fn misspelled_directions_are_linted() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"It echoes.",paths:[
+                    (directions:["nort"],target:"Hall",opening:None,lock:None),
+                    (directions:["door"],target:"Hall",opening:None,lock:None)
+                ])
+            }))"#,
+        )
+        .unwrap();
+
+        let findings: Vec<String> = cli.lint().iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            findings,
+            vec![String::from(
+                "warning\tmisspelled-direction\tHall: a path leads nort, which is not a direction (did you mean north?)"
+            )]
+        );
+    }
+
+    fn cli_with_doors() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"It is long.",paths:[
+                    (directions:["iron door","north"],target:"Guardroom",opening:Some(Closed),lock:Some(Locked("")),
+                        knock:Some("A gruff voice grunts, and the iron door grinds open."),answered:true,
+                        listen:Some("You hear dice rattling on a table."),
+                        under:Some("Lamplight flickers through the gap.")),
+                    (directions:["kennel door","east"],target:"Kennel",opening:Some(Closed),lock:None,
+                        knock:Some("A dog barks furiously on the other side.")),
+                    (directions:["archway","west"],target:"Kennel",opening:None,lock:None)
+                ]),
+                "Guardroom":(name:"Guardroom",desc:"Dice lie on a table.",paths:[(directions:["south"],target:"Hall",opening:None,lock:None)]),
+                "Kennel":(name:"Kennel",desc:"It smells of dog.",paths:[(directions:["west"],target:"Hall",opening:None,lock:None)])
+            }))"#,
+        ).unwrap()
+    }
+
+    #[test]
+    fn knocking() {
+        let cli = cli_with_doors();
+
+        assert_eq!(
+            cli.ask("knock on kennel door"),
+            "A dog barks furiously on the other side."
+        );
+        assert_eq!(cli.ask("e"), "The way is shut.");
+
+        assert_eq!(
+            cli.ask("knock on iron door"),
+            "A gruff voice grunts, and the iron door grinds open."
+        );
+        assert!(cli.ask("n").contains("Guardroom"));
+    }
+
+    #[test]
+    fn listening_and_looking_under() {
+        let cli = cli_with_doors();
+
+        assert_eq!(
+            cli.ask("listen at iron door"),
+            "You hear dice rattling on a table."
+        );
+        assert_eq!(
+            cli.ask("look under iron door"),
+            "Lamplight flickers through the gap."
+        );
+        assert_eq!(
+            cli.ask("listen at kennel door"),
+            "You press an ear to the kennel door, but hear nothing."
+        );
+        assert_eq!(
+            cli.ask("look under kennel door"),
+            "You peer under the kennel door, but see only shadow."
+        );
+    }
+
+    #[test]
+    fn only_doors() {
+        let cli = cli_with_doors();
+
+        assert_eq!(
+            cli.ask("knock archway"),
+            "There is no door there to knock on."
+        );
+        assert_eq!(
+            cli.ask("look under archway"),
+            "There is nothing to look under."
+        );
+        assert_eq!(cli.ask("knock on"), "What do you want to knock on?");
+    }
+
+    fn cli_with_exits() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Chapel",rooms:{
+                "Chapel":(name:"Chapel",desc:"Pews line the walls.",paths:[
+                    (directions:["chute"],target:"Crypt",desc:"A dark chute slopes away.",opening:None,lock:None,
+                        message:Some("You slide down the chute!")),
+                    (directions:["stair","down"],target:"Crypt",desc:"A hidden stair winds down.",opening:None,lock:None,
+                        condition:Some(Item("silver gem"))),
+                    (directions:["vestry door","east"],target:"Vestry",desc:"A door leads east.",opening:Some(Closed),lock:None),
+                    (directions:["west"],target:"Vestry",desc:"A draft blows from the west.",opening:None,lock:None,
+                        condition:Some(Open("vestry door")))
+                ]),
+                "Crypt":(name:"Crypt",desc:"Bones fill the niches.",paths:[
+                    (directions:["north"],target:"Vestry",desc:"A passage leads north.",opening:None,lock:None,
+                        condition:Some(Cleared))
+                ]),
+                "Vestry":(name:"Vestry",desc:"Robes hang on hooks.",paths:[])
+            }))"#,
+        ).unwrap()
+    }
+
+    #[test]
+    fn traversal_message() {
+        let cli = cli_with_exits();
+
+        let res = cli.ask("enter chute");
+        assert!(res.starts_with("You slide down the chute!\nCrypt"));
+    }
+
+    #[test]
+    fn one_way_exits_have_no_way_back() {
+        let cli = cli_with_exits();
+        cli.ask("enter chute");
+
+        assert_eq!(cli.ask("enter chute"), "You cannot go that way.");
+    }
+
+    #[test]
+    fn hidden_until_item_is_placed() {
+        let cli = cli_with_exits();
+
+        assert!(!cli.ask("l").contains("A hidden stair winds down."));
+        assert_eq!(cli.ask("d"), "You cannot go that way.");
+
+        cli.add_item(
+            "Chapel",
+            Item::Thing(Thing::new("silver gem", "It glitters.")),
+        );
+        assert!(cli.ask("l").contains("A hidden stair winds down."));
+        assert!(cli.ask("d").contains("Crypt"));
+    }
+
+    #[test]
+    fn hidden_until_another_path_opens() {
+        let cli = cli_with_exits();
+
+        assert_eq!(cli.ask("w"), "You cannot go that way.");
+        cli.ask("open vestry door");
+        assert!(cli.ask("w").contains("Vestry"));
+    }
+
+    #[test]
+    fn hidden_until_room_is_cleared() {
+        let cli = cli_with_exits();
+        cli.ask("enter chute");
+        cli.spawn_enemy(
+            "Crypt",
+            Enemy::new("ghoul", "It is gaunt.", EnemyStatus::Asleep),
+        );
+
+        assert!(!cli.ask("l").contains("A passage leads north."));
+        assert_eq!(cli.ask("n"), "You cannot go that way.");
+
+        let cli = cli_with_exits();
+        cli.ask("enter chute");
+        assert!(cli.ask("l").contains("A passage leads north."));
+        assert!(cli.ask("n").contains("Vestry"));
+    }
+
+    #[test]
+    fn dead_ends_say_why() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Clifftop",rooms:{
+                "Clifftop":(name:"Clifftop",desc:"Wind whips the grass.",paths:[
+                    (directions:["path","north"],target:"Hut",opening:None,lock:None),
+                    (directions:["steps","down"],target:"Beach",opening:None,lock:None,
+                        condition:Some(Item("lantern")))
+                ],dead_ends:[
+                    ("east","The cliff drops away to the sea far below."),
+                    ("down","The rock face is too sheer to climb down.")
+                ]),
+                "Hut":(name:"Hut",desc:"Nets hang drying.",paths:[]),
+                "Beach":(name:"Beach",desc:"Waves break on the shingle.",paths:[])
+            }))"#,
+        )
+        .unwrap();
+
+        assert_eq!(cli.ask("e"), "The cliff drops away to the sea far below.");
+        assert_eq!(cli.ask("w"), "You cannot go that way.");
+        assert_eq!(cli.ask("d"), "The rock face is too sheer to climb down.");
+        // a way that opens goes before the dead end
+        cli.add_item("Clifftop", Item::Thing(Thing::new("lantern", "It is lit.")));
+        assert!(cli.ask("d").starts_with("Beach"));
+
+        cli.add_dead_end("Beach", "west", "The headland juts into the surf.");
+        assert_eq!(cli.ask("w"), "The headland juts into the surf.");
+    }
+
+    fn cli_with_portal() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Hub",rooms:{
+                "Hub":(name:"Hub",desc:"A portal shimmers here.",paths:[
+                    (directions:["portal"],target:"Hub",desc:"The portal flickers.",opening:None,lock:None,
+                        destinations:[("Red Room",None),("Blue Room",None),("Green Room",None)]),
+                    (directions:["gate"],target:"Hub",desc:"A dark gate stands here.",opening:None,lock:None,
+                        destinations:[("Red Room",Some(Cleared)),("Blue Room",Some(Item("blue key")))])
+                ]),
+                "Red Room":(name:"Red Room",desc:"Everything is red.",paths:[(directions:["portal"],target:"Hub",opening:None,lock:None)]),
+                "Blue Room":(name:"Blue Room",desc:"Everything is blue.",paths:[(directions:["portal"],target:"Hub",opening:None,lock:None)]),
+                "Green Room":(name:"Green Room",desc:"Everything is green.",paths:[(directions:["portal"],target:"Hub",opening:None,lock:None)])
+            }))"#,
+        ).unwrap()
+    }
+
+    fn destinations(seed: u64) -> Vec<String> {
+        let cli = cli_with_portal();
+        cli.seed(seed);
+        (0..10)
+            .map(|_| {
+                let room = cli.ask("enter portal");
+                cli.ask("enter portal");
+                room.lines().next().unwrap_or_default().to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn portal_picks_among_destinations() {
+        let rooms = destinations(7);
+
+        for room in rooms.iter() {
+            assert!(["Red Room", "Blue Room", "Green Room"].contains(&room.as_str()));
+        }
+        assert!(rooms.iter().any(|room| room != &rooms[0]));
+    }
+
+    #[test]
+    fn same_seed_same_destinations() {
+        assert_eq!(destinations(42), destinations(42));
+    }
+
+    #[test]
+    fn conditions_narrow_destinations() {
+        let cli = cli_with_portal();
+
+        for _ in 0..5 {
+            assert!(cli.ask("enter gate").starts_with("Red Room"));
+            cli.ask("enter portal");
+        }
+    }
+
+    #[test]
+    fn lint_follows_every_destination() {
+        let cli = cli_with_portal();
+
+        assert!(!cli
+            .lint()
+            .iter()
+            .any(|finding| finding.to_string().contains("Green Room")));
+    }
+
+    fn cli_with_floors() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Attic",rooms:{
+                "Attic":(name:"Attic",desc:"Dust hangs in the air.",paths:[],elements:[
+                    (name:"rotten floorboards",desc:"The floorboards are rotten.",inspect:"They creak.",
+                        breach:Some((reply:"The floorboards splinter and give way.",directions:["down","hole"],
+                            target:"Parlor",desc:"A jagged hole gapes in the floor.",durability:Durable(1))))
+                ]),
+                "Parlor":(name:"Parlor",desc:"Sheets cover the chairs.",paths:[]),
+                "Cellar":(name:"Cellar",desc:"It is cold and damp.",paths:[])
+            }))"#,
+        ).unwrap()
+    }
+
+    #[test]
+    fn breaking_a_floor_drops_the_player() {
+        let cli = cli_with_floors();
+
+        let res = cli.ask("smash floorboards");
+        assert!(res.starts_with("The floorboards splinter and give way."));
+        assert!(res.contains("You fall through the broken rotten floorboards!"));
+        assert!(res.contains("Parlor"));
+        assert_eq!(cli.ask("u"), "You cannot go that way.");
+    }
+
+    #[test]
+    fn sturdy_floors_take_several_blows() {
+        let cli = cli_with_floors();
+        cli.add_element(
+            "Parlor",
+            Element::new(
+                "trapdoor",
+                "A nailed trapdoor is set in the floor.",
+                "It is oak.",
+            )
+            .with_breach(
+                Breach::new(
+                    "The trapdoor bursts apart, and you drop a ladder through.",
+                    &["down", "ladder"],
+                    "Cellar",
+                    "A ladder leads down through the broken trapdoor.",
+                    1000,
+                )
+                .with_ladder(&["up", "ladder"]),
+            ),
+        );
+        cli.ask("smash floorboards");
+
+        assert_eq!(
+            cli.ask("smash trapdoor"),
+            "You strike the trapdoor, but it holds."
+        );
+        assert!(cli
+            .ask("l")
+            .contains("A nailed trapdoor is set in the floor."));
+    }
+
+    #[test]
+    fn a_dropped_ladder_leads_back() {
+        let cli = cli_with_floors();
+        cli.add_element(
+            "Parlor",
+            Element::new(
+                "trapdoor",
+                "A nailed trapdoor is set in the floor.",
+                "It is oak.",
+            )
+            .with_breach(
+                Breach::new(
+                    "The trapdoor bursts apart, and you drop a ladder through.",
+                    &["down", "ladder"],
+                    "Cellar",
+                    "A ladder leads down through the broken trapdoor.",
+                    1,
+                )
+                .with_ladder(&["up", "ladder"]),
+            ),
+        );
+        cli.ask("smash floorboards");
+
+        assert_eq!(
+            cli.ask("break trapdoor"),
+            "The trapdoor bursts apart, and you drop a ladder through."
+        );
+        assert!(cli
+            .ask("l")
+            .contains("A ladder leads down through the broken trapdoor."));
+        assert!(cli.ask("d").contains("Cellar"));
+        assert!(cli.ask("l").contains("A ladder leads up."));
+        assert!(cli.ask("u").contains("Parlor"));
+    }
+
+    #[test]
+    fn only_breakable_things_break() {
+        let cli = test_world();
+
+        assert_eq!(cli.ask("smash statue"), "There is no \"statue\" here.");
+        cli.add_element(
+            "Central Room",
+            Element::new("statue", "A marble statue stands here.", "It is solid."),
+        );
+        assert_eq!(cli.ask("smash statue"), "The statue will not break.");
+    }
+
+    fn cli_with_chair() -> Cli {
+        let cli = test_world();
+        cli.add_element(
+            "Central Room",
+            Element::new(
+                "wooden chair",
+                "A wooden chair sits beneath a high shelf.",
+                "It looks sturdy enough to stand on.",
+            )
+            .with_furniture(Furniture::On),
+        );
+        cli.add_element(
+            "Central Room",
+            Element::new("iron cage", "An iron cage hangs open.", "It is rusty.")
+                .with_furniture(Furniture::In),
+        );
+        cli.add_item(
+            "Central Room",
+            Item::Thing(
+                Thing::new("brass key", "It is tarnished.")
+                    .with_desc("A brass key glints on the high shelf."),
+            ),
+        );
+        cli.set_reach("Central Room", "brass key", "wooden chair");
+        cli
+    }
+
+    #[test]
+    fn sit_and_stand() {
+        let cli = cli_with_chair();
+
+        assert_eq!(cli.ask("sit on chair"), "You get on the wooden chair.");
+        assert!(cli.ask("l").contains("You are on the wooden chair."));
+        assert_eq!(
+            cli.ask("get in cage"),
+            "You are already on the wooden chair."
+        );
+        assert_eq!(cli.ask("stand"), "You get off the wooden chair.");
+        assert_eq!(cli.ask("stand up"), "You are already standing.");
+        assert_eq!(cli.ask("get in cage"), "You get in the iron cage.");
+        assert_eq!(cli.ask("get out"), "You get out of the iron cage.");
+    }
+
+    #[test]
+    fn not_furniture() {
+        let cli = cli_with_chair();
+        cli.add_element(
+            "Central Room",
+            Element::new("mural", "A mural covers the wall.", "It shows a battle."),
+        );
+
+        assert_eq!(cli.ask("sit on mural"), "You cannot get on the mural.");
+        assert!(cli
+            .ask("sit on sofa")
+            .contains("There is no \"sofa\" here."));
+    }
+
+    #[test]
+    fn leave_furniture_before_room() {
+        let cli = cli_with_chair();
+
+        cli.ask("get on chair");
+        assert_eq!(
+            cli.ask("n"),
+            "You will have to get off the wooden chair first."
+        );
+        cli.ask("get off chair");
+        assert!(cli.ask("n").contains("Small Cave"));
+    }
+
+    #[test]
+    fn reach_from_furniture() {
+        let cli = cli_with_chair();
+
+        assert_eq!(
+            cli.ask("take brass key"),
+            "You cannot reach the brass key from here."
+        );
+        assert_eq!(cli.ask("x key"), "You cannot reach the key from here.");
+        cli.ask("take all");
+        assert!(!cli.ask("i").contains("brass key"));
+        cli.ask("climb chair");
+        assert_eq!(cli.ask("x key"), "It is tarnished.");
+        assert_eq!(cli.ask("take key"), "Taken.");
+    }
+
+    fn cli_with_fountain() -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Central Room",
+            Item::Thing(
+                Thing::new("stone fountain", "Water trickles from a carved fish.")
+                    .with_desc("A stone fountain stands in the middle of the room.")
+                    .with_scenery("The fountain is far too heavy to move."),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn inspect_but_not_take() {
+        let cli = cli_with_fountain();
+
+        assert!(cli
+            .ask("l")
+            .contains("A stone fountain stands in the middle of the room."));
+        assert_eq!(cli.ask("x fountain"), "Water trickles from a carved fish.");
+        assert_eq!(
+            cli.ask("take fountain"),
+            "The fountain is far too heavy to move."
+        );
+        assert!(!cli.ask("i").contains("fountain"));
+    }
+
+    #[test]
+    fn take_all_leaves_scenery() {
+        let cli = cli_with_fountain();
+
+        cli.ask("take all");
+        let inventory = cli.ask("i");
+        assert!(inventory.contains("leaf"));
+        assert!(!inventory.contains("fountain"));
+        assert!(cli.ask("l").contains("A stone fountain"));
+        assert_eq!(cli.ask("take all"), "There is nothing to take.");
+    }
+
+    fn cli_with_safe() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Study",rooms:{
+                "Study":(name:"Study",desc:"Ledgers are stacked on the desk.",paths:[
+                    (directions:["north","door"],target:"Vault",opening:Some(Closed),lock:Some(Locked("")))
+                ]),
+                "Vault":(name:"Vault",desc:"Coins are stacked to the ceiling.",paths:[
+                    (directions:["south"],target:"Study",opening:None,lock:None)
+                ])
+            }))"#,
+        ).unwrap();
+        cli.add_element(
+            "Study",
+            Element::new(
+                "dial",
+                "A brass dial is set into the vault door.",
+                "It is numbered from 0 to 9.",
+            )
+            .with_dial(
+                Dial::new(
+                    &[7, 2, 9],
+                    "The tumblers fall into place, and the door swings open.",
+                )
+                .with_opens("north"),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn turning_one_number_at_a_time() {
+        let cli = cli_with_safe();
+
+        assert_eq!(cli.ask("turn dial to 7"), "The dial clicks to 7.");
+        assert_eq!(cli.ask("turn dial to 2"), "The dial clicks to 7-2.");
+        assert!(cli.ask("x dial").contains("The dial reads 7-2."));
+        assert_eq!(
+            cli.ask("turn dial to 9"),
+            "The tumblers fall into place, and the door swings open."
+        );
+        assert!(cli.ask("n").starts_with("Vault"));
+    }
+
+    #[test]
+    fn setting_the_whole_combination() {
+        let cli = cli_with_safe();
+
+        assert_eq!(
+            cli.ask("set dial to 7-2-9"),
+            "The tumblers fall into place, and the door swings open."
+        );
+        assert_eq!(cli.ask("spin dial to 1"), "The dial is already open.");
+    }
+
+    #[test]
+    fn wrong_combinations_start_over() {
+        let cli = cli_with_safe();
+
+        assert_eq!(
+            cli.ask("set dial to 1-2-3"),
+            "Nothing gives, and the dial spins back to the start."
+        );
+        assert_eq!(cli.ask("turn dial to 7"), "The dial clicks to 7.");
+        assert_eq!(
+            cli.ask("reset dial"),
+            "You spin the dial back to the start."
+        );
+        assert_eq!(cli.ask("turn dial to 2"), "The dial clicks to 2.");
+        assert_eq!(
+            cli.ask("turn dial"),
+            "What do you want to turn the dial to?"
+        );
+    }
+
+    fn cli_with_gear() -> Cli {
+        let cli = test_world();
+        cli.add_item(
+            "Small Cave",
+            Item::Weapon(
+                Weapon::new("war hammer", "A hammer with a head of solid iron.", 12)
+                    .with_requirements(Requirements::new().with_ability(Ability::Strength, 16)),
+            ),
+        );
+        cli.add_item(
+            "Small Cave",
+            Item::Weapon(
+                Weapon::new("oak staff", "Runes are carved along the staff.", 8)
+                    .with_requirements(Requirements::new().with_class(Class::Mage)),
+            ),
+        );
+        cli.add_item(
+            "Small Cave",
+            Item::Armor(
+                Armor::new("silk robe", "The robe shimmers faintly.", 12).with_requirements(
+                    Requirements::new()
+                        .with_class(Class::Mage)
+                        .with_class(Class::Cleric),
+                ),
+            ),
+        );
+        cli.ask("n");
+        cli.ask("take all");
+        cli
+    }
+
+    #[test]
+    fn ability_requirement() {
+        let cli = cli_with_gear();
+
+        assert_eq!(
+            cli.ask("equip hammer"),
+            "You need 16 Strength to use the war hammer, but you have 14."
+        );
+        assert!(!cli.ask("i").contains("Main hand"));
+
+        cli.ask("increase strength");
+        cli.ask("increase strength");
+        assert_eq!(cli.ask("equip hammer"), "Equipped.");
+    }
+
+    #[test]
+    fn class_requirement() {
+        let cli = cli_with_gear();
+
+        assert_eq!(cli.ask("equip staff"), "Only a Mage can use the oak staff.");
+        assert_eq!(
+            cli.ask("don robe"),
+            "Only a Mage or a Cleric can use the silk robe."
+        );
+
+        cli.set_class(Class::Mage);
+        assert_eq!(cli.ask("equip staff"), "Equipped.");
+        assert_eq!(cli.ask("don robe"), "Donned.");
+    }
+
+    #[test]
+    fn inspect_shows_requirements() {
+        let cli = cli_with_gear();
+
+        assert_eq!(
+            cli.ask("x hammer"),
+            "A hammer with a head of solid iron.\nRequires: Strength 16."
+        );
+        assert_eq!(
+            cli.ask("x robe"),
+            "The robe shimmers faintly.\nRequires: Mage or Cleric."
+        );
+        assert_eq!(
+            cli.ask("x sword"),
+            "The iron sword is of crude workmanship, but it should be usable."
+        );
+    }
+
+    #[test]
+    fn equip_best_skips_unusable() {
+        let cli = cli_with_gear();
+
+        assert_eq!(cli.ask("equip best"), "You equip the iron sword.");
+    }
+
+    fn cli_with_window() -> Cli {
+        let cli = test_world();
+        cli.add_element(
+            "Central Room",
+            Element::new(
+                "cracked window",
+                "A cracked window looks north into the cave.",
+                "The glass is grimy.",
+            )
+            .with_view("Small Cave"),
+        );
+        cli.add_element(
+            "Central Room",
+            Element::new("keyhole", "", "It is a tiny keyhole.").with_view("Closet"),
+        );
+        cli
+    }
+
+    #[test]
+    fn look_through_window() {
+        let cli = cli_with_window();
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("goblin", "It is ugly.", EnemyStatus::Asleep),
+        );
+
+        let res = cli.ask("look through window");
+        assert_eq!(
+            res,
+            "Through the cracked window you see the Small Cave.\n\
+             You are in a small cave.\n\
+             There is a goblin here. It is asleep."
+        );
+        assert!(cli.ask("l").contains("You are in the central room."));
+    }
+
+    #[test]
+    fn peek_through_keyhole() {
+        let cli = cli_with_window();
+
+        assert_eq!(
+            cli.ask("peek through keyhole"),
+            "Through the keyhole you see the Closet.\n\
+             This isn't a very large or clean closet."
+        );
+        assert_eq!(cli.ask("look through keyhole"), cli.ask("peek keyhole"));
+    }
+
+    #[test]
+    fn windows_into_missing_rooms_are_linted() {
+        let cli = test_world();
+        cli.add_element(
+            "Central Room",
+            Element::new("window", "", "").with_view("Smal Cave"),
+        );
+
+        assert!(cli
+            .lint()
+            .iter()
+            .any(|finding| finding.message().contains("a window looks into Smal Cave")));
+    }
+
+    fn cli_in_desert(chance: u32) -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Dunes",rooms:{
+                "Dunes":(name:"Dunes",desc:"Sand stretches away.",paths:[
+                    (directions:["east"],target:"Camp",opening:None,lock:None,turns:4)
+                ]),
+                "Camp":(name:"Camp",desc:"Tents flap in the wind.",paths:[
+                    (directions:["west"],target:"Dunes",opening:None,lock:None,turns:4)
+                ])
+            }))"#,
+        )
+        .unwrap();
+        cli.add_region(
+            "Desert",
+            Region::new(chance).with_wanderer(
+                1,
+                Enemy::new("scorpion", "Its tail is raised.", EnemyStatus::Distracted)
+                    .with_desc("A scorpion skitters over the sand."),
+            ),
+        );
+        cli.set_region("Dunes", "Desert");
+        cli
+    }
+
+    #[test]
+    fn wanderers_waylay_journeys() {
+        let cli = cli_in_desert(100);
+
+        let res = cli.ask("e");
+        assert!(res.starts_with("You set out east, but the scorpion waylays you on the way!"));
+        assert!(res.contains("A scorpion skitters over the sand."));
+    }
+
+    #[test]
+    fn wanderers_find_idle_players() {
+        let cli = cli_in_desert(100);
+
+        assert_eq!(
+            cli.ask("wait"),
+            "Something wanders near.\nA scorpion skitters over the sand."
+        );
+        assert!(cli.ask("l").contains("A scorpion skitters over the sand."));
+    }
+
+    #[test]
+    fn quiet_regions_let_travellers_pass() {
+        let cli = cli_in_desert(0);
+
+        assert!(cli.ask("e").contains("Camp"));
+        assert!(!cli.ask("wait").contains("Something wanders near."));
+    }
+
+    #[test]
+    fn rooms_outside_the_region_stay_quiet() {
+        let cli = cli_in_desert(100);
+        cli.set_region("Dunes", "Nowhere");
+
+        assert!(!cli.ask("wait").contains("Something wanders near."));
+        assert!(cli.lint().iter().any(|finding| finding
+            .message()
+            .contains("region Nowhere, which does not exist")));
+    }
+
+    #[test]
+    fn regions_are_saved() {
+        let cli = cli_in_desert(100);
+
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(cli.ask("wait").contains("Something wanders near."));
+    }
+
+    fn cli_on_the_road() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Forest Edge",rooms:{
+                "Forest Edge":(name:"Forest Edge",desc:"Pines thin out here.",paths:[
+                    (directions:["east"],target:"City Gate",opening:None,lock:None)
+                ]),
+                "City Gate":(name:"City Gate",desc:"Guards lean on their spears.",paths:[
+                    (directions:["west"],target:"Forest Edge",opening:None,lock:None),
+                    (directions:["east"],target:"Market",opening:None,lock:None)
+                ]),
+                "Market":(name:"Market",desc:"Stalls crowd the square.",paths:[
+                    (directions:["west"],target:"City Gate",opening:None,lock:None),
+                    (directions:["south"],target:"Docks",opening:None,lock:None)
+                ]),
+                "Docks":(name:"Docks",desc:"Gulls cry overhead.",paths:[
+                    (directions:["north"],target:"Market",opening:None,lock:None)
+                ])
+            },regions:{
+                "Forest":(),
+                "City":(borders:[("Forest","You leave the forest behind as the city walls rise ahead.")],arrival:Some("The noise of the city closes around you.")),
+            }))"#,
+        ).unwrap();
+        cli.set_region("Forest Edge", "Forest");
+        cli.set_region("City Gate", "City");
+        cli.set_region("Market", "City");
+        cli
+    }
+
+    #[test]
+    fn borders_are_described_on_crossing() {
+        let cli = cli_on_the_road();
+
+        assert!(cli
+            .ask("e")
+            .starts_with("You leave the forest behind as the city walls rise ahead.\nCity Gate\n"));
+        assert!(cli.ask("e").starts_with("Market\n"));
+        assert!(cli.ask("s").starts_with("Docks\n"));
+        assert!(cli
+            .ask("n")
+            .starts_with("The noise of the city closes around you.\nMarket\n"));
+    }
+
+    #[test]
+    fn border_paragraphs_are_saved() {
+        let cli = cli_on_the_road();
+
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(cli
+            .ask("e")
+            .starts_with("You leave the forest behind as the city walls rise ahead."));
+        assert!(!cli.ask("w").contains("city"));
+    }
+
+    #[test]
+    fn regions_can_be_built_with_borders() {
+        let cli = cli_in_desert(0);
+        cli.add_region(
+            "Oasis",
+            Region::new(0)
+                .with_border("Desert", "Palms sway as the sand gives way to grass.")
+                .with_arrival("Water glints ahead."),
+        );
+        cli.set_region("Camp", "Oasis");
+
+        assert!(cli.ask("e").starts_with(
+            "You travel east for 4 turns.\nPalms sway as the sand gives way to grass.\nCamp"
+        ));
+    }
+
+    fn cli_with_journeys() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Oasis",rooms:{
+                "Oasis":(name:"Oasis",desc:"Palms shade a pool.",paths:[
+                    (directions:["east"],target:"Ruins",desc:"Dunes stretch east.",opening:None,lock:None,turns:5),
+                    (directions:["west"],target:"Ruins",desc:"A trail winds west.",opening:None,lock:None,turns:2,
+                        message:Some("You trudge along the trail."),
+                        encounter:Some((chance:100,enemy:(name:"jackal",desc:"A jackal snarls at you.",
+                            inspect:"It is mangy.",hp:1,ac:0,xp:1,damage:1,status:Distracted)))),
+                    (directions:["north"],target:"Ruins",desc:"A road leads north.",opening:None,lock:None)
+                ]),
+                "Ruins":(name:"Ruins",desc:"Broken columns jut from the sand.",paths:[
+                    (directions:["south"],target:"Oasis",opening:None,lock:None)
+                ])
+            }))"#,
+        ).unwrap()
+    }
+
+    #[test]
+    fn long_journeys_take_turns() {
+        let cli = cli_with_journeys();
+
+        let res = cli.ask("e");
+        assert!(res.starts_with("You travel east for 5 turns.\nRuins"));
+        assert_eq!(cli.status().turns(), 5);
+
+        cli.ask("s");
+        assert_eq!(cli.status().turns(), 6);
+    }
+
+    #[test]
+    fn encounters_interrupt_journeys() {
+        let cli = cli_with_journeys();
+
+        let res = cli.ask("w");
+        assert!(res.starts_with("You set out west, but the jackal waylays you on the way!"));
+        assert!(cli.ask("l").starts_with("Oasis"));
+        assert!(cli.ask("l").contains("A jackal snarls at you."));
+    }
+
+    #[test]
+    fn resuming_a_journey() {
+        let cli = cli_with_journeys();
+        cli.ask("w");
+
+        let res = cli.ask("continue");
+        assert!(res.starts_with("You trudge along the trail.\nRuins"));
+        assert_eq!(cli.status().turns(), 2);
+        assert_eq!(cli.ask("continue"), "You are not on a journey.");
+    }
+
+    #[test]
+    fn leaving_aborts_a_journey() {
+        let cli = cli_with_journeys();
+        cli.ask("w");
+        cli.ask("n");
+        cli.ask("s");
+
+        assert_eq!(cli.ask("continue"), "You are not on a journey.");
+    }
+
+    fn cli_with_sphinx() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Antechamber",rooms:{
+                "Antechamber":(name:"Antechamber",desc:"Sand drifts across the tiles.",paths:[
+                    (directions:["north","door"],target:"Vault",opening:Some(Closed),lock:Some(Locked("")))
+                ]),
+                "Vault":(name:"Vault",desc:"Gold glitters in the gloom.",paths:[
+                    (directions:["south"],target:"Antechamber",opening:None,lock:None)
+                ])
+            }))"#,
+        ).unwrap();
+        cli.add_element(
+            "Antechamber",
+            Element::new(
+                "sphinx",
+                "A sphinx crouches before the door.",
+                "Its eyes are ancient.",
+            )
+            .with_riddle(
+                Riddle::new(
+                    "What speaks without a mouth and hears without ears?",
+                    "an echo",
+                    "The sphinx bows its head, and the door grinds open.",
+                )
+                .with_pattern(".*echo.*")
+                .with_hint("It lives in caves.")
+                .with_hint("It answers you back.")
+                .with_penalty(2)
+                .with_opens("north"),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn wrong_guesses_hurt_and_give_hints() {
+        let cli = cli_with_sphinx();
+
+        assert_eq!(
+            cli.ask("answer sphinx"),
+            "What speaks without a mouth and hears without ears?\n(leave to step away)"
+        );
+        assert!(cli
+            .ask("the wind")
+            .starts_with("That is not the answer. It lives in caves.\nYou take 2 damage."));
+        assert!(cli
+            .ask("a bat")
+            .starts_with("That is not the answer. It answers you back."));
+        assert!(cli
+            .ask("a cat")
+            .starts_with("That is not the answer. It answers you back."));
+        assert_eq!(cli.ask("leave"), "You step away from the sphinx.");
+        assert_eq!(cli.ask("n"), "The way is shut.");
+    }
+
+    #[test]
+    fn answers_are_normalized_and_open_the_way() {
+        let cli = cli_with_sphinx();
+
+        cli.ask("talk to sphinx");
+        assert_eq!(
+            cli.ask("An ECHO!"),
+            "The sphinx bows its head, and the door grinds open."
+        );
+        assert!(cli.ask("n").contains("Vault"));
+        cli.ask("s");
+        assert_eq!(
+            cli.ask("answer sphinx"),
+            "The sphinx has nothing more to ask."
+        );
+    }
+
+    #[test]
+    fn patterns_accept_looser_answers() {
+        let cli = cli_with_sphinx();
+
+        cli.ask("solve sphinx");
+        assert_eq!(
+            cli.ask("it must be an echo of course"),
+            "The sphinx bows its head, and the door grinds open."
+        );
+    }
+
+    #[test]
+    fn patterns_are_regular_expressions() {
+        let cli = cli_with_sphinx();
+        cli.add_element(
+            "Antechamber",
+            Element::new("door", "A talking door.", "Its knocker is a mouth.").with_riddle(
+                Riddle::new("How many sides has a square?", "four", "The door yawns.")
+                    .with_pattern("[0-9]+ sides?|four sides?"),
+            ),
+        );
+
+        cli.ask("answer door");
+        assert!(cli.ask("three").starts_with("That is not the answer."));
+        assert_eq!(cli.ask("4 sides"), "The door yawns.");
+    }
+
+    #[test]
+    fn broken_patterns_are_linted() {
+        let cli = test_world();
+        cli.add_element(
+            "Central Room",
+            Element::new("sphinx", "A sphinx crouches here.", "Its eyes are ancient.").with_riddle(
+                Riddle::new("What walks on four legs?", "a man", "The sphinx bows.")
+                    .with_pattern("*man*"),
+            ),
+        );
+
+        assert!(cli.lint().iter().any(|finding| finding.to_string()
+            == "error\tbad-pattern\tCentral Room: the sphinx accepts answers like *man*, which is not a regular expression"));
+        cli.ask("answer sphinx");
+        assert_eq!(cli.ask("man"), "The sphinx bows.");
+    }
+
+    fn cli_with_deadly_sphinx() -> Cli {
+        let cli = test_world();
+        cli.add_element(
+            "Central Room",
+            Element::new("sphinx", "A sphinx crouches here.", "Its eyes are ancient.").with_riddle(
+                Riddle::new("What walks on four legs?", "a man", "The sphinx bows.")
+                    .with_penalty(1000),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn deadly_penalties_end_the_game() {
+        let cli = cli_with_deadly_sphinx();
+
+        cli.ask("answer sphinx");
+        assert!(cli.ask("a dog").ends_with("You died. Farewell."));
+        assert!(cli.is_over());
+    }
+
+    #[test]
+    fn deadly_penalties_can_be_survived() {
+        let cli = cli_with_deadly_sphinx();
+        cli.receive_item(Item::Revival(Revival::new(
+            "phoenix feather",
+            "It is warm to the touch.",
+            50,
+        )));
+
+        cli.ask("answer sphinx");
+        let res = cli.ask("a dog");
+        assert!(res.contains("The phoenix feather crumbles to ash"));
+        assert!(!res.contains("You died."));
+        assert!(!cli.is_over());
+    }
+
+    #[test]
+    fn patterns_with_many_stars_answer_quickly() {
+        let cli = cli_with_sphinx();
+        cli.add_element(
+            "Antechamber",
+            Element::new("statue", "A statue stares down.", "It is carved of basalt.").with_riddle(
+                Riddle::new("Say the word.", "oob", "The statue nods.")
+                    .with_pattern(&format!("{}.*b", ".*o".repeat(12))),
+            ),
+        );
+
+        cli.ask("answer statue");
+        assert!(cli
+            .ask(&"o".repeat(200))
+            .starts_with("That is not the answer."));
+        assert_eq!(
+            cli.ask(&format!("{}b", "o".repeat(200))),
+            "The statue nods."
+        );
+    }
+
+    #[test]
+    fn paths_can_be_built_without_world_files() {
+        let cli = test_world();
+        cli.add_room("Vault", "Gold glitters everywhere.");
+        cli.add_pathway(
+            "Central Room",
+            Pathway::new(&["east"], "Vault", "A gate leads east.", "It is iron.")
+                .with_guard(Guard::new("royal guard", 30).with_refusal("\"Halt!\"")),
+        );
+        cli.add_pathway(
+            "Central Room",
+            Pathway::new(&["down"], "Vault", "A hatch leads down.", "It is oak.")
+                .with_opening(Opening::Open)
+                .with_lock(DoorLock::new("vault key")),
+        );
+        cli.add_pathway(
+            "Central Room",
+            Pathway::new(
+                &["west"],
+                "Vault",
+                "A long road leads west.",
+                "It is dusty.",
+            )
+            .with_turns(3)
+            .with_encounter(Encounter::new(
+                100,
+                Enemy::new("bandit", "A bandit blocks the road.", EnemyStatus::Angry),
+            )),
+        );
+
+        assert_eq!(cli.ask("e"), "\"Halt!\"");
+        assert!(cli.ask("d").starts_with("The down is locked."));
+        assert!(cli
+            .ask("w")
+            .starts_with("You set out west, but the bandit waylays you on the way!"));
+    }
+}
@@ -1,10 +1,12 @@
+#![cfg(feature = "serde")]
+
 #[cfg(test)]
 mod tests {
     use kingslayer::Cli;
 
     #[test]
     fn put_in_take_from() {
-        let cli = Cli::from_file("worlds/test_world.ron");
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
 
         cli.ask("take leaf");
         cli.ask("drop the leaf");
@@ -0,0 +1,586 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use kingslayer::{
+        Campaign, Chapter, Class, Cli, Enemy, EnemyStatus, FileStore, Item, Metadata, Race,
+        SaveInfo, SaveStore, Weapon, WorldFormat,
+    };
+
+    use crate::common::{test_world, Loaded, MemoryStore};
+
+    fn minutes(n: u64) -> Duration {
+        Duration::from_secs(n * 60)
+    }
+
+    #[test]
+    fn autosave_is_off_by_default() {
+        let cli = test_world();
+        let start = Instant::now();
+        assert!(cli.autosave_if_due(start).is_none());
+        assert!(cli.autosave_if_due(start + minutes(1)).is_none());
+        assert!(cli.ask("settings").contains("autosave: off"));
+    }
+
+    #[test]
+    fn autosave_counts_only_active_play() {
+        let cli = test_world();
+        assert_eq!(
+            cli.ask("set autosave 5"),
+            "autosave set to every 5 minutes."
+        );
+
+        // a command each minute, with an hour away from the keyboard in the middle
+        let start = Instant::now();
+        let mut now = start;
+        for _ in 0..3 {
+            now += minutes(1);
+            assert!(cli.autosave_if_due(now).is_none());
+        }
+        now += minutes(60);
+        assert!(cli.autosave_if_due(now).is_none());
+        now += minutes(1);
+        assert_eq!(
+            cli.autosave_if_due(now),
+            Some(String::from("(Autosaved to 'autosave.save.ron'.)"))
+        );
+
+        let saved = fs::read_to_string("autosave.save.ron").unwrap();
+        fs::remove_file("autosave.save.ron").unwrap();
+        assert!(cli.load_save(&saved).is_ok());
+
+        // the count starts over after each autosave
+        now += minutes(1);
+        assert!(cli.autosave_if_due(now).is_none());
+
+        assert_eq!(cli.ask("set autosave off"), "autosave set to off.");
+        assert!(cli.autosave_if_due(now + minutes(10)).is_none());
+    }
+
+    fn cli_partway() -> Cli {
+        let cli = test_world();
+        cli.set_metadata(Metadata::new("Test World").with_release("2"));
+        cli.set_race(Race::Elf);
+        cli.set_class(Class::Ranger);
+        cli.complete_quest("find the key");
+        cli.ask("n");
+        cli
+    }
+
+    #[test]
+    fn saves_can_be_read_without_loading_them() {
+        let info = SaveInfo::from_ron_str(&cli_partway().to_ron()).unwrap();
+
+        assert_eq!(info.metadata().title(), "Test World");
+        assert_eq!(info.metadata().release(), "2");
+        assert_eq!(info.room(), "Small Cave");
+        assert_eq!(info.moves(), 1);
+        assert_eq!(info.level(), 1);
+        assert_eq!(info.race(), &Race::Elf);
+        assert_eq!(info.class(), &Class::Ranger);
+        assert_eq!(info.hp(), info.hp_cap() as i32);
+        assert_eq!(info.flags(), [String::from("find the key")]);
+        assert_eq!(info.summary(), "Small Cave - level 1 Elf Ranger, 1 moves");
+    }
+
+    #[test]
+    fn saved_slots_can_be_inspected() {
+        let dir = env::temp_dir().join("kingslayer_save_info");
+        fs::create_dir_all(&dir).unwrap();
+        let cli = cli_partway();
+        cli.set_save_store(Arc::new(Mutex::new(FileStore::new(&dir))));
+        cli.ask("save slot1");
+
+        assert_eq!(cli.inspect_saved("slot1").unwrap().room(), "Small Cave");
+        assert_eq!(
+            SaveInfo::from_file(dir.join("slot1.save.ron"))
+                .unwrap()
+                .moves(),
+            1
+        );
+        assert!(cli.inspect_saved("slot2").is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn saves_show_the_name_of_the_room() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hold 1",rooms:{
+                "Hold 1":(name:"Hold",desc:"Crates are lashed to the walls.",paths:[])
+            }))"#,
+        )
+        .unwrap();
+
+        let info = SaveInfo::from_ron_str(&cli.to_ron()).unwrap();
+        assert_eq!(info.room(), "Hold");
+        assert!(info.summary().starts_with("Hold - "));
+    }
+
+    #[test]
+    fn broken_saves_are_refused() {
+        assert!(SaveInfo::from_ron_str("(world: nonsense").is_err());
+        assert!(SaveInfo::from_ron_str("()").is_err());
+    }
+
+    fn cli_with_peril() -> (Cli, Arc<Mutex<MemoryStore>>) {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Camp",restrict_saves:true,rooms:{
+                "Camp":(name:"Camp",desc:"A fire burns low.",paths:[
+                    (directions:["north"],target:"Lair",opening:None,lock:None)
+                ]),
+                "Lair":(name:"Lair",desc:"Bones crunch underfoot.",paths:[
+                    (directions:["south"],target:"Camp",opening:None,lock:None)
+                ],dangerous:true)
+            }))"#,
+        )
+        .unwrap();
+        let store = Arc::new(Mutex::new(MemoryStore::default()));
+        cli.set_save_store(store.clone());
+        (cli, store)
+    }
+
+    #[test]
+    fn dangerous_rooms_refuse_saves() {
+        let (cli, store) = cli_with_peril();
+
+        cli.ask("n");
+        assert_eq!(cli.ask("save"), "This place is too dangerous to save in.");
+        assert!(store.lock().unwrap().0.is_empty());
+        cli.ask("s");
+        assert_eq!(cli.ask("save"), "Moves: 2\nSaved to 'memory:world'.");
+    }
+
+    #[test]
+    fn fights_refuse_saves() {
+        let (cli, _) = cli_with_peril();
+        cli.set_random_source(Loaded);
+        cli.spawn_enemy(
+            "Camp",
+            Enemy::new("wolf", "It bares its teeth.", EnemyStatus::Angry).with_hp(1),
+        );
+
+        assert_eq!(
+            cli.can_save(),
+            Err(String::from("You cannot save with enemies upon you."))
+        );
+        assert_eq!(cli.ask("save"), "You cannot save with enemies upon you.");
+        cli.receive_item(Item::Weapon(Weapon::new("club", "It is knotted.", 10)));
+        cli.ask("kill wolf with club");
+        assert!(cli.can_save().is_ok());
+    }
+
+    #[test]
+    fn autosaves_wait_for_safety() {
+        let (cli, store) = cli_with_peril();
+        cli.set_autosave(1);
+        cli.ask("n");
+
+        let start = Instant::now();
+        cli.autosave_if_due(start);
+        assert!(cli
+            .autosave_if_due(start + Duration::from_secs(90))
+            .is_none());
+        cli.ask("s");
+        assert_eq!(
+            cli.autosave_if_due(start + Duration::from_secs(100)),
+            Some(String::from("(Autosaved to 'memory:autosave'.)"))
+        );
+        assert!(store.lock().unwrap().0.contains_key("autosave"));
+    }
+
+    #[test]
+    fn saves_are_free_unless_restricted() {
+        let (cli, _) = cli_with_peril();
+        cli.restrict_saves(false);
+
+        cli.ask("n");
+        assert!(cli.can_save().is_ok());
+        cli.restrict_saves(true);
+        assert!(cli.can_save().is_err());
+        cli.set_dangerous("Lair", false);
+        assert!(cli.can_save().is_ok());
+    }
+
+    #[test]
+    fn saves_go_to_the_store_that_is_set() {
+        let store = Arc::new(Mutex::new(MemoryStore::default()));
+        let cli = test_world();
+        cli.set_save_store(store.clone());
+
+        cli.ask("n");
+        assert_eq!(cli.ask("save"), "Moves: 1\nSaved to 'memory:world'.");
+        assert_eq!(cli.ask("save quest"), "Moves: 1\nSaved to 'memory:quest'.");
+        assert_eq!(store.lock().unwrap().0.len(), 2);
+
+        let loaded = cli.load_saved("world").unwrap();
+        assert_eq!(loaded.ask("l"), cli.ask("l"));
+        assert!(cli.load_saved("missing").is_err());
+    }
+
+    #[test]
+    fn file_stores_keep_saves_in_their_directory() {
+        let dir = env::temp_dir().join("kingslayer_save_store");
+        fs::create_dir_all(&dir).unwrap();
+        let cli = test_world();
+        cli.set_save_store(Arc::new(Mutex::new(FileStore::new(&dir))));
+
+        assert!(cli.ask("save").ends_with(&format!(
+            "Saved to '{}'.",
+            dir.join("world.save.ron").display()
+        )));
+        assert!(cli.load_saved("world").is_ok());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn file_stores_refuse_names_outside_their_directory() {
+        let dir = env::temp_dir().join("kingslayer_save_store_names");
+        let mut store = FileStore::new(&dir);
+
+        for name in [
+            "../escape",
+            "/etc/escape",
+            "sub\\escape",
+            "C:escape",
+            "..",
+            "",
+        ] {
+            assert!(store.write(name, "()").is_err(), "{}", name);
+            assert!(store.read(name).is_err(), "{}", name);
+        }
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn undo() {
+        let cli = test_world();
+        assert_eq!(cli.ask("undo"), "There is nothing to undo.");
+
+        cli.ask("take leaf");
+        assert!(cli.ask("i").contains("leaf"));
+        cli.ask("undo");
+        assert!(cli.ask("undo").contains("Central Room"));
+        assert!(!cli.ask("i").contains("leaf"));
+        assert!(cli.ask("l").contains("leaf"));
+    }
+
+    #[test]
+    fn restart() {
+        let cli = test_world();
+        cli.ask("take leaf");
+        cli.ask("n");
+
+        assert!(cli.ask("restart").contains("Central Room"));
+        assert_eq!(cli.ask("undo"), "There is nothing to undo.");
+        assert!(!cli.ask("i").contains("leaf"));
+        assert_eq!(cli.status().turns(), 1);
+    }
+
+    #[test]
+    fn new_game_plus() {
+        let cli = test_world();
+        cli.ask("take leaf");
+        cli.ask("n");
+
+        assert!(cli.new_game_plus().contains("Central Room"));
+        assert!(cli.ask("i").contains("leaf"));
+        assert!(cli.ask("l").contains("leaf"));
+    }
+
+    #[test]
+    fn disable_undo() {
+        let cli = test_world();
+        cli.set_undo(false);
+        cli.ask("take leaf");
+        assert_eq!(cli.ask("undo"), "There is nothing to undo.");
+    }
+
+    #[test]
+    fn world_file_metadata() {
+        let cli = Cli::from_file("worlds/world.ron").unwrap();
+
+        assert_eq!(cli.metadata().title(), "Kingslayer");
+        assert!(cli.intro().contains("You wake to the creak of timbers"));
+        let res = cli.ask("about");
+        assert!(res.contains("Kingslayer by Zaechus"));
+        assert!(res.contains("Release 1"));
+        assert!(res.contains("Kingslayer engine"));
+    }
+
+    #[test]
+    fn no_metadata() {
+        let cli = test_world();
+
+        assert_eq!(cli.intro(), "");
+        assert!(cli.ask("version").contains("An untitled world"));
+    }
+
+    #[test]
+    fn max_score() {
+        let cli = test_world();
+        cli.set_metadata(Metadata::new("Test").with_max_score(350));
+
+        assert!(cli.ask("about").contains("Maximum score: 350"));
+    }
+
+    #[test]
+    fn saves_check_release() {
+        let cli = test_world();
+        cli.set_metadata(Metadata::new("Test").with_release("1"));
+        cli.ask("n");
+        let save = cli.to_ron();
+
+        let same = test_world();
+        same.set_metadata(Metadata::new("Test").with_release("1"));
+        assert!(same
+            .load_save(&save)
+            .unwrap()
+            .ask("l")
+            .contains("Small Cave"));
+
+        let newer = test_world();
+        newer.set_metadata(Metadata::new("Test").with_release("2"));
+        assert_eq!(
+            newer.load_save(&save).unwrap_err().to_string(),
+            "This save is from Test release 1, not Test release 2."
+        );
+    }
+
+    fn cli_with_warnings() -> Cli {
+        let cli = test_world();
+        cli.set_metadata(
+            Metadata::new("Test")
+                .with_content_warning("violence")
+                .with_content_warning("spiders")
+                .with_min_age(13),
+        );
+        cli
+    }
+
+    #[test]
+    fn content_warnings() {
+        let cli = cli_with_warnings();
+
+        assert_eq!(cli.metadata().content_warnings(), ["violence", "spiders"]);
+        assert_eq!(cli.metadata().min_age(), Some(13));
+        let intro = cli.intro();
+        assert!(intro.contains("Content warning: violence, spiders."));
+        assert!(intro.contains("Intended for ages 13 and up."));
+        assert!(cli.ask("about").contains("Content warning"));
+    }
+
+    #[test]
+    fn acknowledgement_gates_first_turn() {
+        let cli = cli_with_warnings();
+        cli.require_acknowledgement(true);
+
+        assert!(cli.needs_acknowledgement());
+        assert!(cli.ask("n").contains("Type \"accept\" to continue."));
+        assert!(cli.ask("accept").contains("Central Room"));
+        assert!(!cli.needs_acknowledgement());
+        assert!(cli.ask("n").contains("Small Cave"));
+    }
+
+    #[test]
+    fn acknowledged_by_host() {
+        let cli = cli_with_warnings();
+        cli.require_acknowledgement(true);
+        cli.acknowledge();
+
+        assert!(cli.ask("n").contains("Small Cave"));
+    }
+
+    #[test]
+    fn acknowledgement_not_required_by_default() {
+        let cli = cli_with_warnings();
+
+        assert!(!cli.needs_acknowledgement());
+        assert!(cli.ask("n").contains("Small Cave"));
+    }
+
+    fn hub() -> Cli {
+        Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"Doors lead off to every chapter.",paths:[
+                    (directions:["north"],target:"Portal",opening:None,lock:None)
+                ],items:[
+                    Thing((name:"lantern",desc:"There is a lantern here.",inspect:"It is dented."))
+                ]),
+                "Portal":(name:"Portal",desc:"The air shimmers.",paths:[
+                    (directions:["south"],target:"Hall",opening:None,lock:None)
+                ])
+            }))"#,
+        )
+        .unwrap()
+    }
+
+    fn crypt() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Stairs",rooms:{
+                "Stairs":(name:"Stairs",desc:"Steps wind down into the dark.",paths:[
+                    (directions:["down"],target:"Tomb",opening:None,lock:None)
+                ]),
+                "Tomb":(name:"Tomb",desc:"Dust lies thick on the lid.",paths:[
+                    (directions:["up"],target:"Daylight",opening:None,lock:None)
+                ],items:[
+                    Thing((name:"gem",desc:"There is a gem here.",inspect:"It glitters."))
+                ]),
+                "Daylight":(name:"Daylight",desc:"You blink in the sun.",paths:[])
+            }))"#,
+        )
+        .unwrap();
+        cli.set_metadata(Metadata::new("The Crypt").with_intro("The dead do not sleep well."));
+        cli
+    }
+
+    fn campaign() -> Campaign {
+        Campaign::new("hub", Chapter::new(hub).with_exit("Portal", "crypt"))
+            .with_chapter("crypt", Chapter::new(crypt).with_finish("Daylight", "hub"))
+    }
+
+    #[test]
+    fn characters_carry_their_things_between_worlds() {
+        let mut campaign = campaign();
+
+        assert_eq!(campaign.ask("take lantern"), "Taken.");
+        let arrival = campaign.ask("n");
+        assert_eq!(campaign.chapter(), "crypt");
+        assert!(arrival.starts_with("Portal"));
+        assert!(arrival.contains("The Crypt\n\nThe dead do not sleep well.\n\nStairs"));
+        assert!(campaign.ask("i").contains("lantern"));
+
+        campaign.ask("d");
+        campaign.ask("take gem");
+        assert!(campaign.ask("u").ends_with("Portal\nThe air shimmers.\n"));
+        assert_eq!(campaign.chapter(), "hub");
+        let inventory = campaign.ask("i");
+        assert!(inventory.contains("lantern") && inventory.contains("gem"));
+    }
+
+    #[test]
+    fn finished_chapters_are_recorded() {
+        let mut campaign = campaign();
+
+        campaign.ask("n");
+        assert!(!campaign.is_complete("crypt"));
+        campaign.ask("d");
+        campaign.ask("u");
+        assert!(campaign.is_complete("crypt"));
+        assert!(!campaign.is_complete("hub"));
+        assert_eq!(campaign.records().len(), 1);
+        assert_eq!(campaign.records()[0].chapter(), "crypt");
+        assert_eq!(campaign.records()[0].moves(), 2);
+    }
+
+    #[test]
+    fn worlds_stay_as_they_were_left() {
+        let mut campaign = campaign();
+
+        campaign.ask("n");
+        campaign.ask("d");
+        assert!(campaign.travel("hub").unwrap().starts_with("Portal"));
+        assert!(campaign.ask("s").contains("lantern"));
+        assert!(campaign.ask("n").contains("Tomb"));
+        assert!(campaign.travel("tower").is_err());
+    }
+
+    #[test]
+    fn campaigns_save_every_world() {
+        let mut campaign = campaign();
+        campaign.ask("take lantern");
+        campaign.ask("n");
+        campaign.ask("d");
+        campaign.ask("u");
+
+        let mut loaded = Campaign::new("hub", Chapter::new(hub).with_exit("Portal", "crypt"))
+            .with_chapter("crypt", Chapter::new(crypt).with_finish("Daylight", "hub"));
+        assert!(loaded.load_ron(&campaign.to_ron()).is_ok());
+        assert_eq!(loaded.chapter(), "hub");
+        assert!(loaded.is_complete("crypt"));
+        assert!(loaded.ask("i").contains("lantern"));
+        loaded.ask("s");
+        assert!(loaded.ask("n").contains("Daylight"));
+
+        let mut lone = Campaign::new("hub", Chapter::new(hub));
+        assert!(lone.load_ron(&campaign.to_ron()).is_err());
+    }
+
+    #[test]
+    fn saving_in_play_saves_the_campaign() {
+        let store = Arc::new(Mutex::new(MemoryStore::default()));
+        let mut campaign = campaign().with_save_store(store.clone());
+        campaign.ask("n");
+
+        assert_eq!(campaign.ask("save"), "Saved to 'memory:campaign'.");
+        let mut loaded = Campaign::new("hub", Chapter::new(hub).with_exit("Portal", "crypt"))
+            .with_chapter("crypt", Chapter::new(crypt).with_finish("Daylight", "hub"))
+            .with_save_store(store);
+        assert!(loaded.load_saved("campaign").is_ok());
+        assert_eq!(loaded.chapter(), "crypt");
+        assert!(loaded.load_saved("slot2").is_err());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let cli = test_world();
+        cli.ask("n");
+
+        let loaded = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(loaded.ask("l").contains("Small Cave"));
+        assert!(loaded.ask("s").contains("Central Room"));
+    }
+
+    #[test]
+    fn invalid_current_room() {
+        assert!(Cli::from_ron_str(
+            r#"(world:(curr_room:"Nowhere",rooms:{"Room":(name:"Room",desc:"It is empty.",paths:[])}))"#,
+        )
+        .is_err());
+    }
+
+    fn round_trip(cli: &Cli, format: WorldFormat, name: &str) -> Cli {
+        let path = env::temp_dir().join(name);
+        let path = path.to_str().unwrap();
+        cli.export_world(path, format).unwrap();
+        let loaded = Cli::from_file(path).unwrap();
+        fs::remove_file(path).unwrap();
+        loaded
+    }
+
+    #[test]
+    fn generated_world() {
+        let cli = Cli::generate(20, 3);
+        let loaded = round_trip(&cli, WorldFormat::PrettyRon, "kingslayer_generated.ron");
+
+        assert_eq!(loaded.ask("l"), Cli::generate(20, 3).ask("l"));
+        assert_eq!(loaded.ask("w"), Cli::generate(20, 3).ask("w"));
+        assert_eq!(loaded.world_stats(), cli.world_stats());
+    }
+
+    #[test]
+    fn edited_world() {
+        let cli = test_world();
+        cli.ask("take leaf");
+        cli.ask("n");
+        let loaded = round_trip(&cli, WorldFormat::Ron, "kingslayer_edited.ron");
+
+        assert!(loaded.ask("l").starts_with("Small Cave"));
+        assert!(!loaded.ask("i").contains("leaf"));
+    }
+
+    #[test]
+    fn unwritable_path() {
+        let cli = Cli::generate(1, 0);
+        assert!(cli
+            .export_world("/nonexistent/world.ron", WorldFormat::Ron)
+            .is_err());
+    }
+}
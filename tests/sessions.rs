@@ -0,0 +1,663 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        sync::{Arc, Mutex},
+        task::{Context, Poll, Wake, Waker},
+        thread,
+        time::Duration,
+    };
+
+    use kingslayer::{
+        AsyncGameSession, Cli, Enemy, EnemyStatus, Filter, Item, Listener, SessionManager, Weapon,
+        WordList,
+    };
+
+    use crate::common::test_world;
+
+    fn manager() -> SessionManager {
+        SessionManager::new(|_| test_world())
+    }
+
+    #[test]
+    fn games_are_independent() {
+        let mut sessions = manager();
+
+        assert!(sessions.ask("alice", "n").contains("Small Cave"));
+        assert!(sessions.ask("bob", "l").contains("Central Room"));
+        assert!(sessions.ask("alice", "l").contains("Small Cave"));
+        assert_eq!(sessions.len(), 2);
+    }
+
+    // every user's game is seeded the same and starts next to a nest of rats
+    fn seeded_manager() -> SessionManager {
+        SessionManager::new(|_| {
+            let cli = test_world();
+            cli.seed(5);
+            for _ in 0..5 {
+                cli.spawn_enemy(
+                    "Central Room",
+                    Enemy::new("rat", "It is small.", EnemyStatus::Asleep).with_hp(30),
+                );
+            }
+            cli.ask("take leaf");
+            cli
+        })
+    }
+
+    #[test]
+    fn seeding_a_game_leaves_the_others_alone() {
+        let mut alone = seeded_manager();
+        let expected: Vec<String> = (0..5)
+            .map(|_| alone.ask("alice", "kill rat with leaf"))
+            .collect();
+
+        let mut sessions = seeded_manager();
+        sessions.ask("alice", "l");
+        for (i, expected) in expected.into_iter().enumerate() {
+            // a newcomer's game is seeded as it starts, and an old hand's keeps rolling
+            sessions.ask(&format!("newcomer{}", i), "l");
+            sessions.ask("bob", "kill rat with leaf");
+            assert_eq!(sessions.ask("alice", "kill rat with leaf"), expected);
+        }
+    }
+
+    #[test]
+    fn idle_sessions_expire() {
+        let mut sessions = manager().with_idle_timeout(Duration::ZERO);
+
+        sessions.ask("alice", "l");
+        assert_eq!(sessions.expire_idle(), vec![String::from("alice")]);
+        assert!(sessions.is_empty());
+    }
+
+    #[test]
+    fn active_sessions_do_not_expire() {
+        let mut sessions = manager().with_idle_timeout(Duration::from_secs(3600));
+
+        sessions.ask("alice", "l");
+        assert!(sessions.expire_idle().is_empty());
+        assert!(sessions.contains("alice"));
+    }
+
+    #[test]
+    fn chatting_keeps_sessions_alive() {
+        let mut sessions = manager().with_idle_timeout(Duration::from_millis(200));
+
+        sessions.ask("alice", "l");
+        for _ in 0..6 {
+            thread::sleep(Duration::from_millis(60));
+            sessions.ask("alice", "say hello");
+            assert!(sessions.expire_idle().is_empty());
+        }
+        assert!(sessions.contains("alice"));
+    }
+
+    #[test]
+    fn autosave_after_turns_and_on_end() {
+        let saves = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&saves);
+        let mut sessions = manager().with_autosave(move |user, cli| {
+            log.lock()
+                .unwrap()
+                .push((user.to_owned(), cli.status().turns()))
+        });
+
+        sessions.ask("alice", "xyzzy");
+        assert!(saves.lock().unwrap().is_empty());
+        sessions.ask("alice", "n");
+        sessions.end("alice");
+        assert_eq!(
+            *saves.lock().unwrap(),
+            vec![(String::from("alice"), 1), (String::from("alice"), 1)]
+        );
+    }
+
+    #[test]
+    fn quitting_ends_session() {
+        let mut sessions = manager();
+
+        sessions.ask("alice", "quit");
+        assert!(!sessions.contains("alice"));
+    }
+
+    #[test]
+    fn broadcast_reaches_every_user() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&sent);
+        let mut sessions = manager().with_broadcast(move |user, message| {
+            log.lock().unwrap().push(format!("{}: {}", user, message))
+        });
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        sessions.broadcast("The server restarts soon.");
+
+        let mut sent = sent.lock().unwrap().clone();
+        sent.sort();
+        assert_eq!(
+            sent,
+            vec![
+                "alice: The server restarts soon.",
+                "bob: The server restarts soon."
+            ]
+        );
+    }
+
+    #[test]
+    fn trade_between_players() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&sent);
+        let mut sessions = manager().with_broadcast(move |user, message| {
+            log.lock().unwrap().push(format!("{}: {}", user, message))
+        });
+
+        sessions.ask("alice", "take leaf");
+        sessions.ask("bob", "l");
+        assert_eq!(
+            sessions.ask("alice", "give leaf to bob"),
+            "You offer the leaf to bob."
+        );
+        assert!(!sessions.ask("alice", "i").contains("leaf"));
+        assert_eq!(
+            sessions.ask("bob", "accept"),
+            "You accept the leaf from alice."
+        );
+        assert!(sessions.ask("bob", "i").contains("leaf"));
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                "bob: alice offers you the leaf. Type \"accept\" or \"decline\".",
+                "alice: bob accepts the leaf."
+            ]
+        );
+    }
+
+    #[test]
+    fn declined_and_abandoned_trades_return_items() {
+        let mut sessions = manager();
+
+        sessions.ask("alice", "take leaf");
+        sessions.ask("bob", "l");
+        sessions.ask("alice", "give leaf to bob");
+        assert_eq!(sessions.ask("bob", "decline"), "You decline the leaf.");
+        assert!(sessions.ask("alice", "i").contains("leaf"));
+
+        sessions.ask("alice", "give leaf to bob");
+        sessions.end("bob");
+        assert!(sessions.ask("alice", "i").contains("leaf"));
+    }
+
+    #[test]
+    fn cannot_give_what_you_lack() {
+        let mut sessions = manager();
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        assert_eq!(
+            sessions.ask("alice", "give leaf to bob"),
+            "You do not have the \"leaf\"."
+        );
+        assert!(!sessions.ask("bob", "accept").contains("leaf"));
+    }
+
+    #[test]
+    fn chat_reaches_the_same_room() {
+        let heard = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&heard);
+        let mut sessions = manager()
+            .with_chat(move |user, chat| log.lock().unwrap().push((user.to_owned(), chat.clone())));
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        sessions.ask("carol", "n");
+
+        assert_eq!(sessions.ask("alice", "say hello"), "You say, \"hello\"");
+        assert_eq!(sessions.ask("alice", "shout help"), "You shout, \"help\"");
+        assert_eq!(sessions.ask("alice", "emote waves"), "alice waves.");
+        assert_eq!(sessions.ask("alice", "say"), "What do you want to say?");
+
+        let heard = heard.lock().unwrap();
+        let to = |user: &str| {
+            heard
+                .iter()
+                .filter(|(listener, _)| listener == user)
+                .map(|(_, chat)| chat.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            to("bob"),
+            vec![
+                "alice says, \"hello\"",
+                "alice shouts, \"help\"",
+                "alice waves."
+            ]
+        );
+        assert_eq!(to("carol"), vec!["Someone shouts nearby, \"help\""]);
+        assert!(to("alice").is_empty());
+    }
+
+    #[test]
+    fn chat_falls_back_to_broadcast() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&sent);
+        let mut sessions = manager().with_broadcast(move |user, message| {
+            log.lock().unwrap().push(format!("{}: {}", user, message))
+        });
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        sessions.ask("alice", "say hi");
+        assert_eq!(*sent.lock().unwrap(), vec!["bob: alice says, \"hi\""]);
+    }
+
+    fn pvp_manager(drop_percent: u32) -> SessionManager {
+        SessionManager::new(|_| {
+            let cli = test_world();
+            cli.allow_pvp("Central Room");
+            cli.add_item(
+                "Central Room",
+                Item::Weapon(Weapon::new("greataxe", "It is enormous.", 100)),
+            );
+            cli
+        })
+        .with_pvp(drop_percent)
+    }
+
+    #[test]
+    fn pvp_only_where_allowed() {
+        let mut sessions = manager();
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        assert_eq!(
+            sessions.ask("alice", "attack bob"),
+            "You cannot fight other players here."
+        );
+
+        let mut sessions = pvp_manager(100);
+        sessions.ask("alice", "take greataxe");
+        sessions.ask("bob", "n");
+        assert!(!sessions.ask("alice", "attack bob").contains("bob"));
+    }
+
+    #[test]
+    fn pvp_slays_and_drops_items() {
+        let mut sessions = pvp_manager(100);
+        sessions.ask("alice", "take greataxe");
+        sessions.ask("alice", "equip greataxe");
+        sessions.ask("bob", "take leaf");
+
+        let res = (0..100)
+            .map(|_| sessions.ask("alice", "attack bob"))
+            .find(|res| res.contains("falls"))
+            .unwrap();
+        assert!(res.ends_with("\nbob falls, dropping their leaf."));
+        assert!(!sessions.contains("bob"));
+        assert!(sessions.ask("alice", "l").contains("leaf"));
+    }
+
+    #[test]
+    fn dropped_items_lie_in_every_game() {
+        let mut sessions = pvp_manager(100);
+        sessions.ask("alice", "take greataxe");
+        sessions.ask("alice", "equip greataxe");
+        sessions.ask("alice", "take leaf");
+        sessions.ask("bob", "take leaf");
+        sessions.ask("carol", "take leaf");
+        while sessions.contains("bob") {
+            sessions.ask("alice", "attack bob");
+        }
+
+        assert!(sessions.ask("carol", "l").contains("leaf"));
+        assert_eq!(sessions.ask("carol", "take leaf"), "Taken.");
+        assert!(!sessions.ask("alice", "l").contains("leaf"));
+    }
+
+    #[test]
+    fn enemies_are_fought_before_players() {
+        let mut sessions = pvp_manager(100);
+        sessions.ask("alice", "take greataxe");
+        sessions.ask("alice", "equip greataxe");
+        sessions.ask("bob", "l");
+        sessions.get("alice").unwrap().spawn_enemy(
+            "Central Room",
+            Enemy::new("bob", "It is a goblin named Bob.", EnemyStatus::Asleep),
+        );
+
+        let res = sessions.ask("alice", "attack bob");
+        assert!(!res.contains("You hit bob for") && !res.contains("You miss bob"));
+        assert!(sessions.ask("bob", "l").starts_with("Central Room"));
+    }
+
+    #[test]
+    fn spectators_see_the_same_output() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let mut sessions =
+            manager()
+                .with_broadcast(|_, _| ())
+                .with_spectate(move |spectator, user, output| {
+                    log.lock()
+                        .unwrap()
+                        .push(format!("{} watching {}: {}", spectator, user, output))
+                });
+
+        assert!(!sessions.watch("teacher", "alice"));
+        sessions.ask("alice", "xyzzy");
+        assert!(sessions.watch("teacher", "alice"));
+
+        let output = sessions.ask("alice", "n");
+        sessions.ask("bob", "l");
+        sessions.ask("bob", "say hi");
+        sessions.stop_watching("teacher", "alice");
+        sessions.ask("alice", "s");
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![format!("teacher watching alice: > n\n{}", output)]
+        );
+    }
+
+    #[test]
+    fn spectators_hear_chat() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let mut sessions = manager()
+            .with_chat(|_, _| ())
+            .with_spectate(move |_, _, output| log.lock().unwrap().push(output.to_owned()));
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        sessions.watch("teacher", "alice");
+        sessions.ask("bob", "say hi");
+        assert_eq!(*seen.lock().unwrap(), vec!["bob says, \"hi\""]);
+    }
+
+    #[test]
+    fn input_is_sanitized() {
+        let mut sessions = manager().with_max_input_len(10);
+
+        assert!(sessions
+            .ask("alice", "l\u{7}oo\u{1b}k")
+            .contains("Central Room"));
+        assert_eq!(
+            sessions.ask("alice", "take everything please"),
+            "That is too long to understand."
+        );
+    }
+
+    #[test]
+    fn rate_limit() {
+        let mut sessions = manager().with_rate_limit(2, Duration::from_secs(3600));
+
+        sessions.ask("alice", "l");
+        sessions.ask("alice", "l");
+        assert_eq!(
+            sessions.ask("alice", "l"),
+            "You are acting too quickly. Wait a moment and try again."
+        );
+        assert!(sessions.ask("bob", "l").contains("Central Room"));
+
+        let mut sessions = manager().with_rate_limit(1, Duration::ZERO);
+        sessions.ask("alice", "l");
+        assert!(sessions.ask("alice", "l").contains("Central Room"));
+    }
+
+    #[test]
+    fn word_lists_mask_what_players_say() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&sent);
+        let mut sessions = manager()
+            .with_filter(WordList::new(&["darn"]))
+            .with_broadcast(move |user, message| {
+                log.lock().unwrap().push(format!("{}: {}", user, message))
+            });
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        assert_eq!(
+            sessions.ask("alice", "say Darn it, darnation!"),
+            "You say, \"**** it, darnation!\""
+        );
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec!["bob: alice says, \"**** it, darnation!\""]
+        );
+    }
+
+    struct Shouty;
+
+    impl Filter for Shouty {
+        fn input(&mut self, _user: &str, text: &str) -> String {
+            text.to_owned()
+        }
+
+        fn output(&mut self, user: &str, text: &str) -> String {
+            format!("[{}] {}", user, text.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn filters_see_everything_sent() {
+        let mut sessions = manager().with_filter(Shouty);
+
+        assert!(sessions
+            .ask("alice", "l")
+            .starts_with("[alice] CENTRAL ROOM"));
+    }
+
+    #[test]
+    fn chat_hooks_hear_filtered_words() {
+        let heard = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&heard);
+        let mut sessions = manager()
+            .with_filter(Shouty)
+            .with_chat(move |_, chat| log.lock().unwrap().push(chat.to_string()));
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        sessions.ask("alice", "say hello");
+
+        assert_eq!(*heard.lock().unwrap(), vec!["alice says, \"[bob] HELLO\""]);
+    }
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = Box::pin(fut);
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    fn assert_send_value<T: Send>(_: &T) {}
+
+    #[test]
+    fn ask_resolves_to_output() {
+        let session = AsyncGameSession::new(test_world());
+
+        let fut = session.ask("l");
+        assert_send_value(&fut);
+        assert!(block_on(fut).contains("Central Room"));
+    }
+
+    #[test]
+    fn outputs_stream_in_order() {
+        let session = AsyncGameSession::new(test_world());
+
+        session.send("n");
+        session.send("s");
+        assert!(block_on(session.recv()).unwrap().contains("Small Cave"));
+        assert!(block_on(session.recv()).unwrap().contains("Central Room"));
+    }
+
+    #[test]
+    fn recv_waits_for_input() {
+        let session = AsyncGameSession::new(test_world());
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(session.poll_recv(&mut cx).is_pending());
+        session.send("l");
+        assert!(session.poll_recv(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn stream_ends_after_quit() {
+        let session = AsyncGameSession::new(test_world());
+
+        session.send("quit");
+        assert!(block_on(session.recv()).unwrap().contains("Farewell"));
+        assert_eq!(block_on(session.recv()), None);
+    }
+
+    #[test]
+    fn close_ends_stream() {
+        let session = AsyncGameSession::new(test_world());
+
+        session.close();
+        session.send("l");
+        assert_eq!(block_on(session.recv()), None);
+        assert!(session.into_inner().ask("l").contains("Central Room"));
+    }
+
+    fn seeded_session() -> AsyncGameSession {
+        let cli = test_world();
+        cli.seed(3);
+        for _ in 0..5 {
+            cli.spawn_enemy(
+                "Central Room",
+                Enemy::new("rat", "It is small.", EnemyStatus::Asleep).with_hp(30),
+            );
+        }
+        cli.ask("take leaf");
+        AsyncGameSession::new(cli)
+    }
+
+    #[test]
+    fn seeded_sessions_replay_on_any_thread() {
+        let here = seeded_session();
+        let expected: Vec<String> = (0..5)
+            .map(|_| block_on(here.ask("kill rat with leaf")))
+            .collect();
+
+        // each input is handled on a different thread, as an executor might
+        let there = Arc::new(seeded_session());
+        for expected in expected {
+            let there = Arc::clone(&there);
+            let output = thread::spawn(move || block_on(there.ask("kill rat with leaf")))
+                .join()
+                .unwrap();
+            assert_eq!(output, expected);
+        }
+    }
+
+    fn assert_send<T: Send>() {}
+
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn send_and_sync() {
+        assert_send::<Cli>();
+        assert_sync::<Mutex<Cli>>();
+    }
+
+    #[test]
+    fn play_on_another_thread() {
+        let cli = test_world();
+        cli.ask("n");
+
+        let cli = thread::spawn(move || {
+            assert!(cli.ask("l").contains("Small Cave"));
+            cli
+        })
+        .join()
+        .unwrap();
+        assert!(cli.ask("s").contains("Central Room"));
+    }
+
+    #[test]
+    fn shared_behind_a_lock() {
+        let cli = Arc::new(Mutex::new(test_world()));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let cli = Arc::clone(&cli);
+                thread::spawn(move || {
+                    cli.lock().unwrap().ask("wait");
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(cli.lock().unwrap().status().turns(), 4);
+    }
+
+    fn cli_with_gatekeeper() -> Cli {
+        let cli = test_world();
+        cli.add_listener(
+            "Central Room",
+            Listener::new("sesame", "With a groan, the small wooden door swings open.")
+                .with_opens("small wooden door"),
+        );
+        cli
+    }
+
+    #[test]
+    fn password_opens_a_door_from_afar() {
+        let cli = cli_with_gatekeeper();
+        cli.ask("n");
+
+        assert_eq!(
+            cli.ask("shout open sesame"),
+            "You shout, \"open sesame\"\nWith a groan, the small wooden door swings open."
+        );
+        assert_eq!(cli.ask("shout sesame"), "You shout, \"sesame\"");
+
+        cli.ask("s");
+        assert!(cli.ask("enter door").contains("Closet"));
+    }
+
+    #[test]
+    fn shouts_only_carry_so_far() {
+        let cli = test_world();
+        cli.add_listener(
+            "Bedroom",
+            Listener::new("hello", "A sleepy voice calls back, \"Hello?\"").with_range(1),
+        );
+        cli.add_listener(
+            "Bedroom",
+            Listener::new("hello", "Something stirs far to the south."),
+        );
+        cli.ask("n");
+
+        assert_eq!(
+            cli.ask("yell hello"),
+            "You shout, \"hello\"\nSomething stirs far to the south."
+        );
+        assert_eq!(cli.ask("shout"), "What do you want to shout?");
+    }
+
+    #[test]
+    fn hosted_players_are_heard() {
+        let mut sessions = SessionManager::new(|_| cli_with_gatekeeper());
+
+        assert_eq!(
+            sessions.ask("alice", "shout Sesame!"),
+            "You shout, \"sesame!\"\nWith a groan, the small wooden door swings open."
+        );
+    }
+}
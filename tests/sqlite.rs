@@ -0,0 +1,49 @@
+#![cfg(feature = "sqlite")]
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use kingslayer::{Cli, SqliteGame};
+
+    #[test]
+    fn only_changed_rooms_are_written() {
+        let mut db = SqliteGame::in_memory("hero").unwrap();
+        let cli = Cli::generate(200, 7);
+        assert_eq!(db.persist(&cli).unwrap(), 200);
+        assert_eq!(db.persist(&cli).unwrap(), 0);
+
+        cli.ask("take all");
+        assert!(db.persist(&cli).unwrap() <= 2);
+    }
+
+    #[test]
+    fn persisted_games_are_restored() {
+        let path = env::temp_dir().join("kingslayer_sqlite_test.db");
+        let _ = fs::remove_file(&path);
+        let path = path.to_str().unwrap();
+
+        let cli = Cli::from_file("worlds/test_world.ron").unwrap();
+        let mut db = SqliteGame::open(path, "hero").unwrap();
+        assert!(db.restore().unwrap().is_none());
+        db.persist(&cli).unwrap();
+        cli.ask("n");
+        cli.ask("take all");
+        db.persist(&cli).unwrap();
+        drop(db);
+
+        // as after a crash, a fresh connection finds the game as of the last turn written
+        let mut db = SqliteGame::open(path, "hero").unwrap();
+        let restored = db.restore().unwrap().unwrap();
+        assert_eq!(db.persist(&restored).unwrap(), 0);
+        assert_eq!(restored.ask("l"), cli.ask("l"));
+        assert_eq!(restored.ask("i"), cli.ask("i"));
+
+        assert!(SqliteGame::open(path, "someone else")
+            .unwrap()
+            .restore()
+            .unwrap()
+            .is_none());
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,635 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use kingslayer::{
+        Ally, Calendar, Cli, Condition, Consequence, Deck, Director, Enemy, EnemyStatus, Gear,
+        Happening, Item, Region, Sequence, SessionManager, Step, StoryEvent, Summon, Thing,
+    };
+
+    use crate::common::test_world;
+
+    fn cli_with_calendar() -> Cli {
+        let cli = test_world();
+        cli.set_calendar(
+            Calendar::new(2)
+                .with_weekdays(&["Moonday", "Sunday"])
+                .with_festival(2, "Feast of Lanterns")
+                .with_event(
+                    StoryEvent::new(3, "The sun goes dark. Somewhere a door creaks open.")
+                        .with_opens("Central Room", "small wooden door"),
+                ),
+        );
+        cli
+    }
+
+    #[test]
+    fn days_pass_with_festivals() {
+        let cli = cli_with_calendar();
+
+        assert_eq!(cli.ask("date"), "It is Moonday, day 1.");
+        cli.ask("wait");
+        cli.ask("wait");
+        assert_eq!(
+            cli.ask("date"),
+            "It is Sunday, day 2. Today is the Feast of Lanterns."
+        );
+    }
+
+    #[test]
+    fn story_events_happen_once_on_their_day() {
+        let cli = cli_with_calendar();
+
+        for _ in 0..3 {
+            assert!(!cli.ask("wait").contains("sun goes dark"));
+        }
+        assert!(cli
+            .ask("wait")
+            .ends_with("\nThe sun goes dark. Somewhere a door creaks open."));
+        assert!(!cli.ask("wait").contains("sun goes dark"));
+        assert!(cli.ask("enter door").contains("Closet"));
+
+        // a save keeps the date and what has already happened
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert_eq!(cli.ask("date"), "It is Sunday, day 4.");
+        assert!(!cli.ask("wait").contains("sun goes dark"));
+    }
+
+    #[test]
+    fn calendars_in_world_files() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Camp",calendar:Some((day_length:1,festivals:[(2,"Midsummer Fair")])),rooms:{
+                "Camp":(name:"Camp",desc:"Tents flap in the wind.",paths:[])
+            }))"#,
+        ).unwrap();
+
+        assert_eq!(cli.ask("date"), "It is day 1.");
+        cli.ask("wait");
+        assert_eq!(cli.ask("date"), "It is day 2. Today is the Midsummer Fair.");
+        assert_eq!(test_world().ask("date"), "You have lost track of the days.");
+    }
+
+    fn cli_in_wilds() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Meadow",rooms:{
+                "Meadow":(name:"Meadow",desc:"Grass sways in the breeze.",region:Some("Wilds"),paths:[
+                    (directions:["north"],target:"Inn",opening:None,lock:None)
+                ]),
+                "Inn":(name:"Inn",desc:"A fire roars in the hearth.",paths:[
+                    (directions:["south"],target:"Meadow",opening:None,lock:None)
+                ])
+            }))"#,
+        ).unwrap();
+        cli.add_region(
+            "Wilds",
+            Region::new(100).with_wanderer(
+                1,
+                Enemy::new("wolf", "Its fur is grey.", EnemyStatus::Distracted)
+                    .with_desc("A wolf prowls nearby."),
+            ),
+        );
+        cli
+    }
+
+    fn give(cli: &Cli, item: Thing) {
+        cli.receive_item(Item::Thing(item));
+    }
+
+    fn firewood() -> Thing {
+        Thing::new("firewood", "It is dry.").with_gear(Gear::Firewood)
+    }
+
+    fn tent() -> Thing {
+        Thing::new("tent", "It is canvas.").with_gear(Gear::Tent)
+    }
+
+    #[test]
+    fn camping_needs_gear_and_the_wilds() {
+        let cli = cli_in_wilds();
+
+        assert_eq!(cli.ask("camp"), "You have nothing to make camp with.");
+        give(&cli, tent());
+        cli.ask("n");
+        assert_eq!(cli.ask("camp"), "You can only make camp out in the wilds.");
+    }
+
+    #[test]
+    fn campfires_burn_firewood_and_cook() {
+        let cli = cli_in_wilds();
+        give(&cli, firewood());
+        give(
+            &cli,
+            Thing::new("raw fish", "It is slimy.").with_cooked(Item::Thing(Thing::new(
+                "grilled fish",
+                "It smells wonderful.",
+            ))),
+        );
+
+        assert!(cli
+            .ask("camp")
+            .starts_with("You light a campfire.\nCamp\nA campfire crackles in a ring of stones."));
+        assert!(!cli.ask("i").contains("firewood"));
+        assert_eq!(
+            cli.ask("cook fish"),
+            "You cook the raw fish into a grilled fish."
+        );
+        assert!(cli.ask("i").contains("grilled fish"));
+    }
+
+    #[test]
+    fn quest_food_stays_raw() {
+        let cli = cli_in_wilds();
+        give(&cli, firewood());
+        give(
+            &cli,
+            Thing::new("raw fish", "It is slimy.")
+                .with_cooked(Item::Thing(Thing::new(
+                    "grilled fish",
+                    "It smells wonderful.",
+                )))
+                .with_quest("feed the heron"),
+        );
+
+        cli.ask("camp");
+        assert_eq!(cli.ask("cook fish"), "You feel you will need this.");
+        assert!(cli.ask("i").contains("raw fish"));
+    }
+
+    #[test]
+    fn tents_are_safe_to_sleep_in() {
+        let cli = cli_in_wilds();
+        give(&cli, tent());
+
+        assert!(cli.ask("camp").starts_with("You pitch your tent.\nCamp"));
+        assert_eq!(cli.ask("cook fish"), "You need a campfire to cook over.");
+        assert_eq!(cli.ask("sleep"), "You sleep soundly and wake fully rested.");
+        assert!(!cli.ask("wait").contains("Something wanders near."));
+        assert!(cli.ask("i").contains("tent"));
+        assert!(cli.ask("exit").contains("Meadow"));
+    }
+
+    #[test]
+    fn leaving_packs_up_camp() {
+        let cli = cli_in_wilds();
+        give(&cli, tent());
+        cli.ask("camp");
+        cli.ask("drop tent");
+
+        assert!(cli.ask("leave camp").contains("Meadow"));
+        assert!(!cli.ask("l").contains("Camp"));
+        assert!(!cli
+            .lint()
+            .iter()
+            .any(|finding| finding.message().contains("camp")));
+    }
+
+    fn cli_keeping_time() -> Cli {
+        let cli = test_world();
+        cli.set_calendar(
+            Calendar::new(2).with_event(StoryEvent::new(2, "The bells ring out at noon.")),
+        );
+        cli.set_turn_length(Duration::from_secs(10));
+        cli
+    }
+
+    #[test]
+    fn real_time_passes_turns() {
+        let cli = cli_keeping_time();
+
+        assert_eq!(cli.tick(Duration::from_secs(15)), "");
+        assert_eq!(cli.ask("date"), "It is day 1.");
+        assert_eq!(cli.tick(Duration::from_secs(4)), "");
+        assert_eq!(
+            cli.tick(Duration::from_secs(1)),
+            "The bells ring out at noon."
+        );
+        assert_eq!(cli.ask("date"), "It is day 2.");
+        assert!(cli.ask("l").starts_with("Central Room"));
+    }
+
+    #[test]
+    fn ticks_do_not_count_as_moves() {
+        let cli = cli_keeping_time();
+
+        cli.tick(Duration::from_secs(60));
+        assert_eq!(cli.status().turns(), 0);
+    }
+
+    #[test]
+    fn worlds_stand_still_without_a_turn_length() {
+        let cli = cli_keeping_time();
+        cli.set_turn_length(Duration::ZERO);
+
+        assert_eq!(cli.tick(Duration::from_secs(3600)), "");
+        assert_eq!(cli.ask("date"), "It is day 1.");
+    }
+
+    #[test]
+    fn happenings_come_between_commands() {
+        let cli = test_world();
+        cli.set_turn_length(Duration::from_secs(10));
+        cli.set_region("Central Room", "Caves");
+        cli.set_deck(
+            Deck::new(100).with_happening(Happening::new("A bat flutters past.").with_once()),
+        );
+
+        assert_eq!(cli.tick(Duration::from_secs(10)), "A bat flutters past.");
+        assert_eq!(cli.tick(Duration::from_secs(10)), "");
+    }
+
+    #[test]
+    fn enemies_wander_near_and_allies_fade_between_commands() {
+        let cli = test_world();
+        cli.set_turn_length(Duration::from_secs(10));
+        cli.receive_item(Item::Summon(Summon::new(
+            "bone horn",
+            "It is carved with wolves.",
+            Ally::new("spirit wolf", "It glows faintly."),
+            2,
+        )));
+        cli.ask("use horn");
+        cli.add_region(
+            "Cave",
+            Region::new(100).with_wanderer(
+                1,
+                Enemy::new("bat", "Its wings twitch.", EnemyStatus::Distracted)
+                    .with_desc("A bat flutters overhead."),
+            ),
+        );
+        cli.set_region("Central Room", "Cave");
+
+        assert_eq!(
+            cli.tick(Duration::from_secs(10)),
+            "Something wanders near.\nA bat flutters overhead.\nThe spirit wolf fades away."
+        );
+        assert!(cli.ask("l").contains("A bat flutters overhead."));
+    }
+
+    #[test]
+    fn sessions_tick_every_game() {
+        let mut sessions = SessionManager::new(|_| cli_keeping_time());
+
+        sessions.ask("alice", "l");
+        sessions.ask("bob", "l");
+        let mut events = sessions.tick(Duration::from_secs(20));
+        events.sort();
+        assert_eq!(
+            events,
+            vec![
+                (
+                    String::from("alice"),
+                    String::from("The bells ring out at noon.")
+                ),
+                (
+                    String::from("bob"),
+                    String::from("The bells ring out at noon.")
+                ),
+            ]
+        );
+    }
+
+    fn cli_with_wolves() -> Cli {
+        let cli = test_world();
+        for (name, turns) in [("spirit wolf", 3), ("ghost hound", 5)] {
+            cli.receive_item(Item::Summon(Summon::new(
+                &format!("{} horn", name),
+                "It is carved with wolves.",
+                Ally::new(name, "It glows faintly."),
+                turns,
+            )));
+        }
+        cli.ask("use spirit wolf horn");
+        cli.ask("use ghost hound horn");
+        cli
+    }
+
+    #[test]
+    fn waiting_passes_several_turns() {
+        let cli = test_world();
+
+        assert_eq!(cli.ask("z"), "Time passes...");
+        assert_eq!(cli.ask("wait 5"), "Time passes... (5 turns)");
+        assert_eq!(cli.ask("z 100"), "Time passes... (20 turns)");
+    }
+
+    #[test]
+    fn waiting_stops_when_something_happens() {
+        let cli = test_world();
+        cli.receive_item(Item::Summon(Summon::new(
+            "bone horn",
+            "It is carved with wolves.",
+            Ally::new("spirit wolf", "It glows faintly."),
+            3,
+        )));
+        cli.ask("use horn");
+
+        assert_eq!(
+            cli.ask("wait 10"),
+            "Time passes... (2 turns)\nThe spirit wolf fades away."
+        );
+    }
+
+    #[test]
+    fn waiting_stops_for_an_attack() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small.", EnemyStatus::Angry).with_damage(1),
+        );
+
+        assert!(!cli.ask("wait 5").contains("turns)"));
+    }
+
+    #[test]
+    fn waiting_until_an_event_reports_everything_on_the_way() {
+        let cli = cli_with_wolves();
+
+        assert_eq!(
+            cli.ask("wait until hound"),
+            "Time passes... (4 turns)\nThe spirit wolf fades away.\nThe ghost hound fades away."
+        );
+    }
+
+    #[test]
+    fn waiting_until_a_turn() {
+        let cli = test_world();
+        cli.ask("z");
+
+        assert_eq!(cli.ask("wait until turn 10"), "Time passes... (9 turns)");
+        assert_eq!(
+            cli.ask("wait until dragon"),
+            "Time passes... (100 turns)\nYou give up waiting."
+        );
+    }
+
+    #[test]
+    fn waiting_until_an_enemy_arrives() {
+        let cli = test_world();
+        cli.add_region(
+            "Cave",
+            Region::new(100).with_wanderer(
+                1,
+                Enemy::new("bat", "Its wings twitch.", EnemyStatus::Distracted)
+                    .with_desc("A bat flutters overhead."),
+            ),
+        );
+        cli.set_region("Central Room", "Cave");
+
+        assert_eq!(
+            cli.ask("wait until enemy"),
+            "Time passes... (1 turn)\nSomething wanders near.\nA bat flutters overhead."
+        );
+    }
+
+    fn cli_on_the_road(deck: Deck) -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Crossroads",rooms:{
+                "Crossroads":(name:"Crossroads",desc:"Roads run off every way.",paths:[
+                    (directions:["north"],target:"Inn",opening:None,lock:None),
+                    (directions:["crack"],target:"Cave",opening:Some(Closed),lock:None)
+                ]),
+                "Inn":(name:"Inn",desc:"A fire crackles in the hearth.",paths:[
+                    (directions:["south"],target:"Crossroads",opening:None,lock:None)
+                ]),
+                "Cave":(name:"Cave",desc:"It drips.",paths:[])
+            }))"#,
+        )
+        .unwrap();
+        cli.set_region("Crossroads", "Lowlands");
+        cli.set_deck(deck);
+        cli
+    }
+
+    #[test]
+    fn happenings_come_once() {
+        let cli = cli_on_the_road(
+            Deck::new(100).with_happening(
+                Happening::new("A courier rides up and hands you a letter.")
+                    .with_effect(Consequence::Gift(Box::new(Item::Thing(Thing::new(
+                        "letter",
+                        "It is sealed with wax.",
+                    )))))
+                    .with_once(),
+            ),
+        );
+
+        assert_eq!(
+            cli.ask("wait"),
+            "Time passes...\nA courier rides up and hands you a letter.\nYou receive a letter."
+        );
+        assert!(cli.ask("i").contains("letter"));
+        assert_eq!(cli.ask("wait"), "Time passes...");
+    }
+
+    #[test]
+    fn happenings_stay_out_in_their_regions() {
+        let cli = cli_on_the_road(
+            Deck::new(100).with_happening(
+                Happening::new("A merchant's cart trundles up.")
+                    .with_region("Lowlands")
+                    .with_effect(Consequence::Arrive(Box::new(Ally::new(
+                        "merchant",
+                        "She has wares to sell.",
+                    )))),
+            ),
+        );
+
+        assert!(!cli.ask("n").contains("merchant"));
+        assert!(!cli.ask("wait").contains("merchant"));
+        assert!(cli.ask("s").contains("A merchant's cart trundles up."));
+        assert!(cli.ask("l").contains("There is a merchant here."));
+
+        cli.set_region("Crossroads", "Highlands");
+        assert!(!cli.ask("wait").contains("merchant"));
+    }
+
+    #[test]
+    fn happenings_wait_on_conditions_and_open_paths() {
+        let cli = cli_on_the_road(
+            Deck::new(100).with_happening(
+                Happening::new("The ground shakes, and a crack in the rock splits wide.")
+                    .with_condition(Condition::Item(String::from("lodestone")))
+                    .with_effect(Consequence::Open(String::from("crack")))
+                    .with_once(),
+            ),
+        );
+
+        assert_eq!(cli.ask("go crack"), "The way is shut.");
+        cli.add_item(
+            "Crossroads",
+            Item::Thing(Thing::new("lodestone", "It tugs at iron.")),
+        );
+        assert!(cli.ask("wait").contains("a crack in the rock splits wide"));
+        assert!(cli.ask("go crack").starts_with("Cave"));
+    }
+
+    #[test]
+    fn spent_happenings_are_saved() {
+        let cli = cli_on_the_road(
+            Deck::new(100)
+                .with_happening(Happening::new("Thunder rolls over the hills.").with_once()),
+        );
+
+        assert!(cli.ask("wait").contains("Thunder rolls"));
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(!cli.ask("wait").contains("Thunder rolls"));
+    }
+
+    #[test]
+    fn quiet_decks_leave_turns_alone() {
+        let cli = cli_on_the_road(
+            Deck::new(0).with_happening(Happening::new("Thunder rolls over the hills.")),
+        );
+
+        assert_eq!(cli.ask("wait"), "Time passes...");
+    }
+
+    fn cli_in_desert(chance: u32) -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Dunes",rooms:{
+                "Dunes":(name:"Dunes",desc:"Sand stretches away.",paths:[])
+            }))"#,
+        )
+        .unwrap();
+        cli.add_region(
+            "Desert",
+            Region::new(chance).with_wanderer(
+                1,
+                Enemy::new("scorpion", "Its tail is raised.", EnemyStatus::Distracted)
+                    .with_desc("A scorpion skitters over the sand."),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn quiet_runs_grow_tense() {
+        let cli = cli_in_desert(1);
+        cli.set_director(Director::new(0, 99).with_calm(2, 50));
+        for _ in 0..4 {
+            cli.ask("wait");
+        }
+
+        cli.set_region("Dunes", "Desert");
+        assert!(cli.ask("wait").contains("Something wanders near."));
+
+        // a save keeps the pace of the run
+        let cli = cli_in_desert(1);
+        cli.set_director(Director::new(0, 99).with_calm(2, 50));
+        for _ in 0..4 {
+            cli.ask("wait");
+        }
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        cli.set_region("Dunes", "Desert");
+        assert!(cli.ask("wait").contains("Something wanders near."));
+    }
+
+    #[test]
+    fn nudges_stay_within_bounds() {
+        let mut director = Director::new(30, 20).with_calm(1, 10);
+        for _ in 0..10 {
+            director.pace(false, 20, 20);
+        }
+        assert_eq!(director.nudge(), 20);
+        assert_eq!(director.adjust(90), 100);
+        assert_eq!(director.adjust(0), 0);
+
+        // losing HP calms things down, and a fight starts the quiet over
+        director.pace(true, 4, 20);
+        assert_eq!(director.nudge(), -30);
+        assert_eq!(director.adjust(20), 0);
+        for _ in 0..6 {
+            director.pace(false, 4, 20);
+        }
+        assert_eq!(director.nudge(), 20);
+    }
+
+    #[test]
+    fn directors_come_from_world_files() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Dunes",rooms:{
+                "Dunes":(name:"Dunes",desc:"Sand stretches away.",paths:[],region:Some("Desert"))
+            },regions:{"Desert":(chance:0)},director:Some((most_lowered:10,most_raised:50))))"#,
+        )
+        .unwrap();
+        assert!(cli.to_ron().contains("most_raised:50"));
+        for _ in 0..10 {
+            assert!(!cli.ask("wait").contains("Something wanders near."));
+        }
+    }
+
+    fn cli_with_ritual() -> Cli {
+        let cli = test_world();
+        cli.add_sequence(
+            "Central Room",
+            Sequence::new(vec![
+                Step::new(&["light"], "incense", "Sweet smoke curls upward."),
+                Step::new(&["ring", "strike"], "bell", "The bell tolls once."),
+                Step::new(
+                    &["read"],
+                    "book",
+                    "The words echo, and the small wooden door swings open.",
+                ),
+            ])
+            .with_wrong("The smoke gutters out. You must begin again.")
+            .with_completes("the ritual")
+            .with_opens("small wooden door"),
+        );
+        cli.add_item(
+            "Central Room",
+            Item::Thing(Thing::new("candle stub", "It is spent.").with_quest("the ritual")),
+        );
+        cli.ask("take stub");
+        cli
+    }
+
+    #[test]
+    fn steps_out_of_turn_start_over() {
+        let cli = cli_with_ritual();
+
+        assert_eq!(
+            cli.ask("ring bell"),
+            "The smoke gutters out. You must begin again."
+        );
+        assert_eq!(cli.ask("light incense"), "Sweet smoke curls upward.");
+        assert_eq!(cli.ask("strike bell"), "The bell tolls once.");
+        assert_eq!(
+            cli.ask("light incense"),
+            "The smoke gutters out. You must begin again.\nSweet smoke curls upward."
+        );
+        assert_eq!(cli.ask("ring the bell"), "The bell tolls once.");
+        assert_eq!(cli.ask("drop stub"), "You feel you will need this.");
+
+        // a save remembers how far along the ritual is
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert_eq!(
+            cli.ask("read book"),
+            "The words echo, and the small wooden door swings open."
+        );
+        assert_ne!(cli.ask("drop stub"), "You feel you will need this.");
+        assert!(cli.ask("enter door").contains("Closet"));
+    }
+
+    #[test]
+    fn sequences_in_world_files() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Shrine",rooms:{
+                "Shrine":(name:"Shrine",desc:"Three levers jut from the wall.",paths:[],sequences:[
+                    (steps:[
+                        (verbs:["pull"],obj:"left lever",feedback:"Click."),
+                        (verbs:["pull"],obj:"right lever",feedback:"Clunk. A panel slides open.")
+                    ])
+                ])
+            }))"#,
+        )
+        .unwrap();
+
+        assert_eq!(cli.ask("pull right lever"), "Nothing seems to come of it.");
+        assert_eq!(cli.ask("pull left lever"), "Click.");
+        assert_eq!(cli.ask("pull right lever"), "Clunk. A panel slides open.");
+    }
+}
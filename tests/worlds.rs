@@ -0,0 +1,728 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
+
+    use kingslayer::{
+        Ally, Cli, Enemy, EnemyStatus, Item, LootTable, Quest, RandomSource, Role, Sequence,
+        Severity, SplitMix64, Step, Thing,
+    };
+
+    use crate::common::{test_world, Loaded, MemoryStore};
+
+    #[test]
+    fn included_files_join_the_world() {
+        let cli = Cli::from_file("tests/includes/world.ron").unwrap();
+        assert!(cli.ask("n").contains("long hall"));
+        assert!(cli.ask("n").contains("barracks"));
+        assert!(cli.ask("e").contains("helmet"));
+        cli.ask("take helmet");
+        assert!(cli.ask("i").contains("helmet"));
+        assert!(cli.validate().is_empty());
+
+        // a save holds the whole world, with nothing left to include
+        let loaded = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(loaded.ask("w").contains("barracks"));
+        assert!(!cli.to_ron().contains("include"));
+
+        let lazy = Cli::from_file_lazy("tests/includes/world.ron", 2).unwrap();
+        assert!(lazy.ask("n").contains("long hall"));
+    }
+
+    #[test]
+    fn duplicates_and_cycles_are_refused() {
+        let dir = env::temp_dir().join("kingslayer_includes_test");
+        fs::create_dir_all(&dir).unwrap();
+        let room = |name: &str| {
+            format!(
+                "\"{}\":(name:\"{}\",desc:\"It is empty.\",paths:[])",
+                name, name
+            )
+        };
+        fs::write(
+            dir.join("main.ron"),
+            format!(
+                "(world:(curr_room:\"Cell\",rooms:{{{}}},include:[\"more.ron\"]))",
+                room("Cell")
+            ),
+        )
+        .unwrap();
+
+        fs::write(
+            dir.join("more.ron"),
+            format!("(rooms:{{{}}})", room("Cell")),
+        )
+        .unwrap();
+        let err = Cli::from_file(dir.join("main.ron").to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("more.ron defines the room Cell, which is already defined"));
+
+        fs::write(
+            dir.join("more.ron"),
+            "(regions:{\"dungeon\":(chance:0)},include:[\"last.ron\"])",
+        )
+        .unwrap();
+        fs::write(dir.join("last.ron"), "(regions:{\"dungeon\":(chance:5)})").unwrap();
+        let err = Cli::from_file(dir.join("main.ron").to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("last.ron defines the region dungeon, which is already defined"));
+
+        fs::write(dir.join("last.ron"), "(include:[\"more.ron\"])").unwrap();
+        let err = Cli::from_file(dir.join("main.ron").to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("more.ron includes itself"));
+
+        fs::write(dir.join("last.ron"), "(include:[\"missing.ron\"])").unwrap();
+        let err = Cli::from_file(dir.join("main.ron").to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unable to include"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parts_merge_quests_and_replies_but_not_settings() {
+        let dir = env::temp_dir().join("kingslayer_include_fields_test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("main.ron"),
+            "(world:(curr_room:\"Cell\",rooms:{\"Cell\":(name:\"Cell\",desc:\"It is empty.\",paths:[])},include:[\"more.ron\"]))",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("more.ron"),
+            "(quests:{\"escape\":()},replies:[(verbs:[\"eat\"],object:\"apple\",failure:Some(\"You are not hungry enough.\"))])",
+        )
+        .unwrap();
+        let cli = Cli::from_file(dir.join("main.ron").to_str().unwrap()).unwrap();
+        assert!(cli
+            .quest_graph()
+            .nodes()
+            .contains(&(String::from("quest"), String::from("escape"))));
+        assert_eq!(cli.ask("eat apple"), "You are not hungry enough.");
+
+        fs::write(dir.join("more.ron"), "(clock:5)").unwrap();
+        let err = Cli::from_file(dir.join("main.ron").to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("more.ron") && err.contains("clock"), "{}", err);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn room(key: &str, desc: &str, paths: &[(&str, &str)], rest: &str) -> String {
+        let paths: Vec<String> = paths
+            .iter()
+            .map(|(direction, target)| {
+                format!(
+                    "(directions:[\"{}\"],target:\"{}\",desc:\"A way leads {}.\",inspect:\"It is dark.\")",
+                    direction, target, direction
+                )
+            })
+            .collect();
+        format!(
+            "\"{}\":(name:\"Cellar\",desc:\"{}\",paths:[{}]{})",
+            key,
+            desc,
+            paths.join(","),
+            rest
+        )
+    }
+
+    // two packs that each define a Cellar, a Vault below it, and a loot table and region of
+    // the same names
+    fn write_packs(dir: &str) -> PathBuf {
+        let dir = env::temp_dir().join(dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("main.ron"),
+            format!(
+                "(world:(curr_room:\"Gate\",rooms:{{{}}},include:[\"dragons.ron\",\"goblins.ron\"]))",
+                room(
+                    "Gate",
+                    "You stand at the gate.",
+                    &[("west", "dragons:Cellar"), ("east", "goblins:Cellar")],
+                    ""
+                )
+            ),
+        )
+        .unwrap();
+        for (pack, back) in [("dragons", "east"), ("goblins", "west")] {
+            let rat = "enemies:[(name:\"rat\",desc:\"A rat is here.\",inspect:\"It is small.\",hp:1,ac:1,xp:1,damage:1,status:Asleep,loot_table:Some(\"vermin\"))],region:Some(\"depths\")";
+            fs::write(
+                dir.join(format!("{}.ron", pack)),
+                format!(
+                    "(pack:Some(\"{}\"),rooms:{{{},{}}},loot_tables:{{\"vermin\":(gold:Some((5,5)))}},regions:{{\"depths\":(chance:0)}})",
+                    pack,
+                    room(
+                        "Cellar",
+                        &format!("The {} cellar is damp.", pack),
+                        &[(back, "Gate"), ("down", "Vault")],
+                        ""
+                    ),
+                    room(
+                        "Vault",
+                        &format!("The {} vault is empty.", pack),
+                        &[("up", "Cellar")],
+                        &format!(",{}", rat)
+                    ),
+                ),
+            )
+            .unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn packs_can_define_the_same_names() {
+        let dir = write_packs("kingslayer_packs_test");
+        let cli = Cli::from_file(dir.join("main.ron").to_str().unwrap()).unwrap();
+        assert!(cli.validate().is_empty(), "{:?}", cli.validate());
+        assert!(cli.lint().iter().all(|finding| !finding.is_error()));
+
+        assert!(cli.ask("w").contains("dragons cellar"));
+        assert!(cli.ask("down").contains("dragons vault"));
+        assert!(cli.ask("up").contains("dragons cellar"));
+        assert!(cli.ask("e").contains("gate"));
+        assert!(cli.ask("e").contains("goblins cellar"));
+        assert!(cli.ask("down").contains("goblins vault"));
+
+        // a save keeps the names apart too
+        let loaded = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(loaded.ask("up").contains("goblins cellar"));
+        assert!(loaded.validate().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn pack_names_must_be_plain() {
+        let dir = write_packs("kingslayer_bad_pack_test");
+        fs::write(dir.join("goblins.ron"), "(pack:Some(\"goblin horde\"))").unwrap();
+        let err = Cli::from_file(dir.join("main.ron").to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("goblin horde is not a valid pack name"),
+            "{}",
+            err
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn packs_keep_their_items_keys_and_quests_apart() {
+        let dir = env::temp_dir().join("kingslayer_pack_items_test");
+        fs::create_dir_all(&dir).unwrap();
+        let herb =
+            "Thing((name:\"herb\",desc:\"There is an herb here.\",inspect:\"It smells bitter.\"))";
+        fs::write(
+            dir.join("main.ron"),
+            format!(
+                "(world:(curr_room:\"Gate\",rooms:{{{}}},quests:{{\"rescue\":()}},include:[\"dragons.ron\"]))",
+                room(
+                    "Gate",
+                    "You stand at the gate.",
+                    &[("west", "dragons:Cellar")],
+                    &format!(",items:[{}]", herb)
+                )
+            ),
+        )
+        .unwrap();
+        let cellar = format!(
+            "\"Cellar\":(name:\"Cellar\",desc:\"The dragons cellar is damp.\",paths:[
+                (directions:[\"east\"],target:\"Gate\",desc:\"A way leads east.\",inspect:\"It is dark.\"),
+                (directions:[\"down\"],target:\"Vault\",desc:\"A door leads down.\",inspect:\"It is locked.\",
+                    opening:Some(Closed),lock:Some(Locked(\"vault\")))
+            ],elements:[
+                (name:\"cauldron\",desc:\"A cauldron bubbles.\",inspect:\"It is black.\",station:Some(\"cauldron\"))
+            ],items:[
+                {},
+                Key((name:\"key\",desc:\"There is a key here.\",inspect:\"It is iron.\",code:\"vault\",quest:Some(\"rescue\")))
+            ])",
+            herb
+        );
+        fs::write(
+            dir.join("dragons.ron"),
+            format!(
+                "(pack:Some(\"dragons\"),rooms:{{{},{}}},recipes:{{\"tea\":(station:\"cauldron\",ingredients:[\"herb\"],
+                    makes:Thing((name:\"tea\",desc:\"There is some tea here.\",inspect:\"It steams.\")),ability:Wisdom,dc:1)
+                }},quests:{{\"rescue\":()}})",
+                cellar,
+                room("Vault", "The dragons vault is empty.", &[], ""),
+            ),
+        )
+        .unwrap();
+
+        let cli = Cli::from_file(dir.join("main.ron").to_str().unwrap()).unwrap();
+        assert!(cli.validate().is_empty(), "{:?}", cli.validate());
+        cli.set_random_source(Loaded);
+
+        // the pack's tea needs the pack's herb, not the one at the gate
+        cli.ask("take herb");
+        cli.ask("w");
+        assert!(cli.ask("brew tea").contains("You need"));
+        assert_eq!(cli.ask("take herb"), "Taken.");
+        assert_eq!(cli.ask("brew tea"), "You make a tea.");
+
+        // the key is needed for the pack's quest, not the world's quest of the same name
+        assert_eq!(cli.ask("take key"), "Taken.");
+        cli.complete_quest("rescue");
+        assert_eq!(cli.ask("drop key"), "You feel you will need this.");
+        let inventory = cli.ask("i");
+        assert!(inventory.contains("key"), "{}", inventory);
+        assert!(!inventory.contains("dragons"), "{}", inventory);
+
+        let saved = cli.to_ron();
+        assert!(saved.contains("\"dragons:rescue\""));
+        assert_eq!(saved.matches("\"dragons:vault\"").count(), 2, "{}", saved);
+        cli.complete_quest("dragons:rescue");
+        assert_eq!(cli.ask("drop key"), "Dropped.");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    // a corridor of rooms, with a comment, a raw string, and brackets in text to scan past
+    fn corridor(rooms: usize) -> String {
+        let mut body = String::new();
+        for i in 0..rooms {
+            let mut paths = Vec::new();
+            if i + 1 < rooms {
+                paths.push(format!(
+                    "(directions:[\"north\"],target:\"Room {}\",desc:\"The hall (dark) goes north.\",inspect:\"It is dark.\")",
+                    i + 1
+                ));
+            }
+            if i > 0 {
+                paths.push(format!(
+                    "(directions:[\"south\"],target:\"Room {}\",desc:r#\"The hall goes \"south\" }}.\"#,inspect:\"It is dark.\")",
+                    i - 1
+                ));
+            }
+            body.push_str(&format!(
+                "// room {i}\n\"Room {i}\":(name:\"Room {i}\",desc:\"You are in room {i}.\",paths:[{}],items:[Thing((name:\"pebble\",desc:\"There is a pebble here.\",inspect:\"It is smooth.\"))]),\n",
+                paths.join(","),
+                i = i
+            ));
+        }
+        format!("(world:(curr_room:\"Room 0\",rooms:{{{}}}))", body)
+    }
+
+    #[test]
+    fn lazily_loaded_worlds_play_the_same() {
+        let ron = corridor(50);
+        let full = Cli::from_ron_str(&ron).unwrap();
+        let lazy = Cli::from_ron_str_lazy(&ron, 3).unwrap();
+
+        for cmd in ["l", "take pebble", "n", "n", "n", "n", "n", "l", "i"] {
+            assert_eq!(lazy.ask(cmd), full.ask(cmd));
+        }
+        for _ in 0..5 {
+            assert_eq!(lazy.ask("s"), full.ask("s"));
+        }
+        // Room 0 went back to text when it was left behind, and kept its pebble taken
+        let res = lazy.ask("l");
+        assert!(!res.contains("pebble"));
+        assert_eq!(res, full.ask("l"));
+
+        assert_eq!(lazy.to_ron(), full.to_ron());
+        assert!(lazy.validate().is_empty());
+    }
+
+    #[test]
+    fn world_files_load_lazily() {
+        let lazy = Cli::from_file_lazy("worlds/test_world.ron", 1).unwrap();
+        let full = test_world();
+        for cmd in ["l", "n", "take all", "s", "s", "l", "n", "n", "l", "i"] {
+            assert_eq!(lazy.ask(cmd), full.ask(cmd));
+        }
+
+        assert!(Cli::from_file_lazy("worlds/missing.ron", 0).is_err());
+    }
+
+    #[test]
+    fn long_rooms_and_comments_are_scanned_whole() {
+        let long = "It goes on (and on) ".repeat(1000);
+        let ron = format!(
+            r#"(world:(curr_room:"Room",rooms:{{
+                /* {} */
+                "Room":(name:"Room",desc:"{}",paths:[
+                    (directions:["north"],target:"Attic",opening:None,lock:None)
+                ]),
+                // {}
+                "Attic":(name:"Attic",desc:"It is dusty.",paths:[])
+            }}))"#,
+            long, long, long
+        );
+        let lazy = Cli::from_ron_str_lazy(&ron, 1).unwrap();
+        assert!(lazy.ask("l").ends_with(&format!("{}\n", long)));
+        assert!(lazy.ask("n").starts_with("Attic"));
+    }
+
+    #[test]
+    fn unexpected_layouts_are_read_whole() {
+        let ron = r#"(world:(curr_room:"Room"))"#;
+        assert_eq!(
+            Cli::from_ron_str_lazy(ron, 0).unwrap_err().to_string(),
+            Cli::from_ron_str(ron).unwrap_err().to_string()
+        );
+
+        let ron = r#"(world:(curr_room:"Nowhere",rooms:{"Room":(name:"Room",desc:"It is empty.",paths:[])}))"#;
+        assert!(Cli::from_ron_str_lazy(ron, 0).is_err());
+    }
+
+    #[test]
+    fn broken_rooms_are_reported_when_first_read() {
+        let ron = r#"(world:(curr_room:"Room",rooms:{
+            "Room":(name:"Room",desc:"It is empty.",paths:[
+                (directions:["north"],target:"Attic",desc:"Stairs go up.",inspect:"They creak.")
+            ]),
+            "Attic":(name:"Attic",desc:"It is dusty.",paths:[],items:[Thing((name:"trunk"))])
+        }))"#;
+        let cli = Cli::from_ron_str_lazy(ron, 0).unwrap();
+        assert!(cli.ask("n").starts_with("Unable to read room Attic"));
+        assert!(cli.ask("l").starts_with("Room"));
+        assert!(cli.lint().iter().any(|finding| finding
+            .to_string()
+            .contains("Attic: the room cannot be read")));
+        assert!(cli.validate()[0].starts_with("Unable to read room Attic"));
+        let store = Arc::new(Mutex::new(MemoryStore::default()));
+        cli.set_save_store(store.clone());
+        assert!(cli.ask("save").ends_with("Error saving world."));
+        assert!(store.lock().unwrap().0.is_empty());
+
+        let ron = ron.replace(r#"curr_room:"Room""#, r#"curr_room:"Attic""#);
+        assert!(Cli::from_ron_str_lazy(&ron, 0)
+            .unwrap_err()
+            .to_string()
+            .starts_with("Unable to read room Attic"));
+    }
+
+    fn cellar() -> Cli {
+        let xml = fs::read_to_string("tests/maps/cellar.trizbort").unwrap();
+        Cli::from_trizbort(&xml).unwrap()
+    }
+
+    #[test]
+    fn rooms_and_connections() {
+        let cli = cellar();
+
+        assert!(cli.ask("l").starts_with("Cellar\nTODO: describe Cellar."));
+        assert!(cli.ask("e").starts_with("Wine & Ale"));
+        cli.ask("w");
+        assert!(cli.ask("up").starts_with("Kitchen"));
+        cli.ask("down");
+        assert!(cli.ask("s").starts_with("Cellar"));
+        assert!(!cli.ask("n").starts_with("Cellar"));
+        assert!(cli.lint().iter().all(|finding| !finding.is_error()));
+    }
+
+    #[test]
+    fn bad_maps() {
+        assert!(Cli::from_trizbort("<trizbort><map></map></trizbort>").is_err());
+        assert!(Cli::from_trizbort(
+            r#"<map><room id="1" name="Hall"/><line><dock id="1" port="n"/><dock id="9" port="s"/></line></map>"#
+        )
+        .is_err());
+        assert!(Cli::from_trizbort(r#"<map><room id="1" name="Hall></map>"#).is_err());
+    }
+
+    #[test]
+    fn generate() {
+        let cli = Cli::generate(100, 7);
+        assert!(cli.validate().is_empty());
+        assert_eq!(cli.ask("l"), Cli::generate(100, 7).ask("l"));
+
+        assert!(cli.ask("west").contains("leads back up"));
+        assert!(cli.ask("up").contains("passage to the east"));
+    }
+
+    #[test]
+    fn validate() {
+        let cli = test_world();
+        assert!(cli.validate().is_empty());
+
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small.", EnemyStatus::Asleep).with_loot_table("missing"),
+        );
+        assert_eq!(
+            cli.validate(),
+            vec!["Central Room: the rat uses the loot table missing, which does not exist"]
+        );
+    }
+
+    #[test]
+    fn validate_paths() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Room",rooms:{"Room":(name:"Room",desc:"It is empty.",paths:[(directions:["north"],target:"Nowhere",opening:None,lock:None)])}))"#,
+        ).unwrap();
+        assert_eq!(
+            cli.validate(),
+            vec!["Room: a path leads to Nowhere, which is not a room"]
+        );
+    }
+
+    #[test]
+    fn counts() {
+        let cli = test_world();
+        cli.spawn_enemy(
+            "Small Cave",
+            Enemy::new("goblin", "It is short and green.", EnemyStatus::Asleep).with_xp(30),
+        );
+        cli.spawn_enemy(
+            "Bedroom",
+            Enemy::new("dragon", "It is enormous.", EnemyStatus::Asleep).with_xp(500),
+        );
+        let stats = cli.world_stats();
+
+        assert!(stats.rooms() > 1);
+        assert!(stats.items() > 0);
+        assert_eq!(stats.enemies(), 2);
+        assert_eq!(stats.enemies_by_difficulty("easy"), 1);
+        assert_eq!(stats.enemies_by_difficulty("hard"), 1);
+        assert_eq!(stats.enemies_by_difficulty("trivial"), 0);
+        assert!(stats.average_exits() > 0.0);
+        assert!(stats.text_len() > 0);
+        assert!(stats.playtime() >= stats.rooms() + 4);
+    }
+
+    #[test]
+    fn debug_command() {
+        let cli = test_world();
+
+        assert!(!cli.ask("worldstats").starts_with("Rooms: "));
+        cli.set_role(Role::Author);
+        let res = cli.ask("worldstats");
+        assert!(res.starts_with("Rooms: "));
+        assert!(res.contains("Enemies: 0 (0 trivial, 0 easy, 0 moderate, 0 hard)"));
+        assert!(res.ends_with(" minutes"));
+    }
+
+    fn cli_with_stones() -> Cli {
+        let cli = test_world();
+        for i in 0..20 {
+            cli.add_item(
+                "Central Room",
+                Item::Thing(Thing::new(&format!("stone{}", i), "It is grey.")),
+            );
+        }
+        cli
+    }
+
+    #[test]
+    fn off_by_default() {
+        let cli = cli_with_stones();
+        for i in 0..20 {
+            assert_eq!(cli.ask(&format!("take stone{}", i)), "Taken.");
+        }
+        assert!(cli.ask("settings").contains("variety: off"));
+    }
+
+    #[test]
+    fn no_repeats_in_window() {
+        let cli = cli_with_stones();
+        cli.seed(3);
+        assert_eq!(cli.ask("set variety on"), "variety set to on.");
+
+        let taken: Vec<String> = (0..20)
+            .map(|i| cli.ask(&format!("take stone{}", i)))
+            .collect();
+        for pair in taken.windows(2) {
+            assert_ne!(pair[0], pair[1]);
+        }
+        for res in &taken {
+            assert!(["Taken.", "Got it.", "You pick it up."].contains(&res.as_str()));
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        let play = || {
+            let cli = cli_with_stones();
+            cli.set_variety(true);
+            cli.seed(11);
+            (0..20)
+                .map(|i| cli.ask(&format!("take stone{}", i)))
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(play(), play());
+    }
+
+    #[test]
+    fn custom_pools() {
+        let cli = cli_with_stones();
+        cli.set_variety(true);
+        cli.set_variations("Taken.", &[(1, "Yoink.")]);
+        cli.set_variation_window(0);
+        assert_eq!(cli.ask("take stone0"), "Yoink.");
+        assert_eq!(cli.ask("take stone1"), "Yoink.");
+
+        cli.set_variations("Taken.", &[]);
+        assert_eq!(cli.ask("take stone2"), "Taken.");
+    }
+
+    #[test]
+    fn custom_source() {
+        let cli = test_world();
+        cli.set_random_source(Loaded);
+        cli.spawn_enemy(
+            "Central Room",
+            Enemy::new("rat", "It is small.", EnemyStatus::Asleep)
+                .with_hp(100)
+                .with_damage(1),
+        );
+
+        cli.ask("take leaf");
+        let first = cli.ask("kill rat with leaf");
+        let second = cli.ask("kill rat with leaf");
+        assert_eq!(first.lines().next(), second.lines().next());
+        assert!(first.contains("The rat hit you for 1 damage."));
+    }
+
+    #[test]
+    fn split_mix() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(SplitMix64::new(1).next_u64(), SplitMix64::new(2).next_u64());
+    }
+
+    #[test]
+    fn clean_world() {
+        let cli = test_world();
+        assert!(cli.lint().is_empty());
+    }
+
+    #[test]
+    fn misspelled_references() {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"It is long.",paths:[(directions:["north"],target:"Kitchn",opening:None,lock:None)]),
+                "Kitchen":(name:"Kitchen",desc:"It smells.",paths:[(directions:["south"],target:"Hall",opening:None,lock:None)])
+            }))"#,
+        ).unwrap();
+        cli.add_loot_table("vermin", LootTable::new().with_gold(1, 1));
+        cli.spawn_enemy(
+            "Hall",
+            Enemy::new("rat", "It is small.", EnemyStatus::Asleep).with_loot_table("vermn"),
+        );
+        cli.add_item("Kitchen", Item::Thing(Thing::new("spoon", "It is dented.")));
+
+        let findings: Vec<String> = cli.lint().iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            findings,
+            vec![
+                "error\tunknown-loot-table\tHall: the rat uses the loot table vermn, which does not exist (did you mean vermin?)",
+                "error\tunknown-room\tHall: a path leads to Kitchn, which is not a room (did you mean Kitchen?)",
+                "warning\tunreachable-room\tKitchen: no path leads here from the start",
+                "warning\tunused-item\tKitchen: the spoon lies in a room that cannot be reached",
+                "warning\tunused-loot-table\tthe loot table vermin is never used",
+            ]
+        );
+        assert_eq!(cli.lint()[0].severity(), Severity::Error);
+        assert!(cli.lint()[0].is_error());
+        assert_eq!(cli.lint()[2].code(), "unreachable-room");
+    }
+
+    fn cli_with_quests() -> Cli {
+        let cli = Cli::from_ron_str(
+            r#"(world:(curr_room:"Hall",rooms:{
+                "Hall":(name:"Hall",desc:"Banners hang here.",paths:[])
+            },quests:{
+                "find the crown":(),
+                "crown the king":(requires:["find the crown"])
+            }))"#,
+        )
+        .unwrap();
+        cli.add_sequence(
+            "Hall",
+            Sequence::new(vec![Step::new(&["kneel"], "throne", "You kneel.")])
+                .with_completes("crown the king"),
+        );
+        cli.spawn_enemy(
+            "Hall",
+            Enemy::new("troll", "It guards the hoard.", EnemyStatus::Asleep).with_item(
+                Item::Thing(Thing::new("iron crown", "It is dented.").with_quest("find the crown")),
+            ),
+        );
+        cli
+    }
+
+    #[test]
+    fn quests_are_graphed() {
+        let graph = cli_with_quests().quest_graph();
+
+        let edges = graph.edges();
+        assert!(edges.contains(&("find the crown", "crown the king", "unlocks")));
+        assert!(edges.contains(&("Hall", "crown the king", "completes")));
+        assert!(edges.contains(&("troll (Hall)", "iron crown", "drops")));
+        assert!(edges.contains(&("iron crown", "find the crown", "needed for")));
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn graphs_export_to_dot_and_json() {
+        let cli = cli_with_quests();
+        cli.add_ally(
+            "Hall",
+            Ally::new("herald", "He clutches a \"royal\" scroll."),
+        );
+        let graph = cli.quest_graph();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph quests {\n"));
+        assert!(dot.contains("[label=\"crown the king\", shape=box];"));
+        assert!(dot.contains("[label=\"unlocks\"];"));
+        assert!(!dot.contains("herald"));
+
+        let json = graph.to_json();
+        assert!(json.starts_with("{\"nodes\":[{\"kind\":\"quest\",\"name\":\"crown the king\"}"));
+        assert!(json.contains("\"label\":\"needed for\""));
+    }
+
+    #[test]
+    fn circular_quests_are_linted() {
+        let cli = cli_with_quests();
+        cli.add_quest(
+            "find the crown",
+            Quest::default().with_requires("crown the king"),
+        );
+        cli.add_quest(
+            "win the war",
+            Quest::default().with_requires("raise an army"),
+        );
+
+        assert_eq!(cli.quest_graph().cycles().len(), 1);
+        let findings = cli.lint();
+        assert!(findings.iter().any(|finding| finding.is_error()
+            && finding
+                .message()
+                .contains("crown the king and find the crown")
+            && finding.message().contains("so none can be done")));
+        assert!(findings.iter().any(|finding| finding
+            .message()
+            .contains("requires the quest raise an army, which does not exist")));
+    }
+
+    #[test]
+    fn quests_are_saved() {
+        let cli = cli_with_quests();
+
+        let cli = Cli::from_ron_str(&cli.to_ron()).unwrap();
+        assert!(cli.quest_graph().edges().contains(&(
+            "find the crown",
+            "crown the king",
+            "unlocks"
+        )));
+    }
+}